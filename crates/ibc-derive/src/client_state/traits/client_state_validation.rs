@@ -33,6 +33,13 @@ pub(crate) fn impl_ClientStateValidation(
         quote! { status(cs, ctx, client_id) },
     );
 
+    let expiry_timestamp_impl = delegate_call_in_match(
+        client_state_enum_name,
+        enum_variants.iter(),
+        opts,
+        quote! { expiry_timestamp(cs, ctx, client_id) },
+    );
+
     let HostClientState = client_state_enum_name;
     let ClientValidationContext = &opts.client_validation_context;
 
@@ -41,6 +48,7 @@ pub(crate) fn impl_ClientStateValidation(
     let ClientError = Imports::ClientError();
     let ClientStateValidation = Imports::ClientStateValidation();
     let Status = Imports::Status();
+    let Timestamp = Imports::Timestamp();
     let UpdateKind = Imports::UpdateKind();
 
     quote! {
@@ -79,6 +87,16 @@ pub(crate) fn impl_ClientStateValidation(
                 }
 
             }
+
+            fn expiry_timestamp(
+                &self,
+                ctx: &#ClientValidationContext,
+                client_id: &#ClientId,
+            ) -> core::result::Result<core::option::Option<#Timestamp>, #ClientError> {
+                match self {
+                    #(#expiry_timestamp_impl),*
+                }
+            }
         }
 
     }
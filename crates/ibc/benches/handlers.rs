@@ -0,0 +1,282 @@
+//! Benchmarks of the hot paths of the handlers: light client verification,
+//! proof verification, packet handling, and message decoding.
+//!
+//! Run them with
+//!
+//! ```text
+//! cargo bench -p ibc --features mocks,serde --bench handlers
+//! ```
+//!
+//! All inputs are generated in memory from fixed parameters, down to the
+//! timestamps of the blocks, so the runs need no network and their results are
+//! comparable across commits. Save the
+//! results of a commit as a baseline, then compare another commit against it:
+//!
+//! ```text
+//! cargo bench -p ibc --features mocks,serde --bench handlers -- --save-baseline main
+//! git checkout my-branch
+//! cargo bench -p ibc --features mocks,serde --bench handlers -- --baseline main
+//! ```
+
+use core::str::FromStr;
+use core::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use ibc::applications::transfer::denom::PrefixedDenom;
+use ibc::clients::ics07_tendermint::client_type as tm_client_type;
+use ibc::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use ibc::core::ics04_channel::msgs::{MsgRecvPacket, PacketMsg};
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::timeout::TimeoutHeight;
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::core::ics23_commitment::specs::ProofSpecs;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc::core::timestamp::Timestamp;
+use ibc::core::{execute, validate, Msg, MsgEnvelope};
+use ibc::mock::context::MockContext;
+use ibc::mock::host::SyntheticTmChain;
+use ibc::mock::scenario::{transfer_router, ChainSetup};
+use ibc::mock::store::{MerkleStore, MultiStore, TreeLayout};
+use ibc::test_utils::get_dummy_account_id;
+use ibc::Height;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use ibc_proto::ics23::commitment_proof::Proof;
+use tendermint_testgen::Validator;
+
+/// The sizes of the validator sets signing the headers of `verify_header`.
+const VALIDATOR_SET_SIZES: [usize; 4] = [4, 16, 64, 128];
+
+/// The numbers of entries of the IAVL stores proven by `verify_membership`,
+/// whose proofs are about as deep as the logarithm of these.
+const STORE_SIZES: [usize; 4] = [16, 256, 4096, 65536];
+
+/// The numbers of hops in the trace paths of the parsed denominations.
+const TRACE_LENGTHS: [usize; 4] = [0, 1, 4, 16];
+
+/// The Unix time, in seconds, of the latest block of the host chains.
+const HOST_TIME_SECS: u64 = 1_700_000_000;
+
+/// Returns the timestamp of the latest block of the host chains, fixed so
+/// that every run benches the same blocks and headers.
+fn host_timestamp() -> Timestamp {
+    Timestamp::from_nanoseconds(HOST_TIME_SECS * 1_000_000_000).expect("valid timestamp")
+}
+
+/// Returns a synthetic Tendermint chain, signed by `validator_count`
+/// validators of equal power, which produced its first 3 blocks before the
+/// latest block of the host chains.
+fn synthetic_tm_chain(validator_count: usize) -> SyntheticTmChain {
+    let validators: Vec<_> = (0..validator_count)
+        .map(|i| Validator::new(&i.to_string()).voting_power(50))
+        .collect();
+    let mut chain = SyntheticTmChain::new(
+        ChainId::new("mockgaiaB", 1).expect("valid chain identifier"),
+        &validators,
+        (host_timestamp() - Duration::from_secs(100)).expect("Never fails"),
+    );
+    chain.produce_blocks_until(3);
+    chain
+}
+
+fn tm_height(height: u64) -> Height {
+    Height::new(1, height).expect("non-zero height")
+}
+
+/// Benchmarks the verification of a Tendermint header skipping from height 1
+/// to height 3, signed by all the trusted validators.
+fn bench_verify_header(c: &mut Criterion) {
+    let client_id = ClientId::new(tm_client_type(), 0).expect("valid client identifier");
+
+    let mut group = c.benchmark_group("ics07_verify_header");
+    for size in VALIDATOR_SET_SIZES {
+        let chain = synthetic_tm_chain(size);
+        let ctx = MockContext::default()
+            .with_latest_timestamp(host_timestamp())
+            .with_tm_client(&client_id, &chain, tm_height(1));
+        let client_state = chain
+            .client_state(&tm_height(1))
+            .expect("the chain has a block at height 1");
+        let header = chain
+            .header(&tm_height(3), &tm_height(1))
+            .expect("the chain has blocks at heights 1 and 3");
+
+        group.bench_with_input(
+            BenchmarkId::new("validators", size),
+            &header,
+            |b, header| {
+                b.iter_batched(
+                    || header.clone(),
+                    |header| {
+                        client_state
+                            .verify_header(&ctx, &client_id, header)
+                            .expect("the header is valid")
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Benchmarks the verification of the membership of a key in an IAVL store
+/// mounted in a multistore, by proofs whose depth grows with the store.
+fn bench_verify_membership(c: &mut Criterion) {
+    let specs = ProofSpecs::default();
+
+    let mut group = c.benchmark_group("merkle_verify_membership");
+    for size in STORE_SIZES {
+        let mut store = MerkleStore::new(TreeLayout::Iavl);
+        for i in 0..size {
+            store.set(
+                format!("key-{i:08}").into_bytes(),
+                format!("value-{i}").into_bytes(),
+            );
+        }
+        let mut multistore = MultiStore::new();
+        multistore.mount("ibc", store);
+
+        let key = format!("key-{:08}", size / 2).into_bytes();
+        let value = format!("value-{}", size / 2).into_bytes();
+        let root = multistore.root();
+        let proof = multistore
+            .proof(b"ibc", &key)
+            .expect("the store is mounted and not empty");
+        let depth = match &proof.proofs[0].proof {
+            Some(Proof::Exist(proof)) => proof.path.len(),
+            _ => unreachable!("the key is in the store"),
+        };
+        let path = MerklePath {
+            key_path: vec![
+                "ibc".to_string(),
+                String::from_utf8(key).expect("the key is UTF-8"),
+            ],
+        };
+
+        group.bench_function(BenchmarkId::new("depth", depth), |b| {
+            b.iter(|| {
+                proof
+                    .verify_membership(&specs, root.clone().into(), path.clone(), value.clone(), 0)
+                    .expect("the proof is valid")
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Returns a chain, at height 10, with an open transfer channel over a mock
+/// client at height 5.
+fn packet_chain() -> ChainSetup {
+    ChainSetup::new(
+        ChainId::new("mockgaiaA", 0).expect("valid chain identifier"),
+        10,
+    )
+    .with_timestamp(host_timestamp())
+    .with_open_transfer_channel(Height::new(0, 5).expect("non-zero height"))
+}
+
+fn recv_packet_msg() -> MsgRecvPacket {
+    MsgRecvPacket {
+        packet: Packet {
+            seq_on_a: Sequence::from(1),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::new(0),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(0),
+            data: br#"{"amount":"100"}"#.to_vec(),
+            timeout_height_on_b: TimeoutHeight::At(Height::new(0, 100).expect("non-zero height")),
+            timeout_timestamp_on_b: Timestamp::none(),
+        },
+        proof_commitment_on_a: CommitmentProofBytes::try_from(vec![1])
+            .expect("the proof is not empty"),
+        proof_height_on_a: Height::new(0, 5).expect("non-zero height"),
+        signer: get_dummy_account_id(),
+    }
+}
+
+/// Benchmarks the validation and the execution of the receipt of a packet
+/// on a mock context, through the routing of the core handlers.
+fn bench_recv_packet(c: &mut Criterion) {
+    let mut router = transfer_router();
+    let ctx = packet_chain()
+        .build(&router)
+        .expect("the chain setup is valid");
+    let msg = MsgEnvelope::Packet(PacketMsg::Recv(recv_packet_msg()));
+
+    let mut group = c.benchmark_group("recv_packet");
+    group.bench_function("validate", |b| {
+        b.iter(|| validate(&ctx, &router, msg.clone()).expect("the packet is receivable"))
+    });
+    // Each execution receives the packet on a fresh copy of the context,
+    // whose cloning is not measured.
+    group.bench_function("execute", |b| {
+        b.iter_batched(
+            || ctx.clone(),
+            |mut ctx| {
+                execute(&mut ctx, &mut router, msg.clone()).expect("the packet is receivable");
+                ctx
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+/// Benchmarks the decoding of messages from their `Any` encoding, for a
+/// small message and one carrying a Tendermint header.
+fn bench_decode_envelope(c: &mut Criterion) {
+    let client_id = ClientId::new(tm_client_type(), 0).expect("valid client identifier");
+    let chain = synthetic_tm_chain(VALIDATOR_SET_SIZES[1]);
+    let update_client = MsgUpdateClient {
+        client_id,
+        client_message: chain
+            .header(&tm_height(3), &tm_height(1))
+            .expect("the chain has blocks at heights 1 and 3")
+            .into(),
+        signer: get_dummy_account_id(),
+    };
+    let messages: [(&str, Any); 2] = [
+        ("recv_packet", recv_packet_msg().to_any()),
+        ("update_client_tm", update_client.to_any()),
+    ];
+
+    let mut group = c.benchmark_group("msg_envelope_try_from_any");
+    for (name, any) in messages {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &any, |b, any| {
+            b.iter_batched(
+                || any.clone(),
+                |any| MsgEnvelope::try_from(any).expect("the message is valid"),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks the parsing of denominations with trace paths of growing
+/// length.
+fn bench_parse_denom(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefixed_denom_from_str");
+    for hops in TRACE_LENGTHS {
+        let denom: String = (0..hops)
+            .map(|i| format!("transfer/channel-{i}/"))
+            .chain(core::iter::once("uatom".to_string()))
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("hops", hops), &denom, |b, denom| {
+            b.iter(|| PrefixedDenom::from_str(black_box(denom)).expect("the denom is valid"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_verify_header,
+    bench_verify_membership,
+    bench_recv_packet,
+    bench_decode_envelope,
+    bench_parse_denom
+);
+criterion_main!(benches);
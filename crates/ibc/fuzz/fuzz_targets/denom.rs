@@ -0,0 +1,26 @@
+//! Parses arbitrary strings as ICS-20 denominations and coins, and checks
+//! that the denominations that parse are displayed back the same way.
+
+#![no_main]
+
+use std::str::FromStr;
+
+use ibc::applications::transfer::{BaseCoin, Coin, PrefixedCoin, PrefixedDenom};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    if let Ok(denom) = PrefixedDenom::from_str(input) {
+        assert_eq!(
+            PrefixedDenom::from_str(&denom.to_string()).ok(),
+            Some(denom)
+        );
+    }
+    let _ = PrefixedCoin::from_str(input);
+    let _ = BaseCoin::from_str(input);
+    let _ = Coin::<PrefixedDenom>::from_string_list(input);
+});
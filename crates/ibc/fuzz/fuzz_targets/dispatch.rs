@@ -0,0 +1,17 @@
+//! Decodes arbitrary bytes as a transaction and dispatches its messages
+//! against a chain that already has a client, a connection and a channel, so
+//! that the handlers get past their first lookups.
+
+#![no_main]
+
+use ibc_fuzz::{dispatch_tx, seeded_chain};
+use ibc_proto::cosmos::tx::v1beta1::TxBody;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = TxBody::decode(data) {
+        let (mut ctx, mut router) = seeded_chain();
+        dispatch_tx(&mut ctx, &mut router, tx);
+    }
+});
@@ -0,0 +1,15 @@
+//! Decodes arbitrary bytes as an `Any` and then as an IBC message, the way a
+//! host unpacks the messages of a transaction.
+
+#![no_main]
+
+use ibc::core::MsgEnvelope;
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(any_msg) = Any::decode(data) {
+        let _ = MsgEnvelope::try_from(any_msg);
+    }
+});
@@ -0,0 +1,20 @@
+//! Parses arbitrary strings as ICS-24 store paths, and checks that the paths
+//! that parse are displayed back the same way.
+
+#![no_main]
+
+use std::str::FromStr;
+
+use ibc::core::ics24_host::path::Path;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    if let Ok(path) = Path::from_str(input) {
+        assert_eq!(Path::from_str(&path.to_string()).ok(), Some(path));
+    }
+});
@@ -0,0 +1,27 @@
+//! Decodes arbitrary bytes as a Tendermint header, both raw and wrapped in an
+//! `Any`, and checks that the headers that decode re-encode losslessly.
+
+#![no_main]
+
+use ibc::clients::ics07_tendermint::header::Header;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawHeader;
+use ibc_proto::protobuf::Protobuf;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for header in [
+        <Header as Protobuf<RawHeader>>::decode_vec(data),
+        <Header as Protobuf<Any>>::decode_vec(data),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let _ = header.validate_basic();
+
+        let encoded = <Header as Protobuf<RawHeader>>::encode_vec(&header);
+        let decoded = <Header as Protobuf<RawHeader>>::decode_vec(&encoded)
+            .expect("an encoded header decodes");
+        assert_eq!(decoded, header);
+    }
+});
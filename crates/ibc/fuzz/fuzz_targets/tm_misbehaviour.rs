@@ -0,0 +1,27 @@
+//! Decodes arbitrary bytes as a Tendermint misbehaviour, both raw and wrapped
+//! in an `Any`, and checks that the ones that decode re-encode losslessly.
+
+#![no_main]
+
+use ibc::clients::ics07_tendermint::misbehaviour::Misbehaviour;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::tendermint::v1::Misbehaviour as RawMisbehaviour;
+use ibc_proto::protobuf::Protobuf;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for misbehaviour in [
+        <Misbehaviour as Protobuf<RawMisbehaviour>>::decode_vec(data),
+        <Misbehaviour as Protobuf<Any>>::decode_vec(data),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let _ = misbehaviour.validate_basic();
+
+        let encoded = <Misbehaviour as Protobuf<RawMisbehaviour>>::encode_vec(&misbehaviour);
+        let decoded = <Misbehaviour as Protobuf<RawMisbehaviour>>::decode_vec(&encoded)
+            .expect("an encoded misbehaviour decodes");
+        assert_eq!(decoded, misbehaviour);
+    }
+});
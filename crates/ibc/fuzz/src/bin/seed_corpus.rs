@@ -0,0 +1,416 @@
+//! Writes the seed corpora of the fuzz targets to `corpus/<target>/`.
+//!
+//! The seeds are valid inputs built from the JSON fixtures of the `ibc` test
+//! suite and from the chain of [`ibc_fuzz::seeded_chain`], so that the fuzzer
+//! starts from inputs that get deep into the decoders and handlers.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use ibc::applications::transfer::packet::PacketData;
+use ibc::applications::transfer::PrefixedCoin;
+use ibc::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState as TmClientState};
+use ibc::clients::ics07_tendermint::client_type as tm_client_type;
+use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+use ibc::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+use ibc::clients::ics07_tendermint::trust_threshold::TrustThreshold;
+use ibc::core::ics02_client::msgs::create_client::MsgCreateClient;
+use ibc::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+use ibc::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use ibc::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+use ibc::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use ibc::core::ics03_connection::version::Version as ConnectionVersion;
+use ibc::core::ics04_channel::acknowledgement::Acknowledgement;
+use ibc::core::ics04_channel::channel::Order;
+use ibc::core::ics04_channel::msgs::{
+    MsgAcknowledgement, MsgChannelOpenInit, MsgRecvPacket, MsgTimeout,
+};
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics04_channel::timeout::TimeoutHeight;
+use ibc::core::ics04_channel::Version as ChannelVersion;
+use ibc::core::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::core::ics23_commitment::specs::ProofSpecs;
+use ibc::core::ics24_host::identifier::{ChainId, ClientId, PortId};
+use ibc::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConsensusStatePath, ClientStatePath, CommitmentPath,
+    ConnectionPath, ReceiptPath, SeqRecvPath, UpgradeClientPath,
+};
+use ibc::core::timestamp::Timestamp;
+use ibc::core::Msg;
+use ibc::mock::client_state::MockClientState;
+use ibc::mock::consensus_state::MockConsensusState;
+use ibc::mock::header::MockHeader;
+use ibc::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+use ibc::{Height, Signer};
+use ibc_fuzz::{channel_id, client_id, connection_id, tx, CLIENT_HEIGHT};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::tendermint::v1::{
+    Header as RawTmHeader, Misbehaviour as RawTmMisbehaviour,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use tendermint::block::signed_header::SignedHeader;
+use tendermint::validator::{Info as ValidatorInfo, Set as ValidatorSet};
+use tendermint::PublicKey;
+
+const FIXTURE_KEYS: [&str; 4] = [
+    include_str!("../../../tests/support/query/serialization/client_state.json"),
+    include_str!("../../../tests/support/query/serialization/client_state_proof.json"),
+    include_str!("../../../tests/support/query/serialization/consensus_state.json"),
+    include_str!("../../../tests/support/query/serialization/consensus_state_proof.json"),
+];
+
+fn height(revision_height: u64) -> Height {
+    Height::new(0, revision_height).expect("the height is not zero")
+}
+
+fn signer() -> Signer {
+    Signer::from("cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng".to_string())
+}
+
+fn proof() -> CommitmentProofBytes {
+    vec![1].try_into().expect("the proof is not empty")
+}
+
+/// Returns the header of `tests/support/signed_header.json`, signed by a
+/// single validator, as the `ibc` unit tests do.
+fn tm_header() -> TmHeader {
+    let signed_header = serde_json::from_str::<SignedHeader>(include_str!(
+        "../../../tests/support/signed_header.json"
+    ))
+    .expect("the fixture is a signed header");
+
+    let validator = ValidatorInfo::new(
+        PublicKey::from_raw_ed25519(
+            &subtle_encoding::hex::decode_upper(
+                "F349539C7E5EF7C49549B09C4BFC2335318AB0FE51FBFAA2433B4F13E816F4A7",
+            )
+            .expect("the key is hex encoded"),
+        )
+        .expect("the key is an ed25519 key"),
+        281_815_u64.try_into().expect("the voting power is valid"),
+    );
+    let validator_set = ValidatorSet::new(vec![validator.clone()], Some(validator));
+
+    TmHeader {
+        signed_header,
+        validator_set: validator_set.clone(),
+        trusted_height: Height::min(0),
+        trusted_next_validator_set: validator_set,
+    }
+}
+
+fn tm_client_state(header: &TmHeader) -> TmClientState {
+    TmClientState::new(
+        ChainId::new("test-chain", 1).expect("the chain name is valid"),
+        TrustThreshold::ONE_THIRD,
+        Duration::from_secs(64000),
+        Duration::from_secs(128000),
+        Duration::from_millis(3000),
+        header.height(),
+        ProofSpecs::default(),
+        vec![],
+        AllowUpdate {
+            after_expiry: false,
+            after_misbehaviour: false,
+        },
+    )
+    .expect("the client state is valid")
+}
+
+/// Returns a mock header with a fixed timestamp, so that the corpus does not
+/// change every time it is generated.
+fn mock_header(revision_height: u64) -> MockHeader {
+    let timestamp =
+        Timestamp::from_nanoseconds(1_690_000_000_000_000_000).expect("the timestamp is valid");
+    MockHeader::new(height(revision_height)).with_timestamp(timestamp)
+}
+
+/// Returns a packet sent to the seeded channel, which has not timed out yet.
+fn packet() -> Packet {
+    let data = PacketData {
+        token: "100uatom"
+            .parse::<PrefixedCoin>()
+            .expect("the coin is valid"),
+        sender: signer(),
+        receiver: signer(),
+        memo: "".to_string().into(),
+    };
+
+    Packet {
+        seq_on_a: Sequence::from(1),
+        port_id_on_a: PortId::transfer(),
+        chan_id_on_a: channel_id(),
+        port_id_on_b: PortId::transfer(),
+        chan_id_on_b: channel_id(),
+        data: serde_json::to_vec(&data).expect("the packet data serializes"),
+        timeout_height_on_b: TimeoutHeight::At(height(1000)),
+        timeout_timestamp_on_b: Timestamp::none(),
+    }
+}
+
+fn msgs() -> Vec<(&'static str, Any)> {
+    let tm_header = tm_header();
+    let tm_client_id = ClientId::new(tm_client_type(), 0).expect("the client type is valid");
+
+    vec![
+        (
+            "create_mock_client",
+            MsgCreateClient::new(
+                MockClientState::new(mock_header(CLIENT_HEIGHT)).into(),
+                MockConsensusState::new(mock_header(CLIENT_HEIGHT)).into(),
+                signer(),
+            )
+            .to_any(),
+        ),
+        (
+            "create_tm_client",
+            MsgCreateClient::new(
+                tm_client_state(&tm_header).into(),
+                TmConsensusState::from(tm_header.clone()).into(),
+                signer(),
+            )
+            .to_any(),
+        ),
+        (
+            "update_mock_client",
+            MsgUpdateClient {
+                client_id: client_id(),
+                client_message: mock_header(CLIENT_HEIGHT + 1).into(),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "update_tm_client",
+            MsgUpdateClient {
+                client_id: tm_client_id.clone(),
+                client_message: tm_header.clone().into(),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "submit_mock_misbehaviour",
+            MsgSubmitMisbehaviour {
+                client_id: client_id(),
+                misbehaviour: MockMisbehaviour {
+                    client_id: client_id(),
+                    header1: mock_header(CLIENT_HEIGHT),
+                    header2: mock_header(CLIENT_HEIGHT),
+                }
+                .into(),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "submit_tm_misbehaviour",
+            MsgSubmitMisbehaviour {
+                client_id: tm_client_id.clone(),
+                misbehaviour: TmMisbehaviour::new(tm_client_id, tm_header.clone(), tm_header)
+                    .into(),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "conn_open_init",
+            MsgConnectionOpenInit {
+                client_id_on_a: client_id(),
+                counterparty: ConnectionCounterparty::new(
+                    client_id(),
+                    None,
+                    b"ibc".to_vec().try_into().expect("the prefix is not empty"),
+                ),
+                version: Some(ConnectionVersion::default()),
+                delay_period: Duration::ZERO,
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "chan_open_init",
+            MsgChannelOpenInit {
+                port_id_on_a: PortId::transfer(),
+                connection_hops_on_a: vec![connection_id()],
+                port_id_on_b: PortId::transfer(),
+                ordering: Order::Unordered,
+                signer: signer(),
+                version_proposal: ChannelVersion::new("ics20-1".to_string()),
+            }
+            .to_any(),
+        ),
+        (
+            "recv_packet",
+            MsgRecvPacket {
+                packet: packet(),
+                proof_commitment_on_a: proof(),
+                proof_height_on_a: height(CLIENT_HEIGHT),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "acknowledgement",
+            MsgAcknowledgement {
+                packet: packet(),
+                acknowledgement: Acknowledgement::try_from(br#"{"result":"AQ=="}"#.to_vec())
+                    .expect("the acknowledgement is not empty"),
+                proof_acked_on_b: proof(),
+                proof_height_on_b: height(CLIENT_HEIGHT),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+        (
+            "timeout",
+            MsgTimeout {
+                packet: packet(),
+                next_seq_recv_on_b: Sequence::from(1),
+                proof_unreceived_on_b: proof(),
+                proof_height_on_b: height(CLIENT_HEIGHT),
+                signer: signer(),
+            }
+            .to_any(),
+        ),
+    ]
+}
+
+/// Returns the keys under which the fixtures of query responses were stored.
+fn fixture_keys() -> Vec<String> {
+    FIXTURE_KEYS
+        .iter()
+        .map(|fixture| {
+            let response: serde_json::Value =
+                serde_json::from_str(fixture).expect("the fixture is JSON");
+            let key = response["key"].as_str().expect("the fixture has a key");
+            let key = subtle_encoding::base64::decode(key).expect("the key is base64 encoded");
+            String::from_utf8(key).expect("the key is UTF-8")
+        })
+        .collect()
+}
+
+fn paths() -> Vec<String> {
+    let mut paths: Vec<String> = [
+        ClientStatePath::new(&client_id()).to_string(),
+        ClientConsensusStatePath::new(&client_id(), &height(CLIENT_HEIGHT)).to_string(),
+        ConnectionPath::new(&connection_id()).to_string(),
+        ChannelEndPath::new(&PortId::transfer(), &channel_id()).to_string(),
+        SeqRecvPath::new(&PortId::transfer(), &channel_id()).to_string(),
+        CommitmentPath::new(&PortId::transfer(), &channel_id(), Sequence::from(1)).to_string(),
+        AckPath::new(&PortId::transfer(), &channel_id(), Sequence::from(1)).to_string(),
+        ReceiptPath::new(&PortId::transfer(), &channel_id(), Sequence::from(1)).to_string(),
+        UpgradeClientPath::UpgradedClientState(CLIENT_HEIGHT).to_string(),
+    ]
+    .into();
+    paths.extend(fixture_keys());
+    paths
+}
+
+/// The messages of [`msgs`] that the seeded chain accepts one after the other.
+/// The handshakes come last, as the channel they open replaces the seeded one.
+const ACCEPTED_MSGS: [&str; 6] = [
+    "update_mock_client",
+    "recv_packet",
+    "acknowledgement",
+    "timeout",
+    "conn_open_init",
+    "chan_open_init",
+];
+
+const DENOMS: [&str; 7] = [
+    "uatom",
+    "transfer/channel-0/uatom",
+    "transfer/channel-0/transfer/channel-1/gamm/pool/1",
+    "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+    "100uatom",
+    "100transfer/channel-0/uatom",
+    "100uatom,25transfer/channel-0/uosmo",
+];
+
+fn write_corpus(target: &str, seeds: impl IntoIterator<Item = (String, Vec<u8>)>) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("corpus")
+        .join(target);
+    fs::create_dir_all(&dir).expect("the corpus directory can be created");
+    for (name, seed) in seeds {
+        fs::write(dir.join(name), seed).expect("the seed can be written");
+    }
+}
+
+fn main() {
+    let tm_header = tm_header();
+    let tm_misbehaviour = TmMisbehaviour::new(
+        ClientId::new(tm_client_type(), 0).expect("the client type is valid"),
+        tm_header.clone(),
+        tm_header.clone(),
+    );
+    let msgs = msgs();
+
+    write_corpus(
+        "msg_envelope",
+        msgs.iter()
+            .map(|(name, msg)| (name.to_string(), msg.encode_to_vec())),
+    );
+    write_corpus(
+        "tm_header",
+        [
+            (
+                "raw".to_string(),
+                <TmHeader as Protobuf<RawTmHeader>>::encode_vec(&tm_header),
+            ),
+            (
+                "any".to_string(),
+                <TmHeader as Protobuf<Any>>::encode_vec(&tm_header),
+            ),
+        ],
+    );
+    write_corpus(
+        "tm_misbehaviour",
+        [
+            (
+                "raw".to_string(),
+                <TmMisbehaviour as Protobuf<RawTmMisbehaviour>>::encode_vec(&tm_misbehaviour),
+            ),
+            (
+                "any".to_string(),
+                <TmMisbehaviour as Protobuf<Any>>::encode_vec(&tm_misbehaviour),
+            ),
+        ],
+    );
+    write_corpus(
+        "denom",
+        DENOMS
+            .iter()
+            .enumerate()
+            .map(|(i, denom)| (format!("denom-{i}"), denom.as_bytes().to_vec())),
+    );
+    write_corpus(
+        "path",
+        paths()
+            .into_iter()
+            .enumerate()
+            .map(|(i, path)| (format!("path-{i}"), path.into_bytes())),
+    );
+
+    // Each message alone, then the ones the seeded chain accepts together.
+    let mut txs: Vec<_> = msgs
+        .iter()
+        .map(|(name, msg)| (name.to_string(), tx(vec![msg.clone()]).encode_to_vec()))
+        .collect();
+    let accepted = ACCEPTED_MSGS
+        .iter()
+        .map(|accepted| {
+            let (_, msg) = msgs
+                .iter()
+                .find(|(name, _)| name == accepted)
+                .expect("the accepted message is a seed");
+            msg.clone()
+        })
+        .collect();
+    txs.push(("accepted".to_string(), tx(accepted).encode_to_vec()));
+    write_corpus("dispatch", txs);
+}
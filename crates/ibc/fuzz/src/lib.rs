@@ -0,0 +1,136 @@
+//! Shared setup for the fuzz targets of the `ibc` crate.
+//!
+//! The targets that run handlers do so against the chain built by
+//! [`seeded_chain`], whose identifiers are exported here so that the seed
+//! corpora can reference them.
+
+use ibc::applications::transfer::MODULE_ID_STR;
+use ibc::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use ibc::core::ics03_connection::version::get_compatible_versions;
+use ibc::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+};
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics04_channel::Version as ChannelVersion;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::router::ModuleId;
+use ibc::core::timestamp::ZERO_DURATION;
+use ibc::core::{dispatch, ExecutionContext, MsgEnvelope, ValidationContext};
+use ibc::mock::client_state::client_type as mock_client_type;
+use ibc::mock::context::MockContext;
+use ibc::mock::host::HostType;
+use ibc::mock::router::MockRouter;
+use ibc::test_utils::DummyTransferModule;
+use ibc::Height;
+use ibc_proto::cosmos::tx::v1beta1::TxBody;
+use ibc_proto::google::protobuf::Any;
+
+/// The height of the seeded chain.
+pub const HOST_HEIGHT: u64 = 20;
+
+/// The height of the mock client of the seeded chain.
+pub const CLIENT_HEIGHT: u64 = 10;
+
+pub fn host_chain_id() -> ChainId {
+    ChainId::new("mockgaia", 0).expect("the chain name is valid")
+}
+
+pub fn client_id() -> ClientId {
+    ClientId::new(mock_client_type(), 0).expect("the mock client type is valid")
+}
+
+pub fn connection_id() -> ConnectionId {
+    ConnectionId::new(0)
+}
+
+pub fn channel_id() -> ChannelId {
+    ChannelId::new(0)
+}
+
+/// Returns a mock chain with a mock client, an open connection on it and an
+/// open unordered ICS-20 channel on that connection, together with a router
+/// serving the `transfer` port.
+pub fn seeded_chain() -> (MockContext, MockRouter) {
+    let client_id = client_id();
+
+    let conn_end = ConnectionEnd::new(
+        ConnectionState::Open,
+        client_id.clone(),
+        ConnectionCounterparty::new(
+            client_id.clone(),
+            Some(connection_id()),
+            b"ibc".to_vec().try_into().expect("the prefix is not empty"),
+        ),
+        get_compatible_versions(),
+        ZERO_DURATION,
+    )
+    .expect("the connection end is valid");
+
+    let chan_end = ChannelEnd::new(
+        ChannelState::Open,
+        Order::Unordered,
+        ChannelCounterparty::new(PortId::transfer(), Some(channel_id())),
+        vec![connection_id()],
+        ChannelVersion::new("ics20-1".to_string()),
+    )
+    .expect("the channel end is valid");
+
+    let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+    let client_height = Height::new(0, CLIENT_HEIGHT).expect("the height is not zero");
+
+    let mut ctx = MockContext::new(
+        host_chain_id(),
+        HostType::Mock,
+        5,
+        Height::new(0, HOST_HEIGHT).expect("the height is not zero"),
+    )
+    .with_client(&client_id, client_height)
+    .with_connection(connection_id(), conn_end)
+    .with_channel(PortId::transfer(), channel_id(), chan_end)
+    .with_send_sequence(PortId::transfer(), channel_id(), Sequence::from(1))
+    .with_recv_sequence(PortId::transfer(), channel_id(), Sequence::from(1))
+    .with_ack_sequence(PortId::transfer(), channel_id(), Sequence::from(1))
+    .with_port(PortId::transfer(), module_id.clone());
+
+    // Packet handlers only accept proofs at heights the client was updated to.
+    let host_timestamp = ctx.host_timestamp().expect("the host has a timestamp");
+    let host_height = ctx.host_height().expect("the host has a height");
+    ctx.store_update_time(client_id.clone(), client_height, host_timestamp)
+        .expect("the mock store accepts writes");
+    ctx.store_update_height(client_id, client_height, host_height)
+        .expect("the mock store accepts writes");
+
+    let mut router = MockRouter::default();
+    router
+        .add_route(module_id.clone(), DummyTransferModule::new())
+        .expect("the route is new");
+    router
+        .scope_port_to_module(PortId::transfer(), module_id)
+        .expect("the port is not scoped yet");
+
+    (ctx, router)
+}
+
+/// Dispatches the messages of a transaction in order, and stops at the first
+/// one that fails to decode or to be handled, as a chain would abort it.
+pub fn dispatch_tx(ctx: &mut MockContext, router: &mut MockRouter, tx: TxBody) {
+    for any_msg in tx.messages {
+        let msg = match MsgEnvelope::try_from(any_msg) {
+            Ok(msg) => msg,
+            Err(_) => return,
+        };
+        if dispatch(ctx, router, msg).is_err() {
+            return;
+        }
+    }
+}
+
+/// Wraps `msgs` in a transaction body.
+pub fn tx(msgs: Vec<Any>) -> TxBody {
+    TxBody {
+        messages: msgs,
+        ..Default::default()
+    }
+}
@@ -5,6 +5,7 @@ use super::ack_success_b64;
 use super::error::TokenTransferError;
 use crate::applications::transfer::events::{AckEvent, AckStatusEvent, RecvEvent, TimeoutEvent};
 use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::params::Params;
 use crate::applications::transfer::relay::on_recv_packet::process_recv_packet_execute;
 use crate::applications::transfer::relay::{
     refund_packet_token_execute, refund_packet_token_validate,
@@ -34,11 +35,38 @@ pub trait TokenTransferValidationContext {
         channel_id: &ChannelId,
     ) -> Result<Self::AccountId, TokenTransferError>;
 
+    /// Returns the parameters of the token transfer module.
+    fn get_params(&self) -> Result<Params, TokenTransferError>;
+
+    /// Returns `true` if `signer` is allowed to update the parameters of the
+    /// token transfer module.
+    fn is_transfer_params_authority(&self, signer: &Signer) -> bool;
+
     /// Returns Ok() if the host chain supports sending coins.
-    fn can_send_coins(&self) -> Result<(), TokenTransferError>;
+    ///
+    /// Defaults to the `send_enabled` parameter.
+    fn can_send_coins(&self) -> Result<(), TokenTransferError> {
+        if self.get_params()?.send_enabled {
+            Ok(())
+        } else {
+            Err(TokenTransferError::SendDisabled {
+                reason: "sending is disabled by the transfer params".to_string(),
+            })
+        }
+    }
 
     /// Returns Ok() if the host chain supports receiving coins.
-    fn can_receive_coins(&self) -> Result<(), TokenTransferError>;
+    ///
+    /// Defaults to the `receive_enabled` parameter.
+    fn can_receive_coins(&self) -> Result<(), TokenTransferError> {
+        if self.get_params()?.receive_enabled {
+            Ok(())
+        } else {
+            Err(TokenTransferError::ReceiveDisabled {
+                reason: "receiving is disabled by the transfer params".to_string(),
+            })
+        }
+    }
 
     /// Validates the sender and receiver accounts and the coin inputs
     fn send_coins_validate(
@@ -71,6 +99,9 @@ pub trait TokenTransferValidationContext {
 
 /// Methods required in token transfer execution, to be implemented by the host
 pub trait TokenTransferExecutionContext: TokenTransferValidationContext {
+    /// Stores the parameters of the token transfer module.
+    fn store_params(&mut self, params: Params) -> Result<(), TokenTransferError>;
+
     /// This function should enable sending ibc fungible tokens from one account to another
     fn send_coins_execute(
         &mut self,
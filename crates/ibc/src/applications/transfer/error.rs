@@ -12,6 +12,7 @@ use crate::core::ics04_channel::channel::Order;
 use crate::core::ics24_host::identifier::{ChannelId, IdentifierError, PortId};
 use crate::core::ContextError;
 use crate::prelude::*;
+use crate::signer::Signer;
 
 #[derive(Display, Debug)]
 pub enum TokenTransferError {
@@ -72,6 +73,12 @@ pub enum TokenTransferError {
     InvalidCoin { coin: String },
     /// decoding raw bytes as UTF8 string error: `{0}`
     Utf8Decode(Utf8Error),
+    /// missing transfer params
+    MissingParams,
+    /// denomination trace not found for hash `{hash}`
+    DenomTraceNotFound { hash: String },
+    /// signer `{signer}` is not the params authority
+    UnauthorizedParamsUpdate { signer: Signer },
 }
 
 #[cfg(feature = "std")]
@@ -9,6 +9,7 @@ pub mod events;
 pub mod memo;
 pub mod msgs;
 pub mod packet;
+pub mod params;
 
 pub use amount::*;
 pub use coin::*;
@@ -16,8 +17,10 @@ pub use denom::*;
 pub use memo::*;
 
 mod relay;
+mod update_params;
 
 pub use relay::send_transfer::{send_transfer, send_transfer_execute, send_transfer_validate};
+pub use update_params::{update_params, update_params_execute, update_params_validate};
 
 /// Module identifier for the ICS20 application.
 pub const MODULE_ID_STR: &str = "transfer";
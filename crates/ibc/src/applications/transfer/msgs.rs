@@ -1,3 +1,4 @@
 //! Defines the token transfer message type
 
 pub mod transfer;
+pub mod update_params;
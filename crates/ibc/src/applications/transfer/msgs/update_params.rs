@@ -0,0 +1,74 @@
+//! Defines the message updating the token transfer parameters
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::transfer::v1::MsgUpdateParams as RawMsgUpdateParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::applications::transfer::error::TokenTransferError;
+use crate::applications::transfer::params::Params;
+use crate::core::Msg;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub(crate) const TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgUpdateParams";
+
+/// Message replacing the parameters of the token transfer module. It must be
+/// signed by the params authority.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct MsgUpdateParams {
+    pub params: Params,
+    pub signer: Signer,
+}
+
+impl Msg for MsgUpdateParams {
+    type Raw = RawMsgUpdateParams;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgUpdateParams> for MsgUpdateParams {}
+
+impl TryFrom<RawMsgUpdateParams> for MsgUpdateParams {
+    type Error = TokenTransferError;
+
+    fn try_from(raw: RawMsgUpdateParams) -> Result<Self, Self::Error> {
+        Ok(MsgUpdateParams {
+            params: raw
+                .params
+                .ok_or(TokenTransferError::MissingParams)?
+                .try_into()?,
+            signer: raw.signer.into(),
+        })
+    }
+}
+
+impl From<MsgUpdateParams> for RawMsgUpdateParams {
+    fn from(domain_msg: MsgUpdateParams) -> Self {
+        RawMsgUpdateParams {
+            signer: domain_msg.signer.to_string(),
+            params: Some(domain_msg.params.into()),
+        }
+    }
+}
+
+impl TryFrom<Any> for MsgUpdateParams {
+    type Error = TokenTransferError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => {
+                MsgUpdateParams::decode_vec(&raw.value).map_err(TokenTransferError::DecodeRawMsg)
+            }
+            _ => Err(TokenTransferError::UnknownMsgType {
+                msg_type: raw.type_url,
+            }),
+        }
+    }
+}
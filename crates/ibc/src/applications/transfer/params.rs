@@ -0,0 +1,62 @@
+//! Defines the parameters of the token transfer module.
+
+use ibc_proto::ibc::applications::transfer::v1::Params as RawParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::applications::transfer::error::TokenTransferError;
+use crate::prelude::*;
+
+/// The parameters of the token transfer module.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    /// Whether tokens can be sent from this chain.
+    pub send_enabled: bool,
+    /// Whether tokens can be received on this chain.
+    pub receive_enabled: bool,
+}
+
+impl Default for Params {
+    /// Enables both sending and receiving tokens.
+    fn default() -> Self {
+        Self {
+            send_enabled: true,
+            receive_enabled: true,
+        }
+    }
+}
+
+impl Protobuf<RawParams> for Params {}
+
+impl TryFrom<RawParams> for Params {
+    type Error = TokenTransferError;
+
+    fn try_from(raw: RawParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            send_enabled: raw.send_enabled,
+            receive_enabled: raw.receive_enabled,
+        })
+    }
+}
+
+impl From<Params> for RawParams {
+    fn from(value: Params) -> Self {
+        RawParams {
+            send_enabled: value.send_enabled,
+            receive_enabled: value.receive_enabled,
+        }
+    }
+}
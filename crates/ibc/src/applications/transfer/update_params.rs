@@ -0,0 +1,228 @@
+//! Implements the processing logic for the message updating the token
+//! transfer parameters.
+
+use super::context::{TokenTransferExecutionContext, TokenTransferValidationContext};
+use crate::applications::transfer::error::TokenTransferError;
+use crate::applications::transfer::msgs::update_params::MsgUpdateParams;
+
+/// Updates the token transfer parameters. Equivalent to calling
+/// [`update_params_validate`], followed by [`update_params_execute`].
+pub fn update_params(
+    ctx: &mut impl TokenTransferExecutionContext,
+    msg: MsgUpdateParams,
+) -> Result<(), TokenTransferError> {
+    update_params_validate(ctx, &msg)?;
+    update_params_execute(ctx, msg)
+}
+
+/// Validates that the message is signed by the params authority.
+pub fn update_params_validate(
+    ctx: &impl TokenTransferValidationContext,
+    msg: &MsgUpdateParams,
+) -> Result<(), TokenTransferError> {
+    if !ctx.is_transfer_params_authority(&msg.signer) {
+        return Err(TokenTransferError::UnauthorizedParamsUpdate {
+            signer: msg.signer.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Stores the new parameters. Assumes that [`update_params_validate`] was
+/// called first.
+pub fn update_params_execute(
+    ctx: &mut impl TokenTransferExecutionContext,
+    msg: MsgUpdateParams,
+) -> Result<(), TokenTransferError> {
+    ctx.store_params(msg.params)
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::applications::transfer::v1::MsgUpdateParams as RawMsgUpdateParams;
+    use test_log::test;
+
+    use super::*;
+    use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::packet::PacketData;
+    use crate::applications::transfer::params::Params;
+    use crate::applications::transfer::relay::send_transfer::send_transfer_validate;
+    use crate::applications::transfer::PrefixedCoin;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::timestamp::Timestamp;
+    use crate::mock::context::MockContext;
+    use crate::prelude::*;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_account_id, get_dummy_bech32_account, DummyTransferModule};
+
+    /// A token transfer context storing its params, which can only be
+    /// updated by `authority`.
+    struct ParamsContext {
+        params: Params,
+        authority: Signer,
+    }
+
+    impl ParamsContext {
+        fn new() -> Self {
+            Self {
+                params: Params::default(),
+                authority: get_dummy_bech32_account().into(),
+            }
+        }
+    }
+
+    impl TokenTransferValidationContext for ParamsContext {
+        type AccountId = Signer;
+
+        fn get_port(&self) -> Result<PortId, TokenTransferError> {
+            Ok(PortId::transfer())
+        }
+
+        fn get_escrow_account(
+            &self,
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+        ) -> Result<Self::AccountId, TokenTransferError> {
+            Ok(get_dummy_account_id())
+        }
+
+        fn get_params(&self) -> Result<Params, TokenTransferError> {
+            Ok(self.params)
+        }
+
+        fn is_transfer_params_authority(&self, signer: &Signer) -> bool {
+            signer == &self.authority
+        }
+
+        fn send_coins_validate(
+            &self,
+            _from_account: &Self::AccountId,
+            _to_account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn mint_coins_validate(
+            &self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn burn_coins_validate(
+            &self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+    }
+
+    impl TokenTransferExecutionContext for ParamsContext {
+        fn store_params(&mut self, params: Params) -> Result<(), TokenTransferError> {
+            self.params = params;
+            Ok(())
+        }
+
+        fn send_coins_execute(
+            &mut self,
+            _from_account: &Self::AccountId,
+            _to_account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn mint_coins_execute(
+            &mut self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn burn_coins_execute(
+            &mut self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+    }
+
+    fn msg_transfer() -> MsgTransfer {
+        MsgTransfer {
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::default(),
+            packet_data: PacketData {
+                token: "100uatom".parse().unwrap(),
+                sender: get_dummy_account_id(),
+                receiver: get_dummy_account_id(),
+                memo: "".to_string().into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b: Timestamp::none(),
+        }
+    }
+
+    #[test]
+    fn update_params_requires_authority() {
+        let mut ctx = DummyTransferModule::new();
+        let msg = MsgUpdateParams {
+            params: Params {
+                send_enabled: false,
+                receive_enabled: true,
+            },
+            signer: get_dummy_account_id(),
+        };
+
+        let raw = RawMsgUpdateParams::from(msg.clone());
+        assert_eq!(MsgUpdateParams::try_from(raw).unwrap(), msg);
+
+        let res = update_params(&mut ctx, msg);
+        assert!(matches!(
+            res,
+            Err(TokenTransferError::UnauthorizedParamsUpdate { .. })
+        ));
+    }
+
+    #[test]
+    fn update_params_by_authority_disables_sending() {
+        let mut ctx = ParamsContext::new();
+        assert!(ctx.can_send_coins().is_ok());
+
+        let params = Params {
+            send_enabled: false,
+            receive_enabled: true,
+        };
+        let msg = MsgUpdateParams {
+            params,
+            signer: ctx.authority.clone(),
+        };
+        update_params(&mut ctx, msg).unwrap();
+
+        assert_eq!(ctx.get_params().unwrap(), params);
+        assert!(ctx.can_receive_coins().is_ok());
+        assert!(matches!(
+            send_transfer_validate(&MockContext::default(), &ctx, msg_transfer()),
+            Err(TokenTransferError::SendDisabled { .. })
+        ));
+    }
+
+    #[test]
+    fn update_params_rejects_missing_params() {
+        let raw = RawMsgUpdateParams {
+            signer: get_dummy_bech32_account(),
+            params: None,
+        };
+
+        assert!(matches!(
+            MsgUpdateParams::try_from(raw),
+            Err(TokenTransferError::MissingParams)
+        ));
+    }
+}
@@ -0,0 +1,420 @@
+//! Strategies for the ICS-04 channel types, packets and messages.
+
+use proptest::prelude::*;
+
+use super::{impl_arbitrary, non_empty_bytes};
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::Version;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::timestamp::Timestamp;
+use crate::prelude::*;
+use crate::{Height, Signer};
+
+fn order() -> impl Strategy<Value = Order> {
+    prop_oneof![Just(Order::Unordered), Just(Order::Ordered)]
+}
+
+impl_arbitrary!(Sequence, (1..=u64::MAX).prop_map(Sequence::from));
+
+impl_arbitrary!(Version, any::<String>().prop_map(Version::new));
+
+impl_arbitrary!(
+    TimeoutHeight,
+    prop_oneof![
+        Just(TimeoutHeight::Never),
+        any::<Height>().prop_map(TimeoutHeight::At),
+    ]
+);
+
+impl_arbitrary!(
+    Acknowledgement,
+    non_empty_bytes(256)
+        .prop_map(|bytes| Acknowledgement::try_from(bytes).expect("the ack is not empty"))
+);
+
+impl_arbitrary!(
+    Counterparty,
+    (any::<PortId>(), any::<Option<ChannelId>>())
+        .prop_map(|(port_id, channel_id)| Counterparty::new(port_id, channel_id))
+);
+
+impl_arbitrary!(
+    ChannelEnd,
+    (
+        prop_oneof![
+            Just(State::Init),
+            Just(State::TryOpen),
+            Just(State::Open),
+            Just(State::Closed),
+        ],
+        order(),
+        any::<Counterparty>(),
+        proptest::collection::vec(any::<ConnectionId>(), 0..3),
+        any::<Version>(),
+    )
+        .prop_map(|(state, ordering, remote, connection_hops, version)| {
+            ChannelEnd::new(state, ordering, remote, connection_hops, version)
+                .expect("the channel end is valid")
+        })
+);
+
+// Packets always have a timeout: when their timeout height is unset, their
+// timeout timestamp is not.
+impl_arbitrary!(
+    Packet,
+    (
+        any::<Sequence>(),
+        (
+            any::<PortId>(),
+            any::<ChannelId>(),
+            any::<PortId>(),
+            any::<ChannelId>()
+        ),
+        non_empty_bytes(256),
+        prop_oneof![
+            (any::<Height>(), any::<Timestamp>())
+                .prop_map(|(height, timestamp)| (TimeoutHeight::At(height), timestamp)),
+            (1..=u64::MAX).prop_map(|nanos| {
+                let timestamp =
+                    Timestamp::from_nanoseconds(nanos).expect("any u64 is a valid timestamp");
+                (TimeoutHeight::Never, timestamp)
+            }),
+        ],
+    )
+        .prop_map(
+            |(
+                seq_on_a,
+                (port_id_on_a, chan_id_on_a, port_id_on_b, chan_id_on_b),
+                data,
+                (timeout_height_on_b, timeout_timestamp_on_b),
+            )| Packet {
+                seq_on_a,
+                port_id_on_a,
+                chan_id_on_a,
+                port_id_on_b,
+                chan_id_on_b,
+                data,
+                timeout_height_on_b,
+                timeout_timestamp_on_b,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgChannelOpenInit,
+    (
+        any::<PortId>(),
+        proptest::collection::vec(any::<ConnectionId>(), 0..3),
+        any::<PortId>(),
+        order(),
+        any::<Signer>(),
+        any::<Version>(),
+    )
+        .prop_map(
+            |(
+                port_id_on_a,
+                connection_hops_on_a,
+                port_id_on_b,
+                ordering,
+                signer,
+                version_proposal,
+            )| MsgChannelOpenInit {
+                port_id_on_a,
+                connection_hops_on_a,
+                port_id_on_b,
+                ordering,
+                signer,
+                version_proposal,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgChannelOpenTry,
+    (
+        (
+            any::<PortId>(),
+            proptest::collection::vec(any::<ConnectionId>(), 0..3),
+        ),
+        (any::<PortId>(), any::<ChannelId>()),
+        any::<Version>(),
+        (any::<CommitmentProofBytes>(), any::<Height>()),
+        order(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(
+                (port_id_on_b, connection_hops_on_b),
+                (port_id_on_a, chan_id_on_a),
+                version_supported_on_a,
+                (proof_chan_end_on_a, proof_height_on_a),
+                ordering,
+                signer,
+            )| {
+                // The deprecated version proposal is not carried by the raw
+                // message, so it is always empty once decoded.
+                #[allow(deprecated)]
+                MsgChannelOpenTry {
+                    port_id_on_b,
+                    connection_hops_on_b,
+                    port_id_on_a,
+                    chan_id_on_a,
+                    version_supported_on_a,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    ordering,
+                    signer,
+                    version_proposal: Version::empty(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgChannelOpenAck,
+    (
+        (any::<PortId>(), any::<ChannelId>(), any::<ChannelId>()),
+        any::<Version>(),
+        (any::<CommitmentProofBytes>(), any::<Height>()),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(
+                (port_id_on_a, chan_id_on_a, chan_id_on_b),
+                version_on_b,
+                (proof_chan_end_on_b, proof_height_on_b),
+                signer,
+            )| MsgChannelOpenAck {
+                port_id_on_a,
+                chan_id_on_a,
+                chan_id_on_b,
+                version_on_b,
+                proof_chan_end_on_b,
+                proof_height_on_b,
+                signer,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgChannelOpenConfirm,
+    (
+        any::<PortId>(),
+        any::<ChannelId>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(port_id_on_b, chan_id_on_b, proof_chan_end_on_a, proof_height_on_a, signer)| {
+                MsgChannelOpenConfirm {
+                    port_id_on_b,
+                    chan_id_on_b,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgChannelCloseInit,
+    (any::<PortId>(), any::<ChannelId>(), any::<Signer>()).prop_map(
+        |(port_id_on_a, chan_id_on_a, signer)| {
+            MsgChannelCloseInit {
+                port_id_on_a,
+                chan_id_on_a,
+                signer,
+            }
+        }
+    )
+);
+
+impl_arbitrary!(
+    MsgChannelCloseConfirm,
+    (
+        any::<PortId>(),
+        any::<ChannelId>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(port_id_on_b, chan_id_on_b, proof_chan_end_on_a, proof_height_on_a, signer)| {
+                MsgChannelCloseConfirm {
+                    port_id_on_b,
+                    chan_id_on_b,
+                    proof_chan_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgRecvPacket,
+    (
+        any::<Packet>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(packet, proof_commitment_on_a, proof_height_on_a, signer)| MsgRecvPacket {
+                packet,
+                proof_commitment_on_a,
+                proof_height_on_a,
+                signer,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgAcknowledgement,
+    (
+        any::<Packet>(),
+        any::<Acknowledgement>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(packet, acknowledgement, proof_acked_on_b, proof_height_on_b, signer)| {
+                MsgAcknowledgement {
+                    packet,
+                    acknowledgement,
+                    proof_acked_on_b,
+                    proof_height_on_b,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgTimeout,
+    (
+        any::<Packet>(),
+        any::<Sequence>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(packet, next_seq_recv_on_b, proof_unreceived_on_b, proof_height_on_b, signer)| {
+                MsgTimeout {
+                    packet,
+                    next_seq_recv_on_b,
+                    proof_unreceived_on_b,
+                    proof_height_on_b,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgTimeoutOnClose,
+    (
+        any::<Packet>(),
+        any::<Sequence>(),
+        (any::<CommitmentProofBytes>(), any::<CommitmentProofBytes>()),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(
+                packet,
+                next_seq_recv_on_b,
+                (proof_unreceived_on_b, proof_close_on_b),
+                proof_height_on_b,
+                signer,
+            )| MsgTimeoutOnClose {
+                packet,
+                next_seq_recv_on_b,
+                proof_unreceived_on_b,
+                proof_close_on_b,
+                proof_height_on_b,
+                signer,
+            }
+        )
+);
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::channel::v1::{
+        Channel as RawChannel, MsgAcknowledgement as RawMsgAcknowledgement,
+        MsgChannelCloseConfirm as RawMsgChannelCloseConfirm,
+        MsgChannelCloseInit as RawMsgChannelCloseInit, MsgChannelOpenAck as RawMsgChannelOpenAck,
+        MsgChannelOpenConfirm as RawMsgChannelOpenConfirm,
+        MsgChannelOpenInit as RawMsgChannelOpenInit, MsgChannelOpenTry as RawMsgChannelOpenTry,
+        MsgRecvPacket as RawMsgRecvPacket, MsgTimeout as RawMsgTimeout,
+        MsgTimeoutOnClose as RawMsgTimeoutOnClose, Packet as RawPacket,
+    };
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn channel_end_and_packet_round_trip(chan_end in any::<ChannelEnd>(), packet in any::<Packet>()) {
+            prop_assert_eq!(ChannelEnd::try_from(RawChannel::from(chan_end.clone())).ok(), Some(chan_end));
+            prop_assert_eq!(Packet::try_from(RawPacket::from(packet.clone())).ok(), Some(packet));
+        }
+
+        #[test]
+        fn handshake_msgs_round_trip(
+            open_init in any::<MsgChannelOpenInit>(),
+            open_try in any::<MsgChannelOpenTry>(),
+            open_ack in any::<MsgChannelOpenAck>(),
+            open_confirm in any::<MsgChannelOpenConfirm>(),
+            close_init in any::<MsgChannelCloseInit>(),
+            close_confirm in any::<MsgChannelCloseConfirm>(),
+        ) {
+            prop_assert_eq!(MsgChannelOpenInit::try_from(RawMsgChannelOpenInit::from(open_init.clone())).ok(), Some(open_init));
+            prop_assert_eq!(MsgChannelOpenTry::try_from(RawMsgChannelOpenTry::from(open_try.clone())).ok(), Some(open_try));
+            prop_assert_eq!(MsgChannelOpenAck::try_from(RawMsgChannelOpenAck::from(open_ack.clone())).ok(), Some(open_ack));
+            prop_assert_eq!(
+                MsgChannelOpenConfirm::try_from(RawMsgChannelOpenConfirm::from(open_confirm.clone())).ok(),
+                Some(open_confirm)
+            );
+            prop_assert_eq!(MsgChannelCloseInit::try_from(RawMsgChannelCloseInit::from(close_init.clone())).ok(), Some(close_init));
+            prop_assert_eq!(
+                MsgChannelCloseConfirm::try_from(RawMsgChannelCloseConfirm::from(close_confirm.clone())).ok(),
+                Some(close_confirm)
+            );
+        }
+
+        #[test]
+        fn packet_msgs_round_trip(
+            recv in any::<MsgRecvPacket>(),
+            ack in any::<MsgAcknowledgement>(),
+            timeout in any::<MsgTimeout>(),
+            timeout_on_close in any::<MsgTimeoutOnClose>(),
+        ) {
+            prop_assert_eq!(MsgRecvPacket::try_from(RawMsgRecvPacket::from(recv.clone())).ok(), Some(recv));
+            prop_assert_eq!(MsgAcknowledgement::try_from(RawMsgAcknowledgement::from(ack.clone())).ok(), Some(ack));
+            prop_assert_eq!(MsgTimeout::try_from(RawMsgTimeout::from(timeout.clone())).ok(), Some(timeout));
+            prop_assert_eq!(
+                MsgTimeoutOnClose::try_from(RawMsgTimeoutOnClose::from(timeout_on_close.clone())).ok(),
+                Some(timeout_on_close)
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Strategies for the circuit breaker messages.
+
+use proptest::prelude::*;
+
+use super::impl_arbitrary;
+use crate::core::circuit_breaker::{Circuit, MsgResetCircuit, MsgTripCircuit};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::prelude::*;
+use crate::Signer;
+
+impl_arbitrary!(
+    Circuit,
+    prop_oneof![
+        any::<String>().prop_map(Circuit::MsgType),
+        any::<ClientId>().prop_map(Circuit::Client),
+        any::<ConnectionId>().prop_map(Circuit::Connection),
+        (any::<PortId>(), any::<ChannelId>())
+            .prop_map(|(port_id, chan_id)| Circuit::Channel(port_id, chan_id)),
+    ]
+);
+
+impl_arbitrary!(
+    MsgTripCircuit,
+    (
+        any::<Signer>(),
+        proptest::collection::vec(any::<Circuit>(), 0..4)
+    )
+        .prop_map(|(authority, circuits)| MsgTripCircuit {
+            authority,
+            circuits
+        })
+);
+
+impl_arbitrary!(
+    MsgResetCircuit,
+    (
+        any::<Signer>(),
+        proptest::collection::vec(any::<Circuit>(), 0..4)
+    )
+        .prop_map(|(authority, circuits)| MsgResetCircuit {
+            authority,
+            circuits
+        })
+);
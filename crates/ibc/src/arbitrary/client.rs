@@ -0,0 +1,130 @@
+//! Strategies for the ICS-02 client messages and parameters.
+
+use proptest::prelude::*;
+
+use super::{any_message, identifier, impl_arbitrary};
+use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::update_params::MsgUpdateClientParams;
+use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
+use crate::core::ics02_client::params::{ClientParams, ALLOW_ALL_CLIENTS};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::prelude::*;
+use crate::Signer;
+
+impl_arbitrary!(
+    ClientParams,
+    prop_oneof![
+        Just(vec![ALLOW_ALL_CLIENTS.to_string()]),
+        proptest::collection::btree_set(identifier(7, 43), 0..4)
+            .prop_map(|allowed| allowed.into_iter().collect()),
+    ]
+    .prop_map(|allowed| ClientParams::new(allowed).expect("the allowed clients are valid"))
+);
+
+impl_arbitrary!(
+    MsgCreateClient,
+    (any_message(), any_message(), any::<Signer>()).prop_map(
+        |(client_state, consensus_state, signer)| {
+            MsgCreateClient::new(client_state, consensus_state, signer)
+        }
+    )
+);
+
+impl_arbitrary!(
+    MsgUpdateClient,
+    (any::<ClientId>(), any_message(), any::<Signer>()).prop_map(
+        |(client_id, client_message, signer)| {
+            MsgUpdateClient {
+                client_id,
+                client_message,
+                signer,
+            }
+        }
+    )
+);
+
+impl_arbitrary!(
+    MsgSubmitMisbehaviour,
+    (any::<ClientId>(), any_message(), any::<Signer>()).prop_map(
+        |(client_id, misbehaviour, signer)| {
+            MsgSubmitMisbehaviour {
+                client_id,
+                misbehaviour,
+                signer,
+            }
+        }
+    )
+);
+
+impl_arbitrary!(
+    MsgUpgradeClient,
+    (
+        any::<ClientId>(),
+        any_message(),
+        any_message(),
+        any::<CommitmentProofBytes>(),
+        any::<CommitmentProofBytes>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(
+                client_id,
+                upgraded_client_state,
+                upgraded_consensus_state,
+                proof_upgrade_client,
+                proof_upgrade_consensus_state,
+                signer,
+            )| MsgUpgradeClient {
+                client_id,
+                upgraded_client_state,
+                upgraded_consensus_state,
+                proof_upgrade_client,
+                proof_upgrade_consensus_state,
+                signer,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgUpdateClientParams,
+    (any::<ClientParams>(), any::<Signer>())
+        .prop_map(|(params, signer)| MsgUpdateClientParams { params, signer })
+);
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::client::v1::{
+        MsgCreateClient as RawMsgCreateClient, MsgSubmitMisbehaviour as RawMsgSubmitMisbehaviour,
+        MsgUpdateClient as RawMsgUpdateClient, MsgUpdateParams as RawMsgUpdateClientParams,
+        MsgUpgradeClient as RawMsgUpgradeClient,
+    };
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn client_msgs_round_trip(
+            create in any::<MsgCreateClient>(),
+            update in any::<MsgUpdateClient>(),
+            misbehaviour in any::<MsgSubmitMisbehaviour>(),
+            upgrade in any::<MsgUpgradeClient>(),
+            update_params in any::<MsgUpdateClientParams>(),
+        ) {
+            prop_assert_eq!(MsgCreateClient::try_from(RawMsgCreateClient::from(create.clone())).ok(), Some(create));
+            prop_assert_eq!(MsgUpdateClient::try_from(RawMsgUpdateClient::from(update.clone())).ok(), Some(update));
+            prop_assert_eq!(
+                MsgSubmitMisbehaviour::try_from(RawMsgSubmitMisbehaviour::from(misbehaviour.clone())).ok(),
+                Some(misbehaviour)
+            );
+            prop_assert_eq!(MsgUpgradeClient::try_from(RawMsgUpgradeClient::from(upgrade.clone())).ok(), Some(upgrade));
+            prop_assert_eq!(
+                MsgUpdateClientParams::try_from(RawMsgUpdateClientParams::from(update_params.clone())).ok(),
+                Some(update_params)
+            );
+        }
+    }
+}
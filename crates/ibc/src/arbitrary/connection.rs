@@ -0,0 +1,262 @@
+//! Strategies for the ICS-03 connection types and messages.
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::connection::v1::{
+    MsgConnectionOpenTry as RawMsgConnectionOpenTry, Version as RawVersion,
+};
+use proptest::prelude::*;
+
+use super::{any_message, identifier, impl_arbitrary};
+use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
+use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics03_connection::msgs::update_params::MsgUpdateConnectionParams;
+use crate::core::ics03_connection::params::ConnectionParams;
+use crate::core::ics03_connection::version::Version;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::prelude::*;
+use crate::{Height, Signer};
+
+/// Returns delay periods that fit in the nanoseconds of the proto
+/// representation.
+fn delay_period() -> impl Strategy<Value = Duration> {
+    any::<u64>().prop_map(Duration::from_nanos)
+}
+
+impl_arbitrary!(
+    Version,
+    (
+        identifier(1, 20),
+        proptest::collection::vec(identifier(1, 20), 0..4)
+    )
+        .prop_map(|(identifier, features)| {
+            RawVersion {
+                identifier,
+                features,
+            }
+            .try_into()
+            .expect("the version is valid")
+        })
+);
+
+impl_arbitrary!(
+    Counterparty,
+    (
+        any::<ClientId>(),
+        any::<Option<ConnectionId>>(),
+        any::<CommitmentPrefix>(),
+    )
+        .prop_map(|(client_id, connection_id, prefix)| {
+            Counterparty::new(client_id, connection_id, prefix)
+        })
+);
+
+impl_arbitrary!(
+    ConnectionEnd,
+    (
+        prop_oneof![Just(State::Init), Just(State::TryOpen), Just(State::Open)],
+        any::<ClientId>(),
+        any::<Counterparty>(),
+        proptest::collection::vec(any::<Version>(), 1..4),
+        delay_period(),
+    )
+        .prop_map(
+            |(state, client_id, counterparty, mut versions, delay_period)| {
+                if state != State::Init {
+                    versions.truncate(1);
+                }
+                ConnectionEnd::new(state, client_id, counterparty, versions, delay_period)
+                    .expect("the connection end is valid")
+            }
+        )
+);
+
+impl_arbitrary!(
+    ConnectionParams,
+    (1..=u64::MAX).prop_map(|nanos| {
+        ConnectionParams::new(Duration::from_nanos(nanos)).expect("the params are valid")
+    })
+);
+
+impl_arbitrary!(
+    MsgConnectionOpenInit,
+    (
+        any::<ClientId>(),
+        any::<ClientId>(),
+        any::<CommitmentPrefix>(),
+        any::<Option<Version>>(),
+        delay_period(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(client_id_on_a, client_id_on_b, prefix, version, delay_period, signer)| {
+                MsgConnectionOpenInit {
+                    client_id_on_a,
+                    counterparty: Counterparty::new(client_id_on_b, None, prefix),
+                    version,
+                    delay_period,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgConnectionOpenTry,
+    (
+        (any::<ClientId>(), any_message(), any::<Counterparty>()),
+        proptest::collection::vec(any::<Version>(), 1..4),
+        (
+            any::<CommitmentProofBytes>(),
+            any::<CommitmentProofBytes>(),
+            any::<CommitmentProofBytes>(),
+            any::<Option<CommitmentProofBytes>>(),
+        ),
+        (any::<Height>(), any::<Height>()),
+        delay_period(),
+        any::<String>(),
+    )
+        .prop_map(
+            |(
+                (client_id_on_b, client_state_of_b_on_a, counterparty),
+                versions_on_a,
+                (proof_conn_end, proof_client_state, proof_consensus_state, proof_host),
+                (proofs_height, consensus_height),
+                delay_period,
+                signer,
+            )| {
+                // The deprecated `previous_connection_id` field can only be
+                // set through the raw message.
+                #[allow(deprecated)]
+                let raw = RawMsgConnectionOpenTry {
+                    client_id: client_id_on_b.to_string(),
+                    previous_connection_id: String::new(),
+                    client_state: Some(client_state_of_b_on_a),
+                    counterparty: Some(counterparty.into()),
+                    delay_period: delay_period.as_nanos() as u64,
+                    counterparty_versions: versions_on_a.into_iter().map(Into::into).collect(),
+                    proof_height: Some(proofs_height.into()),
+                    proof_init: proof_conn_end.into(),
+                    proof_client: proof_client_state.into(),
+                    proof_consensus: proof_consensus_state.into(),
+                    consensus_height: Some(consensus_height.into()),
+                    signer,
+                    host_consensus_state_proof: proof_host.map(Into::into).unwrap_or_default(),
+                };
+                raw.try_into().expect("the message is valid")
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgConnectionOpenAck,
+    (
+        (any::<ConnectionId>(), any::<ConnectionId>(), any_message()),
+        (
+            any::<CommitmentProofBytes>(),
+            any::<CommitmentProofBytes>(),
+            any::<CommitmentProofBytes>(),
+            any::<Option<CommitmentProofBytes>>(),
+        ),
+        (any::<Height>(), any::<Height>()),
+        any::<Version>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(
+                (conn_id_on_a, conn_id_on_b, client_state_of_a_on_b),
+                (
+                    proof_conn_end_on_b,
+                    proof_client_state_of_a_on_b,
+                    proof_consensus_state_of_a_on_b,
+                    proof_consensus_state_of_a,
+                ),
+                (proofs_height_on_b, consensus_height_of_a_on_b),
+                version,
+                signer,
+            )| MsgConnectionOpenAck {
+                conn_id_on_a,
+                conn_id_on_b,
+                client_state_of_a_on_b,
+                proof_conn_end_on_b,
+                proof_client_state_of_a_on_b,
+                proof_consensus_state_of_a_on_b,
+                proofs_height_on_b,
+                consensus_height_of_a_on_b,
+                version,
+                signer,
+                proof_consensus_state_of_a,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgConnectionOpenConfirm,
+    (
+        any::<ConnectionId>(),
+        any::<CommitmentProofBytes>(),
+        any::<Height>(),
+        any::<Signer>(),
+    )
+        .prop_map(
+            |(conn_id_on_b, proof_conn_end_on_a, proof_height_on_a, signer)| {
+                MsgConnectionOpenConfirm {
+                    conn_id_on_b,
+                    proof_conn_end_on_a,
+                    proof_height_on_a,
+                    signer,
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgUpdateConnectionParams,
+    (any::<ConnectionParams>(), any::<Signer>())
+        .prop_map(|(params, signer)| MsgUpdateConnectionParams { params, signer })
+);
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::connection::v1::{
+        ConnectionEnd as RawConnectionEnd, MsgConnectionOpenAck as RawMsgConnectionOpenAck,
+        MsgConnectionOpenConfirm as RawMsgConnectionOpenConfirm,
+        MsgConnectionOpenInit as RawMsgConnectionOpenInit,
+        MsgUpdateParams as RawMsgUpdateConnectionParams,
+    };
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn connection_end_round_trips(conn_end in any::<ConnectionEnd>()) {
+            prop_assert_eq!(ConnectionEnd::try_from(RawConnectionEnd::from(conn_end.clone())).ok(), Some(conn_end));
+        }
+
+        #[test]
+        fn connection_msgs_round_trip(
+            init in any::<MsgConnectionOpenInit>(),
+            try_ in any::<MsgConnectionOpenTry>(),
+            ack in any::<MsgConnectionOpenAck>(),
+            confirm in any::<MsgConnectionOpenConfirm>(),
+            update_params in any::<MsgUpdateConnectionParams>(),
+        ) {
+            prop_assert_eq!(MsgConnectionOpenInit::try_from(RawMsgConnectionOpenInit::from(init.clone())).ok(), Some(init));
+            prop_assert_eq!(MsgConnectionOpenTry::try_from(RawMsgConnectionOpenTry::from(try_.clone())).ok(), Some(try_));
+            prop_assert_eq!(MsgConnectionOpenAck::try_from(RawMsgConnectionOpenAck::from(ack.clone())).ok(), Some(ack));
+            prop_assert_eq!(
+                MsgConnectionOpenConfirm::try_from(RawMsgConnectionOpenConfirm::from(confirm.clone())).ok(),
+                Some(confirm)
+            );
+            prop_assert_eq!(
+                MsgUpdateConnectionParams::try_from(RawMsgUpdateConnectionParams::from(update_params.clone())).ok(),
+                Some(update_params)
+            );
+        }
+    }
+}
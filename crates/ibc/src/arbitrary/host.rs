@@ -0,0 +1,79 @@
+//! Strategies for the ICS-24 identifiers.
+
+use proptest::prelude::*;
+
+use super::{identifier, impl_arbitrary};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use crate::prelude::*;
+
+/// Returns chain names made of alphanumeric characters only, so that the
+/// revision number of the resulting chain identifiers is never ambiguous.
+pub fn chain_name(max: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!("[a-zA-Z][a-zA-Z0-9]{{0,{}}}", max - 1))
+        .expect("the regex is valid")
+}
+
+impl_arbitrary!(
+    ChainId,
+    (chain_name(43), any::<u64>()).prop_map(|(name, revision_number)| {
+        ChainId::new(&name, revision_number).expect("the chain name is valid")
+    })
+);
+
+impl_arbitrary!(
+    ClientType,
+    identifier(7, 43)
+        .prop_map(|client_type| ClientType::new(&client_type).expect("the client type is valid"))
+);
+
+impl_arbitrary!(
+    ClientId,
+    (any::<ClientType>(), any::<u64>()).prop_map(|(client_type, counter)| {
+        ClientId::new(client_type, counter).expect("the client type is valid")
+    })
+);
+
+impl_arbitrary!(
+    ConnectionId,
+    identifier(10, 64).prop_map(|id| id.parse().expect("the connection id is valid"))
+);
+
+impl_arbitrary!(
+    PortId,
+    identifier(2, 128).prop_map(|id| id.parse().expect("the port id is valid"))
+);
+
+impl_arbitrary!(
+    ChannelId,
+    identifier(8, 64).prop_map(|id| id.parse().expect("the channel id is valid"))
+);
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn chain_id_round_trips(chain_id in any::<ChainId>()) {
+            prop_assert_eq!(ChainId::from_str(chain_id.as_str()).ok(), Some(chain_id));
+        }
+
+        #[test]
+        fn identifiers_round_trip(
+            client_id in any::<ClientId>(),
+            conn_id in any::<ConnectionId>(),
+            port_id in any::<PortId>(),
+            chan_id in any::<ChannelId>(),
+        ) {
+            prop_assert_eq!(ClientId::from_str(client_id.as_str()).ok(), Some(client_id));
+            prop_assert_eq!(ConnectionId::from_str(conn_id.as_str()).ok(), Some(conn_id));
+            prop_assert_eq!(PortId::from_str(port_id.as_str()).ok(), Some(port_id));
+            prop_assert_eq!(ChannelId::from_str(chan_id.as_str()).ok(), Some(chan_id));
+        }
+    }
+}
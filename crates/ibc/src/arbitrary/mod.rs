@@ -0,0 +1,101 @@
+//! [`proptest`] strategies for property-based testing of IBC hosts and
+//! relayers.
+//!
+//! Every domain type, from identifiers to messages, implements
+//! [`Arbitrary`](proptest::arbitrary::Arbitrary): its values are always
+//! valid, so they convert to their raw form and back unchanged. The [`raw`]
+//! module complements them with strategies for raw messages whose fields are
+//! deliberately malformed, to check that hosts reject them gracefully.
+//!
+//! ```
+//! use ibc::core::ics04_channel::packet::Packet;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(packet in any::<Packet>())| {
+//!     prop_assert!(!packet.data.is_empty());
+//! });
+//! ```
+
+mod channel;
+mod circuit_breaker;
+mod client;
+mod connection;
+mod host;
+pub mod raw;
+mod tendermint;
+mod transfer;
+
+use proptest::prelude::*;
+
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::core::timestamp::Timestamp;
+use crate::prelude::*;
+use crate::{Any, Height, Signer};
+
+/// Implements `Arbitrary`, with no parameters, for a type generated by the
+/// given strategy.
+macro_rules! impl_arbitrary {
+    ($ty:ty, $strategy:expr) => {
+        impl proptest::arbitrary::Arbitrary for $ty {
+            type Parameters = ();
+            type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                proptest::strategy::Strategy::boxed($strategy)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_arbitrary;
+
+/// Returns the strings made of the characters allowed in ICS-24 identifiers,
+/// with a length between `min` and `max`.
+pub fn identifier(min: usize, max: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!(r"[a-zA-Z0-9._+#<>\[\]-]{{{min},{max}}}"))
+        .expect("the regex is valid")
+}
+
+/// Returns non-empty byte strings of up to `max` bytes.
+pub fn non_empty_bytes(max: usize) -> impl Strategy<Value = Vec<u8>> {
+    proptest::collection::vec(any::<u8>(), 1..=max)
+}
+
+impl_arbitrary!(
+    Height,
+    (any::<u64>(), 1..=u64::MAX).prop_map(|(revision_number, revision_height)| {
+        Height::new(revision_number, revision_height).expect("the height is not zero")
+    })
+);
+
+impl_arbitrary!(
+    Timestamp,
+    any::<u64>().prop_map(|nanoseconds| {
+        Timestamp::from_nanoseconds(nanoseconds).expect("any u64 is a valid timestamp")
+    })
+);
+
+impl_arbitrary!(Signer, any::<String>().prop_map(Signer::from));
+
+impl_arbitrary!(
+    CommitmentProofBytes,
+    non_empty_bytes(256).prop_map(|bytes| {
+        CommitmentProofBytes::try_from(bytes).expect("the proof is not empty")
+    })
+);
+
+impl_arbitrary!(
+    CommitmentPrefix,
+    non_empty_bytes(32)
+        .prop_map(|bytes| { CommitmentPrefix::try_from(bytes).expect("the prefix is not empty") })
+);
+
+/// Returns `Any`s with a well-formed type URL and arbitrary contents, which
+/// the messages carrying them do not decode.
+pub fn any_message() -> impl Strategy<Value = Any> {
+    (
+        "/[a-z]{1,10}(\\.[a-z0-9]{1,10}){0,3}\\.[A-Z][a-zA-Z]{0,20}",
+        proptest::collection::vec(any::<u8>(), 0..256),
+    )
+        .prop_map(|(type_url, value)| Any { type_url, value })
+}
@@ -0,0 +1,801 @@
+//! Strategies for raw messages that their domain conversions must reject.
+//!
+//! Each strategy starts from a valid message and corrupts exactly one of its
+//! fields, so that a host rejecting it for an unrelated reason would go
+//! unnoticed. [`msg_envelope`] mixes valid, corrupted and undecodable
+//! messages behind the type URLs that the router dispatches on.
+
+use ibc_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use ibc_proto::ibc::applications::transfer::v1::MsgTransfer as RawMsgTransfer;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement as RawMsgAcknowledgement,
+    MsgChannelCloseConfirm as RawMsgChannelCloseConfirm,
+    MsgChannelCloseInit as RawMsgChannelCloseInit, MsgChannelOpenAck as RawMsgChannelOpenAck,
+    MsgChannelOpenConfirm as RawMsgChannelOpenConfirm, MsgChannelOpenInit as RawMsgChannelOpenInit,
+    MsgChannelOpenTry as RawMsgChannelOpenTry, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose, Packet as RawPacket,
+};
+use ibc_proto::ibc::core::client::v1::{
+    Height as RawHeight, MsgCreateClient as RawMsgCreateClient,
+    MsgSubmitMisbehaviour as RawMsgSubmitMisbehaviour, MsgUpdateClient as RawMsgUpdateClient,
+    MsgUpdateParams as RawMsgUpdateClientParams, MsgUpgradeClient as RawMsgUpgradeClient,
+    Params as RawClientParams,
+};
+use ibc_proto::ibc::core::connection::v1::{
+    MsgConnectionOpenAck as RawMsgConnectionOpenAck,
+    MsgConnectionOpenConfirm as RawMsgConnectionOpenConfirm,
+    MsgConnectionOpenInit as RawMsgConnectionOpenInit,
+    MsgConnectionOpenTry as RawMsgConnectionOpenTry,
+    MsgUpdateParams as RawMsgUpdateConnectionParams, Params as RawConnectionParams,
+    Version as RawVersion,
+};
+use proptest::prelude::*;
+use prost::Message;
+
+use super::{any_message, identifier};
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::core::ics02_client::msgs::create_client::{self, MsgCreateClient};
+use crate::core::ics02_client::msgs::misbehaviour::{self, MsgSubmitMisbehaviour};
+use crate::core::ics02_client::msgs::update_client::{self, MsgUpdateClient};
+use crate::core::ics02_client::msgs::update_params::{
+    self as client_update_params, MsgUpdateClientParams,
+};
+use crate::core::ics02_client::msgs::upgrade_client::{self, MsgUpgradeClient};
+use crate::core::ics03_connection::msgs::conn_open_ack::{self, MsgConnectionOpenAck};
+use crate::core::ics03_connection::msgs::conn_open_confirm::{self, MsgConnectionOpenConfirm};
+use crate::core::ics03_connection::msgs::conn_open_init::{self, MsgConnectionOpenInit};
+use crate::core::ics03_connection::msgs::conn_open_try::{self, MsgConnectionOpenTry};
+use crate::core::ics03_connection::msgs::update_params::{
+    self as connection_update_params, MsgUpdateConnectionParams,
+};
+use crate::core::ics04_channel::msgs::acknowledgement::{self, MsgAcknowledgement};
+use crate::core::ics04_channel::msgs::chan_close_confirm::{self, MsgChannelCloseConfirm};
+use crate::core::ics04_channel::msgs::chan_close_init::{self, MsgChannelCloseInit};
+use crate::core::ics04_channel::msgs::chan_open_ack::{self, MsgChannelOpenAck};
+use crate::core::ics04_channel::msgs::chan_open_confirm::{self, MsgChannelOpenConfirm};
+use crate::core::ics04_channel::msgs::chan_open_init::{self, MsgChannelOpenInit};
+use crate::core::ics04_channel::msgs::chan_open_try::{self, MsgChannelOpenTry};
+use crate::core::ics04_channel::msgs::recv_packet::{self, MsgRecvPacket};
+use crate::core::ics04_channel::msgs::timeout::{self, MsgTimeout};
+use crate::core::ics04_channel::msgs::timeout_on_close::{self, MsgTimeoutOnClose};
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId};
+use crate::core::Msg;
+use crate::prelude::*;
+use crate::Any;
+
+/// Returns strings that no ICS-24 identifier accepts: empty, too short, too
+/// long, or containing a path separator or a forbidden character.
+pub fn invalid_identifier() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        identifier(1, 1),
+        identifier(129, 160),
+        (identifier(4, 30), identifier(4, 30)).prop_map(|(a, b)| format!("{a}/{b}")),
+        (identifier(4, 30), "[ !@$%^&*()=]").prop_map(|(id, c)| format!("{id}{c}{id}")),
+    ]
+}
+
+/// Returns proof heights that are either missing or zero.
+pub fn invalid_proof_height() -> impl Strategy<Value = Option<RawHeight>> {
+    prop_oneof![
+        Just(None),
+        any::<u64>().prop_map(|revision_number| Some(RawHeight {
+            revision_number,
+            revision_height: 0,
+        })),
+    ]
+}
+
+pub fn msg_create_client() -> impl Strategy<Value = RawMsgCreateClient> {
+    let valid = || any::<MsgCreateClient>().prop_map(RawMsgCreateClient::from);
+    prop_oneof![
+        valid().prop_map(|mut msg| {
+            msg.client_state = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.consensus_state = None;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_update_client() -> impl Strategy<Value = RawMsgUpdateClient> {
+    let valid = || any::<MsgUpdateClient>().prop_map(RawMsgUpdateClient::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, client_id)| {
+            msg.client_id = client_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.client_message = None;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_submit_misbehaviour() -> impl Strategy<Value = RawMsgSubmitMisbehaviour> {
+    let valid = || any::<MsgSubmitMisbehaviour>().prop_map(RawMsgSubmitMisbehaviour::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, client_id)| {
+            msg.client_id = client_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.misbehaviour = None;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_upgrade_client() -> impl Strategy<Value = RawMsgUpgradeClient> {
+    let valid = || any::<MsgUpgradeClient>().prop_map(RawMsgUpgradeClient::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, client_id)| {
+            msg.client_id = client_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.client_state = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.consensus_state = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_upgrade_client = Vec::new();
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_upgrade_consensus_state = Vec::new();
+            msg
+        }),
+    ]
+}
+
+/// Returns client parameters with a blank or duplicated allowed client, or
+/// with the wildcard mixed with specific client types.
+pub fn client_params() -> impl Strategy<Value = RawClientParams> {
+    prop_oneof![
+        "[ \t]{0,3}".prop_map(|blank| vec![blank]),
+        identifier(1, 20).prop_map(|client_type| vec![client_type.clone(), client_type]),
+        identifier(1, 20).prop_map(|client_type| vec![client_type, "*".to_string()]),
+    ]
+    .prop_map(|allowed_clients| RawClientParams { allowed_clients })
+}
+
+pub fn msg_update_client_params() -> impl Strategy<Value = RawMsgUpdateClientParams> {
+    let valid = || any::<MsgUpdateClientParams>().prop_map(RawMsgUpdateClientParams::from);
+    prop_oneof![
+        valid().prop_map(|mut msg| {
+            msg.params = None;
+            msg
+        }),
+        (valid(), client_params()).prop_map(|(mut msg, params)| {
+            msg.params = Some(params);
+            msg
+        }),
+    ]
+}
+
+pub fn msg_conn_open_init() -> impl Strategy<Value = RawMsgConnectionOpenInit> {
+    let valid = || any::<MsgConnectionOpenInit>().prop_map(RawMsgConnectionOpenInit::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, client_id)| {
+            msg.client_id = client_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.counterparty = None;
+            msg
+        }),
+        (valid(), any::<ConnectionId>()).prop_map(|(mut msg, conn_id)| {
+            if let Some(counterparty) = msg.counterparty.as_mut() {
+                counterparty.connection_id = conn_id.to_string();
+            }
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            if let Some(counterparty) = msg.counterparty.as_mut() {
+                counterparty.prefix = None;
+            }
+            msg
+        }),
+        (valid(), "[ \t]{0,3}").prop_map(|(mut msg, identifier)| {
+            msg.version = Some(RawVersion {
+                identifier,
+                features: Vec::new(),
+            });
+            msg
+        }),
+    ]
+}
+
+pub fn msg_conn_open_try() -> impl Strategy<Value = RawMsgConnectionOpenTry> {
+    let valid = || any::<MsgConnectionOpenTry>().prop_map(RawMsgConnectionOpenTry::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, client_id)| {
+            msg.client_id = client_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.client_state = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.counterparty = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.counterparty_versions = Vec::new();
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_init = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, consensus_height)| {
+            msg.consensus_height = consensus_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_conn_open_ack() -> impl Strategy<Value = RawMsgConnectionOpenAck> {
+    let valid = || any::<MsgConnectionOpenAck>().prop_map(RawMsgConnectionOpenAck::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, conn_id)| {
+            msg.connection_id = conn_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.client_state = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.version = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_try = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_conn_open_confirm() -> impl Strategy<Value = RawMsgConnectionOpenConfirm> {
+    let valid = || any::<MsgConnectionOpenConfirm>().prop_map(RawMsgConnectionOpenConfirm::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, conn_id)| {
+            msg.connection_id = conn_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_ack = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_update_connection_params() -> impl Strategy<Value = RawMsgUpdateConnectionParams> {
+    let valid = || any::<MsgUpdateConnectionParams>().prop_map(RawMsgUpdateConnectionParams::from);
+    prop_oneof![
+        valid().prop_map(|mut msg| {
+            msg.params = None;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.params = Some(RawConnectionParams {
+                max_expected_time_per_block: 0,
+            });
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_open_init() -> impl Strategy<Value = RawMsgChannelOpenInit> {
+    let valid = || any::<MsgChannelOpenInit>().prop_map(RawMsgChannelOpenInit::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.port_id = port_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.channel = None;
+            msg
+        }),
+        (valid(), 2..=4i32).prop_map(|(mut msg, state)| {
+            if let Some(channel) = msg.channel.as_mut() {
+                channel.state = state;
+            }
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            if let Some(channel) = msg.channel.as_mut() {
+                channel.ordering = 0;
+            }
+            msg
+        }),
+        (valid(), any::<ChannelId>()).prop_map(|(mut msg, chan_id)| {
+            if let Some(counterparty) = msg.channel.as_mut().and_then(|c| c.counterparty.as_mut()) {
+                counterparty.channel_id = chan_id.to_string();
+            }
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_open_try() -> impl Strategy<Value = RawMsgChannelOpenTry> {
+    let valid = || any::<MsgChannelOpenTry>().prop_map(RawMsgChannelOpenTry::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.port_id = port_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.channel = None;
+            msg
+        }),
+        (valid(), prop_oneof![Just(1i32), 3..=4i32]).prop_map(|(mut msg, state)| {
+            if let Some(channel) = msg.channel.as_mut() {
+                channel.state = state;
+            }
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            if let Some(counterparty) = msg.channel.as_mut().and_then(|c| c.counterparty.as_mut()) {
+                counterparty.channel_id = String::new();
+            }
+            msg
+        }),
+        (valid(), any::<ChannelId>()).prop_map(|(mut msg, chan_id)| {
+            #[allow(deprecated)]
+            {
+                msg.previous_channel_id = chan_id.to_string();
+            }
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_init = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_open_ack() -> impl Strategy<Value = RawMsgChannelOpenAck> {
+    let valid = || any::<MsgChannelOpenAck>().prop_map(RawMsgChannelOpenAck::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, chan_id)| {
+            msg.channel_id = chan_id;
+            msg
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut msg, chan_id)| {
+            msg.counterparty_channel_id = chan_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_try = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_open_confirm() -> impl Strategy<Value = RawMsgChannelOpenConfirm> {
+    let valid = || any::<MsgChannelOpenConfirm>().prop_map(RawMsgChannelOpenConfirm::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.port_id = port_id;
+            msg
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut msg, chan_id)| {
+            msg.channel_id = chan_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_ack = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_close_init() -> impl Strategy<Value = RawMsgChannelCloseInit> {
+    let valid = || any::<MsgChannelCloseInit>().prop_map(RawMsgChannelCloseInit::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.port_id = port_id;
+            msg
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut msg, chan_id)| {
+            msg.channel_id = chan_id;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_chan_close_confirm() -> impl Strategy<Value = RawMsgChannelCloseConfirm> {
+    let valid = || any::<MsgChannelCloseConfirm>().prop_map(RawMsgChannelCloseConfirm::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.port_id = port_id;
+            msg
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut msg, chan_id)| {
+            msg.channel_id = chan_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_init = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn packet() -> impl Strategy<Value = RawPacket> {
+    let valid = || any::<Packet>().prop_map(RawPacket::from);
+    prop_oneof![
+        valid().prop_map(|mut packet| {
+            packet.sequence = 0;
+            packet
+        }),
+        valid().prop_map(|mut packet| {
+            packet.data = Vec::new();
+            packet
+        }),
+        valid().prop_map(|mut packet| {
+            packet.timeout_height = None;
+            packet.timeout_timestamp = 0;
+            packet
+        }),
+        (valid(), 1..=u64::MAX).prop_map(|(mut packet, revision_number)| {
+            packet.timeout_height = Some(RawHeight {
+                revision_number,
+                revision_height: 0,
+            });
+            packet
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut packet, port_id)| {
+            packet.source_port = port_id;
+            packet
+        }),
+        (valid(), invalid_identifier()).prop_map(|(mut packet, chan_id)| {
+            packet.destination_channel = chan_id;
+            packet
+        }),
+    ]
+}
+
+pub fn msg_recv_packet() -> impl Strategy<Value = RawMsgRecvPacket> {
+    let valid = || any::<MsgRecvPacket>().prop_map(RawMsgRecvPacket::from);
+    prop_oneof![
+        valid().prop_map(|mut msg| {
+            msg.packet = None;
+            msg
+        }),
+        (valid(), packet()).prop_map(|(mut msg, packet)| {
+            msg.packet = Some(packet);
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_commitment = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_acknowledgement() -> impl Strategy<Value = RawMsgAcknowledgement> {
+    let valid = || any::<MsgAcknowledgement>().prop_map(RawMsgAcknowledgement::from);
+    prop_oneof![
+        (valid(), packet()).prop_map(|(mut msg, packet)| {
+            msg.packet = Some(packet);
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.acknowledgement = Vec::new();
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_acked = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_timeout() -> impl Strategy<Value = RawMsgTimeout> {
+    let valid = || any::<MsgTimeout>().prop_map(RawMsgTimeout::from);
+    prop_oneof![
+        (valid(), packet()).prop_map(|(mut msg, packet)| {
+            msg.packet = Some(packet);
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.next_sequence_recv = 0;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_unreceived = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_timeout_on_close() -> impl Strategy<Value = RawMsgTimeoutOnClose> {
+    let valid = || any::<MsgTimeoutOnClose>().prop_map(RawMsgTimeoutOnClose::from);
+    prop_oneof![
+        valid().prop_map(|mut msg| {
+            msg.packet = None;
+            msg
+        }),
+        (valid(), packet()).prop_map(|(mut msg, packet)| {
+            msg.packet = Some(packet);
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.next_sequence_recv = 0;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_unreceived = Vec::new();
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.proof_close = Vec::new();
+            msg
+        }),
+        (valid(), invalid_proof_height()).prop_map(|(mut msg, proof_height)| {
+            msg.proof_height = proof_height;
+            msg
+        }),
+    ]
+}
+
+pub fn msg_transfer() -> impl Strategy<Value = RawMsgTransfer> {
+    let valid = || any::<MsgTransfer>().prop_map(RawMsgTransfer::from);
+    prop_oneof![
+        (valid(), invalid_identifier()).prop_map(|(mut msg, port_id)| {
+            msg.source_port = port_id;
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.token = None;
+            msg
+        }),
+        (valid(), "(-[0-9]{1,10}|[a-z]{1,10})").prop_map(|(mut msg, amount)| {
+            if let Some(token) = msg.token.as_mut() {
+                token.amount = amount;
+            }
+            msg
+        }),
+        (valid(), "[ \t]{0,3}").prop_map(|(mut msg, denom)| {
+            if let Some(ProtoCoin {
+                denom: token_denom, ..
+            }) = msg.token.as_mut()
+            {
+                *token_denom = denom;
+            }
+            msg
+        }),
+        valid().prop_map(|mut msg| {
+            msg.timeout_height = None;
+            msg.timeout_timestamp = 0;
+            msg
+        }),
+    ]
+}
+
+/// Returns messages for the router to decode: valid ones, ones with a
+/// corrupted field, and ones whose contents do not match their type URL.
+pub fn msg_envelope() -> impl Strategy<Value = Any> {
+    fn encode<M: Message>(type_url: &str, msg: M) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    let valid = prop_oneof![
+        any::<MsgCreateClient>().prop_map(Msg::to_any),
+        any::<MsgUpdateClient>().prop_map(Msg::to_any),
+        any::<MsgUpgradeClient>().prop_map(Msg::to_any),
+        any::<MsgSubmitMisbehaviour>().prop_map(Msg::to_any),
+        any::<MsgUpdateClientParams>().prop_map(Msg::to_any),
+        any::<MsgConnectionOpenInit>().prop_map(Msg::to_any),
+        any::<MsgConnectionOpenTry>().prop_map(Msg::to_any),
+        any::<MsgConnectionOpenAck>().prop_map(Msg::to_any),
+        any::<MsgConnectionOpenConfirm>().prop_map(Msg::to_any),
+        any::<MsgUpdateConnectionParams>().prop_map(Msg::to_any),
+        any::<MsgChannelOpenInit>().prop_map(Msg::to_any),
+        any::<MsgChannelOpenTry>().prop_map(Msg::to_any),
+        any::<MsgChannelOpenAck>().prop_map(Msg::to_any),
+        any::<MsgChannelOpenConfirm>().prop_map(Msg::to_any),
+        any::<MsgChannelCloseInit>().prop_map(Msg::to_any),
+        any::<MsgChannelCloseConfirm>().prop_map(Msg::to_any),
+        any::<MsgRecvPacket>().prop_map(Msg::to_any),
+        any::<MsgAcknowledgement>().prop_map(Msg::to_any),
+        any::<MsgTimeout>().prop_map(Msg::to_any),
+        any::<MsgTimeoutOnClose>().prop_map(Msg::to_any),
+    ];
+    let invalid = prop_oneof![
+        msg_create_client().prop_map(|msg| encode(create_client::TYPE_URL, msg)),
+        msg_update_client().prop_map(|msg| encode(update_client::TYPE_URL, msg)),
+        msg_upgrade_client().prop_map(|msg| encode(upgrade_client::TYPE_URL, msg)),
+        msg_submit_misbehaviour().prop_map(|msg| encode(misbehaviour::TYPE_URL, msg)),
+        msg_update_client_params().prop_map(|msg| encode(client_update_params::TYPE_URL, msg)),
+        msg_conn_open_init().prop_map(|msg| encode(conn_open_init::TYPE_URL, msg)),
+        msg_conn_open_try().prop_map(|msg| encode(conn_open_try::TYPE_URL, msg)),
+        msg_conn_open_ack().prop_map(|msg| encode(conn_open_ack::TYPE_URL, msg)),
+        msg_conn_open_confirm().prop_map(|msg| encode(conn_open_confirm::TYPE_URL, msg)),
+        msg_update_connection_params()
+            .prop_map(|msg| encode(connection_update_params::TYPE_URL, msg)),
+        msg_chan_open_init().prop_map(|msg| encode(chan_open_init::TYPE_URL, msg)),
+        msg_chan_open_try().prop_map(|msg| encode(chan_open_try::TYPE_URL, msg)),
+        msg_chan_open_ack().prop_map(|msg| encode(chan_open_ack::TYPE_URL, msg)),
+        msg_chan_open_confirm().prop_map(|msg| encode(chan_open_confirm::TYPE_URL, msg)),
+        msg_chan_close_init().prop_map(|msg| encode(chan_close_init::TYPE_URL, msg)),
+        msg_chan_close_confirm().prop_map(|msg| encode(chan_close_confirm::TYPE_URL, msg)),
+        msg_recv_packet().prop_map(|msg| encode(recv_packet::TYPE_URL, msg)),
+        msg_acknowledgement().prop_map(|msg| encode(acknowledgement::TYPE_URL, msg)),
+        msg_timeout().prop_map(|msg| encode(timeout::TYPE_URL, msg)),
+        msg_timeout_on_close().prop_map(|msg| encode(timeout_on_close::TYPE_URL, msg)),
+    ];
+    let undecodable = (
+        proptest::sample::select(vec![
+            create_client::TYPE_URL,
+            update_client::TYPE_URL,
+            upgrade_client::TYPE_URL,
+            misbehaviour::TYPE_URL,
+            client_update_params::TYPE_URL,
+            conn_open_init::TYPE_URL,
+            conn_open_try::TYPE_URL,
+            conn_open_ack::TYPE_URL,
+            conn_open_confirm::TYPE_URL,
+            connection_update_params::TYPE_URL,
+            chan_open_init::TYPE_URL,
+            chan_open_try::TYPE_URL,
+            chan_open_ack::TYPE_URL,
+            chan_open_confirm::TYPE_URL,
+            chan_close_init::TYPE_URL,
+            chan_close_confirm::TYPE_URL,
+            recv_packet::TYPE_URL,
+            acknowledgement::TYPE_URL,
+            timeout::TYPE_URL,
+            timeout_on_close::TYPE_URL,
+        ]),
+        proptest::collection::vec(any::<u8>(), 0..256),
+    )
+        .prop_map(|(type_url, value)| Any {
+            type_url: type_url.to_string(),
+            value,
+        });
+
+    prop_oneof![valid, invalid, undecodable, any_message()]
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::core::ics24_host::identifier::{ClientId, PortId};
+    use crate::core::MsgEnvelope;
+
+    proptest! {
+        #[test]
+        fn invalid_identifiers_are_rejected(id in invalid_identifier()) {
+            prop_assert!(id.parse::<ClientId>().is_err());
+            prop_assert!(id.parse::<ConnectionId>().is_err());
+            prop_assert!(id.parse::<PortId>().is_err());
+            prop_assert!(id.parse::<ChannelId>().is_err());
+        }
+
+        #[test]
+        fn invalid_client_msgs_are_rejected(
+            create in msg_create_client(),
+            update in msg_update_client(),
+            upgrade in msg_upgrade_client(),
+            misbehaviour in msg_submit_misbehaviour(),
+            update_params in msg_update_client_params(),
+        ) {
+            prop_assert!(MsgCreateClient::try_from(create).is_err());
+            prop_assert!(MsgUpdateClient::try_from(update).is_err());
+            prop_assert!(MsgUpgradeClient::try_from(upgrade).is_err());
+            prop_assert!(MsgSubmitMisbehaviour::try_from(misbehaviour).is_err());
+            prop_assert!(MsgUpdateClientParams::try_from(update_params).is_err());
+        }
+
+        #[test]
+        fn invalid_connection_handshake_msgs_are_rejected(
+            conn_open_init in msg_conn_open_init(),
+            conn_open_try in msg_conn_open_try(),
+            conn_open_ack in msg_conn_open_ack(),
+            conn_open_confirm in msg_conn_open_confirm(),
+            update_params in msg_update_connection_params(),
+        ) {
+            prop_assert!(MsgConnectionOpenInit::try_from(conn_open_init).is_err());
+            prop_assert!(MsgConnectionOpenTry::try_from(conn_open_try).is_err());
+            prop_assert!(MsgConnectionOpenAck::try_from(conn_open_ack).is_err());
+            prop_assert!(MsgConnectionOpenConfirm::try_from(conn_open_confirm).is_err());
+            prop_assert!(MsgUpdateConnectionParams::try_from(update_params).is_err());
+        }
+
+        #[test]
+        fn invalid_channel_handshake_msgs_are_rejected(
+            chan_open_init in msg_chan_open_init(),
+            chan_open_try in msg_chan_open_try(),
+            chan_open_ack in msg_chan_open_ack(),
+            chan_open_confirm in msg_chan_open_confirm(),
+            chan_close_init in msg_chan_close_init(),
+            chan_close_confirm in msg_chan_close_confirm(),
+        ) {
+            prop_assert!(MsgChannelOpenInit::try_from(chan_open_init).is_err());
+            prop_assert!(MsgChannelOpenTry::try_from(chan_open_try).is_err());
+            prop_assert!(MsgChannelOpenAck::try_from(chan_open_ack).is_err());
+            prop_assert!(MsgChannelOpenConfirm::try_from(chan_open_confirm).is_err());
+            prop_assert!(MsgChannelCloseInit::try_from(chan_close_init).is_err());
+            prop_assert!(MsgChannelCloseConfirm::try_from(chan_close_confirm).is_err());
+        }
+
+        #[test]
+        fn invalid_packet_msgs_are_rejected(
+            packet in packet(),
+            recv in msg_recv_packet(),
+            ack in msg_acknowledgement(),
+            timeout in msg_timeout(),
+            timeout_on_close in msg_timeout_on_close(),
+            transfer in msg_transfer(),
+        ) {
+            prop_assert!(Packet::try_from(packet).is_err());
+            prop_assert!(MsgRecvPacket::try_from(recv).is_err());
+            prop_assert!(MsgAcknowledgement::try_from(ack).is_err());
+            prop_assert!(MsgTimeout::try_from(timeout).is_err());
+            prop_assert!(MsgTimeoutOnClose::try_from(timeout_on_close).is_err());
+            prop_assert!(MsgTransfer::try_from(transfer).is_err());
+        }
+
+        #[test]
+        fn msg_envelope_decoding_never_panics(any_msg in msg_envelope()) {
+            let _ = MsgEnvelope::try_from(any_msg);
+        }
+    }
+}
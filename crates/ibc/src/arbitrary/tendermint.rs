@@ -0,0 +1,93 @@
+//! Strategies for the ICS-07 Tendermint client state.
+
+use core::time::Duration;
+
+use proptest::prelude::*;
+
+use super::host::chain_name;
+use super::impl_arbitrary;
+use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
+use crate::clients::ics07_tendermint::trust_threshold::TrustThreshold;
+use crate::core::ics23_commitment::specs::ProofSpecs;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::prelude::*;
+use crate::Height;
+
+/// Returns non-zero durations whose seconds fit in a `u32`, so that sums of
+/// them stay representable as protobuf durations.
+fn period() -> impl Strategy<Value = Duration> {
+    (0..=u64::from(u32::MAX), 0..1_000_000_000u32)
+        .prop_filter("the period is not zero", |(secs, nanos)| {
+            *secs != 0 || *nanos != 0
+        })
+        .prop_map(|(secs, nanos)| Duration::new(secs, nanos))
+}
+
+// Tendermint only accepts trust thresholds in the range `[1/3, 1)`.
+impl_arbitrary!(
+    TrustThreshold,
+    (2..=1000u64)
+        .prop_flat_map(|denominator| ((denominator + 2) / 3..denominator, Just(denominator)))
+        .prop_map(|(numerator, denominator)| {
+            TrustThreshold::new(numerator, denominator).expect("the trust threshold is valid")
+        })
+);
+
+impl_arbitrary!(
+    ClientState,
+    (
+        (chain_name(29), any::<u64>(), 1..=u64::MAX),
+        any::<TrustThreshold>(),
+        (period(), period(), period()),
+        proptest::collection::vec("[a-zA-Z0-9]{1,16}", 0..3),
+        any::<(bool, bool)>(),
+    )
+        .prop_map(
+            |(
+                (name, revision_number, revision_height),
+                trust_level,
+                (trusting_period, unbonding_margin, max_clock_drift),
+                upgrade_path,
+                (after_expiry, after_misbehaviour),
+            )| {
+                let chain_id =
+                    ChainId::new(&name, revision_number).expect("the chain name is valid");
+                let latest_height =
+                    Height::new(revision_number, revision_height).expect("the height is not zero");
+
+                ClientState::new(
+                    chain_id,
+                    trust_level,
+                    trusting_period,
+                    trusting_period + unbonding_margin,
+                    max_clock_drift,
+                    latest_height,
+                    ProofSpecs::default(),
+                    upgrade_path,
+                    AllowUpdate {
+                        after_expiry,
+                        after_misbehaviour,
+                    },
+                )
+                .expect("the client state is valid")
+            }
+        )
+);
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::lightclients::tendermint::v1::ClientState as RawTmClientState;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn client_state_round_trips(client_state in any::<ClientState>()) {
+            prop_assert_eq!(
+                ClientState::try_from(RawTmClientState::from(client_state.clone())).ok(),
+                Some(client_state)
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Strategies for the ICS-20 token transfer types and messages.
+
+use proptest::prelude::*;
+
+use super::impl_arbitrary;
+use crate::applications::transfer::msgs::transfer::MsgTransfer;
+use crate::applications::transfer::msgs::update_params::MsgUpdateParams;
+use crate::applications::transfer::packet::PacketData;
+use crate::applications::transfer::params::Params;
+use crate::applications::transfer::{
+    Amount, BaseCoin, BaseDenom, Memo, PrefixedCoin, PrefixedDenom, TracePath, TracePrefix,
+};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::timestamp::Timestamp;
+use crate::prelude::*;
+use crate::{Height, Signer};
+
+// Base denominations follow the Cosmos SDK format, except for `/` which
+// would make them indistinguishable from a trace path once prefixed.
+impl_arbitrary!(
+    BaseDenom,
+    "[a-zA-Z][a-zA-Z0-9:._-]{2,127}"
+        .prop_map(|denom| denom.parse().expect("the base denom is not blank"))
+);
+
+impl_arbitrary!(
+    TracePrefix,
+    (any::<PortId>(), any::<ChannelId>())
+        .prop_map(|(port_id, chan_id)| TracePrefix::new(port_id, chan_id))
+);
+
+impl_arbitrary!(
+    TracePath,
+    proptest::collection::vec(any::<TracePrefix>(), 0..4).prop_map(TracePath::from)
+);
+
+impl_arbitrary!(
+    PrefixedDenom,
+    (any::<TracePath>(), any::<BaseDenom>()).prop_map(|(trace_path, base_denom)| PrefixedDenom {
+        trace_path,
+        base_denom,
+    })
+);
+
+impl_arbitrary!(Amount, any::<[u64; 4]>().prop_map(Amount::from));
+
+impl_arbitrary!(
+    PrefixedCoin,
+    (any::<PrefixedDenom>(), any::<Amount>())
+        .prop_map(|(denom, amount)| PrefixedCoin { denom, amount })
+);
+
+impl_arbitrary!(
+    BaseCoin,
+    (any::<BaseDenom>(), any::<Amount>()).prop_map(|(denom, amount)| BaseCoin { denom, amount })
+);
+
+impl_arbitrary!(Memo, any::<String>().prop_map(Memo::from));
+
+impl_arbitrary!(
+    PacketData,
+    (
+        any::<PrefixedCoin>(),
+        any::<Signer>(),
+        any::<Signer>(),
+        any::<Memo>(),
+    )
+        .prop_map(|(token, sender, receiver, memo)| PacketData {
+            token,
+            sender,
+            receiver,
+            memo,
+        })
+);
+
+impl_arbitrary!(
+    Params,
+    any::<(bool, bool)>().prop_map(|(send_enabled, receive_enabled)| Params {
+        send_enabled,
+        receive_enabled,
+    })
+);
+
+// Transfers always have a timeout, like the packets they send.
+impl_arbitrary!(
+    MsgTransfer,
+    (
+        any::<PortId>(),
+        any::<ChannelId>(),
+        any::<PacketData>(),
+        prop_oneof![
+            (any::<Height>(), any::<Timestamp>())
+                .prop_map(|(height, timestamp)| (TimeoutHeight::At(height), timestamp)),
+            (1..=u64::MAX).prop_map(|nanos| {
+                let timestamp =
+                    Timestamp::from_nanoseconds(nanos).expect("any u64 is a valid timestamp");
+                (TimeoutHeight::Never, timestamp)
+            }),
+        ],
+    )
+        .prop_map(
+            |(
+                port_id_on_a,
+                chan_id_on_a,
+                packet_data,
+                (timeout_height_on_b, timeout_timestamp_on_b),
+            )| MsgTransfer {
+                port_id_on_a,
+                chan_id_on_a,
+                packet_data,
+                timeout_height_on_b,
+                timeout_timestamp_on_b,
+            }
+        )
+);
+
+impl_arbitrary!(
+    MsgUpdateParams,
+    (any::<Params>(), any::<Signer>())
+        .prop_map(|(params, signer)| MsgUpdateParams { params, signer })
+);
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+    use ibc_proto::ibc::applications::transfer::v1::{
+        MsgTransfer as RawMsgTransfer, MsgUpdateParams as RawMsgUpdateParams,
+    };
+    use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn denoms_and_coins_round_trip(denom in any::<PrefixedDenom>(), coin in any::<PrefixedCoin>()) {
+            prop_assert_eq!(PrefixedDenom::from_str(&denom.to_string()).ok(), Some(denom));
+            prop_assert_eq!(PrefixedCoin::try_from(ProtoCoin::from(coin.clone())).ok(), Some(coin));
+        }
+
+        #[test]
+        fn transfer_msgs_round_trip(
+            packet_data in any::<PacketData>(),
+            transfer in any::<MsgTransfer>(),
+            update_params in any::<MsgUpdateParams>(),
+        ) {
+            prop_assert_eq!(PacketData::try_from(RawPacketData::from(packet_data.clone())).ok(), Some(packet_data));
+            prop_assert_eq!(MsgTransfer::try_from(RawMsgTransfer::from(transfer.clone())).ok(), Some(transfer));
+            prop_assert_eq!(MsgUpdateParams::try_from(RawMsgUpdateParams::from(update_params.clone())).ok(), Some(update_params));
+        }
+    }
+}
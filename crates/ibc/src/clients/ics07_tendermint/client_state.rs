@@ -45,7 +45,7 @@ use crate::core::ics24_host::identifier::{ChainId, ClientId};
 use crate::core::ics24_host::path::{
     ClientConsensusStatePath, ClientStatePath, Path, UpgradeClientPath,
 };
-use crate::core::timestamp::ZERO_DURATION;
+use crate::core::timestamp::{Timestamp, ZERO_DURATION};
 use crate::prelude::*;
 use crate::Height;
 
@@ -489,6 +489,26 @@ where
 
         Ok(Status::Active)
     }
+
+    fn expiry_timestamp(
+        &self,
+        ctx: &ClientValidationContext,
+        client_id: &ClientId,
+    ) -> Result<Option<Timestamp>, ClientError> {
+        let latest_consensus_state: TmConsensusState = ctx
+            .consensus_state(&ClientConsensusStatePath::new(
+                client_id,
+                &self.latest_height,
+            ))?
+            .try_into()?;
+
+        let expiry_timestamp = (latest_consensus_state.timestamp() + self.trusting_period)
+            .map_err(|e| ClientError::Other {
+                description: e.to_string(),
+            })?;
+
+        Ok(Some(expiry_timestamp))
+    }
 }
 
 impl<E> ClientStateExecution<E> for ClientState
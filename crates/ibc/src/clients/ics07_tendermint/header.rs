@@ -125,14 +125,6 @@ impl Header {
                 validators_hash: self.validator_set.hash(),
             });
         }
-
-        if self.trusted_next_validator_set.hash() != self.signed_header.header.next_validators_hash
-        {
-            return Err(Error::MismatchValidatorsHashes {
-                signed_header_validators_hash: self.signed_header.header.next_validators_hash,
-                validators_hash: self.trusted_next_validator_set.hash(),
-            });
-        }
         Ok(())
     }
 }
@@ -0,0 +1,21 @@
+//! Defines the authorization error type
+
+use displaydoc::Display;
+
+use crate::core::ics24_host::identifier::ClientId;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Encodes all the possible authorization errors
+#[derive(Debug, Display)]
+pub enum AuthorizationError {
+    /// relayer `{signer}` is not whitelisted for client `{client_id}`
+    RelayerNotWhitelisted { signer: Signer, client_id: ClientId },
+    /// message `{type_url}` can only be signed by the authority, not by `{signer}`
+    NotAuthority { signer: Signer, type_url: String },
+    /// signer `{signer}` is unauthorized: `{reason}`
+    Unauthorized { signer: Signer, reason: String },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuthorizationError {}
@@ -0,0 +1,120 @@
+//! Defines [`AffectedIdentifiers`] and the logic to find the identifiers a
+//! message involves.
+
+use crate::core::ics02_client::msgs::ClientMsg;
+use crate::core::ics03_connection::msgs::ConnectionMsg;
+use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::ChannelEndPath;
+use crate::core::{MsgEnvelope, ValidationContext};
+use crate::prelude::*;
+
+/// The clients, connections and channel ends a message involves.
+///
+/// Channel and connection ends are looked up in the store, so that e.g. a
+/// packet message also involves the connections and client its channel is
+/// built upon. Lookup failures are ignored here, as they are reported by the
+/// message handlers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AffectedIdentifiers {
+    pub client_ids: Vec<ClientId>,
+    pub connection_ids: Vec<ConnectionId>,
+    pub channels: Vec<(PortId, ChannelId)>,
+}
+
+impl AffectedIdentifiers {
+    /// Collects the identifiers that `msg` involves.
+    pub fn new<Ctx>(ctx: &Ctx, msg: &MsgEnvelope) -> Self
+    where
+        Ctx: ValidationContext,
+    {
+        let mut identifiers = Self::default();
+
+        match msg {
+            MsgEnvelope::Client(msg) => match msg {
+                ClientMsg::CreateClient(_) => {}
+                ClientMsg::UpdateClient(msg) => identifiers.push_client(&msg.client_id),
+                ClientMsg::Misbehaviour(msg) => identifiers.push_client(&msg.client_id),
+                ClientMsg::UpgradeClient(msg) => identifiers.push_client(&msg.client_id),
+                ClientMsg::UpdateParams(_) => {}
+            },
+            MsgEnvelope::Connection(msg) => match msg {
+                ConnectionMsg::OpenInit(msg) => identifiers.push_client(&msg.client_id_on_a),
+                ConnectionMsg::OpenTry(msg) => identifiers.push_client(&msg.client_id_on_b),
+                ConnectionMsg::OpenAck(msg) => identifiers.push_connection(ctx, &msg.conn_id_on_a),
+                ConnectionMsg::OpenConfirm(msg) => {
+                    identifiers.push_connection(ctx, &msg.conn_id_on_b)
+                }
+                ConnectionMsg::UpdateParams(_) => {}
+            },
+            MsgEnvelope::Channel(msg) => match msg {
+                ChannelMsg::OpenInit(msg) => msg
+                    .connection_hops_on_a
+                    .iter()
+                    .for_each(|conn_id| identifiers.push_connection(ctx, conn_id)),
+                ChannelMsg::OpenTry(msg) => msg
+                    .connection_hops_on_b
+                    .iter()
+                    .for_each(|conn_id| identifiers.push_connection(ctx, conn_id)),
+                ChannelMsg::OpenAck(msg) => {
+                    identifiers.push_channel(ctx, &msg.port_id_on_a, &msg.chan_id_on_a)
+                }
+                ChannelMsg::OpenConfirm(msg) => {
+                    identifiers.push_channel(ctx, &msg.port_id_on_b, &msg.chan_id_on_b)
+                }
+                ChannelMsg::CloseInit(msg) => {
+                    identifiers.push_channel(ctx, &msg.port_id_on_a, &msg.chan_id_on_a)
+                }
+                ChannelMsg::CloseConfirm(msg) => {
+                    identifiers.push_channel(ctx, &msg.port_id_on_b, &msg.chan_id_on_b)
+                }
+            },
+            MsgEnvelope::Packet(msg) => {
+                let (port_id, chan_id) = match msg {
+                    PacketMsg::Recv(msg) => (&msg.packet.port_id_on_b, &msg.packet.chan_id_on_b),
+                    PacketMsg::Ack(msg) => (&msg.packet.port_id_on_a, &msg.packet.chan_id_on_a),
+                    PacketMsg::Timeout(msg) => (&msg.packet.port_id_on_a, &msg.packet.chan_id_on_a),
+                    PacketMsg::TimeoutOnClose(msg) => {
+                        (&msg.packet.port_id_on_a, &msg.packet.chan_id_on_a)
+                    }
+                };
+                identifiers.push_channel(ctx, port_id, chan_id)
+            }
+        }
+
+        identifiers
+    }
+
+    fn push_client(&mut self, client_id: &ClientId) {
+        if !self.client_ids.contains(client_id) {
+            self.client_ids.push(client_id.clone());
+        }
+    }
+
+    fn push_connection<Ctx>(&mut self, ctx: &Ctx, conn_id: &ConnectionId)
+    where
+        Ctx: ValidationContext,
+    {
+        if !self.connection_ids.contains(conn_id) {
+            self.connection_ids.push(conn_id.clone());
+        }
+
+        if let Ok(conn_end) = ctx.connection_end(conn_id) {
+            self.push_client(conn_end.client_id());
+        }
+    }
+
+    fn push_channel<Ctx>(&mut self, ctx: &Ctx, port_id: &PortId, chan_id: &ChannelId)
+    where
+        Ctx: ValidationContext,
+    {
+        self.channels.push((port_id.clone(), chan_id.clone()));
+
+        if let Ok(chan_end) = ctx.channel_end(&ChannelEndPath::new(port_id, chan_id)) {
+            chan_end
+                .connection_hops()
+                .iter()
+                .for_each(|conn_id| self.push_connection(ctx, conn_id));
+        }
+    }
+}
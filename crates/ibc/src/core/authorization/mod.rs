@@ -0,0 +1,24 @@
+//! Lets hosts restrict who may submit IBC messages, on top of the
+//! well-formedness check of
+//! [`validate_message_signer`](crate::core::ValidationContext::validate_message_signer).
+//!
+//! Before handling a message, [`validate`](crate::core::validate) hands its
+//! signer, the message itself and the [`AffectedIdentifiers`] to
+//! [`ValidationContext::authorize_message`](crate::core::ValidationContext::authorize_message),
+//! and rejects it with
+//! [`RouterError::AuthorizationError`](crate::core::RouterError::AuthorizationError)
+//! if authorization fails. Hosts implement [`Authorization`] for their own
+//! policies, or compose the provided allow-lists: [`ClientRelayerWhitelist`]
+//! and [`PrivilegedMsgAuthority`].
+
+mod error;
+mod identifiers;
+mod policy;
+mod privileged;
+mod relayer_whitelist;
+
+pub use error::AuthorizationError;
+pub use identifiers::AffectedIdentifiers;
+pub use policy::Authorization;
+pub use privileged::PrivilegedMsgAuthority;
+pub use relayer_whitelist::ClientRelayerWhitelist;
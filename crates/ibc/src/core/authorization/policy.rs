@@ -0,0 +1,17 @@
+//! Defines the [`Authorization`] trait.
+
+use super::{AffectedIdentifiers, AuthorizationError};
+use crate::core::MsgEnvelope;
+use crate::signer::Signer;
+
+/// A policy deciding whether `signer` may submit `msg`.
+pub trait Authorization {
+    /// Returns an error if `signer` is not allowed to submit `msg`, which
+    /// involves the given `identifiers`.
+    fn authorize(
+        &self,
+        signer: &Signer,
+        msg: &MsgEnvelope,
+        identifiers: &AffectedIdentifiers,
+    ) -> Result<(), AuthorizationError>;
+}
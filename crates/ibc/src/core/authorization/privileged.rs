@@ -0,0 +1,102 @@
+//! Provides [`PrivilegedMsgAuthority`], which reserves privileged client
+//! messages to an authority such as the governance module.
+
+use super::{AffectedIdentifiers, Authorization, AuthorizationError};
+use crate::core::ics02_client::msgs::ClientMsg;
+use crate::core::MsgEnvelope;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Only lets `authority` submit privileged client messages.
+///
+/// Currently, `MsgUpgradeClient` is the only privileged message, since this
+/// crate does not support client recovery (`MsgRecoverClient`) yet. All other
+/// messages are left untouched.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrivilegedMsgAuthority {
+    authority: Signer,
+}
+
+impl PrivilegedMsgAuthority {
+    pub fn new(authority: Signer) -> Self {
+        Self { authority }
+    }
+
+    pub fn authority(&self) -> &Signer {
+        &self.authority
+    }
+
+    /// Returns `true` if `msg` can only be submitted by the authority.
+    pub fn is_privileged(msg: &MsgEnvelope) -> bool {
+        matches!(msg, MsgEnvelope::Client(ClientMsg::UpgradeClient(_)))
+    }
+}
+
+impl Authorization for PrivilegedMsgAuthority {
+    fn authorize(
+        &self,
+        signer: &Signer,
+        msg: &MsgEnvelope,
+        _identifiers: &AffectedIdentifiers,
+    ) -> Result<(), AuthorizationError> {
+        if Self::is_privileged(msg) && signer != &self.authority {
+            return Err(AuthorizationError::NotAuthority {
+                signer: signer.clone(),
+                type_url: msg.type_url(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
+    use crate::core::{validate, RouterError};
+    use crate::mock::context::MockContext;
+    use crate::mock::router::MockRouter;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    #[test]
+    fn upgrade_client_requires_authority() {
+        let authority: Signer = "authority".to_string().into();
+        let ctx = MockContext::default().with_privileged_msg_authority(authority.clone());
+        let router = MockRouter::default();
+
+        let msg = MsgUpgradeClient::new_dummy(Height::new(1, 26).unwrap());
+        assert_eq!(msg.signer, get_dummy_account_id());
+
+        let res = validate(
+            &ctx,
+            &router,
+            MsgEnvelope::Client(ClientMsg::UpgradeClient(msg.clone())),
+        );
+        assert!(matches!(
+            res,
+            Err(RouterError::AuthorizationError(
+                AuthorizationError::NotAuthority { .. }
+            ))
+        ));
+
+        let msg = MsgUpgradeClient {
+            signer: authority,
+            ..msg
+        };
+        let res = validate(
+            &ctx,
+            &router,
+            MsgEnvelope::Client(ClientMsg::UpgradeClient(msg)),
+        );
+        assert!(!matches!(res, Err(RouterError::AuthorizationError(_))));
+    }
+}
@@ -0,0 +1,195 @@
+//! Provides [`ClientRelayerWhitelist`], which restricts relaying on given
+//! clients to a set of relayers.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use super::{AffectedIdentifiers, Authorization, AuthorizationError};
+use crate::core::ics24_host::identifier::ClientId;
+use crate::core::MsgEnvelope;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Permissioned relaying: messages involving a whitelisted client, or any
+/// connection or channel built upon it, must be signed by one of the relayers
+/// whitelisted for that client.
+///
+/// Clients without a whitelist remain permissionless.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClientRelayerWhitelist {
+    relayers: BTreeMap<ClientId, BTreeSet<Signer>>,
+}
+
+impl ClientRelayerWhitelist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows `relayer` to relay on `client_id`, making the client
+    /// permissioned if it was not already.
+    pub fn allow(&mut self, client_id: ClientId, relayer: Signer) {
+        self.relayers.entry(client_id).or_default().insert(relayer);
+    }
+
+    /// Removes the whitelist of `client_id`, making the client permissionless.
+    pub fn clear(&mut self, client_id: &ClientId) {
+        self.relayers.remove(client_id);
+    }
+
+    /// Returns the relayers whitelisted for `client_id`, or `None` if the
+    /// client is permissionless.
+    pub fn relayers(&self, client_id: &ClientId) -> Option<&BTreeSet<Signer>> {
+        self.relayers.get(client_id)
+    }
+}
+
+impl Authorization for ClientRelayerWhitelist {
+    fn authorize(
+        &self,
+        signer: &Signer,
+        _msg: &MsgEnvelope,
+        identifiers: &AffectedIdentifiers,
+    ) -> Result<(), AuthorizationError> {
+        for client_id in identifiers.client_ids.iter() {
+            match self.relayers.get(client_id) {
+                Some(relayers) if !relayers.contains(signer) => {
+                    return Err(AuthorizationError::RelayerNotWhitelisted {
+                        signer: signer.clone(),
+                        client_id: client_id.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnCounterparty, State as ConnState,
+    };
+    use crate::core::ics03_connection::version::Version as ConnVersion;
+    use crate::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty as ChannelCounterparty, Order as ChannelOrder,
+        State as ChannelState,
+    };
+    use crate::core::ics04_channel::msgs::chan_close_init::test_util::get_dummy_raw_msg_chan_close_init;
+    use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics04_channel::Version as ChannelVersion;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::core::router::ModuleId;
+    use crate::core::{validate, RouterError};
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::router::MockRouter;
+    use crate::test_utils::{get_dummy_account_id, get_dummy_bech32_account, DummyTransferModule};
+    use crate::Height;
+
+    fn is_not_whitelisted(res: Result<(), RouterError>) -> bool {
+        matches!(
+            res,
+            Err(RouterError::AuthorizationError(
+                AuthorizationError::RelayerNotWhitelisted { .. }
+            ))
+        )
+    }
+
+    #[test]
+    fn whitelisted_client_requires_whitelisted_relayer() {
+        let client_id = ClientId::default();
+        let relayer = get_dummy_account_id();
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 5).unwrap())
+            .with_whitelisted_relayer(client_id.clone(), relayer.clone());
+        let router = MockRouter::default();
+
+        let update_client = |signer: Signer| {
+            MsgEnvelope::Client(ClientMsg::UpdateClient(MsgUpdateClient {
+                client_id: client_id.clone(),
+                client_message: MockHeader::new(Height::new(0, 6).unwrap()).into(),
+                signer,
+            }))
+        };
+
+        let res = validate(
+            &ctx,
+            &router,
+            update_client(get_dummy_bech32_account().into()),
+        );
+        assert!(is_not_whitelisted(res));
+
+        validate(&ctx, &router, update_client(relayer)).unwrap();
+    }
+
+    #[test]
+    fn whitelist_applies_to_channels_on_top_of_client() {
+        let client_id = ClientId::default();
+        let conn_id = ConnectionId::new(0);
+        let module_id = ModuleId::new("transfer".to_string());
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 1).unwrap())
+            .with_connection(
+                conn_id.clone(),
+                ConnectionEnd::new(
+                    ConnState::Open,
+                    client_id.clone(),
+                    ConnCounterparty::new(
+                        client_id.clone(),
+                        Some(conn_id.clone()),
+                        CommitmentPrefix::default(),
+                    ),
+                    vec![ConnVersion::default()],
+                    Duration::MAX,
+                )
+                .unwrap(),
+            )
+            .with_channel(
+                PortId::default(),
+                ChannelId::default(),
+                ChannelEnd::new(
+                    ChannelState::Open,
+                    ChannelOrder::Unordered,
+                    ChannelCounterparty::new(PortId::default(), Some(ChannelId::default())),
+                    vec![conn_id],
+                    ChannelVersion::default(),
+                )
+                .unwrap(),
+            )
+            .with_port(PortId::default(), module_id.clone());
+
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .scope_port_to_module(PortId::default(), module_id)
+            .unwrap();
+
+        let msg = MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap();
+        let signer = msg.signer.clone();
+        let msg = MsgEnvelope::Channel(ChannelMsg::CloseInit(msg));
+
+        let ctx = ctx.with_whitelisted_relayer(client_id.clone(), get_dummy_account_id());
+        let res = validate(&ctx, &router, msg.clone());
+        assert!(is_not_whitelisted(res));
+
+        let ctx = ctx.with_whitelisted_relayer(client_id, signer);
+        validate(&ctx, &router, msg).unwrap();
+    }
+}
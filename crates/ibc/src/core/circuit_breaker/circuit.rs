@@ -0,0 +1,74 @@
+//! Defines the [`Circuit`] type and the logic to find the circuits a message
+//! travels through.
+
+use derive_more::Display;
+
+use crate::core::authorization::AffectedIdentifiers;
+use crate::core::context::RouterError;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::{MsgEnvelope, ValidationContext};
+use crate::prelude::*;
+
+/// A unit of IBC traffic that can be halted by the circuit breaker.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum Circuit {
+    /// All messages with the given type URL, e.g. `/ibc.core.channel.v1.MsgRecvPacket`
+    #[display(fmt = "message type {_0}")]
+    MsgType(String),
+    /// All messages that involve the given client
+    #[display(fmt = "client {_0}")]
+    Client(ClientId),
+    /// All messages that involve the given connection
+    #[display(fmt = "connection {_0}")]
+    Connection(ConnectionId),
+    /// All messages that involve the given channel end
+    #[display(fmt = "channel {_1} on port {_0}")]
+    Channel(PortId, ChannelId),
+}
+
+/// Returns an error if any of the circuits that `msg` travels through has
+/// been tripped.
+pub(crate) fn ensure_circuits_closed<Ctx>(
+    ctx: &Ctx,
+    msg: &MsgEnvelope,
+    identifiers: &AffectedIdentifiers,
+) -> Result<(), RouterError>
+where
+    Ctx: ValidationContext,
+{
+    match affected_circuits(msg, identifiers)
+        .into_iter()
+        .find(|circuit| ctx.is_circuit_broken(circuit))
+    {
+        Some(circuit) => Err(RouterError::CircuitBroken { circuit }),
+        None => Ok(()),
+    }
+}
+
+/// Collects the circuits that `msg` travels through, i.e. its type and all
+/// the identifiers it involves.
+fn affected_circuits(msg: &MsgEnvelope, identifiers: &AffectedIdentifiers) -> Vec<Circuit> {
+    let mut circuits = vec![Circuit::MsgType(msg.type_url())];
+
+    circuits.extend(
+        identifiers
+            .channels
+            .iter()
+            .map(|(port_id, chan_id)| Circuit::Channel(port_id.clone(), chan_id.clone())),
+    );
+    circuits.extend(
+        identifiers
+            .connection_ids
+            .iter()
+            .cloned()
+            .map(Circuit::Connection),
+    );
+    circuits.extend(identifiers.client_ids.iter().cloned().map(Circuit::Client));
+
+    circuits
+}
@@ -0,0 +1,24 @@
+//! Defines the context required by the circuit breaker handler.
+
+use super::{Circuit, CircuitBreakerError};
+use crate::signer::Signer;
+
+/// Context to be implemented by hosts that let an authority trip and reset
+/// circuits through [`circuit_breaker_handler`](super::circuit_breaker_handler).
+///
+/// For the tripped circuits to be enforced, the host must also forward
+/// [`ValidationContext::is_circuit_broken`](crate::core::ValidationContext::is_circuit_broken)
+/// to [`CircuitBreakerContext::is_circuit_tripped`].
+pub trait CircuitBreakerContext {
+    /// Returns `true` if the given `signer` is allowed to trip and reset circuits.
+    fn is_circuit_breaker_authority(&self, signer: &Signer) -> bool;
+
+    /// Returns `true` if the given circuit is currently tripped.
+    fn is_circuit_tripped(&self, circuit: &Circuit) -> bool;
+
+    /// Trips the given circuit, halting all traffic covered by it.
+    fn trip_circuit(&mut self, circuit: Circuit) -> Result<(), CircuitBreakerError>;
+
+    /// Resets the given circuit, resuming all traffic covered by it.
+    fn reset_circuit(&mut self, circuit: &Circuit) -> Result<(), CircuitBreakerError>;
+}
@@ -0,0 +1,25 @@
+//! Defines the circuit breaker error type
+
+use displaydoc::Display;
+
+use super::Circuit;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Encodes all the possible circuit breaker errors
+#[derive(Debug, Display)]
+pub enum CircuitBreakerError {
+    /// signer `{signer}` is not a circuit breaker authority
+    Unauthorized { signer: Signer },
+    /// no circuit was specified
+    EmptyCircuits,
+    /// circuit `{circuit}` is already tripped
+    AlreadyTripped { circuit: Circuit },
+    /// circuit `{circuit}` is not tripped
+    NotTripped { circuit: Circuit },
+    /// other error: `{description}`
+    Other { description: String },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CircuitBreakerError {}
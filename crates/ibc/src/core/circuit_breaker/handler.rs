@@ -0,0 +1,302 @@
+use alloc::collections::BTreeSet;
+
+use super::{CircuitBreakerContext, CircuitBreakerError, CircuitBreakerMsg};
+
+/// Handles a circuit breaker message
+///
+/// The signer of the message must be a circuit breaker authority. All the
+/// circuits of the message are then either tripped or reset: the message is
+/// validated as a whole first, so that either all of its circuits are applied
+/// or, if any of them cannot be, none is.
+pub fn circuit_breaker_handler<Ctx>(
+    ctx: &mut Ctx,
+    msg: CircuitBreakerMsg,
+) -> Result<(), CircuitBreakerError>
+where
+    Ctx: CircuitBreakerContext,
+{
+    circuit_breaker_validate(ctx, &msg)?;
+    circuit_breaker_execute(ctx, msg)
+}
+
+/// Checks that the signer of a circuit breaker message is an authority, and
+/// that each of its circuits, listed once, can be tripped or reset.
+pub fn circuit_breaker_validate<Ctx>(
+    ctx: &Ctx,
+    msg: &CircuitBreakerMsg,
+) -> Result<(), CircuitBreakerError>
+where
+    Ctx: CircuitBreakerContext,
+{
+    let (authority, circuits) = match msg {
+        CircuitBreakerMsg::Trip(msg) => (&msg.authority, &msg.circuits),
+        CircuitBreakerMsg::Reset(msg) => (&msg.authority, &msg.circuits),
+    };
+
+    if !ctx.is_circuit_breaker_authority(authority) {
+        return Err(CircuitBreakerError::Unauthorized {
+            signer: authority.clone(),
+        });
+    }
+
+    if circuits.is_empty() {
+        return Err(CircuitBreakerError::EmptyCircuits);
+    }
+
+    // A circuit listed twice would fail to be applied the second time.
+    let mut listed = BTreeSet::new();
+    for circuit in circuits {
+        let is_duplicate = !listed.insert(circuit);
+        let is_tripped = ctx.is_circuit_tripped(circuit);
+        match msg {
+            CircuitBreakerMsg::Trip(_) if is_tripped || is_duplicate => {
+                return Err(CircuitBreakerError::AlreadyTripped {
+                    circuit: circuit.clone(),
+                });
+            }
+            CircuitBreakerMsg::Reset(_) if !is_tripped || is_duplicate => {
+                return Err(CircuitBreakerError::NotTripped {
+                    circuit: circuit.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Trips or resets all the circuits of a circuit breaker message, which must
+/// have been validated with [`circuit_breaker_validate`].
+pub fn circuit_breaker_execute<Ctx>(
+    ctx: &mut Ctx,
+    msg: CircuitBreakerMsg,
+) -> Result<(), CircuitBreakerError>
+where
+    Ctx: CircuitBreakerContext,
+{
+    match msg {
+        CircuitBreakerMsg::Trip(msg) => {
+            for circuit in msg.circuits {
+                ctx.trip_circuit(circuit)?;
+            }
+        }
+        CircuitBreakerMsg::Reset(msg) => {
+            for circuit in msg.circuits.iter() {
+                ctx.reset_circuit(circuit)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use test_log::test;
+
+    use super::*;
+    use crate::core::circuit_breaker::{Circuit, MsgResetCircuit, MsgTripCircuit};
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnCounterparty, State as ConnState,
+    };
+    use crate::core::ics03_connection::version::Version as ConnVersion;
+    use crate::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty as ChannelCounterparty, Order as ChannelOrder,
+        State as ChannelState,
+    };
+    use crate::core::ics04_channel::msgs::chan_close_init::test_util::get_dummy_raw_msg_chan_close_init;
+    use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics04_channel::Version as ChannelVersion;
+    use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::router::ModuleId;
+    use crate::core::{dispatch, validate, MsgEnvelope, RouterError};
+    use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
+    use crate::mock::consensus_state::MockConsensusState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::router::MockRouter;
+    use crate::prelude::*;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
+    use crate::Height;
+
+    fn authority() -> Signer {
+        "authority".to_string().into()
+    }
+
+    fn trip(circuits: Vec<Circuit>) -> CircuitBreakerMsg {
+        CircuitBreakerMsg::Trip(MsgTripCircuit {
+            authority: authority(),
+            circuits,
+        })
+    }
+
+    fn reset(circuits: Vec<Circuit>) -> CircuitBreakerMsg {
+        CircuitBreakerMsg::Reset(MsgResetCircuit {
+            authority: authority(),
+            circuits,
+        })
+    }
+
+    #[test]
+    fn trip_requires_authority() {
+        let mut ctx = MockContext::default().with_circuit_breaker_authority(authority());
+
+        let msg = CircuitBreakerMsg::Trip(MsgTripCircuit {
+            authority: get_dummy_account_id(),
+            circuits: vec![Circuit::Client(ClientId::default())],
+        });
+        let res = circuit_breaker_handler(&mut ctx, msg);
+        assert!(matches!(res, Err(CircuitBreakerError::Unauthorized { .. })));
+
+        let res = circuit_breaker_handler(&mut ctx, trip(vec![]));
+        assert!(matches!(res, Err(CircuitBreakerError::EmptyCircuits)));
+
+        let res =
+            circuit_breaker_handler(&mut ctx, reset(vec![Circuit::Client(ClientId::default())]));
+        assert!(matches!(res, Err(CircuitBreakerError::NotTripped { .. })));
+    }
+
+    #[test]
+    fn failing_circuit_applies_none_of_the_message() {
+        let mut ctx = MockContext::default().with_circuit_breaker_authority(authority());
+        let client = |n| Circuit::Client(ClientId::new(mock_client_type(), n).unwrap());
+
+        circuit_breaker_handler(&mut ctx, trip(vec![client(2)])).unwrap();
+
+        // the third circuit is already tripped
+        let res = circuit_breaker_handler(&mut ctx, trip(vec![client(0), client(1), client(2)]));
+        assert!(matches!(
+            res,
+            Err(CircuitBreakerError::AlreadyTripped { .. })
+        ));
+        assert!(!ctx.is_circuit_tripped(&client(0)));
+        assert!(!ctx.is_circuit_tripped(&client(1)));
+
+        // the same circuit is listed twice
+        let res = circuit_breaker_handler(&mut ctx, trip(vec![client(0), client(0)]));
+        assert!(matches!(
+            res,
+            Err(CircuitBreakerError::AlreadyTripped { .. })
+        ));
+        assert!(!ctx.is_circuit_tripped(&client(0)));
+
+        // the first circuit is not tripped
+        let res = circuit_breaker_handler(&mut ctx, reset(vec![client(0), client(2)]));
+        assert!(matches!(res, Err(CircuitBreakerError::NotTripped { .. })));
+        assert!(ctx.is_circuit_tripped(&client(2)));
+
+        let res = circuit_breaker_handler(&mut ctx, reset(vec![client(2), client(2)]));
+        assert!(matches!(res, Err(CircuitBreakerError::NotTripped { .. })));
+        assert!(ctx.is_circuit_tripped(&client(2)));
+
+        circuit_breaker_handler(&mut ctx, reset(vec![client(2)])).unwrap();
+        assert!(!ctx.is_circuit_tripped(&client(2)));
+    }
+
+    #[test]
+    fn tripped_msg_type_and_client_are_rejected() {
+        let client_id = ClientId::default();
+        let client_height = Height::new(0, 5).unwrap();
+        let mut ctx = MockContext::default()
+            .with_client(&client_id, client_height)
+            .with_circuit_breaker_authority(authority());
+        let mut router = MockRouter::default();
+
+        let create_client_msg = MsgEnvelope::Client(ClientMsg::CreateClient(MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(client_height)).into(),
+            MockConsensusState::new(MockHeader::new(client_height)).into(),
+            get_dummy_account_id(),
+        )));
+        let create_client_circuit = Circuit::MsgType(create_client_msg.type_url());
+
+        circuit_breaker_handler(&mut ctx, trip(vec![create_client_circuit.clone()])).unwrap();
+        let res = dispatch(&mut ctx, &mut router, create_client_msg.clone());
+        assert!(
+            matches!(res, Err(RouterError::CircuitBroken { ref circuit }) if circuit == &create_client_circuit)
+        );
+
+        circuit_breaker_handler(&mut ctx, reset(vec![create_client_circuit])).unwrap();
+        dispatch(&mut ctx, &mut router, create_client_msg).unwrap();
+
+        let update_client_msg = MsgEnvelope::Client(ClientMsg::UpdateClient(MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message: MockHeader::new(Height::new(0, 6).unwrap()).into(),
+            signer: get_dummy_account_id(),
+        }));
+
+        circuit_breaker_handler(&mut ctx, trip(vec![Circuit::Client(client_id.clone())])).unwrap();
+        let res = validate(&ctx, &router, update_client_msg.clone());
+        assert!(matches!(res, Err(RouterError::CircuitBroken { .. })));
+
+        circuit_breaker_handler(&mut ctx, reset(vec![Circuit::Client(client_id)])).unwrap();
+        validate(&ctx, &router, update_client_msg).unwrap();
+    }
+
+    #[test]
+    fn tripped_client_halts_channels_on_top_of_it() {
+        let client_id = ClientId::default();
+        let conn_id = ConnectionId::new(0);
+        let module_id = ModuleId::new("transfer".to_string());
+        let mut ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 1).unwrap())
+            .with_connection(
+                conn_id.clone(),
+                ConnectionEnd::new(
+                    ConnState::Open,
+                    client_id.clone(),
+                    ConnCounterparty::new(
+                        client_id.clone(),
+                        Some(conn_id.clone()),
+                        CommitmentPrefix::default(),
+                    ),
+                    vec![ConnVersion::default()],
+                    Duration::MAX,
+                )
+                .unwrap(),
+            )
+            .with_channel(
+                PortId::default(),
+                ChannelId::default(),
+                ChannelEnd::new(
+                    ChannelState::Open,
+                    ChannelOrder::Unordered,
+                    ChannelCounterparty::new(PortId::default(), Some(ChannelId::default())),
+                    vec![conn_id],
+                    ChannelVersion::default(),
+                )
+                .unwrap(),
+            )
+            .with_port(PortId::default(), module_id.clone())
+            .with_circuit_breaker_authority(authority());
+
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .scope_port_to_module(PortId::default(), module_id)
+            .unwrap();
+
+        let msg = MsgEnvelope::Channel(ChannelMsg::CloseInit(
+            MsgChannelCloseInit::try_from(get_dummy_raw_msg_chan_close_init()).unwrap(),
+        ));
+
+        circuit_breaker_handler(&mut ctx, trip(vec![Circuit::Client(client_id.clone())])).unwrap();
+        let res = validate(&ctx, &router, msg.clone());
+        assert!(
+            matches!(res, Err(RouterError::CircuitBroken { circuit: Circuit::Client(ref id) }) if id == &client_id)
+        );
+
+        circuit_breaker_handler(&mut ctx, reset(vec![Circuit::Client(client_id)])).unwrap();
+        dispatch(&mut ctx, &mut router, msg).unwrap();
+    }
+}
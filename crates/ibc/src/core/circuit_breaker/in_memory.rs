@@ -0,0 +1,70 @@
+//! Provides an in-memory implementation of [`CircuitBreakerContext`].
+
+use alloc::collections::BTreeSet;
+
+use super::{Circuit, CircuitBreakerContext, CircuitBreakerError};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// A reference implementation of [`CircuitBreakerContext`] which keeps the
+/// authorities and tripped circuits in memory.
+///
+/// Hosts can embed it in their context and persist it alongside the rest of
+/// their IBC state.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InMemoryCircuitBreaker {
+    authorities: BTreeSet<Signer>,
+    tripped: BTreeSet<Circuit>,
+}
+
+impl InMemoryCircuitBreaker {
+    /// Creates a circuit breaker which can be operated by the given authorities.
+    pub fn new(authorities: impl IntoIterator<Item = Signer>) -> Self {
+        Self {
+            authorities: authorities.into_iter().collect(),
+            tripped: BTreeSet::new(),
+        }
+    }
+
+    /// Adds an authority allowed to trip and reset circuits.
+    pub fn add_authority(&mut self, authority: Signer) {
+        self.authorities.insert(authority);
+    }
+
+    /// Returns all the currently tripped circuits.
+    pub fn tripped_circuits(&self) -> impl Iterator<Item = &Circuit> {
+        self.tripped.iter()
+    }
+}
+
+impl CircuitBreakerContext for InMemoryCircuitBreaker {
+    fn is_circuit_breaker_authority(&self, signer: &Signer) -> bool {
+        self.authorities.contains(signer)
+    }
+
+    fn is_circuit_tripped(&self, circuit: &Circuit) -> bool {
+        self.tripped.contains(circuit)
+    }
+
+    fn trip_circuit(&mut self, circuit: Circuit) -> Result<(), CircuitBreakerError> {
+        if self.tripped.contains(&circuit) {
+            return Err(CircuitBreakerError::AlreadyTripped { circuit });
+        }
+        self.tripped.insert(circuit);
+        Ok(())
+    }
+
+    fn reset_circuit(&mut self, circuit: &Circuit) -> Result<(), CircuitBreakerError> {
+        if !self.tripped.remove(circuit) {
+            return Err(CircuitBreakerError::NotTripped {
+                circuit: circuit.clone(),
+            });
+        }
+        Ok(())
+    }
+}
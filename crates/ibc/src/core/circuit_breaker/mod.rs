@@ -0,0 +1,23 @@
+//! Provides a circuit breaker which lets hosts halt IBC traffic at runtime,
+//! e.g. during an incident, without requiring a chain upgrade.
+//!
+//! Once a [`Circuit`] is tripped, [`validate`](crate::core::validate) rejects
+//! every message covered by it with
+//! [`RouterError::CircuitBroken`](crate::core::RouterError::CircuitBroken).
+//! Circuits are tripped and reset through the governance-style messages
+//! handled by [`circuit_breaker_handler`].
+
+mod circuit;
+mod context;
+mod error;
+mod handler;
+mod in_memory;
+mod msgs;
+
+pub(crate) use circuit::ensure_circuits_closed;
+pub use circuit::Circuit;
+pub use context::CircuitBreakerContext;
+pub use error::CircuitBreakerError;
+pub use handler::{circuit_breaker_execute, circuit_breaker_handler, circuit_breaker_validate};
+pub use in_memory::InMemoryCircuitBreaker;
+pub use msgs::{CircuitBreakerMsg, MsgResetCircuit, MsgTripCircuit};
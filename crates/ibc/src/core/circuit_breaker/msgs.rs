@@ -0,0 +1,47 @@
+//! Defines the governance-style messages to trip and reset circuits.
+
+use super::Circuit;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// Halts all IBC traffic covered by the given circuits.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgTripCircuit {
+    /// The address allowed to operate the circuit breaker, typically the
+    /// governance module account
+    pub authority: Signer,
+    /// The circuits to trip
+    pub circuits: Vec<Circuit>,
+}
+
+/// Resumes all IBC traffic covered by the given circuits.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgResetCircuit {
+    /// The address allowed to operate the circuit breaker, typically the
+    /// governance module account
+    pub authority: Signer,
+    /// The circuits to reset
+    pub circuits: Vec<Circuit>,
+}
+
+/// All circuit breaker messages
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitBreakerMsg {
+    Trip(MsgTripCircuit),
+    Reset(MsgResetCircuit),
+}
@@ -5,29 +5,37 @@ use derive_more::From;
 use displaydoc::Display;
 use ibc_proto::google::protobuf::Any;
 
+use super::authorization::{AffectedIdentifiers, AuthorizationError};
+use super::circuit_breaker::Circuit;
 use super::ics02_client::client_state::ClientState;
 use super::ics02_client::consensus_state::ConsensusState;
+use super::ics02_client::params::ClientParams;
 use super::ics02_client::ClientExecutionContext;
 use super::ics24_host::identifier::PortId;
-use crate::core::events::IbcEvent;
+use crate::core::events::{IbcEvent, IbcEventListener};
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics03_connection::params::ConnectionParams;
 use crate::core::ics03_connection::version::{
     get_compatible_versions, pick_version, Version as ConnectionVersion,
 };
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::calculate_block_delay;
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
-use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::packet::{Packet, Receipt, Sequence};
+use crate::core::ics05_port::error::PortError;
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 use crate::core::ics24_host::path::{
     AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, CommitmentPath,
-    ConnectionPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+    ConnectionPath, PortPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
 };
+use crate::core::router::ModuleId;
 use crate::core::timestamp::Timestamp;
+use crate::core::MsgEnvelope;
 use crate::prelude::*;
 use crate::signer::Signer;
 use crate::Height;
@@ -43,6 +51,8 @@ pub enum ContextError {
     ChannelError(ChannelError),
     /// ICS04 Packet error: {0}
     PacketError(PacketError),
+    /// ICS05 Port error: {0}
+    PortError(PortError),
 }
 
 #[cfg(feature = "std")]
@@ -53,6 +63,7 @@ impl std::error::Error for ContextError {
             Self::ConnectionError(e) => Some(e),
             Self::ChannelError(e) => Some(e),
             Self::PacketError(e) => Some(e),
+            Self::PortError(e) => Some(e),
         }
     }
 }
@@ -71,6 +82,10 @@ pub enum RouterError {
     UnknownPort { port_id: PortId },
     /// module not found
     ModuleNotFound,
+    /// circuit for {circuit} is broken
+    CircuitBroken { circuit: Circuit },
+    /// authorization error: `{0}`
+    AuthorizationError(AuthorizationError),
 }
 
 impl From<ContextError> for RouterError {
@@ -85,6 +100,7 @@ impl std::error::Error for RouterError {
         match &self {
             Self::ContextError(e) => Some(e),
             Self::MalformedMessageBytes(e) => Some(e),
+            Self::AuthorizationError(e) => Some(e),
             _ => None,
         }
     }
@@ -193,6 +209,10 @@ pub trait ValidationContext {
         Ok(version)
     }
 
+    /// Returns the [`ModuleId`] of the module the given port is bound to, or
+    /// `None` if the port is not bound.
+    fn port_owner(&self, port_path: &PortPath) -> Result<Option<ModuleId>, ContextError>;
+
     /// Returns the `ChannelEnd` for the given `port_id` and `chan_id`.
     fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError>;
 
@@ -227,18 +247,58 @@ pub trait ValidationContext {
     /// `ExecutionContext::increase_channel_counter`.
     fn channel_counter(&self) -> Result<u64, ContextError>;
 
+    /// Returns the parameters of the client submodule.
+    fn client_params(&self) -> Result<ClientParams, ContextError>;
+
+    /// Returns the parameters of the connection submodule.
+    fn connection_params(&self) -> Result<ConnectionParams, ContextError>;
+
+    /// Returns `true` if `signer` is allowed to update the parameters of the
+    /// client and connection submodules, e.g. if it is the governance module.
+    fn is_params_authority(&self, signer: &Signer) -> bool;
+
     /// Returns the maximum expected time per block
-    fn max_expected_time_per_block(&self) -> Duration;
+    ///
+    /// Defaults to the value of the connection params.
+    fn max_expected_time_per_block(&self) -> Result<Duration, ContextError> {
+        self.connection_params()
+            .map(|params| params.max_expected_time_per_block())
+    }
 
     /// Calculates the block delay period using the connection's delay period and the maximum
     /// expected time per block.
-    fn block_delay(&self, delay_period_time: &Duration) -> u64 {
-        calculate_block_delay(delay_period_time, &self.max_expected_time_per_block())
+    fn block_delay(&self, delay_period_time: &Duration) -> Result<u64, ContextError> {
+        Ok(calculate_block_delay(
+            delay_period_time,
+            &self.max_expected_time_per_block()?,
+        ))
     }
 
     /// Validates the `signer` field of IBC messages, which represents the address
     /// of the user/relayer that signed the given message.
     fn validate_message_signer(&self, signer: &Signer) -> Result<(), ContextError>;
+
+    /// Returns `true` if the given [`Circuit`] has been tripped, in which case
+    /// [`validate`](crate::core::validate) rejects every message covered by it.
+    ///
+    /// By default, no circuit is ever tripped.
+    fn is_circuit_broken(&self, _circuit: &Circuit) -> bool {
+        false
+    }
+
+    /// Authorizes `signer` to submit `msg`, which involves the given
+    /// `identifiers`. Hosts typically delegate to one or more
+    /// [`Authorization`](crate::core::authorization::Authorization) policies.
+    ///
+    /// By default, every well-formed signer is authorized.
+    fn authorize_message(
+        &self,
+        _signer: &Signer,
+        _msg: &MsgEnvelope,
+        _identifiers: &AffectedIdentifiers,
+    ) -> Result<(), AuthorizationError> {
+        Ok(())
+    }
 }
 
 /// Context to be implemented by the host that provides all "write-only" methods.
@@ -287,6 +347,12 @@ pub trait ExecutionContext: ValidationContext {
         conn_id: ConnectionId,
     ) -> Result<(), ContextError>;
 
+    /// Stores the parameters of the client submodule.
+    fn store_client_params(&mut self, params: ClientParams) -> Result<(), ContextError>;
+
+    /// Stores the parameters of the connection submodule.
+    fn store_connection_params(&mut self, params: ConnectionParams) -> Result<(), ContextError>;
+
     /// Called upon connection identifier creation (Init or Try process).
     /// Increases the counter which keeps track of how many connections have been created.
     /// Should never fail.
@@ -322,6 +388,16 @@ pub trait ExecutionContext: ValidationContext {
     /// Deletes the packet acknowledgement at the given store path
     fn delete_packet_acknowledgement(&mut self, ack_path: &AckPath) -> Result<(), ContextError>;
 
+    /// Binds the port at the given path to the module identified by `module_id`.
+    fn store_port_owner(
+        &mut self,
+        port_path: &PortPath,
+        module_id: ModuleId,
+    ) -> Result<(), ContextError>;
+
+    /// Releases the binding of the port at the given path.
+    fn delete_port_owner(&mut self, port_path: &PortPath) -> Result<(), ContextError>;
+
     /// Stores the given channel_end at a path associated with the port_id and channel_id.
     fn store_channel(
         &mut self,
@@ -358,6 +434,42 @@ pub trait ExecutionContext: ValidationContext {
     /// Emit the given IBC event
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError>;
 
+    /// Returns the listener notified of every IBC event the handlers emit, if
+    /// any. Returns `None` by default.
+    fn ibc_event_listener(&self) -> Option<&dyn IbcEventListener> {
+        None
+    }
+
+    /// Emits the given IBC event with [`emit_ibc_event`](Self::emit_ibc_event),
+    /// then notifies the [`ibc_event_listener`](Self::ibc_event_listener) of it
+    /// if it was emitted. The handlers emit all their events through this
+    /// method.
+    fn publish_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
+        self.emit_ibc_event(event.clone())?;
+        if let Some(listener) = self.ibc_event_listener() {
+            listener.on_ibc_event(&event);
+        }
+        Ok(())
+    }
+
     /// Log the given message.
     fn log_message(&mut self, message: String) -> Result<(), ContextError>;
+
+    /// Called with the full packet once it is sent, as the store only holds
+    /// its commitment. Hosts may index it to serve packet data queries to
+    /// relayers. Does nothing by default.
+    fn record_sent_packet(&mut self, _packet: &Packet) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    /// Called with the received packet and its acknowledgement once the
+    /// acknowledgement is written. Hosts may index them to serve packet data
+    /// queries to relayers. Does nothing by default.
+    fn record_written_acknowledgement(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
 }
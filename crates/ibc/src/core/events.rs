@@ -1,6 +1,7 @@
 //! Events emitted during message handling
 
 use core::convert::{TryFrom, TryInto};
+use core::fmt::Debug;
 
 use displaydoc::Display;
 use tendermint::abci;
@@ -51,7 +52,7 @@ impl std::error::Error for Error {
     }
 }
 
-const MESSAGE_EVENT: &str = "message";
+pub(crate) const MESSAGE_EVENT: &str = "message";
 
 /// Events created by the IBC component of a chain, destined for a relayer.
 #[cfg_attr(
@@ -97,6 +98,17 @@ pub enum IbcEvent {
     Message(MessageEvent),
 }
 
+/// Receives the IBC events emitted by the handlers, once the host emitted them.
+///
+/// Hosts hand their listener to the handlers through
+/// [`ExecutionContext::ibc_event_listener`](crate::core::ExecutionContext::ibc_event_listener).
+/// Listeners are notified while the transaction is still executing: those
+/// forwarding events outside the host should hold them back until the host
+/// commits the transaction.
+pub trait IbcEventListener: Debug {
+    fn on_ibc_event(&self, event: &IbcEvent);
+}
+
 impl TryFrom<IbcEvent> for abci::Event {
     type Error = Error;
 
@@ -1,8 +1,13 @@
+use super::authorization::AffectedIdentifiers;
+use super::circuit_breaker::ensure_circuits_closed;
 use super::context::RouterError;
-use super::ics02_client::handler::{create_client, update_client, upgrade_client};
+use super::ics02_client::handler::{
+    create_client, update_client, update_params as client_update_params, upgrade_client,
+};
 use super::ics02_client::msgs::{ClientMsg, MsgUpdateOrMisbehaviour};
 use super::ics03_connection::handler::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try,
+    update_params as connection_update_params,
 };
 use super::ics03_connection::msgs::ConnectionMsg;
 use super::ics04_channel::handler::acknowledgement::{
@@ -29,6 +34,7 @@ use super::ics04_channel::handler::timeout::{
 use super::ics04_channel::msgs::{
     channel_msg_to_port_id, packet_msg_to_port_id, ChannelMsg, PacketMsg,
 };
+use super::ics05_port::verify_port_owner;
 use super::msgs::MsgEnvelope;
 use super::router::Router;
 use super::{ExecutionContext, ValidationContext};
@@ -55,6 +61,11 @@ pub fn validate<Ctx>(ctx: &Ctx, router: &impl Router, msg: MsgEnvelope) -> Resul
 where
     Ctx: ValidationContext,
 {
+    let identifiers = AffectedIdentifiers::new(ctx, &msg);
+    ensure_circuits_closed(ctx, &msg, &identifiers)?;
+    ctx.authorize_message(msg.signer(), &msg, &identifiers)
+        .map_err(RouterError::AuthorizationError)?;
+
     match msg {
         MsgEnvelope::Client(msg) => match msg {
             ClientMsg::CreateClient(msg) => create_client::validate(ctx, msg),
@@ -65,6 +76,7 @@ where
                 update_client::validate(ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg))
             }
             ClientMsg::UpgradeClient(msg) => upgrade_client::validate(ctx, msg),
+            ClientMsg::UpdateParams(ref msg) => client_update_params::validate(ctx, msg),
         }
         .map_err(RouterError::ContextError),
         MsgEnvelope::Connection(msg) => match msg {
@@ -72,6 +84,7 @@ where
             ConnectionMsg::OpenTry(msg) => conn_open_try::validate(ctx, msg),
             ConnectionMsg::OpenAck(msg) => conn_open_ack::validate(ctx, msg),
             ConnectionMsg::OpenConfirm(ref msg) => conn_open_confirm::validate(ctx, msg),
+            ConnectionMsg::UpdateParams(ref msg) => connection_update_params::validate(ctx, msg),
         }
         .map_err(RouterError::ContextError),
         MsgEnvelope::Channel(msg) => {
@@ -84,6 +97,7 @@ where
             let module = router
                 .get_route(&module_id)
                 .ok_or(RouterError::ModuleNotFound)?;
+            verify_port_owner(ctx, port_id, &module_id)?;
 
             match msg {
                 ChannelMsg::OpenInit(msg) => chan_open_init_validate(ctx, module, msg),
@@ -140,6 +154,7 @@ where
                 update_client::execute(ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg))
             }
             ClientMsg::UpgradeClient(msg) => upgrade_client::execute(ctx, msg),
+            ClientMsg::UpdateParams(msg) => client_update_params::execute(ctx, msg),
         }
         .map_err(RouterError::ContextError),
         MsgEnvelope::Connection(msg) => match msg {
@@ -147,6 +162,7 @@ where
             ConnectionMsg::OpenTry(msg) => conn_open_try::execute(ctx, msg),
             ConnectionMsg::OpenAck(msg) => conn_open_ack::execute(ctx, msg),
             ConnectionMsg::OpenConfirm(ref msg) => conn_open_confirm::execute(ctx, msg),
+            ConnectionMsg::UpdateParams(msg) => connection_update_params::execute(ctx, msg),
         }
         .map_err(RouterError::ContextError),
         MsgEnvelope::Channel(msg) => {
@@ -198,17 +214,18 @@ where
 
 #[cfg(test)]
 mod tests {
+    use alloc::sync::Arc;
     use core::default::Default;
     use core::time::Duration;
 
+    use parking_lot::Mutex;
     use test_log::test;
 
     use super::*;
     use crate::applications::transfer::error::TokenTransferError;
     use crate::applications::transfer::msgs::transfer::MsgTransfer;
     use crate::applications::transfer::{send_transfer, MODULE_ID_STR};
-    use crate::core::dispatch;
-    use crate::core::events::{IbcEvent, MessageEvent};
+    use crate::core::events::{IbcEvent, IbcEventListener, MessageEvent};
     use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
     use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
     use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
@@ -221,6 +238,9 @@ mod tests {
     use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
     use crate::core::ics03_connection::msgs::ConnectionMsg;
     use crate::core::ics03_connection::version::Version as ConnVersion;
+    use crate::core::ics04_channel::acknowledgement::{
+        Acknowledgement, AcknowledgementStatus, StatusValue,
+    };
     use crate::core::ics04_channel::channel::{
         ChannelEnd, Counterparty as ChannelCounterparty, Order as ChannelOrder,
         State as ChannelState,
@@ -247,17 +267,24 @@ mod tests {
     use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
     use crate::core::ics04_channel::timeout::TimeoutHeight;
     use crate::core::ics04_channel::Version as ChannelVersion;
+    use crate::core::ics05_port::error::PortError;
+    use crate::core::ics05_port::{bind_port, release_port};
     use crate::core::ics23_commitment::commitment::CommitmentPrefix;
-    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
-    use crate::core::ics24_host::path::CommitmentPath;
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics24_host::path::{ChannelEndPath, CommitmentPath};
     use crate::core::msgs::MsgEnvelope;
     use crate::core::router::ModuleId;
     use crate::core::timestamp::Timestamp;
+    use crate::core::{dispatch, ContextError};
     use crate::mock::client_state::MockClientState;
     use crate::mock::consensus_state::MockConsensusState;
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::module::{
+        Callback, CallbackArgs, Invocation, ScriptedModule, SCRIPTED_EVENT_KIND,
+    };
     use crate::mock::router::MockRouter;
+    use crate::mock::scenario::{Action, ChainSetup, PacketSpec, ScenarioMsg};
     use crate::prelude::*;
     use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
     use crate::Height;
@@ -396,7 +423,13 @@ mod tests {
             "ICS26 routing dispatch test 'client creation' failed for message {create_client_msg:?} with result: {res:?}",
         );
 
-        router.scope_port_to_module(msg_chan_init.port_id_on_a.clone(), transfer_module_id);
+        router
+            .scope_port_to_module(
+                msg_chan_init.port_id_on_a.clone(),
+                transfer_module_id.clone(),
+            )
+            .unwrap();
+        bind_port(&mut ctx, &msg_chan_init.port_id_on_a, transfer_module_id).unwrap();
 
         // Figure out the ID of the client that was just created.
         assert!(matches!(
@@ -666,7 +699,8 @@ mod tests {
                     Duration::MAX,
                 )
                 .unwrap(),
-            );
+            )
+            .with_port(PortId::default(), module_id.clone());
         let mut router = MockRouter::default();
 
         router
@@ -674,11 +708,45 @@ mod tests {
             .unwrap();
 
         // Note: messages will be using the default port
-        router.scope_port_to_module(PortId::default(), module_id);
+        router
+            .scope_port_to_module(PortId::default(), module_id)
+            .unwrap();
 
         (ctx, router)
     }
 
+    #[test]
+    fn channel_msg_requires_port_ownership() {
+        let (mut ctx, mut router) = get_channel_events_ctx_router();
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+        let other_module_id = ModuleId::new("other".to_string());
+
+        let msg = MsgEnvelope::Channel(ChannelMsg::OpenInit(
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap(),
+        ));
+
+        release_port(&mut ctx, &PortId::default(), &module_id).unwrap();
+        let res = validate(&ctx, &router, msg.clone());
+        assert!(matches!(
+            res,
+            Err(RouterError::ContextError(ContextError::PortError(
+                PortError::UnboundPort { .. }
+            )))
+        ));
+
+        bind_port(&mut ctx, &PortId::default(), other_module_id).unwrap();
+        let res = validate(&ctx, &router, msg.clone());
+        assert!(matches!(
+            res,
+            Err(RouterError::ContextError(ContextError::PortError(
+                PortError::NotPortOwner { .. }
+            )))
+        ));
+
+        let mut ctx = ctx.with_port(PortId::default(), module_id);
+        dispatch(&mut ctx, &mut router, msg).unwrap();
+    }
+
     #[test]
     fn test_chan_open_init_event() {
         let (mut ctx, mut router) = get_channel_events_ctx_router();
@@ -701,6 +769,36 @@ mod tests {
         assert!(matches!(ctx.events[1], IbcEvent::OpenInitChannel(_)));
     }
 
+    /// Records the events it is notified of.
+    #[derive(Clone, Debug, Default)]
+    struct RecordingListener(Arc<Mutex<Vec<IbcEvent>>>);
+
+    impl IbcEventListener for RecordingListener {
+        fn on_ibc_event(&self, event: &IbcEvent) {
+            self.0.lock().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn event_listener_is_notified_of_the_emitted_events() {
+        let (ctx, mut router) = get_channel_events_ctx_router();
+        let listener = RecordingListener::default();
+        let mut ctx = ctx.with_event_listener(listener.clone());
+
+        let msg_chan_open_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init(None)).unwrap();
+
+        dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Channel(ChannelMsg::OpenInit(msg_chan_open_init)),
+        )
+        .unwrap();
+
+        assert_eq!(ctx.events.len(), 2);
+        assert_eq!(*listener.0.lock(), ctx.events);
+    }
+
     #[test]
     fn test_chan_open_try_event() {
         let (mut ctx, mut router) = get_channel_events_ctx_router();
@@ -858,4 +956,255 @@ mod tests {
         ));
         assert!(matches!(ctx.events[1], IbcEvent::CloseConfirmChannel(_)));
     }
+
+    /// Returns a chain whose transfer port is routed to `module`, with an open
+    /// connection and, if its state is given, a channel on that port.
+    fn get_scripted_ctx_router(
+        module: ScriptedModule,
+        channel_state: Option<ChannelState>,
+    ) -> (MockContext, MockRouter) {
+        let module_id = ModuleId::new("scripted".to_string());
+        let mut router = MockRouter::default();
+        router.add_route(module_id.clone(), module).unwrap();
+        router
+            .scope_port_to_module(PortId::transfer(), module_id)
+            .unwrap();
+
+        let mut chain = ChainSetup::new(ChainId::new("mockgaia", 0).unwrap(), 10)
+            .with_open_connection(Height::new(0, 5).unwrap());
+        if let Some(state) = channel_state {
+            chain = chain.with_transfer_channel(state);
+        }
+
+        (chain.build(&router).unwrap(), router)
+    }
+
+    fn chan_open_init(version: &str) -> Action {
+        Action::Deliver(ScenarioMsg::ChanOpenInit {
+            port_id: PortId::transfer(),
+            connection_id: ConnectionId::new(0),
+            counterparty_port_id: PortId::transfer(),
+            ordering: ChannelOrder::Unordered,
+            version: ChannelVersion::new(version.to_string()),
+        })
+    }
+
+    fn packet(timeout_height: TimeoutHeight) -> PacketSpec {
+        PacketSpec {
+            sequence: 1,
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: "packet".to_string(),
+            timeout_height,
+            timeout_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn module_rejecting_a_version_fails_the_handshake() {
+        let module =
+            ScriptedModule::new().reject_version(ChannelVersion::new("ics20-1".to_string()));
+        let (mut ctx, mut router) = get_scripted_ctx_router(module.clone(), None);
+
+        let err = chan_open_init("ics20-1")
+            .run(&mut ctx, &mut router)
+            .unwrap_err();
+        assert!(err.contains("version `ics20-1` is not supported"), "{err}");
+        assert!(ctx.ibc_store.lock().channels.is_empty());
+
+        chan_open_init("ics20-2")
+            .run(&mut ctx, &mut router)
+            .unwrap();
+        assert_eq!(
+            module.callbacks(),
+            [
+                Callback::ChanOpenInitValidate,
+                Callback::ChanOpenInitValidate,
+                Callback::ChanOpenInitExecute,
+            ]
+        );
+    }
+
+    #[test]
+    fn module_callbacks_receive_the_channel_arguments() {
+        let module = ScriptedModule::new();
+        let (mut ctx, mut router) = get_scripted_ctx_router(module.clone(), None);
+
+        Action::Deliver(ScenarioMsg::ChanOpenTry {
+            port_id: PortId::transfer(),
+            connection_id: ConnectionId::new(0),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: ChannelId::new(3),
+            ordering: ChannelOrder::Unordered,
+            version: ChannelVersion::new("ics20-1".to_string()),
+        })
+        .run(&mut ctx, &mut router)
+        .unwrap();
+
+        let args = CallbackArgs::ChanOpen {
+            order: ChannelOrder::Unordered,
+            connection_hops: vec![ConnectionId::new(0)],
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            counterparty: ChannelCounterparty::new(PortId::transfer(), Some(ChannelId::new(3))),
+            version: ChannelVersion::new("ics20-1".to_string()),
+        };
+        assert_eq!(
+            module.invocations(),
+            [
+                Invocation {
+                    callback: Callback::ChanOpenTryValidate,
+                    args: args.clone(),
+                },
+                Invocation {
+                    callback: Callback::ChanOpenTryExecute,
+                    args,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn module_events_follow_the_core_events() {
+        let module = ScriptedModule::new().with_events(2);
+        let (mut ctx, mut router) = get_scripted_ctx_router(module.clone(), None);
+
+        chan_open_init("ics20-1")
+            .run(&mut ctx, &mut router)
+            .unwrap();
+
+        assert_eq!(ctx.events.len(), 4);
+        assert!(matches!(ctx.events[1], IbcEvent::OpenInitChannel(_)));
+        let module_events = ctx.events[2..]
+            .iter()
+            .filter(|event| matches!(event, IbcEvent::Module(e) if e.kind == SCRIPTED_EVENT_KIND))
+            .count();
+        assert_eq!(module_events, 2);
+        assert_eq!(module.events_emitted(), module_events);
+    }
+
+    #[test]
+    fn failing_channel_callbacks_leave_the_channel_unchanged() {
+        let module =
+            ScriptedModule::new().fail(Callback::ChanCloseInitExecute, "channel must stay open");
+        let (mut ctx, mut router) =
+            get_scripted_ctx_router(module.clone(), Some(ChannelState::Open));
+
+        let err = Action::Deliver(ScenarioMsg::ChanCloseInit {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+        })
+        .run(&mut ctx, &mut router)
+        .unwrap_err();
+        assert!(err.contains("channel must stay open"), "{err}");
+
+        let channel_end = ctx
+            .channel_end(&ChannelEndPath::new(
+                &PortId::transfer(),
+                &ChannelId::new(0),
+            ))
+            .unwrap();
+        assert_eq!(channel_end.state, ChannelState::Open);
+        assert_eq!(
+            module.callbacks(),
+            [
+                Callback::ChanCloseInitValidate,
+                Callback::ChanCloseInitExecute
+            ]
+        );
+    }
+
+    #[test]
+    fn module_acknowledgements_are_written() {
+        let error_ack: Acknowledgement =
+            AcknowledgementStatus::error(StatusValue::new("insufficient funds").unwrap()).into();
+        let custom_ack = Acknowledgement::try_from(b"custom".to_vec()).unwrap();
+
+        for (module, expected_ack) in [
+            (
+                ScriptedModule::new().fail(Callback::RecvPacketExecute, "insufficient funds"),
+                error_ack,
+            ),
+            (
+                ScriptedModule::new().with_acknowledgement(custom_ack.clone()),
+                custom_ack,
+            ),
+        ] {
+            let (mut ctx, mut router) = get_scripted_ctx_router(module, Some(ChannelState::Open));
+
+            Action::Deliver(ScenarioMsg::RecvPacket(packet(TimeoutHeight::Never)))
+                .run(&mut ctx, &mut router)
+                .unwrap();
+
+            let written = ctx.ibc_store.lock().written_acknowledgements[&PortId::transfer()]
+                [&ChannelId::new(0)][&1.into()]
+                .1
+                .clone();
+            assert_eq!(written, expected_ack);
+            assert!(ctx.events.iter().any(|event| matches!(
+                event,
+                IbcEvent::WriteAcknowledgement(e) if e.acknowledgement() == &expected_ack
+            )));
+        }
+    }
+
+    #[test]
+    fn failing_acknowledgement_callback_keeps_the_packet_commitment() {
+        let module =
+            ScriptedModule::new().fail(Callback::AcknowledgementPacketExecute, "cannot refund");
+        let (mut ctx, mut router) =
+            get_scripted_ctx_router(module.clone(), Some(ChannelState::Open));
+        Action::SendPacket(packet(TimeoutHeight::Never))
+            .run(&mut ctx, &mut router)
+            .unwrap();
+
+        let err = Action::Deliver(ScenarioMsg::Acknowledgement {
+            packet: packet(TimeoutHeight::Never),
+            acknowledgement: r#"{"result":"AQ=="}"#.to_string(),
+        })
+        .run(&mut ctx, &mut router)
+        .unwrap_err();
+        assert!(err.contains("cannot refund"), "{err}");
+
+        let commitment_path =
+            CommitmentPath::new(&PortId::transfer(), &ChannelId::new(0), 1.into());
+        assert!(ctx.get_packet_commitment(&commitment_path).is_ok());
+        assert_eq!(
+            module.callbacks(),
+            [
+                Callback::AcknowledgementPacketValidate,
+                Callback::AcknowledgementPacketExecute,
+            ]
+        );
+    }
+
+    #[test]
+    fn failing_timeout_validation_rejects_the_timeout() {
+        let module =
+            ScriptedModule::new().fail(Callback::TimeoutPacketValidate, "timeout not allowed");
+        let (mut ctx, mut router) =
+            get_scripted_ctx_router(module.clone(), Some(ChannelState::Open));
+        let timeout_height = TimeoutHeight::At(Height::new(0, 12).unwrap());
+
+        Action::SendPacket(packet(timeout_height))
+            .run(&mut ctx, &mut router)
+            .unwrap();
+        Action::Deliver(ScenarioMsg::UpdateClient {
+            client_id: "9999-mock-0".parse().unwrap(),
+            height: Height::new(0, 15).unwrap(),
+        })
+        .run(&mut ctx, &mut router)
+        .unwrap();
+
+        let err = Action::Deliver(ScenarioMsg::Timeout {
+            packet: packet(timeout_height),
+            next_sequence_recv: 1,
+        })
+        .run(&mut ctx, &mut router)
+        .unwrap_err();
+        assert!(err.contains("timeout not allowed"), "{err}");
+        assert_eq!(module.callbacks(), [Callback::TimeoutPacketValidate]);
+    }
 }
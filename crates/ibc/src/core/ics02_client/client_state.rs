@@ -13,6 +13,7 @@ use crate::core::ics23_commitment::commitment::{
 };
 use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics24_host::path::Path;
+use crate::core::timestamp::Timestamp;
 use crate::prelude::*;
 use crate::Height;
 
@@ -30,7 +31,7 @@ pub enum UpdateKind {
 }
 
 /// Represents the status of a client
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Status {
     /// The client is active and allowed to be used
     Active,
@@ -172,6 +173,17 @@ pub trait ClientStateValidation<ClientValidationContext> {
         ctx: &ClientValidationContext,
         client_id: &ClientId,
     ) -> Result<Status, ClientError>;
+
+    /// Returns the time at which the client expires if it is not updated, i.e.
+    /// the timestamp of its latest consensus state plus its trusting period.
+    /// Returns `None` if the client never expires, which is the default.
+    fn expiry_timestamp(
+        &self,
+        _ctx: &ClientValidationContext,
+        _client_id: &ClientId,
+    ) -> Result<Option<Timestamp>, ClientError> {
+        Ok(None)
+    }
 }
 
 /// `ClientState` methods which require access to the client's
@@ -10,6 +10,7 @@ use crate::core::ics24_host::identifier::{ClientId, IdentifierError};
 use crate::core::timestamp::Timestamp;
 use crate::core::ContextError;
 use crate::prelude::*;
+use crate::signer::Signer;
 use crate::Height;
 
 /// Encodes all the possible client errors
@@ -105,6 +106,12 @@ pub enum ClientError {
     ClientSpecific { description: String },
     /// client counter overflow error
     CounterOverflow,
+    /// client type `{client_type}` is not allowed by the client params
+    ClientTypeNotAllowed { client_type: ClientType },
+    /// invalid client params: `{reason}`
+    InvalidParams { reason: String },
+    /// signer `{signer}` is not the params authority
+    UnauthorizedParamsUpdate { signer: Signer },
     /// other error: `{description}`
     Other { description: String },
 }
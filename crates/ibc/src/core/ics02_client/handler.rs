@@ -2,4 +2,5 @@
 
 pub mod create_client;
 pub mod update_client;
+pub mod update_params;
 pub mod upgrade_client;
@@ -31,6 +31,10 @@ where
 
     let client_type = client_state.client_type();
 
+    if !ctx.client_params()?.is_allowed_client(&client_type) {
+        return Err(ClientError::ClientTypeNotAllowed { client_type }.into());
+    }
+
     let client_id = ClientId::new(client_type, id_counter).map_err(|e| {
         ClientError::ClientIdentifierConstructor {
             client_type: client_state.client_type(),
@@ -88,8 +92,8 @@ where
         client_type,
         latest_height,
     ));
-    ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
-    ctx.emit_ibc_event(event)?;
+    ctx.publish_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
+    ctx.publish_ibc_event(event)?;
 
     ctx.log_message(format!(
         "success: generated new client identifier: {client_id}"
@@ -79,8 +79,8 @@ where
             client_id,
             client_state.client_type(),
         ));
-        ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
-        ctx.emit_ibc_event(event)?;
+        ctx.publish_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
+        ctx.publish_ibc_event(event)?;
     } else {
         if !matches!(update_kind, UpdateKind::UpdateClient) {
             return Err(ClientError::MisbehaviourHandlingFailure {
@@ -122,8 +122,8 @@ where
                     header.encode_to_vec(),
                 ))
             };
-            ctx.emit_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
-            ctx.emit_ibc_event(event)?;
+            ctx.publish_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
+            ctx.publish_ibc_event(event)?;
         }
     }
 
@@ -137,6 +137,7 @@ mod tests {
 
     use ibc_proto::google::protobuf::Any;
     use ibc_proto::ibc::lightclients::tendermint::v1::{ClientState as RawTmClientState, Fraction};
+    use tendermint_testgen::Validator;
     use test_log::test;
 
     use super::*;
@@ -155,7 +156,7 @@ mod tests {
     use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
     use crate::mock::context::{AnyConsensusState, MockContext};
     use crate::mock::header::MockHeader;
-    use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::host::{HostBlock, HostType, SyntheticTmChain};
     use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
     use crate::test_utils::get_dummy_account_id;
     use crate::{downcast, Height};
@@ -697,4 +698,141 @@ mod tests {
         assert!(res.is_ok());
         ensure_misbehaviour(&ctx_a, &client_id, &tm_client_type());
     }
+
+    /// Returns a synthetic Tendermint chain whose blocks, signed by two
+    /// validators of equal power, are all in the past of the host chains.
+    fn synthetic_tm_chain() -> SyntheticTmChain {
+        SyntheticTmChain::new(
+            ChainId::new("mockgaiaB", 1).unwrap(),
+            &[
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(50),
+            ],
+            (Timestamp::now() - Duration::from_secs(100)).unwrap(),
+        )
+    }
+
+    fn update_client_msg(
+        client_id: &ClientId,
+        chain: &SyntheticTmChain,
+        height: u64,
+        trusted_height: u64,
+    ) -> MsgUpdateClient {
+        let header = chain
+            .header(
+                &Height::new(1, height).unwrap(),
+                &Height::new(1, trusted_height).unwrap(),
+            )
+            .unwrap();
+        MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message: header.into(),
+            signer: get_dummy_account_id(),
+        }
+    }
+
+    #[test]
+    fn test_update_synthetic_tendermint_client_validator_set_changes() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let mut chain_b = synthetic_tm_chain();
+        chain_b.set_voting_power(4, "1", 90);
+        chain_b.set_validators(
+            7,
+            &[
+                Validator::new("3").voting_power(50),
+                Validator::new("4").voting_power(50),
+            ],
+        );
+        chain_b.produce_blocks_until(8);
+
+        let mut ctx_a =
+            MockContext::default().with_tm_client(&client_id, &chain_b, Height::new(1, 2).unwrap());
+
+        // the voting powers changed, but the trusted validators still signed
+        let msg = update_client_msg(&client_id, &chain_b, 5, 2);
+        validate(&ctx_a, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone())).unwrap();
+        execute(&mut ctx_a, MsgUpdateOrMisbehaviour::UpdateClient(msg)).unwrap();
+
+        // none of the trusted validators signed after the rotation
+        let msg = update_client_msg(&client_id, &chain_b, 8, 5);
+        assert!(validate(&ctx_a, MsgUpdateOrMisbehaviour::UpdateClient(msg)).is_err());
+
+        // the rotation is followed block by block
+        for height in 6..=8 {
+            let msg = update_client_msg(&client_id, &chain_b, height, height - 1);
+            validate(&ctx_a, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone())).unwrap();
+            execute(&mut ctx_a, MsgUpdateOrMisbehaviour::UpdateClient(msg)).unwrap();
+        }
+        let client_state = ctx_a.client_state(&client_id).unwrap();
+        assert_eq!(client_state.latest_height(), Height::new(1, 8).unwrap());
+    }
+
+    /// Tests misbehaviour handling for the synthetic Tendermint client, with
+    /// the conflicting headers of a fork of its chain.
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_fork() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let trusted_height = Height::new(1, 2).unwrap();
+        let misbehaviour_height = Height::new(1, 5).unwrap();
+
+        let mut chain_b = synthetic_tm_chain();
+        chain_b.produce_blocks_until(5);
+        let mut fork_b = chain_b.fork(4);
+        fork_b.produce_blocks_until(5);
+        let ctx_a = MockContext::default().with_tm_client(&client_id, &chain_b, trusted_height);
+
+        // the validators signed both chains
+        let mut ctx = ctx_a.clone();
+        let misbehaviour = chain_b
+            .misbehaviour(
+                &fork_b,
+                client_id.clone(),
+                &misbehaviour_height,
+                &trusted_height,
+            )
+            .unwrap();
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour.into(),
+            signer: get_dummy_account_id(),
+        };
+        validate(&ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg.clone())).unwrap();
+        execute(&mut ctx, MsgUpdateOrMisbehaviour::Misbehaviour(msg)).unwrap();
+        ensure_misbehaviour(&ctx, &client_id, &tm_client_type());
+
+        // an update to a conflicting header freezes the client
+        let mut ctx = ctx_a.clone();
+        let msg = update_client_msg(&client_id, &chain_b, 5, 2);
+        execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg)).unwrap();
+        let msg = update_client_msg(&client_id, &fork_b, 5, 2);
+        validate(&ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg.clone())).unwrap();
+        execute(&mut ctx, MsgUpdateOrMisbehaviour::UpdateClient(msg)).unwrap();
+        let client_state = ctx.client_state(&client_id).unwrap();
+        assert!(client_state.status(&ctx, &client_id).unwrap().is_frozen());
+
+        // a fork signed by validators the client does not trust is no evidence
+        let mut fork_b = chain_b.fork(4);
+        fork_b.set_validators(
+            5,
+            &[
+                Validator::new("3").voting_power(50),
+                Validator::new("4").voting_power(50),
+            ],
+        );
+        fork_b.produce_blocks_until(5);
+        let misbehaviour = chain_b
+            .misbehaviour(
+                &fork_b,
+                client_id.clone(),
+                &misbehaviour_height,
+                &trusted_height,
+            )
+            .unwrap();
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour.into(),
+            signer: get_dummy_account_id(),
+        };
+        assert!(validate(&ctx_a, MsgUpdateOrMisbehaviour::Misbehaviour(msg)).is_err());
+    }
 }
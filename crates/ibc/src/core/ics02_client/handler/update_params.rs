@@ -0,0 +1,107 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgUpdateClientParams`.
+
+use crate::core::context::ContextError;
+use crate::core::events::{IbcEvent, MessageEvent};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::msgs::update_params::MsgUpdateClientParams;
+use crate::core::{ExecutionContext, ValidationContext};
+use crate::prelude::*;
+
+pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: &MsgUpdateClientParams) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    ctx.validate_message_signer(&msg.signer)?;
+
+    if !ctx.is_params_authority(&msg.signer) {
+        return Err(ClientError::UnauthorizedParamsUpdate {
+            signer: msg.signer.clone(),
+        }
+        .into());
+    }
+
+    msg.params.validate()?;
+
+    Ok(())
+}
+
+pub(crate) fn execute<Ctx>(ctx: &mut Ctx, msg: MsgUpdateClientParams) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    ctx.store_client_params(msg.params)?;
+
+    ctx.publish_ibc_event(IbcEvent::Message(MessageEvent::Client))?;
+    ctx.log_message("success: client params updated".to_string())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics02_client::params::ClientParams;
+    use crate::core::{dispatch, MsgEnvelope, RouterError};
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::consensus_state::MockConsensusState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::router::MockRouter;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    #[test]
+    fn update_client_params_restricts_client_creation() {
+        let authority: Signer = "authority".to_string().into();
+        let mut ctx = MockContext::default().with_params_authority(authority.clone());
+        let mut router = MockRouter::default();
+        let params = ClientParams::new(vec!["07-tendermint".to_string()]).unwrap();
+
+        let msg = MsgUpdateClientParams {
+            params: params.clone(),
+            signer: get_dummy_account_id(),
+        };
+        let res = validate(&ctx, &msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::ClientError(
+                ClientError::UnauthorizedParamsUpdate { .. }
+            ))
+        ));
+
+        let msg = MsgUpdateClientParams {
+            signer: authority,
+            ..msg
+        };
+        dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Client(ClientMsg::UpdateParams(msg)),
+        )
+        .unwrap();
+        assert_eq!(ctx.client_params().unwrap(), params);
+
+        let height = Height::new(0, 42).unwrap();
+        let create_client_msg = MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(height)).into(),
+            MockConsensusState::new(MockHeader::new(height)).into(),
+            get_dummy_account_id(),
+        );
+        let res = dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Client(ClientMsg::CreateClient(create_client_msg)),
+        );
+        assert!(matches!(
+            res,
+            Err(RouterError::ContextError(ContextError::ClientError(
+                ClientError::ClientTypeNotAllowed { .. }
+            )))
+        ));
+    }
+}
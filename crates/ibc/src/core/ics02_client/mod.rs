@@ -8,6 +8,7 @@ pub mod events;
 pub mod handler;
 pub mod height;
 pub mod msgs;
+pub mod params;
 
 mod context;
 pub use context::ClientExecutionContext;
@@ -4,6 +4,7 @@ use ibc_proto::google::protobuf::Any;
 use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
 use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
 use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::update_params::MsgUpdateClientParams;
 use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::prelude::*;
@@ -12,6 +13,7 @@ use crate::signer::Signer;
 pub mod create_client;
 pub mod misbehaviour;
 pub mod update_client;
+pub mod update_params;
 pub mod upgrade_client;
 
 /// Encodes all the different client messages
@@ -27,6 +29,7 @@ pub enum ClientMsg {
     UpdateClient(MsgUpdateClient),
     Misbehaviour(MsgSubmitMisbehaviour),
     UpgradeClient(MsgUpgradeClient),
+    UpdateParams(MsgUpdateClientParams),
 }
 
 pub(crate) enum MsgUpdateOrMisbehaviour {
@@ -0,0 +1,62 @@
+//! Definition of domain type message `MsgUpdateClientParams`.
+
+use ibc_proto::ibc::core::client::v1::MsgUpdateParams as RawMsgUpdateClientParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::params::ClientParams;
+use crate::core::Msg;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub(crate) const TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateParams";
+
+/// A type of message that replaces the parameters of the client submodule.
+/// It must be signed by the params authority.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgUpdateClientParams {
+    pub params: ClientParams,
+    pub signer: Signer,
+}
+
+impl Msg for MsgUpdateClientParams {
+    type Raw = RawMsgUpdateClientParams;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgUpdateClientParams> for MsgUpdateClientParams {}
+
+impl TryFrom<RawMsgUpdateClientParams> for MsgUpdateClientParams {
+    type Error = ClientError;
+
+    fn try_from(raw: RawMsgUpdateClientParams) -> Result<Self, Self::Error> {
+        let params = raw
+            .params
+            .ok_or(ClientError::InvalidParams {
+                reason: "missing params".to_string(),
+            })?
+            .try_into()?;
+
+        Ok(MsgUpdateClientParams {
+            params,
+            signer: raw.signer.into(),
+        })
+    }
+}
+
+impl From<MsgUpdateClientParams> for RawMsgUpdateClientParams {
+    fn from(ics_msg: MsgUpdateClientParams) -> Self {
+        RawMsgUpdateClientParams {
+            signer: ics_msg.signer.to_string(),
+            params: Some(ics_msg.params.into()),
+        }
+    }
+}
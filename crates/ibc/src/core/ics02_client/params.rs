@@ -0,0 +1,137 @@
+//! Defines the parameters of the client submodule.
+
+use ibc_proto::ibc::core::client::v1::Params as RawClientParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::ClientError;
+use crate::prelude::*;
+
+/// Wildcard which allows the creation of clients of any type.
+pub const ALLOW_ALL_CLIENTS: &str = "*";
+
+/// The parameters of the client submodule.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientParams {
+    /// The client types that can be created, or [`ALLOW_ALL_CLIENTS`] to
+    /// allow any client type.
+    allowed_clients: Vec<String>,
+}
+
+impl ClientParams {
+    pub fn new(allowed_clients: Vec<String>) -> Result<Self, ClientError> {
+        let params = Self { allowed_clients };
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn allowed_clients(&self) -> &[String] {
+        &self.allowed_clients
+    }
+
+    /// Returns `true` if clients of type `client_type` can be created.
+    pub fn is_allowed_client(&self, client_type: &ClientType) -> bool {
+        self.allowed_clients
+            .iter()
+            .any(|allowed| allowed == ALLOW_ALL_CLIENTS || allowed == client_type.as_str())
+    }
+
+    /// Checks that the allowed client types are neither blank nor duplicated,
+    /// and that the wildcard is not mixed with specific client types.
+    pub fn validate(&self) -> Result<(), ClientError> {
+        if self.allowed_clients.len() > 1
+            && self
+                .allowed_clients
+                .iter()
+                .any(|allowed| allowed == ALLOW_ALL_CLIENTS)
+        {
+            return Err(ClientError::InvalidParams {
+                reason: format!(
+                    "`{ALLOW_ALL_CLIENTS}` must be the only allowed client when present"
+                ),
+            });
+        }
+
+        for (i, allowed) in self.allowed_clients.iter().enumerate() {
+            if allowed.trim().is_empty() {
+                return Err(ClientError::InvalidParams {
+                    reason: format!("allowed client at position {i} is blank"),
+                });
+            }
+            if self.allowed_clients[..i].contains(allowed) {
+                return Err(ClientError::InvalidParams {
+                    reason: format!("allowed client `{allowed}` is duplicated"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ClientParams {
+    /// Allows the creation of clients of any type.
+    fn default() -> Self {
+        Self {
+            allowed_clients: vec![ALLOW_ALL_CLIENTS.to_string()],
+        }
+    }
+}
+
+impl Protobuf<RawClientParams> for ClientParams {}
+
+impl TryFrom<RawClientParams> for ClientParams {
+    type Error = ClientError;
+
+    fn try_from(raw: RawClientParams) -> Result<Self, Self::Error> {
+        Self::new(raw.allowed_clients)
+    }
+}
+
+impl From<ClientParams> for RawClientParams {
+    fn from(value: ClientParams) -> Self {
+        RawClientParams {
+            allowed_clients: value.allowed_clients,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::mock::client_state::client_type as mock_client_type;
+
+    #[test]
+    fn client_params_validation() {
+        let params = ClientParams::default();
+        assert!(params.is_allowed_client(&mock_client_type()));
+
+        let params = ClientParams::try_from(RawClientParams {
+            allowed_clients: vec!["07-tendermint".to_string()],
+        })
+        .unwrap();
+        assert!(!params.is_allowed_client(&mock_client_type()));
+        assert_eq!(
+            RawClientParams::from(params.clone()),
+            RawClientParams {
+                allowed_clients: vec!["07-tendermint".to_string()],
+            }
+        );
+
+        for allowed_clients in [
+            vec![" ".to_string()],
+            vec!["07-tendermint".to_string(), "07-tendermint".to_string()],
+            vec![ALLOW_ALL_CLIENTS.to_string(), "07-tendermint".to_string()],
+        ] {
+            assert!(ClientParams::new(allowed_clients).is_err());
+        }
+    }
+}
@@ -22,7 +22,7 @@ where
 
     // Fetch the connection delay time and height periods.
     let conn_delay_time_period = connection_end.delay_period();
-    let conn_delay_height_period = ctx.block_delay(&conn_delay_time_period);
+    let conn_delay_height_period = ctx.block_delay(&conn_delay_time_period)?;
 
     // Verify that the current host chain time is later than the last client update time
     let earliest_valid_time = (last_client_update_time + conn_delay_time_period)
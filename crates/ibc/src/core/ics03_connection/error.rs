@@ -9,6 +9,7 @@ use crate::core::ics02_client::error as client_error;
 use crate::core::ics03_connection::version::Version;
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId, IdentifierError};
 use crate::core::timestamp::{Timestamp, TimestampOverflowError};
+use crate::signer::Signer;
 use crate::Height;
 
 #[derive(Debug, Display)]
@@ -87,6 +88,10 @@ pub enum ConnectionError {
     TimestampOverflow(TimestampOverflowError),
     /// connection counter overflow error
     CounterOverflow,
+    /// invalid connection params: `{reason}`
+    InvalidParams { reason: String },
+    /// signer `{signer}` is not the params authority
+    UnauthorizedParamsUpdate { signer: Signer },
     /// other error: `{description}`
     Other { description: String },
 }
@@ -5,6 +5,7 @@ pub mod conn_open_ack;
 pub mod conn_open_confirm;
 pub mod conn_open_init;
 pub mod conn_open_try;
+pub mod update_params;
 
 #[cfg(test)]
 pub mod test_util {
@@ -117,8 +117,8 @@ where
         conn_id_on_a.clone(),
         client_id_on_a.clone(),
     ));
-    ctx_b.emit_ibc_event(IbcEvent::Message(MessageEvent::Connection))?;
-    ctx_b.emit_ibc_event(event)?;
+    ctx_b.publish_ibc_event(IbcEvent::Message(MessageEvent::Connection))?;
+    ctx_b.publish_ibc_event(event)?;
     ctx_b.log_message("success: conn_open_confirm verification passed".to_string())?;
 
     {
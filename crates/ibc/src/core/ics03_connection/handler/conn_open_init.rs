@@ -73,8 +73,8 @@ where
             msg.client_id_on_a.clone(),
             client_id_on_b,
         ));
-        ctx_a.emit_ibc_event(IbcEvent::Message(MessageEvent::Connection))?;
-        ctx_a.emit_ibc_event(event)?;
+        ctx_a.publish_ibc_event(IbcEvent::Message(MessageEvent::Connection))?;
+        ctx_a.publish_ibc_event(event)?;
     }
 
     ctx_a.increase_connection_counter()?;
@@ -0,0 +1,94 @@
+//! Protocol logic specific to processing ICS3 messages of type `MsgUpdateConnectionParams`.
+
+use crate::core::context::ContextError;
+use crate::core::events::{IbcEvent, MessageEvent};
+use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics03_connection::msgs::update_params::MsgUpdateConnectionParams;
+use crate::core::{ExecutionContext, ValidationContext};
+use crate::prelude::*;
+
+pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: &MsgUpdateConnectionParams) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    ctx.validate_message_signer(&msg.signer)?;
+
+    if !ctx.is_params_authority(&msg.signer) {
+        return Err(ConnectionError::UnauthorizedParamsUpdate {
+            signer: msg.signer.clone(),
+        }
+        .into());
+    }
+
+    msg.params.validate()?;
+
+    Ok(())
+}
+
+pub(crate) fn execute<Ctx>(
+    ctx: &mut Ctx,
+    msg: MsgUpdateConnectionParams,
+) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    ctx.store_connection_params(msg.params)?;
+
+    ctx.publish_ibc_event(IbcEvent::Message(MessageEvent::Connection))?;
+    ctx.log_message("success: connection params updated".to_string())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics03_connection::msgs::ConnectionMsg;
+    use crate::core::ics03_connection::params::ConnectionParams;
+    use crate::core::{dispatch, MsgEnvelope};
+    use crate::mock::context::MockContext;
+    use crate::mock::router::MockRouter;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_account_id;
+
+    #[test]
+    fn update_connection_params() {
+        let authority: Signer = "authority".to_string().into();
+        let mut ctx = MockContext::default().with_params_authority(authority.clone());
+        let mut router = MockRouter::default();
+        let params = ConnectionParams::new(Duration::from_secs(7)).unwrap();
+
+        let msg = MsgUpdateConnectionParams {
+            params: params.clone(),
+            signer: get_dummy_account_id(),
+        };
+        let res = validate(&ctx, &msg);
+        assert!(matches!(
+            res,
+            Err(ContextError::ConnectionError(
+                ConnectionError::UnauthorizedParamsUpdate { .. }
+            ))
+        ));
+
+        let msg = MsgUpdateConnectionParams {
+            signer: authority,
+            ..msg
+        };
+        dispatch(
+            &mut ctx,
+            &mut router,
+            MsgEnvelope::Connection(ConnectionMsg::UpdateParams(msg)),
+        )
+        .unwrap();
+
+        assert_eq!(ctx.connection_params().unwrap(), params);
+        assert_eq!(
+            ctx.max_expected_time_per_block().unwrap(),
+            Duration::from_secs(7)
+        );
+    }
+}
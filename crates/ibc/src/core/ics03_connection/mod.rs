@@ -8,4 +8,5 @@ pub mod events;
 /// Message processing logic (protocol) for ICS 03.
 pub mod handler;
 pub mod msgs;
+pub mod params;
 pub mod version;
@@ -16,12 +16,14 @@ use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
 use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
 use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
 use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics03_connection::msgs::update_params::MsgUpdateConnectionParams;
 use crate::prelude::*;
 
 pub mod conn_open_ack;
 pub mod conn_open_confirm;
 pub mod conn_open_init;
 pub mod conn_open_try;
+pub mod update_params;
 
 /// Enumeration of all possible messages that the ICS3 protocol processes.
 #[cfg_attr(
@@ -35,6 +37,7 @@ pub enum ConnectionMsg {
     OpenTry(MsgConnectionOpenTry),
     OpenAck(MsgConnectionOpenAck),
     OpenConfirm(MsgConnectionOpenConfirm),
+    UpdateParams(MsgUpdateConnectionParams),
 }
 
 #[cfg(test)]
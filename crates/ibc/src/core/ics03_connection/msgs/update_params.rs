@@ -0,0 +1,95 @@
+//! Definition of domain type message `MsgUpdateConnectionParams`.
+
+use ibc_proto::ibc::core::connection::v1::MsgUpdateParams as RawMsgUpdateConnectionParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics03_connection::params::ConnectionParams;
+use crate::core::Msg;
+use crate::prelude::*;
+use crate::signer::Signer;
+
+pub(crate) const TYPE_URL: &str = "/ibc.core.connection.v1.MsgUpdateParams";
+
+/// A type of message that replaces the parameters of the connection
+/// submodule. It must be signed by the params authority.
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgUpdateConnectionParams {
+    pub params: ConnectionParams,
+    pub signer: Signer,
+}
+
+impl Msg for MsgUpdateConnectionParams {
+    type Raw = RawMsgUpdateConnectionParams;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgUpdateConnectionParams> for MsgUpdateConnectionParams {}
+
+impl TryFrom<RawMsgUpdateConnectionParams> for MsgUpdateConnectionParams {
+    type Error = ConnectionError;
+
+    fn try_from(raw: RawMsgUpdateConnectionParams) -> Result<Self, Self::Error> {
+        let params = raw
+            .params
+            .ok_or(ConnectionError::InvalidParams {
+                reason: "missing params".to_string(),
+            })?
+            .try_into()?;
+
+        Ok(MsgUpdateConnectionParams {
+            params,
+            signer: raw.signer.into(),
+        })
+    }
+}
+
+impl From<MsgUpdateConnectionParams> for RawMsgUpdateConnectionParams {
+    fn from(ics_msg: MsgUpdateConnectionParams) -> Self {
+        RawMsgUpdateConnectionParams {
+            signer: ics_msg.signer.to_string(),
+            params: Some(ics_msg.params.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use ibc_proto::ibc::core::connection::v1::Params as RawConnectionParams;
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn msg_update_connection_params_roundtrip() {
+        let msg = MsgUpdateConnectionParams {
+            params: ConnectionParams::new(Duration::from_secs(5)).unwrap(),
+            signer: "authority".to_string().into(),
+        };
+
+        let raw = RawMsgUpdateConnectionParams::from(msg.clone());
+        assert_eq!(
+            raw.params.as_ref().unwrap().max_expected_time_per_block,
+            5_000_000_000
+        );
+        assert_eq!(MsgUpdateConnectionParams::try_from(raw).unwrap(), msg);
+
+        let raw = RawMsgUpdateConnectionParams {
+            signer: "authority".to_string(),
+            params: Some(RawConnectionParams {
+                max_expected_time_per_block: 0,
+            }),
+        };
+        assert!(MsgUpdateConnectionParams::try_from(raw).is_err());
+    }
+}
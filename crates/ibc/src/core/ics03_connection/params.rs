@@ -0,0 +1,189 @@
+//! Defines the parameters of the connection submodule.
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::connection::v1::Params as RawConnectionParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics03_connection::error::ConnectionError;
+use crate::prelude::*;
+
+/// The default maximum expected time per block, as in ibc-go.
+pub const DEFAULT_MAX_EXPECTED_TIME_PER_BLOCK: Duration = Duration::from_secs(30);
+
+#[cfg(feature = "borsh")]
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// The parameters of the connection submodule.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedConnectionParams"))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionParams {
+    /// The maximum expected time per block, used to enforce block delay
+    /// periods on connections.
+    max_expected_time_per_block: Duration,
+}
+
+impl ConnectionParams {
+    pub fn new(max_expected_time_per_block: Duration) -> Result<Self, ConnectionError> {
+        let params = Self {
+            max_expected_time_per_block,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    pub fn max_expected_time_per_block(&self) -> Duration {
+        self.max_expected_time_per_block
+    }
+
+    /// Checks that the maximum expected time per block is non-zero and fits
+    /// in the nanoseconds of the proto representation.
+    pub fn validate(&self) -> Result<(), ConnectionError> {
+        if self.max_expected_time_per_block.is_zero() {
+            return Err(ConnectionError::InvalidParams {
+                reason: "max expected time per block cannot be zero".to_string(),
+            });
+        }
+        if u64::try_from(self.max_expected_time_per_block.as_nanos()).is_err() {
+            return Err(ConnectionError::InvalidParams {
+                reason: "max expected time per block overflows u64 nanoseconds".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ConnectionParams {
+    fn default() -> Self {
+        Self {
+            max_expected_time_per_block: DEFAULT_MAX_EXPECTED_TIME_PER_BLOCK,
+        }
+    }
+}
+
+/// The connection params as deserialized by serde, before their validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedConnectionParams {
+    max_expected_time_per_block: Duration,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedConnectionParams> for ConnectionParams {
+    type Error = ConnectionError;
+
+    fn try_from(params: UncheckedConnectionParams) -> Result<Self, Self::Error> {
+        Self::new(params.max_expected_time_per_block)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for ConnectionParams {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        let value = (
+            self.max_expected_time_per_block.as_secs(),
+            self.max_expected_time_per_block.subsec_nanos(),
+        );
+        borsh::BorshSerialize::serialize(&value, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for ConnectionParams {
+    fn deserialize_reader<R: borsh::maybestd::io::Read>(
+        reader: &mut R,
+    ) -> borsh::maybestd::io::Result<Self> {
+        let (secs, nanos) = <(u64, u32)>::deserialize_reader(reader)?;
+        let invalid_data = |reason: String| {
+            borsh::maybestd::io::Error::new(borsh::maybestd::io::ErrorKind::InvalidData, reason)
+        };
+        if u64::from(nanos) >= NANOS_PER_SEC {
+            return Err(invalid_data(format!(
+                "subsecond nanoseconds out of range: {nanos}"
+            )));
+        }
+
+        let params = Self {
+            max_expected_time_per_block: Duration::new(secs, nanos),
+        };
+        params.validate().map_err(|e| invalid_data(e.to_string()))?;
+        Ok(params)
+    }
+}
+
+impl Protobuf<RawConnectionParams> for ConnectionParams {}
+
+impl TryFrom<RawConnectionParams> for ConnectionParams {
+    type Error = ConnectionError;
+
+    fn try_from(raw: RawConnectionParams) -> Result<Self, Self::Error> {
+        Self::new(Duration::from_nanos(raw.max_expected_time_per_block))
+    }
+}
+
+impl From<ConnectionParams> for RawConnectionParams {
+    fn from(value: ConnectionParams) -> Self {
+        RawConnectionParams {
+            max_expected_time_per_block: value.max_expected_time_per_block.as_nanos() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let params = ConnectionParams::new(Duration::new(12, 345)).unwrap();
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ConnectionParams>(&json).unwrap(),
+            params
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_invalid_durations() {
+        for json in [
+            r#"{"max_expected_time_per_block":{"secs":0,"nanos":0}}"#,
+            r#"{"max_expected_time_per_block":{"secs":18446744073709551615,"nanos":0}}"#,
+        ] {
+            assert!(serde_json::from_str::<ConnectionParams>(json).is_err());
+        }
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_roundtrip() {
+        use borsh::{BorshDeserialize, BorshSerialize};
+
+        let params = ConnectionParams::new(Duration::new(12, 345)).unwrap();
+        let bytes = params.try_to_vec().unwrap();
+        assert_eq!(ConnectionParams::try_from_slice(&bytes).unwrap(), params);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_rejects_invalid_durations() {
+        use borsh::{BorshDeserialize, BorshSerialize};
+
+        for (secs, nanos) in [
+            (u64::MAX, 1_000_000_000u32),
+            (1, u32::MAX),
+            (0, 0),
+            (u64::MAX, 0),
+        ] {
+            let bytes = (secs, nanos).try_to_vec().unwrap();
+            assert!(ConnectionParams::try_from_slice(&bytes).is_err());
+        }
+    }
+}
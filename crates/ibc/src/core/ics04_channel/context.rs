@@ -4,7 +4,7 @@ use core::time::Duration;
 
 use num_traits::float::FloatCore;
 
-use super::packet::Sequence;
+use super::packet::{Packet, Sequence};
 use crate::core::events::IbcEvent;
 use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::consensus_state::ConsensusState;
@@ -107,6 +107,11 @@ pub trait SendPacketExecutionContext: SendPacketValidationContext {
 
     /// Logging facility
     fn log_message(&mut self, message: String) -> Result<(), ContextError>;
+
+    /// Called with the full packet once it is sent. Does nothing by default.
+    fn record_sent_packet(&mut self, _packet: &Packet) -> Result<(), ContextError> {
+        Ok(())
+    }
 }
 
 impl<T> SendPacketExecutionContext for T
@@ -130,12 +135,16 @@ where
     }
 
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
-        self.emit_ibc_event(event)
+        self.publish_ibc_event(event)
     }
 
     fn log_message(&mut self, message: String) -> Result<(), ContextError> {
         self.log_message(message)
     }
+
+    fn record_sent_packet(&mut self, packet: &Packet) -> Result<(), ContextError> {
+        self.record_sent_packet(packet)
+    }
 }
 
 pub(crate) fn calculate_block_delay(
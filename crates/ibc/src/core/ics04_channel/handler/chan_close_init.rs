@@ -75,11 +75,11 @@ where
                 conn_id_on_a,
             ))
         };
-        ctx_a.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
-        ctx_a.emit_ibc_event(core_event)?;
+        ctx_a.publish_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
+        ctx_a.publish_ibc_event(core_event)?;
 
         for module_event in extras.events {
-            ctx_a.emit_ibc_event(IbcEvent::Module(module_event))?;
+            ctx_a.publish_ibc_event(IbcEvent::Module(module_event))?;
         }
 
         for log_message in extras.log {
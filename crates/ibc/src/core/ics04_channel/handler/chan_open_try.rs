@@ -102,11 +102,11 @@ where
             conn_id_on_b,
             version,
         ));
-        ctx_b.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
-        ctx_b.emit_ibc_event(core_event)?;
+        ctx_b.publish_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
+        ctx_b.publish_ibc_event(core_event)?;
 
         for module_event in extras.events {
-            ctx_b.emit_ibc_event(IbcEvent::Module(module_event))?;
+            ctx_b.publish_ibc_event(IbcEvent::Module(module_event))?;
         }
 
         for log_message in extras.log {
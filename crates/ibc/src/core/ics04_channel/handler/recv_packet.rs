@@ -106,6 +106,8 @@ where
             &ack_path_on_b,
             compute_ack_commitment(&acknowledgement),
         )?;
+
+        ctx_b.record_written_acknowledgement(&msg.packet, &acknowledgement)?;
     }
 
     // emit events and logs
@@ -119,18 +121,18 @@ where
             chan_end_on_b.ordering,
             conn_id_on_b.clone(),
         ));
-        ctx_b.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
-        ctx_b.emit_ibc_event(event)?;
+        ctx_b.publish_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
+        ctx_b.publish_ibc_event(event)?;
         let event = IbcEvent::WriteAcknowledgement(WriteAcknowledgement::new(
             msg.packet,
             acknowledgement,
             conn_id_on_b.clone(),
         ));
-        ctx_b.emit_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
-        ctx_b.emit_ibc_event(event)?;
+        ctx_b.publish_ibc_event(IbcEvent::Message(MessageEvent::Channel))?;
+        ctx_b.publish_ibc_event(event)?;
 
         for module_event in extras.events {
-            ctx_b.emit_ibc_event(IbcEvent::Module(module_event))?;
+            ctx_b.publish_ibc_event(IbcEvent::Module(module_event))?;
         }
 
         for log_message in extras.log {
@@ -287,6 +289,7 @@ mod tests {
 
     use super::*;
     use crate::applications::transfer::MODULE_ID_STR;
+    use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
     use crate::core::ics03_connection::connection::{
         ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
     };
@@ -296,10 +299,11 @@ mod tests {
     use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
     use crate::core::ics04_channel::packet::Packet;
     use crate::core::ics04_channel::Version;
-    use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
     use crate::core::router::{ModuleId, Router};
     use crate::core::timestamp::{Timestamp, ZERO_DURATION};
-    use crate::mock::context::MockContext;
+    use crate::mock::context::{MockClientRecord, MockContext};
+    use crate::mock::host::HostType;
     use crate::mock::ics18_relayer::context::RelayerContext;
     use crate::mock::router::MockRouter;
     use crate::test_utils::{get_dummy_account_id, DummyTransferModule};
@@ -440,6 +444,107 @@ mod tests {
         )
     }
 
+    #[rstest]
+    fn recv_packet_validate_commitment_proof(fixture: Fixture) {
+        let Fixture {
+            context,
+            msg,
+            chan_end_on_b,
+            ..
+        } = fixture;
+        let packet = msg.packet.clone();
+
+        // Chain A commits to the packet in a provable store.
+        let mut ctx_a = MockContext::new(
+            ChainId::new("mockgaiaA", 0).unwrap(),
+            HostType::SyntheticTendermint,
+            5,
+            Height::new(0, 5).unwrap(),
+        )
+        .with_provable_store()
+        .with_packet_commitment(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+            packet.seq_on_a,
+            compute_packet_commitment(
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            ),
+        );
+        ctx_a.advance_host_chain_height();
+        let proof_height = ctx_a.latest_height();
+
+        // Chain B tracks chain A at the proof height with a Tendermint client.
+        let block = ctx_a.host_block(&proof_height).unwrap().clone();
+        let tm_block = block.clone().try_into_tm_block().unwrap();
+        let client_record = MockClientRecord {
+            client_state: Some(
+                TmClientState::new_dummy_from_header(tm_block.header().clone()).into(),
+            ),
+            consensus_states: vec![(proof_height, block.into())].into_iter().collect(),
+        };
+        let conn_end_on_b = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                ctx_a.commitment_prefix(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        )
+        .unwrap();
+        let mut ctx_b = context
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_id_on_b.clone(),
+                packet.chan_id_on_b.clone(),
+                chan_end_on_b,
+            );
+        ctx_b
+            .ibc_store
+            .lock()
+            .clients
+            .insert(ClientId::default(), client_record);
+        ctx_b
+            .store_update_time(ClientId::default(), proof_height, Timestamp::now())
+            .unwrap();
+        ctx_b
+            .store_update_height(ClientId::default(), proof_height, ctx_b.latest_height())
+            .unwrap();
+
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        let proof = ctx_a
+            .commitment_proof(&proof_height, &Path::Commitment(commitment_path))
+            .unwrap();
+        let msg = MsgRecvPacket::new(
+            packet.clone(),
+            proof.try_into().unwrap(),
+            proof_height,
+            get_dummy_account_id(),
+        );
+
+        let res = validate(&ctx_b, &msg);
+        assert!(res.is_ok(), "the commitment proof should verify: {res:?}");
+
+        // The proof does not hold for other packet data.
+        let tampered_msg = MsgRecvPacket {
+            packet: Packet {
+                data: vec![1, 2, 3],
+                ..packet
+            },
+            ..msg
+        };
+        let res = validate(&ctx_b, &tampered_msg);
+        assert!(
+            res.is_err(),
+            "the commitment proof should not verify for other packet data"
+        );
+    }
+
     #[rstest]
     fn recv_packet_timeout_expired(fixture: Fixture) {
         let Fixture {
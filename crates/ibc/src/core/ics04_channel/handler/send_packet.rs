@@ -119,6 +119,8 @@ pub fn send_packet_execute(
         ),
     )?;
 
+    ctx_a.record_sent_packet(&packet)?;
+
     // emit events and logs
     {
         let chan_end_path_on_a = ChannelEndPath::new(&packet.port_id_on_a, &packet.chan_id_on_a);
@@ -313,6 +315,15 @@ mod tests {
                     ));
                     // TODO: The object in the output is a PacketResult what can we check on it?
                     assert!(matches!(&test.ctx.events[1], &IbcEvent::SendPacket(_)));
+
+                    assert!(test
+                        .ctx
+                        .ibc_store
+                        .lock()
+                        .sent_packets
+                        .get(&test.packet.port_id_on_a)
+                        .and_then(|channels| channels.get(&test.packet.chan_id_on_a))
+                        .map_or(false, |packets| packets.contains_key(&test.packet.seq_on_a)));
                 }
                 Err(e) => {
                     assert!(
@@ -138,7 +138,10 @@ pub struct Packet {
     pub chan_id_on_b: ChannelId,
     #[cfg_attr(
         feature = "serde",
-        serde(serialize_with = "crate::serializers::ser_hex_upper")
+        serde(
+            serialize_with = "crate::serializers::ser_hex_upper",
+            deserialize_with = "crate::serializers::de_hex_upper"
+        )
     )]
     pub data: Vec<u8>,
     pub timeout_height_on_b: TimeoutHeight,
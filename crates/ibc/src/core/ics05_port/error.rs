@@ -0,0 +1,26 @@
+//! Defines the port error type
+
+use displaydoc::Display;
+
+use crate::core::ics24_host::identifier::PortId;
+use crate::core::router::ModuleId;
+use crate::prelude::*;
+
+#[derive(Debug, Display)]
+pub enum PortError {
+    /// port `{port_id}` is not bound to any module
+    UnboundPort { port_id: PortId },
+    /// port `{port_id}` is already bound to module `{owner}`
+    PortAlreadyBound { port_id: PortId, owner: ModuleId },
+    /// module `{module_id}` does not own port `{port_id}`, which is bound to module `{owner}`
+    NotPortOwner {
+        port_id: PortId,
+        module_id: ModuleId,
+        owner: ModuleId,
+    },
+    /// other error: `{description}`
+    Other { description: String },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PortError {}
@@ -0,0 +1,147 @@
+//! Protocol logic for binding and releasing ports.
+
+use crate::core::ics05_port::error::PortError;
+use crate::core::ics24_host::identifier::PortId;
+use crate::core::ics24_host::path::PortPath;
+use crate::core::router::ModuleId;
+use crate::core::{ContextError, ExecutionContext, ValidationContext};
+use crate::prelude::*;
+
+/// Binds the given port to `module_id`, including all necessary validation.
+///
+/// Equivalent to calling [`bind_port_validate`], followed by [`bind_port_execute`]
+pub fn bind_port(
+    ctx: &mut impl ExecutionContext,
+    port_id: &PortId,
+    module_id: ModuleId,
+) -> Result<(), ContextError> {
+    bind_port_validate(ctx, port_id)?;
+    bind_port_execute(ctx, port_id, module_id)
+}
+
+/// Validate that binding the given port would succeed, i.e. that the port is
+/// not already bound to a module.
+pub fn bind_port_validate(
+    ctx: &impl ValidationContext,
+    port_id: &PortId,
+) -> Result<(), ContextError> {
+    match ctx.port_owner(&PortPath::new(port_id))? {
+        Some(owner) => Err(PortError::PortAlreadyBound {
+            port_id: port_id.clone(),
+            owner,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Binds the given port to `module_id`. Assumes that [`bind_port_validate`]
+/// was called first.
+pub fn bind_port_execute(
+    ctx: &mut impl ExecutionContext,
+    port_id: &PortId,
+    module_id: ModuleId,
+) -> Result<(), ContextError> {
+    ctx.store_port_owner(&PortPath::new(port_id), module_id.clone())?;
+    ctx.log_message(format!(
+        "success: port {port_id} bound to module {module_id}"
+    ))?;
+
+    Ok(())
+}
+
+/// Releases the given port held by `module_id`, including all necessary validation.
+///
+/// Equivalent to calling [`release_port_validate`], followed by [`release_port_execute`]
+pub fn release_port(
+    ctx: &mut impl ExecutionContext,
+    port_id: &PortId,
+    module_id: &ModuleId,
+) -> Result<(), ContextError> {
+    release_port_validate(ctx, port_id, module_id)?;
+    release_port_execute(ctx, port_id)
+}
+
+/// Validate that releasing the given port would succeed, i.e. that the port
+/// is bound to `module_id`.
+pub fn release_port_validate(
+    ctx: &impl ValidationContext,
+    port_id: &PortId,
+    module_id: &ModuleId,
+) -> Result<(), ContextError> {
+    verify_port_owner(ctx, port_id, module_id)
+}
+
+/// Releases the given port. Assumes that [`release_port_validate`] was called
+/// first.
+pub fn release_port_execute(
+    ctx: &mut impl ExecutionContext,
+    port_id: &PortId,
+) -> Result<(), ContextError> {
+    ctx.delete_port_owner(&PortPath::new(port_id))?;
+    ctx.log_message(format!("success: port {port_id} released"))?;
+
+    Ok(())
+}
+
+/// Verifies that the given port is bound to `module_id`.
+pub(crate) fn verify_port_owner(
+    ctx: &impl ValidationContext,
+    port_id: &PortId,
+    module_id: &ModuleId,
+) -> Result<(), ContextError> {
+    match ctx.port_owner(&PortPath::new(port_id))? {
+        Some(owner) if &owner == module_id => Ok(()),
+        Some(owner) => Err(PortError::NotPortOwner {
+            port_id: port_id.clone(),
+            module_id: module_id.clone(),
+            owner,
+        }
+        .into()),
+        None => Err(PortError::UnboundPort {
+            port_id: port_id.clone(),
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn bind_and_release_port() {
+        let mut ctx = MockContext::default();
+        let port_id = PortId::new("icacontroller-owner".to_string()).unwrap();
+        let controller = ModuleId::new("icacontroller".to_string());
+        let other = ModuleId::new("other".to_string());
+
+        let res = release_port(&mut ctx, &port_id, &controller);
+        assert!(matches!(
+            res,
+            Err(ContextError::PortError(PortError::UnboundPort { .. }))
+        ));
+
+        bind_port(&mut ctx, &port_id, controller.clone()).unwrap();
+        verify_port_owner(&ctx, &port_id, &controller).unwrap();
+
+        let res = bind_port(&mut ctx, &port_id, other.clone());
+        assert!(matches!(
+            res,
+            Err(ContextError::PortError(PortError::PortAlreadyBound { .. }))
+        ));
+
+        let res = release_port(&mut ctx, &port_id, &other);
+        assert!(matches!(
+            res,
+            Err(ContextError::PortError(PortError::NotPortOwner { .. }))
+        ));
+
+        release_port(&mut ctx, &port_id, &controller).unwrap();
+        bind_port(&mut ctx, &port_id, other.clone()).unwrap();
+        verify_port_owner(&ctx, &port_id, &other).unwrap();
+    }
+}
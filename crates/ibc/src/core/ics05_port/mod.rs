@@ -0,0 +1,11 @@
+//! ICS 05: Port allocation, which lets modules bind to ports at runtime and
+//! prevents other modules from opening or closing channels on them.
+
+pub mod error;
+mod handler;
+
+pub(crate) use handler::verify_port_owner;
+pub use handler::{
+    bind_port, bind_port_execute, bind_port_validate, release_port, release_port_execute,
+    release_port_validate,
+};
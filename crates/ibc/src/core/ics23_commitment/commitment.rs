@@ -17,7 +17,10 @@ use crate::prelude::*;
 pub struct CommitmentRoot {
     #[cfg_attr(
         feature = "serde",
-        serde(serialize_with = "crate::serializers::ser_hex_upper")
+        serde(
+            serialize_with = "crate::serializers::ser_hex_upper",
+            deserialize_with = "crate::serializers::de_hex_upper"
+        )
     )]
     bytes: Vec<u8>,
 }
@@ -73,7 +76,10 @@ impl From<Vec<u8>> for CommitmentRoot {
 pub struct CommitmentProofBytes {
     #[cfg_attr(
         feature = "serde",
-        serde(serialize_with = "crate::serializers::ser_hex_upper")
+        serde(
+            serialize_with = "crate::serializers::ser_hex_upper",
+            deserialize_with = "crate::serializers::de_hex_upper"
+        )
     )]
     bytes: Vec<u8>,
 }
@@ -150,7 +156,6 @@ impl TryFrom<CommitmentProofBytes> for RawMerkleProof {
     feature = "borsh",
     derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Clone, PartialEq, Eq, Hash, Default)]
 pub struct CommitmentPrefix {
@@ -189,13 +194,27 @@ impl fmt::Debug for CommitmentPrefix {
     }
 }
 
+/// Serializes the prefix as upper case hex, like the other commitment types,
+/// since it is not necessarily valid UTF-8.
 #[cfg(feature = "serde")]
 impl serde::Serialize for CommitmentPrefix {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        format!("{self:?}").serialize(serializer)
+        crate::serializers::ser_hex_upper(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CommitmentPrefix {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::serializers::de_hex_upper(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
     }
 }
 
@@ -215,3 +234,26 @@ pub mod test_util {
         raw_mp.try_into().unwrap()
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_prefix_serde_roundtrip() {
+        for bytes in [b"ibc".to_vec(), vec![0xff, 0x00, 0xfe]] {
+            let prefix = CommitmentPrefix::try_from(bytes).unwrap();
+            let json = serde_json::to_string(&prefix).unwrap();
+            assert_eq!(
+                serde_json::from_str::<CommitmentPrefix>(&json).unwrap(),
+                prefix
+            );
+        }
+
+        assert_eq!(
+            serde_json::to_string(&CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap()).unwrap(),
+            r#""696263""#
+        );
+        assert!(serde_json::from_str::<CommitmentPrefix>(r#""""#).is_err());
+    }
+}
@@ -27,6 +27,12 @@ pub enum CommitmentError {
     InvalidMerkleProof,
     /// proof verification failed
     VerificationFailure,
+    /// empty batch of merkle proofs
+    EmptyBatch,
+    /// mismatch between the upper layers of the proofs in a batch
+    BatchLayersMismatch,
+    /// mismatch between the upper keys of the paths in a batch
+    BatchKeysMismatch,
     /// encoded commitment prefix is not a valid hex string: `{0}`
     EncodingFailure(String),
 }
@@ -3,8 +3,8 @@
 use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof, MerkleRoot};
 use ibc_proto::ics23::commitment_proof::Proof;
 use ibc_proto::ics23::{
-    calculate_existence_root, verify_membership, verify_non_membership, CommitmentProof,
-    NonExistenceProof,
+    batch_entry, calculate_existence_root, verify_membership, verify_non_membership, BatchEntry,
+    BatchProof, CommitmentProof, NonExistenceProof,
 };
 
 use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
@@ -172,6 +172,170 @@ impl MerkleProof {
     }
 }
 
+impl MerkleProof {
+    /// Combines the proofs of several keys at the same height into a single
+    /// proof.
+    ///
+    /// The lowest layers of the proofs are merged into one ICS-23
+    /// `CompressedBatchProof`, while the upper layers, which prove the root of
+    /// the lowest subtree, must be identical for all the proofs and are kept
+    /// once.
+    pub fn batch(proofs: impl IntoIterator<Item = MerkleProof>) -> Result<Self, CommitmentError> {
+        let mut entries = Vec::new();
+        let mut upper_layers: Option<Vec<CommitmentProof>> = None;
+
+        for proof in proofs {
+            let mut layers = proof.proofs.into_iter();
+            let lowest = layers.next().ok_or(CommitmentError::EmptyMerkleProof)?;
+            let layers: Vec<_> = layers.collect();
+
+            match &upper_layers {
+                Some(upper_layers) if upper_layers != &layers => {
+                    return Err(CommitmentError::BatchLayersMismatch);
+                }
+                Some(_) => {}
+                None => upper_layers = Some(layers),
+            }
+
+            let lowest =
+                ics23::decompress(&lowest).map_err(|_| CommitmentError::InvalidMerkleProof)?;
+            match lowest.proof {
+                Some(Proof::Exist(existence_proof)) => entries.push(BatchEntry {
+                    proof: Some(batch_entry::Proof::Exist(existence_proof)),
+                }),
+                Some(Proof::Nonexist(non_existence_proof)) => entries.push(BatchEntry {
+                    proof: Some(batch_entry::Proof::Nonexist(non_existence_proof)),
+                }),
+                Some(Proof::Batch(batch_proof)) => entries.extend(batch_proof.entries),
+                _ => return Err(CommitmentError::InvalidMerkleProof),
+            }
+        }
+
+        let upper_layers = upper_layers.ok_or(CommitmentError::EmptyBatch)?;
+
+        let batch_proof = ics23::compress(&CommitmentProof {
+            proof: Some(Proof::Batch(BatchProof { entries })),
+        })
+        .map_err(|_| CommitmentError::InvalidMerkleProof)?;
+
+        let mut proofs = vec![batch_proof];
+        proofs.extend(upper_layers);
+
+        Ok(Self { proofs })
+    }
+
+    /// Verifies a batch proof, as built by [`MerkleProof::batch`], of the
+    /// membership of every `(key, value)` pair in `items`.
+    pub fn verify_batch_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: MerkleRoot,
+        items: Vec<(MerklePath, Vec<u8>)>,
+    ) -> Result<(), CommitmentError> {
+        self.verify_batch(
+            specs,
+            root,
+            items
+                .into_iter()
+                .map(|(keys, value)| (keys, Some(value)))
+                .collect(),
+        )
+    }
+
+    /// Verifies a batch proof, as built by [`MerkleProof::batch`], of the
+    /// non-membership of every key in `keys`.
+    pub fn verify_batch_non_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: MerkleRoot,
+        keys: Vec<MerklePath>,
+    ) -> Result<(), CommitmentError> {
+        self.verify_batch(
+            specs,
+            root,
+            keys.into_iter().map(|keys| (keys, None)).collect(),
+        )
+    }
+
+    /// Verifies the membership of the keys paired with a value, and the
+    /// non-membership of the others.
+    pub fn verify_batch(
+        &self,
+        specs: &ProofSpecs,
+        root: MerkleRoot,
+        items: Vec<(MerklePath, Option<Vec<u8>>)>,
+    ) -> Result<(), CommitmentError> {
+        // validate arguments
+        if self.proofs.is_empty() {
+            return Err(CommitmentError::EmptyMerkleProof);
+        }
+        if root.hash.is_empty() {
+            return Err(CommitmentError::EmptyMerkleRoot);
+        }
+        let num = self.proofs.len();
+        let ics23_specs = Vec::<ics23::ProofSpec>::from(specs.clone());
+        if ics23_specs.len() != num {
+            return Err(CommitmentError::NumberOfSpecsMismatch);
+        }
+        let upper_keys = match items.first() {
+            Some((keys, _)) => keys,
+            None => return Err(CommitmentError::EmptyBatch),
+        };
+        for (keys, value) in &items {
+            if keys.key_path.len() != num {
+                return Err(CommitmentError::NumberOfKeysMismatch);
+            }
+            // keys are represented from root-to-leaf
+            if keys.key_path[..num - 1] != upper_keys.key_path[..num - 1] {
+                return Err(CommitmentError::BatchKeysMismatch);
+            }
+            if value.as_ref().map_or(false, Vec::is_empty) {
+                return Err(CommitmentError::EmptyVerifiedValue);
+            }
+        }
+
+        // verify the keys against the root of the lowest subtree
+        let proof =
+            ics23::decompress(&self.proofs[0]).map_err(|_| CommitmentError::InvalidMerkleProof)?;
+        let spec = &ics23_specs[0];
+        let subroot = match &proof.proof {
+            Some(Proof::Batch(batch_proof)) => match batch_proof
+                .entries
+                .first()
+                .and_then(|entry| entry.proof.as_ref())
+            {
+                Some(batch_entry::Proof::Exist(existence_proof)) => {
+                    calculate_existence_root::<ics23::HostFunctionsManager>(existence_proof)
+                        .map_err(|_| CommitmentError::InvalidMerkleProof)?
+                }
+                Some(batch_entry::Proof::Nonexist(non_existence_proof)) => {
+                    calculate_non_existence_root(non_existence_proof)?
+                }
+                None => return Err(CommitmentError::InvalidMerkleProof),
+            },
+            _ => return Err(CommitmentError::InvalidMerkleProof),
+        };
+
+        for (keys, value) in &items {
+            let key = keys.key_path[num - 1].as_bytes();
+            let verified = match value {
+                Some(value) => verify_membership::<ics23::HostFunctionsManager>(
+                    &proof, spec, &subroot, key, value,
+                ),
+                None => verify_non_membership::<ics23::HostFunctionsManager>(
+                    &proof, spec, &subroot, key,
+                ),
+            };
+            if !verified {
+                return Err(CommitmentError::VerificationFailure);
+            }
+        }
+
+        // verify membership proofs starting from index 1 with value = subroot
+        self.verify_membership(specs, root, upper_keys.clone(), subroot, 1)
+    }
+}
+
 // TODO move to ics23
 fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, CommitmentError> {
     if let Some(left) = &proof.left {
@@ -184,3 +348,227 @@ fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, Co
         Err(CommitmentError::InvalidMerkleProof)
     }
 }
+
+#[cfg(test)]
+pub mod test_utils {
+    use ibc_proto::ics23::{ExistenceProof, HashOp, InnerOp, LeafOp, LengthOp};
+    use sha2::{Digest, Sha256};
+
+    use crate::prelude::*;
+
+    /// Returns the leaf operation of the tendermint proof spec.
+    pub fn leaf_op() -> LeafOp {
+        LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::NoHash.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::VarProto.into(),
+            prefix: vec![0],
+        }
+    }
+
+    /// Returns the hash of a leaf under the tendermint proof spec, for keys
+    /// shorter than 128 bytes.
+    pub fn leaf_hash(key: &[u8], value: &[u8]) -> Vec<u8> {
+        let hashed_value = Sha256::digest(value);
+        let mut preimage = vec![0, key.len() as u8];
+        preimage.extend(key);
+        preimage.push(hashed_value.len() as u8);
+        preimage.extend(hashed_value);
+        Sha256::digest(preimage).to_vec()
+    }
+
+    pub fn existence_proof(key: &str, value: &[u8], path: Vec<InnerOp>) -> ExistenceProof {
+        ExistenceProof {
+            key: key.as_bytes().to_vec(),
+            value: value.to_vec(),
+            leaf: Some(leaf_op()),
+            path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ics23::{ExistenceProof, HashOp, InnerOp};
+    use sha2::{Digest, Sha256};
+    use test_log::test;
+
+    use super::test_utils::{existence_proof, leaf_hash};
+    use super::*;
+
+    fn commitment_proof(proof: Proof) -> CommitmentProof {
+        CommitmentProof { proof: Some(proof) }
+    }
+
+    /// A two-layer store, following the tendermint proof spec, with keys `a`
+    /// and `b` in the `ibc` store.
+    struct Store {
+        root: Vec<u8>,
+        a: ExistenceProof,
+        b: ExistenceProof,
+        ibc: ExistenceProof,
+    }
+
+    impl Store {
+        fn new() -> Self {
+            let hash_a = leaf_hash(b"a", b"va");
+            let hash_b = leaf_hash(b"b", b"vb");
+
+            let inner_op = |prefix: Vec<u8>, suffix: Vec<u8>| InnerOp {
+                hash: HashOp::Sha256.into(),
+                prefix,
+                suffix,
+            };
+            let a = existence_proof("a", b"va", vec![inner_op(vec![1], hash_b.clone())]);
+            let b = existence_proof(
+                "b",
+                b"vb",
+                vec![inner_op([&[1], &hash_a[..]].concat(), vec![])],
+            );
+
+            let store_root = Sha256::digest([&[1], &hash_a[..], &hash_b[..]].concat()).to_vec();
+            let ibc = existence_proof("ibc", &store_root, vec![]);
+            let root = leaf_hash(b"ibc", &store_root);
+
+            Self { root, a, b, ibc }
+        }
+
+        fn proof(&self, lowest: Proof) -> MerkleProof {
+            MerkleProof {
+                proofs: vec![
+                    commitment_proof(lowest),
+                    commitment_proof(Proof::Exist(self.ibc.clone())),
+                ],
+            }
+        }
+
+        fn non_existence_proof(&self, key: &str) -> Proof {
+            Proof::Nonexist(NonExistenceProof {
+                key: key.as_bytes().to_vec(),
+                left: Some(self.a.clone()),
+                right: Some(self.b.clone()),
+            })
+        }
+    }
+
+    fn specs() -> ProofSpecs {
+        vec![ics23::tendermint_spec(), ics23::tendermint_spec()].into()
+    }
+
+    fn keys(key: &str) -> MerklePath {
+        MerklePath {
+            key_path: vec!["ibc".to_string(), key.to_string()],
+        }
+    }
+
+    #[test]
+    fn verify_batch_proof() {
+        let store = Store::new();
+        let root = MerkleRoot {
+            hash: store.root.clone(),
+        };
+
+        let proof_a = store.proof(Proof::Exist(store.a.clone()));
+        proof_a
+            .verify_membership(&specs(), root.clone(), keys("a"), b"va".to_vec(), 0)
+            .unwrap();
+
+        let batch_proof = MerkleProof::batch(vec![
+            proof_a,
+            store.proof(Proof::Exist(store.b.clone())),
+            store.proof(store.non_existence_proof("aa")),
+        ])
+        .unwrap();
+        assert_eq!(batch_proof.proofs.len(), 2);
+        assert!(ics23::is_compressed(&batch_proof.proofs[0]));
+
+        batch_proof
+            .verify_batch(
+                &specs(),
+                root.clone(),
+                vec![
+                    (keys("a"), Some(b"va".to_vec())),
+                    (keys("b"), Some(b"vb".to_vec())),
+                    (keys("aa"), None),
+                ],
+            )
+            .unwrap();
+        batch_proof
+            .verify_batch_non_membership(&specs(), root.clone(), vec![keys("aa")])
+            .unwrap();
+
+        assert!(matches!(
+            batch_proof.verify_batch_membership(
+                &specs(),
+                root.clone(),
+                vec![(keys("a"), b"vb".to_vec())]
+            ),
+            Err(CommitmentError::VerificationFailure)
+        ));
+        assert!(matches!(
+            batch_proof.verify_batch_membership(
+                &specs(),
+                root.clone(),
+                vec![(keys("c"), b"vc".to_vec())]
+            ),
+            Err(CommitmentError::VerificationFailure)
+        ));
+        assert!(matches!(
+            batch_proof.verify_batch_membership(
+                &specs(),
+                root,
+                vec![(
+                    MerklePath {
+                        key_path: vec!["bank".to_string(), "a".to_string()]
+                    },
+                    b"va".to_vec()
+                )]
+            ),
+            Err(CommitmentError::VerificationFailure)
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_batch() {
+        let store = Store::new();
+
+        assert!(matches!(
+            MerkleProof::batch(vec![]),
+            Err(CommitmentError::EmptyBatch)
+        ));
+
+        let other_store_proof = MerkleProof {
+            proofs: vec![
+                commitment_proof(Proof::Exist(store.b.clone())),
+                commitment_proof(Proof::Exist(store.a.clone())),
+            ],
+        };
+        assert!(matches!(
+            MerkleProof::batch(vec![
+                store.proof(Proof::Exist(store.a.clone())),
+                other_store_proof
+            ]),
+            Err(CommitmentError::BatchLayersMismatch)
+        ));
+
+        let batch_proof =
+            MerkleProof::batch(vec![store.proof(Proof::Exist(store.a.clone()))]).unwrap();
+        assert!(matches!(
+            batch_proof.verify_batch(
+                &specs(),
+                MerkleRoot { hash: store.root },
+                vec![
+                    (keys("a"), Some(b"va".to_vec())),
+                    (
+                        MerklePath {
+                            key_path: vec!["bank".to_string(), "b".to_string()]
+                        },
+                        None
+                    ),
+                ]
+            ),
+            Err(CommitmentError::BatchKeysMismatch)
+        ));
+    }
+}
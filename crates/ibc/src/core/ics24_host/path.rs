@@ -156,6 +156,12 @@ impl ConnectionPath {
 #[display(fmt = "ports/{_0}")]
 pub struct PortPath(pub PortId);
 
+impl PortPath {
+    pub fn new(port_id: &PortId) -> PortPath {
+        PortPath(port_id.clone())
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
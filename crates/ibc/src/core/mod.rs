@@ -22,9 +22,12 @@
 // `client_state_of_a_on_b` means "the client state of light client for chain A
 // stored on chain B"
 
+pub mod authorization;
+pub mod circuit_breaker;
 pub mod ics02_client;
 pub mod ics03_connection;
 pub mod ics04_channel;
+pub mod ics05_port;
 pub mod ics23_commitment;
 pub mod ics24_host;
 pub mod router;
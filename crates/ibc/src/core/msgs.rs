@@ -3,16 +3,19 @@ use ibc_proto::protobuf::Protobuf;
 
 use crate::core::context::RouterError;
 use crate::core::ics02_client::msgs::{
-    create_client, misbehaviour, update_client, upgrade_client, ClientMsg,
+    create_client, misbehaviour, update_client, update_params as client_update_params,
+    upgrade_client, ClientMsg,
 };
 use crate::core::ics03_connection::msgs::{
-    conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
+    conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try,
+    update_params as connection_update_params, ConnectionMsg,
 };
 use crate::core::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
     chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
 };
 use crate::prelude::*;
+use crate::signer::Signer;
 
 /// Trait to be implemented by all IBC messages
 pub trait Msg: Clone {
@@ -48,6 +51,76 @@ pub enum MsgEnvelope {
     Packet(PacketMsg),
 }
 
+impl MsgEnvelope {
+    /// Returns the signer of the enclosed message.
+    pub fn signer(&self) -> &Signer {
+        match self {
+            MsgEnvelope::Client(msg) => match msg {
+                ClientMsg::CreateClient(msg) => &msg.signer,
+                ClientMsg::UpdateClient(msg) => &msg.signer,
+                ClientMsg::Misbehaviour(msg) => &msg.signer,
+                ClientMsg::UpgradeClient(msg) => &msg.signer,
+                ClientMsg::UpdateParams(msg) => &msg.signer,
+            },
+            MsgEnvelope::Connection(msg) => match msg {
+                ConnectionMsg::OpenInit(msg) => &msg.signer,
+                ConnectionMsg::OpenTry(msg) => &msg.signer,
+                ConnectionMsg::OpenAck(msg) => &msg.signer,
+                ConnectionMsg::OpenConfirm(msg) => &msg.signer,
+                ConnectionMsg::UpdateParams(msg) => &msg.signer,
+            },
+            MsgEnvelope::Channel(msg) => match msg {
+                ChannelMsg::OpenInit(msg) => &msg.signer,
+                ChannelMsg::OpenTry(msg) => &msg.signer,
+                ChannelMsg::OpenAck(msg) => &msg.signer,
+                ChannelMsg::OpenConfirm(msg) => &msg.signer,
+                ChannelMsg::CloseInit(msg) => &msg.signer,
+                ChannelMsg::CloseConfirm(msg) => &msg.signer,
+            },
+            MsgEnvelope::Packet(msg) => match msg {
+                PacketMsg::Recv(msg) => &msg.signer,
+                PacketMsg::Ack(msg) => &msg.signer,
+                PacketMsg::Timeout(msg) => &msg.signer,
+                PacketMsg::TimeoutOnClose(msg) => &msg.signer,
+            },
+        }
+    }
+
+    /// Returns the type URL of the enclosed message.
+    pub fn type_url(&self) -> String {
+        match self {
+            MsgEnvelope::Client(msg) => match msg {
+                ClientMsg::CreateClient(msg) => msg.type_url(),
+                ClientMsg::UpdateClient(msg) => msg.type_url(),
+                ClientMsg::Misbehaviour(msg) => msg.type_url(),
+                ClientMsg::UpgradeClient(msg) => msg.type_url(),
+                ClientMsg::UpdateParams(msg) => msg.type_url(),
+            },
+            MsgEnvelope::Connection(msg) => match msg {
+                ConnectionMsg::OpenInit(msg) => msg.type_url(),
+                ConnectionMsg::OpenTry(msg) => msg.type_url(),
+                ConnectionMsg::OpenAck(msg) => msg.type_url(),
+                ConnectionMsg::OpenConfirm(msg) => msg.type_url(),
+                ConnectionMsg::UpdateParams(msg) => msg.type_url(),
+            },
+            MsgEnvelope::Channel(msg) => match msg {
+                ChannelMsg::OpenInit(msg) => msg.type_url(),
+                ChannelMsg::OpenTry(msg) => msg.type_url(),
+                ChannelMsg::OpenAck(msg) => msg.type_url(),
+                ChannelMsg::OpenConfirm(msg) => msg.type_url(),
+                ChannelMsg::CloseInit(msg) => msg.type_url(),
+                ChannelMsg::CloseConfirm(msg) => msg.type_url(),
+            },
+            MsgEnvelope::Packet(msg) => match msg {
+                PacketMsg::Recv(msg) => msg.type_url(),
+                PacketMsg::Ack(msg) => msg.type_url(),
+                PacketMsg::Timeout(msg) => msg.type_url(),
+                PacketMsg::TimeoutOnClose(msg) => msg.type_url(),
+            },
+        }
+    }
+}
+
 impl TryFrom<Any> for MsgEnvelope {
     type Error = RouterError;
 
@@ -75,6 +148,12 @@ impl TryFrom<Any> for MsgEnvelope {
                     .map_err(RouterError::MalformedMessageBytes)?;
                 Ok(MsgEnvelope::Client(ClientMsg::Misbehaviour(domain_msg)))
             }
+            client_update_params::TYPE_URL => {
+                let domain_msg =
+                    client_update_params::MsgUpdateClientParams::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Client(ClientMsg::UpdateParams(domain_msg)))
+            }
 
             // ICS03
             conn_open_init::TYPE_URL => {
@@ -100,6 +179,14 @@ impl TryFrom<Any> for MsgEnvelope {
                     domain_msg,
                 )))
             }
+            connection_update_params::TYPE_URL => {
+                let domain_msg =
+                    connection_update_params::MsgUpdateConnectionParams::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Connection(ConnectionMsg::UpdateParams(
+                    domain_msg,
+                )))
+            }
 
             // ICS04 channel messages
             chan_open_init::TYPE_URL => {
@@ -1,8 +1,11 @@
 //! Defines the `Router`, which binds modules to ports
 
 use alloc::borrow::Borrow;
+use alloc::collections::BTreeMap;
 use core::fmt::{Debug, Display, Error as FmtError, Formatter};
 
+use displaydoc::Display as DisplayDoc;
+
 use crate::core::events::ModuleEvent;
 use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
@@ -242,3 +245,255 @@ pub trait Module: Debug {
         relayer: &Signer,
     ) -> (ModuleExtras, Result<(), PacketError>);
 }
+
+/// Errors raised while registering modules and port routes in a [`ModuleRouter`]
+#[derive(Debug, DisplayDoc)]
+pub enum ModuleRouterError {
+    /// module `{module_id}` is already registered
+    DuplicateModule { module_id: ModuleId },
+    /// module `{module_id}` is not registered
+    UnknownModule { module_id: ModuleId },
+    /// port `{port_id}` is already routed to module `{module_id}`
+    DuplicatePortRoute {
+        port_id: PortId,
+        module_id: ModuleId,
+    },
+    /// port prefix `{prefix}` is already routed to module `{module_id}`
+    DuplicatePortPrefixRoute { prefix: String, module_id: ModuleId },
+    /// port prefix must not be empty
+    EmptyPortPrefix,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ModuleRouterError {}
+
+/// A reusable [`Router`] implementation, which owns the registered modules
+/// and routes ports to them.
+///
+/// Ports are routed either exactly (e.g. `transfer`), or by prefix (e.g.
+/// `icacontroller-`, which routes every `icacontroller-{owner}` port). Exact
+/// routes take precedence over prefix routes, and among prefix routes the
+/// longest matching prefix wins.
+#[derive(Debug, Default)]
+pub struct ModuleRouter {
+    modules: BTreeMap<ModuleId, Box<dyn Module>>,
+    port_routes: BTreeMap<PortId, ModuleId>,
+    port_prefix_routes: BTreeMap<String, ModuleId>,
+}
+
+impl ModuleRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `module` under `module_id`.
+    pub fn add_module(
+        &mut self,
+        module_id: ModuleId,
+        module: impl Module + 'static,
+    ) -> Result<(), ModuleRouterError> {
+        if self.modules.contains_key(&module_id) {
+            return Err(ModuleRouterError::DuplicateModule { module_id });
+        }
+        self.modules.insert(module_id, Box::new(module));
+
+        Ok(())
+    }
+
+    /// Routes the given port to the registered module `module_id`.
+    pub fn add_port_route(
+        &mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+    ) -> Result<(), ModuleRouterError> {
+        self.ensure_module_registered(&module_id)?;
+        if let Some(owner) = self.port_routes.get(&port_id) {
+            return Err(ModuleRouterError::DuplicatePortRoute {
+                port_id,
+                module_id: owner.clone(),
+            });
+        }
+        self.port_routes.insert(port_id, module_id);
+
+        Ok(())
+    }
+
+    /// Routes every port starting with `prefix` to the registered module
+    /// `module_id`.
+    pub fn add_port_prefix_route(
+        &mut self,
+        prefix: String,
+        module_id: ModuleId,
+    ) -> Result<(), ModuleRouterError> {
+        if prefix.is_empty() {
+            return Err(ModuleRouterError::EmptyPortPrefix);
+        }
+        self.ensure_module_registered(&module_id)?;
+        if let Some(owner) = self.port_prefix_routes.get(&prefix) {
+            return Err(ModuleRouterError::DuplicatePortPrefixRoute {
+                prefix,
+                module_id: owner.clone(),
+            });
+        }
+        self.port_prefix_routes.insert(prefix, module_id);
+
+        Ok(())
+    }
+
+    /// Returns the identifiers of all the registered modules.
+    pub fn module_ids(&self) -> impl Iterator<Item = &ModuleId> {
+        self.modules.keys()
+    }
+
+    /// Returns all the exact port routes.
+    pub fn port_routes(&self) -> impl Iterator<Item = (&PortId, &ModuleId)> {
+        self.port_routes.iter()
+    }
+
+    /// Returns all the port prefix routes.
+    pub fn port_prefix_routes(&self) -> impl Iterator<Item = (&str, &ModuleId)> {
+        self.port_prefix_routes
+            .iter()
+            .map(|(prefix, module_id)| (prefix.as_str(), module_id))
+    }
+
+    fn ensure_module_registered(&self, module_id: &ModuleId) -> Result<(), ModuleRouterError> {
+        if self.modules.contains_key(module_id) {
+            Ok(())
+        } else {
+            Err(ModuleRouterError::UnknownModule {
+                module_id: module_id.clone(),
+            })
+        }
+    }
+}
+
+impl Router for ModuleRouter {
+    fn get_route(&self, module_id: &ModuleId) -> Option<&dyn Module> {
+        self.modules.get(module_id).map(Box::as_ref)
+    }
+
+    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
+        match self.modules.get_mut(module_id) {
+            Some(module) => Some(module.as_mut()),
+            None => None,
+        }
+    }
+
+    fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId> {
+        if let Some(module_id) = self.port_routes.get(port_id) {
+            return Some(module_id.clone());
+        }
+
+        self.port_prefix_routes
+            .iter()
+            .filter(|(prefix, _)| port_id.as_str().starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, module_id)| module_id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::test_utils::DummyTransferModule;
+
+    fn port(s: &str) -> PortId {
+        PortId::new(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn module_router_routes_exact_and_prefixed_ports() {
+        let transfer = ModuleId::new("transfer".to_string());
+        let controller = ModuleId::new("icacontroller".to_string());
+        let special = ModuleId::new("special".to_string());
+
+        let mut router = ModuleRouter::new();
+        router
+            .add_module(transfer.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .add_module(controller.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .add_module(special.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .add_port_route(PortId::transfer(), transfer.clone())
+            .unwrap();
+        router
+            .add_port_prefix_route("icacontroller-".to_string(), controller.clone())
+            .unwrap();
+        router
+            .add_port_prefix_route("icacontroller-special".to_string(), special.clone())
+            .unwrap();
+        router
+            .add_port_route(port("icacontroller-exact"), transfer.clone())
+            .unwrap();
+
+        assert_eq!(
+            router.lookup_module(&PortId::transfer()),
+            Some(transfer.clone())
+        );
+        assert_eq!(
+            router.lookup_module(&port("icacontroller-owner")),
+            Some(controller)
+        );
+        assert_eq!(
+            router.lookup_module(&port("icacontroller-special-owner")),
+            Some(special)
+        );
+        assert_eq!(
+            router.lookup_module(&port("icacontroller-exact")),
+            Some(transfer.clone())
+        );
+        assert_eq!(router.lookup_module(&port("icahost")), None);
+
+        assert!(router.get_route(&transfer).is_some());
+        assert!(router.get_route_mut(&transfer).is_some());
+        assert_eq!(router.module_ids().count(), 3);
+    }
+
+    #[test]
+    fn module_router_rejects_duplicates() {
+        let transfer = ModuleId::new("transfer".to_string());
+        let unknown = ModuleId::new("unknown".to_string());
+
+        let mut router = ModuleRouter::new();
+        router
+            .add_module(transfer.clone(), DummyTransferModule::new())
+            .unwrap();
+
+        let res = router.add_module(transfer.clone(), DummyTransferModule::new());
+        assert!(matches!(
+            res,
+            Err(ModuleRouterError::DuplicateModule { .. })
+        ));
+
+        let res = router.add_port_route(PortId::transfer(), unknown);
+        assert!(matches!(res, Err(ModuleRouterError::UnknownModule { .. })));
+
+        router
+            .add_port_route(PortId::transfer(), transfer.clone())
+            .unwrap();
+        let res = router.add_port_route(PortId::transfer(), transfer.clone());
+        assert!(matches!(
+            res,
+            Err(ModuleRouterError::DuplicatePortRoute { .. })
+        ));
+
+        let res = router.add_port_prefix_route(String::new(), transfer.clone());
+        assert!(matches!(res, Err(ModuleRouterError::EmptyPortPrefix)));
+
+        router
+            .add_port_prefix_route("transfer-".to_string(), transfer.clone())
+            .unwrap();
+        let res = router.add_port_prefix_route("transfer-".to_string(), transfer);
+        assert!(matches!(
+            res,
+            Err(ModuleRouterError::DuplicatePortPrefixRoute { .. })
+        ));
+    }
+}
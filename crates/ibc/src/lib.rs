@@ -54,6 +54,9 @@ pub mod clients;
 pub mod core;
 pub mod hosts;
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+
 #[cfg(feature = "grpc")]
 pub mod services;
 
@@ -1,6 +1,9 @@
 //! Implementation of a global context mock. Used in testing handlers of all IBC modules.
 
 mod clients;
+#[cfg(feature = "grpc")]
+mod query;
+mod snapshot;
 
 use alloc::collections::btree_map::BTreeMap;
 use alloc::sync::Arc;
@@ -13,8 +16,11 @@ use derive_more::{From, TryInto};
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::Protobuf;
 use parking_lot::Mutex;
+use prost::Message;
+use tendermint::AppHash;
 use tracing::debug;
 
+pub use self::snapshot::{MockSnapshot, SnapshotDiff, SnapshotError, StoreChange};
 use super::client_state::{MOCK_CLIENT_STATE_TYPE_URL, MOCK_CLIENT_TYPE};
 use super::consensus_state::MOCK_CONSENSUS_STATE_TYPE_URL;
 use crate::clients::ics07_tendermint::client_state::{
@@ -24,38 +30,56 @@ use crate::clients::ics07_tendermint::consensus_state::{
     ConsensusState as TmConsensusState, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
 };
 use crate::clients::ics07_tendermint::TENDERMINT_CLIENT_TYPE;
-use crate::core::events::IbcEvent;
-use crate::core::ics02_client::client_state::ClientState;
+use crate::core::authorization::{
+    AffectedIdentifiers, Authorization, AuthorizationError, ClientRelayerWhitelist,
+    PrivilegedMsgAuthority,
+};
+use crate::core::circuit_breaker::{
+    Circuit, CircuitBreakerContext, CircuitBreakerError, InMemoryCircuitBreaker,
+};
+use crate::core::events::{IbcEvent, IbcEventListener};
+use crate::core::ics02_client::client_state::{ClientState, ClientStateCommon};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::params::ClientParams;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics03_connection::params::ConnectionParams;
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
-use crate::core::ics04_channel::packet::{Receipt, Sequence};
-use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics04_channel::packet::{Packet, Receipt, Sequence};
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+use crate::core::ics23_commitment::merkle::MerkleProof;
 use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use crate::core::ics24_host::path::{
-    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, CommitmentPath,
-    ConnectionPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, Path, PortPath, ReceiptPath, SeqAckPath, SeqRecvPath,
+    SeqSendPath,
 };
-use crate::core::router::Router;
+use crate::core::router::{ModuleId, Router};
 use crate::core::timestamp::Timestamp;
 use crate::core::{dispatch, ContextError, ExecutionContext, MsgEnvelope, ValidationContext};
 use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
 use crate::mock::consensus_state::MockConsensusState;
 use crate::mock::header::MockHeader;
-use crate::mock::host::{HostBlock, HostType};
+use crate::mock::host::{HostBlock, HostType, SyntheticTmChain};
 use crate::mock::ics18_relayer::context::RelayerContext;
 use crate::mock::ics18_relayer::error::RelayerError;
+use crate::mock::store::{MerkleStore, MultiStore, TreeLayout};
 use crate::prelude::*;
 use crate::signer::Signer;
 use crate::Height;
 
 pub const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Any", into = "Any")
+)]
 #[derive(Debug, Clone, From, PartialEq, ClientState)]
 #[generics(ClientValidationContext = MockContext,
            ClientExecutionContext = MockContext)
@@ -93,6 +117,11 @@ impl From<AnyClientState> for Any {
     }
 }
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Any", into = "Any")
+)]
 #[derive(Debug, Clone, From, TryInto, PartialEq, ConsensusState)]
 pub enum AnyConsensusState {
     Tendermint(TmConsensusState),
@@ -128,31 +157,45 @@ impl From<AnyConsensusState> for Any {
 
 /// A mock of an IBC client record as it is stored in a mock context.
 /// For testing ICS02 handlers mostly, cf. `MockClientContext`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug)]
 pub struct MockClientRecord {
     /// The client state (representing only the latest height at the moment).
     pub client_state: Option<AnyClientState>,
 
     /// Mapping of heights to consensus states for this client.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_entries"))]
     pub consensus_states: BTreeMap<Height, AnyConsensusState>,
 }
 
 /// An object that stores all IBC related data.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Default)]
 pub struct MockIbcStore {
     /// The set of all clients, indexed by their id.
     pub clients: BTreeMap<ClientId, MockClientRecord>,
 
     /// Tracks the processed time for clients header updates
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_entries"))]
     pub client_processed_times: BTreeMap<(ClientId, Height), Timestamp>,
 
     /// Tracks the processed height for the clients
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_entries"))]
     pub client_processed_heights: BTreeMap<(ClientId, Height), Height>,
 
     /// Counter for the client identifiers, necessary for `increase_client_counter` and the
     /// `client_counter` methods.
     pub client_ids_counter: u64,
 
+    /// The parameters of the client submodule.
+    pub client_params: ClientParams,
+
+    /// The parameters of the connection submodule.
+    pub connection_params: ConnectionParams,
+
+    /// The signer allowed to update the client and connection params, if any.
+    pub params_authority: Option<Signer>,
+
     /// Association between client ids and connection ids.
     pub client_connections: BTreeMap<ClientId, ConnectionId>,
 
@@ -168,6 +211,9 @@ pub struct MockIbcStore {
     /// Counter for channel identifiers (see `increase_channel_counter`).
     pub channel_ids_counter: u64,
 
+    /// The module each bound port is owned by.
+    pub port_owners: BTreeMap<PortId, ModuleId>,
+
     /// All the channels in the store. TODO Make new key PortId X ChannelId
     pub channels: PortChannelIdMap<ChannelEnd>,
 
@@ -187,6 +233,116 @@ pub struct MockIbcStore {
 
     // Used by unordered channel
     pub packet_receipt: PortChannelIdMap<BTreeMap<Sequence, Receipt>>,
+
+    /// The circuits tripped by the circuit breaker authorities.
+    pub circuit_breaker: InMemoryCircuitBreaker,
+
+    /// The relayers allowed to relay on permissioned clients.
+    pub relayer_whitelist: ClientRelayerWhitelist,
+
+    /// The authority allowed to submit privileged client messages, if any.
+    pub privileged_msg_authority: Option<PrivilegedMsgAuthority>,
+
+    /// Full packets sent, recorded for packet data queries.
+    pub sent_packets: PortChannelIdMap<BTreeMap<Sequence, Packet>>,
+
+    /// Received packets and their written acknowledgements, recorded for packet data queries.
+    pub written_acknowledgements: PortChannelIdMap<BTreeMap<Sequence, (Packet, Acknowledgement)>>,
+}
+
+impl MockIbcStore {
+    /// Returns a Merkle store holding the IBC state, each entry under its
+    /// path and encoded as the handlers expect it in proofs.
+    pub fn merkle_store(&self) -> MerkleStore {
+        let mut store = MerkleStore::new(TreeLayout::Iavl);
+        let mut set = |path: Path, value: Vec<u8>| {
+            store.set(path.to_string().into_bytes(), value);
+        };
+
+        for (client_id, client_record) in &self.clients {
+            if let Some(client_state) = &client_record.client_state {
+                set(
+                    ClientStatePath::new(client_id).into(),
+                    Any::from(client_state.clone()).encode_to_vec(),
+                );
+            }
+            for (height, consensus_state) in &client_record.consensus_states {
+                set(
+                    ClientConsensusStatePath::new(client_id, height).into(),
+                    Any::from(consensus_state.clone()).encode_to_vec(),
+                );
+            }
+        }
+
+        for (connection_id, connection_end) in &self.connections {
+            set(
+                ConnectionPath::new(connection_id).into(),
+                connection_end.encode_vec(),
+            );
+        }
+
+        for (port_id, channel_id, channel_end) in port_channel_entries(&self.channels) {
+            set(
+                ChannelEndPath::new(port_id, channel_id).into(),
+                channel_end.encode_vec(),
+            );
+        }
+        for (port_id, channel_id, sequence) in port_channel_entries(&self.next_sequence_send) {
+            set(
+                SeqSendPath::new(port_id, channel_id).into(),
+                u64::from(*sequence).encode_to_vec(),
+            );
+        }
+        for (port_id, channel_id, sequence) in port_channel_entries(&self.next_sequence_recv) {
+            set(
+                SeqRecvPath::new(port_id, channel_id).into(),
+                u64::from(*sequence).encode_to_vec(),
+            );
+        }
+        for (port_id, channel_id, sequence) in port_channel_entries(&self.next_sequence_ack) {
+            set(
+                SeqAckPath::new(port_id, channel_id).into(),
+                u64::from(*sequence).encode_to_vec(),
+            );
+        }
+
+        for (port_id, channel_id, commitments) in port_channel_entries(&self.packet_commitment) {
+            for (sequence, commitment) in commitments {
+                set(
+                    CommitmentPath::new(port_id, channel_id, *sequence).into(),
+                    commitment.as_ref().to_vec(),
+                );
+            }
+        }
+        for (port_id, channel_id, receipts) in port_channel_entries(&self.packet_receipt) {
+            for sequence in receipts.keys() {
+                set(
+                    ReceiptPath::new(port_id, channel_id, *sequence).into(),
+                    vec![1],
+                );
+            }
+        }
+        for (port_id, channel_id, acks) in port_channel_entries(&self.packet_acknowledgement) {
+            for (sequence, ack_commitment) in acks {
+                set(
+                    AckPath::new(port_id, channel_id, *sequence).into(),
+                    ack_commitment.as_ref().to_vec(),
+                );
+            }
+        }
+
+        store
+    }
+}
+
+fn port_channel_entries<V>(
+    map: &PortChannelIdMap<V>,
+) -> impl Iterator<Item = (&PortId, &ChannelId, &V)> {
+    map.iter().flat_map(|(port_id, channels)| {
+        channels
+            .iter()
+            .map(move |(channel_id, value)| (port_id, channel_id, value))
+    })
 }
 
 /// A context implementing the dependencies necessary for testing any IBC module.
@@ -211,9 +367,20 @@ pub struct MockContext {
     /// An object that stores all IBC related data.
     pub ibc_store: Arc<Mutex<MockIbcStore>>,
 
+    /// Snapshots of the IBC store at the heights still in `history`, taken when
+    /// each height became the latest one. Used to answer queries at past heights.
+    store_history: BTreeMap<Height, MockIbcStore>,
+
     pub events: Vec<IbcEvent>,
 
     pub logs: Vec<String>,
+
+    /// Notified of every event the handlers emit.
+    event_listener: Option<Arc<dyn IbcEventListener + Send + Sync>>,
+
+    /// Whether the blocks of the host chain commit to the IBC store, whose
+    /// entries are then provable against them.
+    provable_store: bool,
 }
 
 /// Returns a MockContext with bare minimum initialization: no clients, no connections and no channels are
@@ -246,8 +413,11 @@ impl Clone for MockContext {
             history: self.history.clone(),
             block_time: self.block_time,
             ibc_store,
+            store_history: self.store_history.clone(),
             events: self.events.clone(),
             logs: self.logs.clone(),
+            event_listener: self.event_listener.clone(),
+            provable_store: self.provable_store,
         }
     }
 }
@@ -307,9 +477,15 @@ impl MockContext {
                 })
                 .collect(),
             block_time,
-            ibc_store: Arc::new(Mutex::new(MockIbcStore::default())),
+            ibc_store: Arc::new(Mutex::new(MockIbcStore {
+                connection_params: ConnectionParams::new(block_time).expect("Never fails"),
+                ..Default::default()
+            })),
+            store_history: BTreeMap::new(),
             events: Vec::new(),
             logs: Vec::new(),
+            event_listener: None,
+            provable_store: false,
         }
     }
 
@@ -481,6 +657,32 @@ impl MockContext {
         self
     }
 
+    /// Associates to this context a Tendermint client of `chain`, with the
+    /// client state and the consensus state of `chain` at `height`.
+    pub fn with_tm_client(
+        self,
+        client_id: &ClientId,
+        chain: &SyntheticTmChain,
+        height: Height,
+    ) -> Self {
+        let client_state = chain
+            .client_state(&height)
+            .expect("the chain has a block at the client height");
+        let consensus_state = chain
+            .consensus_state(&height)
+            .expect("the chain has a block at the client height");
+
+        let client_record = MockClientRecord {
+            client_state: Some(client_state.into()),
+            consensus_states: BTreeMap::from([(height, consensus_state.into())]),
+        };
+        self.ibc_store
+            .lock()
+            .clients
+            .insert(client_id.clone(), client_record);
+        self
+    }
+
     /// Associates a connection to this context.
     pub fn with_connection(
         self,
@@ -576,6 +778,30 @@ impl MockContext {
         }
     }
 
+    /// Regenerates the blocks of the history of this context, which are at
+    /// the current time, so that the latest one is at `timestamp`, e.g. for
+    /// the blocks of the context to be the same across runs.
+    pub fn with_latest_timestamp(self, timestamp: Timestamp) -> Self {
+        let latest_index = self.history.len() - 1;
+        let history = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(index, block)| {
+                let blocks_before_latest = (latest_index - index) as u32;
+                HostBlock::generate_block(
+                    self.host_chain_id.clone(),
+                    self.host_chain_type,
+                    block.height().revision_height(),
+                    timestamp
+                        .sub(self.block_time * blocks_before_latest)
+                        .expect("Never fails"),
+                )
+            })
+            .collect();
+        Self { history, ..self }
+    }
+
     pub fn with_packet_commitment(
         self,
         port_id: PortId,
@@ -594,6 +820,67 @@ impl MockContext {
         self
     }
 
+    /// Binds `port_id` to the module identified by `module_id`.
+    pub fn with_port(self, port_id: PortId, module_id: ModuleId) -> Self {
+        self.ibc_store.lock().port_owners.insert(port_id, module_id);
+        self
+    }
+
+    /// Notifies `listener` of every event the handlers emit on this context.
+    pub fn with_event_listener(
+        self,
+        listener: impl IbcEventListener + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            event_listener: Some(Arc::new(listener)),
+            ..self
+        }
+    }
+
+    /// Commits to the IBC store in a Merkle store, whose root is the app hash
+    /// of the blocks produced from now on, so that the IBC state at their
+    /// heights can be proven (see `commitment_proof`).
+    ///
+    /// Only the headers of SyntheticTendermint blocks carry the root.
+    pub fn with_provable_store(self) -> Self {
+        Self {
+            provable_store: true,
+            ..self
+        }
+    }
+
+    /// Allows `authority` to trip and reset circuits in this context.
+    pub fn with_circuit_breaker_authority(self, authority: Signer) -> Self {
+        self.ibc_store
+            .lock()
+            .circuit_breaker
+            .add_authority(authority);
+        self
+    }
+
+    /// Restricts relaying on `client_id` to the whitelisted relayers, adding
+    /// `relayer` to them.
+    pub fn with_whitelisted_relayer(self, client_id: ClientId, relayer: Signer) -> Self {
+        self.ibc_store
+            .lock()
+            .relayer_whitelist
+            .allow(client_id, relayer);
+        self
+    }
+
+    /// Allows `authority` to update the client and connection params.
+    pub fn with_params_authority(self, authority: Signer) -> Self {
+        self.ibc_store.lock().params_authority = Some(authority);
+        self
+    }
+
+    /// Reserves privileged client messages to `authority`.
+    pub fn with_privileged_msg_authority(self, authority: Signer) -> Self {
+        self.ibc_store.lock().privileged_msg_authority =
+            Some(PrivilegedMsgAuthority::new(authority));
+        self
+    }
+
     /// Accessor for a block of the local (host) chain from this context.
     /// Returns `None` if the block at the requested height does not exist.
     pub fn host_block(&self, target_height: &Height) -> Option<&HostBlock> {
@@ -610,8 +897,15 @@ impl MockContext {
 
     /// Triggers the advancing of the host chain, by extending the history of blocks (or headers).
     pub fn advance_host_chain_height(&mut self) {
+        let app_hash = if self.provable_store {
+            let root = self.multistore(&self.ibc_store.lock()).root();
+            AppHash::try_from(root.into_vec()).expect("Never fails")
+        } else {
+            AppHash::default()
+        };
+
         let latest_block = self.history.last().expect("history cannot be empty");
-        let new_block = HostBlock::generate_block(
+        let new_block = HostBlock::generate_block_with_app_hash(
             self.host_chain_id.clone(),
             self.host_chain_type,
             latest_block.height().increment().revision_height(),
@@ -619,6 +913,7 @@ impl MockContext {
                 .timestamp()
                 .add(self.block_time)
                 .expect("Never fails"),
+            app_hash,
         );
 
         // Append the new header at the tip of the history.
@@ -630,6 +925,16 @@ impl MockContext {
             // History is not full yet.
             self.history.push(new_block);
         }
+
+        // Snapshot the store at the new height, and drop the pruned snapshots.
+        let oldest_height = self
+            .history
+            .first()
+            .expect("history cannot be empty")
+            .height();
+        self.store_history = self.store_history.split_off(&oldest_height);
+        self.store_history
+            .insert(self.latest_height(), self.ibc_store.lock().clone());
     }
 
     /// A datagram passes from the relayer to the IBC module (on host chain).
@@ -701,6 +1006,71 @@ impl MockContext {
             .height()
     }
 
+    /// Returns a copy of this context as it was when `height` was the latest
+    /// height, or `None` if that state is no longer in the history.
+    pub fn at_height(&self, height: &Height) -> Option<Self> {
+        let ibc_store = self.store_history.get(height)?.clone();
+
+        Some(Self {
+            host_chain_type: self.host_chain_type,
+            host_chain_id: self.host_chain_id.clone(),
+            max_history_size: self.max_history_size,
+            history: self
+                .history
+                .iter()
+                .filter(|block| block.height() <= *height)
+                .cloned()
+                .collect(),
+            block_time: self.block_time,
+            ibc_store: Arc::new(Mutex::new(ibc_store)),
+            store_history: self
+                .store_history
+                .range(..=height)
+                .map(|(height, store)| (*height, store.clone()))
+                .collect(),
+            events: Vec::new(),
+            logs: Vec::new(),
+            event_listener: None,
+            provable_store: self.provable_store,
+        })
+    }
+
+    /// Returns the root the block at `height` commits to, or `None` if the
+    /// store is not provable or the state at `height` is no longer in the
+    /// history.
+    pub fn commitment_root(&self, height: &Height) -> Option<CommitmentRoot> {
+        if !self.provable_store {
+            return None;
+        }
+        let ibc_store = self.store_history.get(height)?;
+        Some(self.multistore(ibc_store).root())
+    }
+
+    /// Returns the proof of the value at `path`, or of its absence, in the IBC
+    /// store at `height`, against the root of the block at that height.
+    ///
+    /// Returns `None` if the store is not provable, if the state at `height`
+    /// is no longer in the history, or if the store was empty then.
+    pub fn commitment_proof(&self, height: &Height, path: &Path) -> Option<MerkleProof> {
+        if !self.provable_store {
+            return None;
+        }
+        let ibc_store = self.store_history.get(height)?;
+        self.multistore(ibc_store).proof(
+            self.commitment_prefix().as_bytes(),
+            path.to_string().as_bytes(),
+        )
+    }
+
+    fn multistore(&self, ibc_store: &MockIbcStore) -> MultiStore {
+        let mut store = MultiStore::new();
+        store.mount(
+            self.commitment_prefix().as_bytes().to_vec(),
+            ibc_store.merkle_store(),
+        );
+        store
+    }
+
     pub fn ibc_store_share(&self) -> Arc<Mutex<MockIbcStore>> {
         self.ibc_store.clone()
     }
@@ -830,39 +1200,72 @@ impl ValidationContext for MockContext {
         &self,
         client_state_of_host_on_counterparty: Any,
     ) -> Result<(), ContextError> {
-        let mock_client_state = MockClientState::try_from(client_state_of_host_on_counterparty)
-            .map_err(|_| ConnectionError::InvalidClientState {
-                reason: "client must be a mock client".to_string(),
+        // The client tracking a Mock chain is a mock client, and the one
+        // tracking a SyntheticTendermint chain a Tendermint client.
+        let client_state = AnyClientState::try_from(client_state_of_host_on_counterparty)
+            .ok()
+            .filter(|client_state| {
+                matches!(
+                    (client_state, self.host_chain_type),
+                    (AnyClientState::Mock(_), HostType::Mock)
+                        | (AnyClientState::Tendermint(_), HostType::SyntheticTendermint)
+                )
+            })
+            .ok_or_else(|| ConnectionError::InvalidClientState {
+                reason: format!(
+                    "client must be a {} client",
+                    match self.host_chain_type {
+                        HostType::Mock => MOCK_CLIENT_TYPE,
+                        HostType::SyntheticTendermint => TENDERMINT_CLIENT_TYPE,
+                    }
+                ),
             })
             .map_err(ContextError::ConnectionError)?;
 
-        if mock_client_state.is_frozen() {
+        let is_frozen = match &client_state {
+            AnyClientState::Mock(client_state) => client_state.is_frozen(),
+            AnyClientState::Tendermint(client_state) => {
+                if client_state.chain_id != self.host_chain_id {
+                    return Err(ContextError::ConnectionError(
+                        ConnectionError::InvalidClientState {
+                            reason: format!(
+                                "client is for chain {}, expected {}",
+                                client_state.chain_id, self.host_chain_id
+                            ),
+                        },
+                    ));
+                }
+                client_state.is_frozen()
+            }
+        };
+
+        if is_frozen {
             return Err(ClientError::ClientFrozen {
                 description: String::new(),
             }
             .into());
         }
 
+        let latest_height = client_state.latest_height();
         let self_chain_id = &self.host_chain_id;
         let self_revision_number = self_chain_id.revision_number();
-        if self_revision_number != mock_client_state.latest_height().revision_number() {
+        if self_revision_number != latest_height.revision_number() {
             return Err(ConnectionError::InvalidClientState {
                 reason: format!(
                     "client is not in the same revision as the chain. expected: {}, got: {}",
                     self_revision_number,
-                    mock_client_state.latest_height().revision_number()
+                    latest_height.revision_number()
                 ),
             })
             .map_err(ContextError::ConnectionError);
         }
 
         let host_current_height = self.latest_height().increment();
-        if mock_client_state.latest_height() >= host_current_height {
+        if latest_height >= host_current_height {
             return Err(ConnectionError::InvalidClientState {
                 reason: format!(
                     "client has latest height {} greater than or equal to chain height {}",
-                    mock_client_state.latest_height(),
-                    host_current_height
+                    latest_height, host_current_height
                 ),
             })
             .map_err(ContextError::ConnectionError);
@@ -879,6 +1282,10 @@ impl ValidationContext for MockContext {
         Ok(self.ibc_store.lock().connection_ids_counter)
     }
 
+    fn port_owner(&self, port_path: &PortPath) -> Result<Option<ModuleId>, ContextError> {
+        Ok(self.ibc_store.lock().port_owners.get(&port_path.0).cloned())
+    }
+
     fn channel_end(&self, chan_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
         let port_id = &chan_end_path.0;
         let channel_id = &chan_end_path.1;
@@ -1072,14 +1479,42 @@ impl ValidationContext for MockContext {
         Ok(self.ibc_store.lock().channel_ids_counter)
     }
 
-    fn max_expected_time_per_block(&self) -> Duration {
-        self.block_time
+    fn client_params(&self) -> Result<ClientParams, ContextError> {
+        Ok(self.ibc_store.lock().client_params.clone())
+    }
+
+    fn connection_params(&self) -> Result<ConnectionParams, ContextError> {
+        Ok(self.ibc_store.lock().connection_params.clone())
+    }
+
+    fn is_params_authority(&self, signer: &Signer) -> bool {
+        self.ibc_store.lock().params_authority.as_ref() == Some(signer)
     }
 
     fn validate_message_signer(&self, _signer: &Signer) -> Result<(), ContextError> {
         Ok(())
     }
 
+    fn is_circuit_broken(&self, circuit: &Circuit) -> bool {
+        self.is_circuit_tripped(circuit)
+    }
+
+    fn authorize_message(
+        &self,
+        signer: &Signer,
+        msg: &MsgEnvelope,
+        identifiers: &AffectedIdentifiers,
+    ) -> Result<(), AuthorizationError> {
+        let ibc_store = self.ibc_store.lock();
+        ibc_store
+            .relayer_whitelist
+            .authorize(signer, msg, identifiers)?;
+        if let Some(authority) = &ibc_store.privileged_msg_authority {
+            authority.authorize(signer, msg, identifiers)?;
+        }
+        Ok(())
+    }
+
     fn get_client_validation_context(&self) -> &Self::ClientValidationContext {
         self
     }
@@ -1155,6 +1590,16 @@ impl ExecutionContext for MockContext {
         Ok(())
     }
 
+    fn store_client_params(&mut self, params: ClientParams) -> Result<(), ContextError> {
+        self.ibc_store.lock().client_params = params;
+        Ok(())
+    }
+
+    fn store_connection_params(&mut self, params: ConnectionParams) -> Result<(), ContextError> {
+        self.ibc_store.lock().connection_params = params;
+        Ok(())
+    }
+
     fn increase_connection_counter(&mut self) -> Result<(), ContextError> {
         let mut ibc_store = self.ibc_store.lock();
 
@@ -1245,6 +1690,23 @@ impl ExecutionContext for MockContext {
         Ok(())
     }
 
+    fn store_port_owner(
+        &mut self,
+        port_path: &PortPath,
+        module_id: ModuleId,
+    ) -> Result<(), ContextError> {
+        self.ibc_store
+            .lock()
+            .port_owners
+            .insert(port_path.0.clone(), module_id);
+        Ok(())
+    }
+
+    fn delete_port_owner(&mut self, port_path: &PortPath) -> Result<(), ContextError> {
+        self.ibc_store.lock().port_owners.remove(&port_path.0);
+        Ok(())
+    }
+
     fn store_channel(
         &mut self,
         channel_end_path: &ChannelEndPath,
@@ -1329,10 +1791,67 @@ impl ExecutionContext for MockContext {
         Ok(())
     }
 
+    fn ibc_event_listener(&self) -> Option<&dyn IbcEventListener> {
+        let listener: &dyn IbcEventListener = self.event_listener.as_deref()?;
+        Some(listener)
+    }
+
     fn log_message(&mut self, message: String) -> Result<(), ContextError> {
         self.logs.push(message);
         Ok(())
     }
+
+    fn record_sent_packet(&mut self, packet: &Packet) -> Result<(), ContextError> {
+        self.ibc_store
+            .lock()
+            .sent_packets
+            .entry(packet.port_id_on_a.clone())
+            .or_default()
+            .entry(packet.chan_id_on_a.clone())
+            .or_default()
+            .insert(packet.seq_on_a, packet.clone());
+        Ok(())
+    }
+
+    fn record_written_acknowledgement(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+    ) -> Result<(), ContextError> {
+        self.ibc_store
+            .lock()
+            .written_acknowledgements
+            .entry(packet.port_id_on_b.clone())
+            .or_default()
+            .entry(packet.chan_id_on_b.clone())
+            .or_default()
+            .insert(packet.seq_on_a, (packet.clone(), acknowledgement.clone()));
+        Ok(())
+    }
+}
+
+impl CircuitBreakerContext for MockContext {
+    fn is_circuit_breaker_authority(&self, signer: &Signer) -> bool {
+        self.ibc_store
+            .lock()
+            .circuit_breaker
+            .is_circuit_breaker_authority(signer)
+    }
+
+    fn is_circuit_tripped(&self, circuit: &Circuit) -> bool {
+        self.ibc_store
+            .lock()
+            .circuit_breaker
+            .is_circuit_tripped(circuit)
+    }
+
+    fn trip_circuit(&mut self, circuit: Circuit) -> Result<(), CircuitBreakerError> {
+        self.ibc_store.lock().circuit_breaker.trip_circuit(circuit)
+    }
+
+    fn reset_circuit(&mut self, circuit: &Circuit) -> Result<(), CircuitBreakerError> {
+        self.ibc_store.lock().circuit_breaker.reset_circuit(circuit)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,374 @@
+//! gRPC query context implementations for `MockContext`
+
+use alloc::collections::btree_map::BTreeMap;
+
+use super::{AnyClientState, AnyConsensusState, MockContext};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::channel::IdentifiedChannelEnd;
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics24_host::path::{AckPath, ChannelEndPath, CommitmentPath, Path};
+use crate::core::ContextError;
+use crate::prelude::*;
+use crate::services::core::context::{PacketDataQueryContext, ProvableContext, QueryContext};
+use crate::services::core::height::QueryHeightError;
+use crate::Height;
+
+/// The proofs are those of the provable store, if the context runs on one
+/// (see `MockContext::with_provable_store`). Otherwise, an empty proof is
+/// returned for any paths at a height still in the history of the host chain.
+impl ProvableContext for MockContext {
+    fn get_proof(&self, height: Height, path: &Path) -> Option<Vec<u8>> {
+        if !self.provable_store {
+            return self.host_block(&height).map(|_| Vec::new());
+        }
+
+        let proof = self.commitment_proof(&height, path)?;
+        CommitmentProofBytes::try_from(proof).ok().map(Into::into)
+    }
+}
+
+impl QueryContext for MockContext {
+    fn query_context_at(&self, height: &Height) -> Result<Self, QueryHeightError> {
+        self.at_height(height)
+            .ok_or(QueryHeightError::Pruned { height: *height })
+    }
+
+    fn client_states(&self) -> Result<Vec<(ClientId, AnyClientState)>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .clients
+            .iter()
+            .filter_map(|(client_id, client_record)| {
+                client_record
+                    .client_state
+                    .clone()
+                    .map(|client_state| (client_id.clone(), client_state))
+            })
+            .collect())
+    }
+
+    fn consensus_states(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Vec<(Height, AnyConsensusState)>, ContextError> {
+        let ibc_store = self.ibc_store.lock();
+        let client_record =
+            ibc_store
+                .clients
+                .get(client_id)
+                .ok_or_else(|| ClientError::ClientStateNotFound {
+                    client_id: client_id.clone(),
+                })?;
+
+        Ok(client_record
+            .consensus_states
+            .iter()
+            .map(|(height, consensus_state)| (*height, consensus_state.clone()))
+            .collect())
+    }
+
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError> {
+        Ok(self
+            .consensus_states(client_id)?
+            .into_iter()
+            .map(|(height, _)| height)
+            .collect())
+    }
+
+    fn connection_ends(&self) -> Result<Vec<IdentifiedConnectionEnd>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .connections
+            .iter()
+            .map(|(connection_id, connection_end)| {
+                IdentifiedConnectionEnd::new(connection_id.clone(), connection_end.clone())
+            })
+            .collect())
+    }
+
+    fn client_connection_ends(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<Vec<ConnectionId>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .client_connections
+            .get(client_id)
+            .cloned()
+            .into_iter()
+            .collect())
+    }
+
+    fn channel_ends(&self) -> Result<Vec<IdentifiedChannelEnd>, ContextError> {
+        Ok(self
+            .ibc_store
+            .lock()
+            .channels
+            .iter()
+            .flat_map(|(port_id, channels)| {
+                channels.iter().map(move |(channel_id, channel_end)| {
+                    IdentifiedChannelEnd::new(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        channel_end.clone(),
+                    )
+                })
+            })
+            .collect())
+    }
+
+    fn packet_commitments(
+        &self,
+        channel_end_path: &ChannelEndPath,
+    ) -> Result<Vec<CommitmentPath>, ContextError> {
+        Ok(self
+            .committed_sequences(channel_end_path)
+            .into_iter()
+            .map(|sequence| CommitmentPath::new(&channel_end_path.0, &channel_end_path.1, sequence))
+            .collect())
+    }
+
+    fn packet_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<AckPath>, ContextError> {
+        let acknowledged: Vec<Sequence> = self
+            .ibc_store
+            .lock()
+            .packet_acknowledgement
+            .get(&channel_end_path.0)
+            .and_then(|channels| channels.get(&channel_end_path.1))
+            .map(|acks| acks.keys().copied().collect())
+            .unwrap_or_default();
+
+        let sequences = if sequences.len() == 0 {
+            acknowledged
+        } else {
+            sequences
+                .filter(|sequence| acknowledged.contains(sequence))
+                .collect()
+        };
+
+        Ok(sequences
+            .into_iter()
+            .map(|sequence| AckPath::new(&channel_end_path.0, &channel_end_path.1, sequence))
+            .collect())
+    }
+
+    fn unreceived_packets(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<Sequence>, ContextError> {
+        let ibc_store = self.ibc_store.lock();
+        let receipts = ibc_store
+            .packet_receipt
+            .get(&channel_end_path.0)
+            .and_then(|channels| channels.get(&channel_end_path.1));
+
+        Ok(sequences
+            .filter(|sequence| receipts.map_or(true, |receipts| !receipts.contains_key(sequence)))
+            .collect())
+    }
+
+    fn unreceived_acks(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<Sequence>, ContextError> {
+        // A packet whose acknowledgement is received has its commitment deleted.
+        let committed = self.committed_sequences(channel_end_path);
+
+        if sequences.len() == 0 {
+            Ok(committed)
+        } else {
+            Ok(sequences
+                .filter(|sequence| committed.contains(sequence))
+                .collect())
+        }
+    }
+}
+
+impl PacketDataQueryContext for MockContext {
+    fn sent_packets(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<Packet>, ContextError> {
+        let ibc_store = self.ibc_store.lock();
+        let sent_packets = ibc_store
+            .sent_packets
+            .get(&channel_end_path.0)
+            .and_then(|channels| channels.get(&channel_end_path.1));
+
+        Ok(filter_sequences(sent_packets, sequences))
+    }
+
+    fn written_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<(Packet, Acknowledgement)>, ContextError> {
+        let ibc_store = self.ibc_store.lock();
+        let written_acknowledgements = ibc_store
+            .written_acknowledgements
+            .get(&channel_end_path.0)
+            .and_then(|channels| channels.get(&channel_end_path.1));
+
+        Ok(filter_sequences(written_acknowledgements, sequences))
+    }
+}
+
+/// Returns the entries at `sequences`, or all the entries if `sequences` is empty.
+fn filter_sequences<V: Clone>(
+    entries: Option<&BTreeMap<Sequence, V>>,
+    sequences: impl ExactSizeIterator<Item = Sequence>,
+) -> Vec<V> {
+    let entries = match entries {
+        Some(entries) => entries,
+        None => return Vec::new(),
+    };
+
+    if sequences.len() == 0 {
+        entries.values().cloned().collect()
+    } else {
+        sequences
+            .filter_map(|sequence| entries.get(&sequence).cloned())
+            .collect()
+    }
+}
+
+impl MockContext {
+    fn committed_sequences(&self, channel_end_path: &ChannelEndPath) -> Vec<Sequence> {
+        self.ibc_store
+            .lock()
+            .packet_commitment
+            .get(&channel_end_path.0)
+            .and_then(|channels| channels.get(&channel_end_path.1))
+            .map(|commitments| commitments.keys().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::channel::v1::query_server::Query as ChannelQuery;
+    use ibc_proto::ibc::core::channel::v1::{
+        QueryPacketCommitmentRequest, QueryPacketCommitmentsRequest,
+    };
+    use tonic::{Code, Request};
+
+    use super::*;
+    use crate::core::ics04_channel::commitment::PacketCommitment;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::services::core::channel::ChannelQueryService;
+    use crate::services::core::height::BLOCK_HEIGHT_METADATA_KEY;
+
+    fn at_height<T>(message: T, height: u64) -> Request<T> {
+        let mut request = Request::new(message);
+        request.metadata_mut().insert(
+            BLOCK_HEIGHT_METADATA_KEY,
+            height.to_string().parse().unwrap(),
+        );
+        request
+    }
+
+    fn commitment_request(sequence: u64) -> QueryPacketCommitmentRequest {
+        QueryPacketCommitmentRequest {
+            port_id: PortId::transfer().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            sequence,
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_at_past_height() {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::default();
+        let commitment = PacketCommitment::from(vec![1]);
+
+        // latest height 5, history window of 5 blocks
+        let mut ctx = MockContext::default();
+        ctx.advance_host_chain_height();
+        ctx = ctx.with_packet_commitment(
+            port_id.clone(),
+            channel_id.clone(),
+            1.into(),
+            commitment.clone(),
+        );
+        ctx.advance_host_chain_height();
+        ctx = ctx.with_packet_commitment(port_id, channel_id, 2.into(), commitment);
+        ctx.advance_host_chain_height();
+        assert_eq!(ctx.latest_height().revision_height(), 8);
+
+        let service = ChannelQueryService::new(ctx.clone());
+
+        let response = service
+            .packet_commitment(at_height(commitment_request(1), 7))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.proof_height.unwrap().revision_height, 7);
+
+        let status = service
+            .packet_commitment(at_height(commitment_request(1), 6))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let commitments_request = QueryPacketCommitmentsRequest {
+            port_id: PortId::transfer().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            pagination: None,
+        };
+        let response = service
+            .packet_commitments(at_height(commitments_request.clone(), 7))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.commitments.len(), 1);
+        assert_eq!(response.height.unwrap().revision_height, 7);
+
+        let response = service
+            .packet_commitments(Request::new(commitments_request))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.commitments.len(), 2);
+        assert_eq!(response.height.unwrap().revision_height, 8);
+
+        // heights 3 to 5 predate the history of the store
+        let status = service
+            .packet_commitment(at_height(commitment_request(1), 4))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        assert!(status.message().contains("pruned"));
+
+        // height 7 drops out of the history window
+        for _ in 0..5 {
+            ctx.advance_host_chain_height();
+        }
+        let service = ChannelQueryService::new(ctx);
+        let status = service
+            .packet_commitment(at_height(commitment_request(1), 7))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        assert!(status.message().contains("pruned"));
+
+        let status = service
+            .packet_commitment(at_height(commitment_request(1), 100))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
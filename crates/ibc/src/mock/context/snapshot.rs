@@ -0,0 +1,572 @@
+//! Snapshots of the state of a [`MockContext`], to save it between test runs
+//! and restore it, or to compare it before and after some steps of a test.
+//!
+//! With the `serde` feature, a snapshot can be saved as JSON, to be read by
+//! people, and with the `mocks-serde` feature also in a compact binary form.
+//! Both encodings are deterministic: the same state always gives the same
+//! JSON and the same bytes.
+
+use alloc::collections::btree_map::BTreeMap;
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::time::Duration;
+
+use displaydoc::Display;
+use ibc_proto::google::protobuf::Any;
+use subtle_encoding::{Encoding, Hex};
+
+use super::{port_channel_entries, MockContext, MockIbcStore};
+use crate::core::events::IbcEvent;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, PortPath, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+};
+use crate::mock::host::{HostBlock, HostType};
+use crate::prelude::*;
+use crate::Height;
+
+/// The full state of a [`MockContext`]: its host chain, its IBC store, with
+/// the copies of it kept for past heights, and what it emitted.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct MockSnapshot {
+    pub host_chain_id: ChainId,
+    pub host_chain_type: HostType,
+    pub max_history_size: usize,
+    pub block_time: Duration,
+    pub provable_store: bool,
+    /// The blocks of the host chain, encoded as client headers.
+    pub history: Vec<Any>,
+    pub ibc_store: MockIbcStore,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_entries"))]
+    pub store_history: BTreeMap<Height, MockIbcStore>,
+    pub events: Vec<IbcEvent>,
+    pub logs: Vec<String>,
+}
+
+impl MockSnapshot {
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string_pretty(self).map_err(|e| SnapshotError::InvalidJson {
+            reason: e.to_string(),
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        serde_json::from_str(json).map_err(|e| SnapshotError::InvalidJson {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Encodes the snapshot with `bincode`, integers being varint-encoded.
+    #[cfg(feature = "mocks-serde")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        use bincode::Options;
+
+        bincode::DefaultOptions::new()
+            .serialize(self)
+            .map_err(|e| SnapshotError::InvalidBytes {
+                reason: e.to_string(),
+            })
+    }
+
+    #[cfg(feature = "mocks-serde")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        use bincode::Options;
+
+        bincode::DefaultOptions::new()
+            .deserialize(bytes)
+            .map_err(|e| SnapshotError::InvalidBytes {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Returns the changes to the IBC store from this snapshot to `later`.
+    pub fn diff(&self, later: &MockSnapshot) -> SnapshotDiff {
+        SnapshotDiff::between(&self.ibc_store, &later.ibc_store)
+    }
+}
+
+impl MockContext {
+    /// Takes a snapshot of the state of the context.
+    pub fn snapshot(&self) -> MockSnapshot {
+        MockSnapshot {
+            host_chain_id: self.host_chain_id.clone(),
+            host_chain_type: self.host_chain_type,
+            max_history_size: self.max_history_size,
+            block_time: self.block_time,
+            provable_store: self.provable_store,
+            history: self.history.iter().cloned().map(Any::from).collect(),
+            ibc_store: self.ibc_store.lock().clone(),
+            store_history: self.store_history.clone(),
+            events: self.events.clone(),
+            logs: self.logs.clone(),
+        }
+    }
+
+    /// Restores the state of a snapshot. The IBC store is replaced in place,
+    /// so that the handles to it that were shared keep seeing it.
+    pub fn restore(&mut self, snapshot: MockSnapshot) -> Result<(), SnapshotError> {
+        let history = snapshot
+            .history
+            .into_iter()
+            .enumerate()
+            .map(|(index, block)| {
+                HostBlock::try_from(block).map_err(|e| SnapshotError::InvalidHostBlock {
+                    index,
+                    reason: e.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if history.is_empty() {
+            return Err(SnapshotError::EmptyHistory);
+        }
+
+        self.host_chain_id = snapshot.host_chain_id;
+        self.host_chain_type = snapshot.host_chain_type;
+        self.max_history_size = snapshot.max_history_size;
+        self.block_time = snapshot.block_time;
+        self.provable_store = snapshot.provable_store;
+        self.history = history;
+        *self.ibc_store.lock() = snapshot.ibc_store;
+        self.store_history = snapshot.store_history;
+        self.events = snapshot.events;
+        self.logs = snapshot.logs;
+
+        Ok(())
+    }
+}
+
+/// The changes to an IBC store, entry by entry, in the order of their keys.
+///
+/// Provable entries are keyed by their ICS-24 path, the others by a path of
+/// the same form, and their values are shown in their debug form.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub changes: Vec<StoreChange>,
+}
+
+impl SnapshotDiff {
+    pub fn between(before: &MockIbcStore, after: &MockIbcStore) -> Self {
+        let before = store_entries(before);
+        let mut after = store_entries(after);
+
+        let mut changes = Vec::new();
+        for (path, value) in before {
+            match after.remove(&path) {
+                None => changes.push(StoreChange::Removed { path, value }),
+                Some(new_value) if new_value != value => changes.push(StoreChange::Changed {
+                    path,
+                    before: value,
+                    after: new_value,
+                }),
+                Some(_) => {}
+            }
+        }
+        changes.extend(
+            after
+                .into_iter()
+                .map(|(path, value)| StoreChange::Added { path, value }),
+        );
+        changes.sort_by(|a, b| a.path().cmp(b.path()));
+
+        Self { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Lists the changes, one per line.
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StoreChange {
+    Added {
+        path: String,
+        value: String,
+    },
+    Removed {
+        path: String,
+        value: String,
+    },
+    Changed {
+        path: String,
+        before: String,
+        after: String,
+    },
+}
+
+impl StoreChange {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+impl Display for StoreChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Added { path, value } => write!(f, "+ {path}: {value}"),
+            Self::Removed { path, value } => write!(f, "- {path}: {value}"),
+            Self::Changed {
+                path,
+                before,
+                after,
+            } => write!(f, "~ {path}: {before} -> {after}"),
+        }
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum SnapshotError {
+    /// invalid JSON snapshot: `{reason}`
+    InvalidJson { reason: String },
+    /// invalid binary snapshot: `{reason}`
+    InvalidBytes { reason: String },
+    /// invalid host block at position {index} of the history: `{reason}`
+    InvalidHostBlock { index: usize, reason: String },
+    /// the history of the snapshot has no block
+    EmptyHistory,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+/// Returns every entry of the store, keyed by its path.
+fn store_entries(store: &MockIbcStore) -> BTreeMap<String, String> {
+    let mut entries = BTreeMap::new();
+    let mut set = |path: String, value: String| {
+        entries.insert(path, value);
+    };
+
+    set(
+        "nextClientSequence".to_string(),
+        store.client_ids_counter.to_string(),
+    );
+    set(
+        "nextConnectionSequence".to_string(),
+        store.connection_ids_counter.to_string(),
+    );
+    set(
+        "nextChannelSequence".to_string(),
+        store.channel_ids_counter.to_string(),
+    );
+    set(
+        "params/client".to_string(),
+        format!("{:?}", store.client_params),
+    );
+    set(
+        "params/connection".to_string(),
+        format!("{:?}", store.connection_params),
+    );
+    set(
+        "params/authority".to_string(),
+        format!("{:?}", store.params_authority),
+    );
+    set(
+        "circuitBreaker".to_string(),
+        format!("{:?}", store.circuit_breaker),
+    );
+    set(
+        "relayerWhitelist".to_string(),
+        format!("{:?}", store.relayer_whitelist),
+    );
+    set(
+        "privilegedMsgAuthority".to_string(),
+        format!("{:?}", store.privileged_msg_authority),
+    );
+
+    for (client_id, client_record) in &store.clients {
+        if let Some(client_state) = &client_record.client_state {
+            set(
+                ClientStatePath::new(client_id).to_string(),
+                format!("{client_state:?}"),
+            );
+        }
+        for (height, consensus_state) in &client_record.consensus_states {
+            set(
+                ClientConsensusStatePath::new(client_id, height).to_string(),
+                format!("{consensus_state:?}"),
+            );
+        }
+    }
+    for ((client_id, height), time) in &store.client_processed_times {
+        set(
+            format!(
+                "{}/processedTime",
+                ClientConsensusStatePath::new(client_id, height)
+            ),
+            time.to_string(),
+        );
+    }
+    for ((client_id, height), host_height) in &store.client_processed_heights {
+        set(
+            format!(
+                "{}/processedHeight",
+                ClientConsensusStatePath::new(client_id, height)
+            ),
+            host_height.to_string(),
+        );
+    }
+    for (client_id, connection_id) in &store.client_connections {
+        set(
+            ClientConnectionPath::new(client_id).to_string(),
+            connection_id.to_string(),
+        );
+    }
+
+    for (connection_id, connection_end) in &store.connections {
+        set(
+            ConnectionPath::new(connection_id).to_string(),
+            format!("{connection_end:?}"),
+        );
+    }
+    for (connection_id, channels) in &store.connection_channels {
+        set(
+            format!("{}/channels", ConnectionPath::new(connection_id)),
+            format!("{channels:?}"),
+        );
+    }
+
+    for (port_id, module_id) in &store.port_owners {
+        set(PortPath::new(port_id).to_string(), module_id.to_string());
+    }
+    for (port_id, channel_id, channel_end) in port_channel_entries(&store.channels) {
+        set(
+            ChannelEndPath::new(port_id, channel_id).to_string(),
+            format!("{channel_end:?}"),
+        );
+    }
+    for (port_id, channel_id, sequence) in port_channel_entries(&store.next_sequence_send) {
+        set(
+            SeqSendPath::new(port_id, channel_id).to_string(),
+            sequence.to_string(),
+        );
+    }
+    for (port_id, channel_id, sequence) in port_channel_entries(&store.next_sequence_recv) {
+        set(
+            SeqRecvPath::new(port_id, channel_id).to_string(),
+            sequence.to_string(),
+        );
+    }
+    for (port_id, channel_id, sequence) in port_channel_entries(&store.next_sequence_ack) {
+        set(
+            SeqAckPath::new(port_id, channel_id).to_string(),
+            sequence.to_string(),
+        );
+    }
+
+    for (port_id, channel_id, commitments) in port_channel_entries(&store.packet_commitment) {
+        for (sequence, commitment) in commitments {
+            set(
+                CommitmentPath::new(port_id, channel_id, *sequence).to_string(),
+                hex(commitment.as_ref()),
+            );
+        }
+    }
+    for (port_id, channel_id, receipts) in port_channel_entries(&store.packet_receipt) {
+        for (sequence, receipt) in receipts {
+            set(
+                ReceiptPath::new(port_id, channel_id, *sequence).to_string(),
+                format!("{receipt:?}"),
+            );
+        }
+    }
+    for (port_id, channel_id, acks) in port_channel_entries(&store.packet_acknowledgement) {
+        for (sequence, ack_commitment) in acks {
+            set(
+                AckPath::new(port_id, channel_id, *sequence).to_string(),
+                hex(ack_commitment.as_ref()),
+            );
+        }
+    }
+
+    for (port_id, channel_id, packets) in port_channel_entries(&store.sent_packets) {
+        for (sequence, packet) in packets {
+            set(
+                format!(
+                    "{}/packet",
+                    CommitmentPath::new(port_id, channel_id, *sequence)
+                ),
+                format!("{packet:?}"),
+            );
+        }
+    }
+    for (port_id, channel_id, written) in port_channel_entries(&store.written_acknowledgements) {
+        for (sequence, (packet, ack)) in written {
+            set(
+                format!("{}/packet", AckPath::new(port_id, channel_id, *sequence)),
+                format!("{packet:?} with {ack:?}"),
+            );
+        }
+    }
+
+    entries
+}
+
+fn hex(bytes: &[u8]) -> String {
+    Hex::upper_case()
+        .encode_to_string(bytes)
+        .unwrap_or_else(|_| format!("{bytes:?}"))
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::ValidationContext;
+    use crate::mock::router::MockRouter;
+    use crate::mock::scenario::{transfer_router, Action, ChainSetup, PacketSpec};
+
+    /// A chain with an open channel over a mock client, on which a packet
+    /// was sent.
+    fn chain_with_packet() -> (MockContext, MockRouter) {
+        let mut router = transfer_router();
+        let mut ctx = ChainSetup::new(ChainId::new("mockgaia", 0).unwrap(), 10)
+            .with_open_transfer_channel(Height::new(0, 5).unwrap())
+            .build(&router)
+            .unwrap();
+
+        send(&mut ctx, &mut router, 1);
+        Action::AdvanceBlocks(2).run(&mut ctx, &mut router).unwrap();
+
+        (ctx, router)
+    }
+
+    fn send(ctx: &mut MockContext, router: &mut MockRouter, sequence: u64) {
+        Action::SendPacket(PacketSpec {
+            sequence,
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::default(),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::default(),
+            data: "packet".to_string(),
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: 0,
+        })
+        .run(ctx, router)
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "mocks-serde")]
+    fn snapshots_round_trip_through_json_and_bytes() {
+        let (ctx, _) = chain_with_packet();
+        let snapshot = ctx.snapshot();
+        let bytes = snapshot.to_bytes().unwrap();
+        let json = snapshot.to_json().unwrap();
+
+        let from_json = MockSnapshot::from_json(&json).unwrap();
+        let from_bytes = MockSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(from_json.to_bytes().unwrap(), bytes);
+        assert_eq!(from_bytes.to_json().unwrap(), json);
+        assert!(bytes.len() < json.len());
+
+        let mut restored = MockContext::default();
+        restored.restore(from_bytes).unwrap();
+        assert_eq!(restored.snapshot().to_bytes().unwrap(), bytes);
+        assert_eq!(restored.latest_height(), ctx.latest_height());
+        assert_eq!(restored.history, ctx.history);
+        assert_eq!(restored.events, ctx.events);
+
+        assert!(matches!(
+            MockSnapshot::from_bytes(&[1, 2, 3]),
+            Err(SnapshotError::InvalidBytes { .. })
+        ));
+    }
+
+    #[test]
+    fn snapshots_of_tendermint_hosts_round_trip() {
+        let ctx = MockContext::new(
+            ChainId::new("mockgaia", 1).unwrap(),
+            HostType::SyntheticTendermint,
+            3,
+            Height::new(1, 5).unwrap(),
+        );
+        let json = ctx.snapshot().to_json().unwrap();
+
+        let mut restored = MockContext::default();
+        restored
+            .restore(MockSnapshot::from_json(&json).unwrap())
+            .unwrap();
+        assert_eq!(restored.history, ctx.history);
+        assert_eq!(
+            restored.host_consensus_state(&ctx.latest_height()).unwrap(),
+            ctx.host_consensus_state(&ctx.latest_height()).unwrap()
+        );
+    }
+
+    #[test]
+    fn restored_contexts_continue_from_the_snapshot() {
+        let (mut ctx, mut router) = chain_with_packet();
+        let snapshot = ctx.snapshot();
+
+        send(&mut ctx, &mut router, 2);
+        ctx.restore(snapshot.clone()).unwrap();
+        assert!(snapshot.diff(&ctx.snapshot()).is_empty());
+
+        // The second packet can be sent again, from the restored state.
+        send(&mut ctx, &mut router, 2);
+    }
+
+    #[test]
+    fn diffs_list_the_changed_entries() {
+        let (mut ctx, mut router) = chain_with_packet();
+        let before = ctx.snapshot();
+        send(&mut ctx, &mut router, 2);
+
+        let diff = before.diff(&ctx.snapshot());
+        let paths: Vec<_> = diff.changes.iter().map(StoreChange::path).collect();
+        assert_eq!(
+            paths,
+            [
+                "commitments/ports/transfer/channels/channel-0/sequences/2",
+                "commitments/ports/transfer/channels/channel-0/sequences/2/packet",
+                "nextSequenceSend/ports/transfer/channels/channel-0",
+            ]
+        );
+        assert!(matches!(diff.changes[0], StoreChange::Added { .. }));
+        assert!(diff
+            .to_string()
+            .contains("~ nextSequenceSend/ports/transfer/channels/channel-0: 2 -> 3\n"));
+
+        let reverse = ctx.snapshot().diff(&before);
+        assert!(matches!(reverse.changes[0], StoreChange::Removed { .. }));
+    }
+
+    #[test]
+    fn invalid_snapshots_are_rejected() {
+        let mut snapshot = MockContext::default().snapshot();
+        snapshot.history[0].type_url = "/unknown.Header".to_string();
+        assert!(matches!(
+            MockContext::default().restore(snapshot),
+            Err(SnapshotError::InvalidHostBlock { index: 0, .. })
+        ));
+
+        let mut snapshot = MockContext::default().snapshot();
+        snapshot.history.clear();
+        assert!(matches!(
+            MockContext::default().restore(snapshot),
+            Err(SnapshotError::EmptyHistory)
+        ));
+
+        assert!(matches!(
+            MockSnapshot::from_json("{"),
+            Err(SnapshotError::InvalidJson { .. })
+        ));
+    }
+}
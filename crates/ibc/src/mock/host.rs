@@ -1,22 +1,33 @@
 //! Host chain types and methods, used by context mock.
 
+use alloc::collections::btree_map::BTreeMap;
 use core::str::FromStr;
+use core::time::Duration;
 
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::lightclients::tendermint::v1::Header as RawHeader;
 use ibc_proto::protobuf::Protobuf as ErasedProtobuf;
+use sha2::{Digest, Sha256};
 use tendermint::block::Header as TmHeader;
-use tendermint_testgen::light_block::TmLightBlock;
-use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+use tendermint::AppHash;
+use tendermint_testgen::light_block::{default_peer_id, TmLightBlock};
+use tendermint_testgen::{
+    Commit as TestgenCommit, Generator, Header as TestgenHeader, LightBlock as TestgenLightBlock,
+    Validator,
+};
 
-use super::context::AnyConsensusState;
+use super::context::{AnyConsensusState, DEFAULT_BLOCK_TIME_SECS};
+use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
 use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
-use crate::clients::ics07_tendermint::header::TENDERMINT_HEADER_TYPE_URL;
+use crate::clients::ics07_tendermint::header::{
+    Header as TmClientHeader, TENDERMINT_HEADER_TYPE_URL,
+};
+use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
 use crate::core::ics02_client::error::ClientError;
-use crate::core::ics24_host::identifier::ChainId;
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
 use crate::core::timestamp::Timestamp;
 use crate::mock::consensus_state::MockConsensusState;
-use crate::mock::header::MockHeader;
+use crate::mock::header::{MockHeader, MOCK_HEADER_TYPE_URL};
 use crate::prelude::*;
 use crate::Height;
 
@@ -25,7 +36,8 @@ use crate::Height;
 /// - `Mock` defines that the context history consists of `MockHeader` blocks.
 /// - `SyntheticTendermint`: the context has synthetically-generated Tendermint (light) blocks.
 /// See also the `HostBlock` enum to get more insights into the underlying block type.
-#[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum HostType {
     Mock,
     SyntheticTendermint,
@@ -89,6 +101,25 @@ impl HostBlock {
         chain_type: HostType,
         height: u64,
         timestamp: Timestamp,
+    ) -> HostBlock {
+        Self::generate_block_with_app_hash(
+            chain_id,
+            chain_type,
+            height,
+            timestamp,
+            AppHash::default(),
+        )
+    }
+
+    /// Generates a new block like `generate_block`, committing to the
+    /// application state whose root is `app_hash`. Mock blocks commit to no
+    /// state, and ignore it.
+    pub fn generate_block_with_app_hash(
+        chain_id: ChainId,
+        chain_type: HostType,
+        height: u64,
+        timestamp: Timestamp,
+        app_hash: AppHash,
     ) -> HostBlock {
         match chain_type {
             HostType::Mock => HostBlock::Mock(Box::new(MockHeader {
@@ -96,7 +127,7 @@ impl HostBlock {
                 timestamp,
             })),
             HostType::SyntheticTendermint => HostBlock::SyntheticTendermint(Box::new(
-                Self::generate_tm_block(chain_id, height, timestamp),
+                Self::generate_tm_block_with_app_hash(chain_id, height, timestamp, app_hash),
             )),
         }
     }
@@ -106,13 +137,29 @@ impl HostBlock {
         height: u64,
         timestamp: Timestamp,
     ) -> SyntheticTmBlock {
-        let light_block = TestgenLightBlock::new_default_with_time_and_chain_id(
-            chain_id.to_string(),
-            timestamp.into_tm_time().expect("Never fails"),
-            height,
-        )
-        .generate()
-        .expect("Never fails");
+        Self::generate_tm_block_with_app_hash(chain_id, height, timestamp, AppHash::default())
+    }
+
+    pub fn generate_tm_block_with_app_hash(
+        chain_id: ChainId,
+        height: u64,
+        timestamp: Timestamp,
+        app_hash: AppHash,
+    ) -> SyntheticTmBlock {
+        let validators = [
+            Validator::new("1").voting_power(50),
+            Validator::new("2").voting_power(50),
+        ];
+        let header = TestgenHeader::new(&validators)
+            .height(height)
+            .chain_id(&chain_id.to_string())
+            .next_validators(&validators)
+            .time(timestamp.into_tm_time().expect("Never fails"))
+            .app_hash(app_hash);
+
+        let light_block = TestgenLightBlock::new_default_with_header(header)
+            .generate()
+            .expect("Never fails");
         SyntheticTmBlock {
             trusted_height: Height::new(chain_id.revision_number(), 1).expect("Never fails"),
             light_block,
@@ -127,6 +174,213 @@ impl HostBlock {
     }
 }
 
+/// A synthetic Tendermint chain, whose blocks are signed by a validator set
+/// which may change over time.
+///
+/// Unlike the blocks of `HostBlock::generate_tm_block`, which are all signed
+/// by the same two validators, the blocks of this chain follow a schedule of
+/// validator sets: rotating the validators or changing their voting powers
+/// lets light clients be tested across validator set changes, including
+/// skipping verification which fails the trust threshold. A chain can also
+/// be forked, and the conflicting headers of a fork make misbehaviour
+/// evidence against the light clients of the chain.
+#[derive(Clone, Debug)]
+pub struct SyntheticTmChain {
+    chain_id: ChainId,
+    genesis_time: Timestamp,
+    block_time: Duration,
+    app_hash: AppHash,
+    /// The validator sets signing the blocks, keyed by the height of the first
+    /// block each of them signs.
+    validator_sets: BTreeMap<u64, Vec<Validator>>,
+    /// The blocks produced so far, the first one at height 1.
+    blocks: Vec<TmLightBlock>,
+}
+
+impl SyntheticTmChain {
+    /// Creates a chain, with no block yet, whose first block is signed by
+    /// `validators` at `genesis_time`.
+    ///
+    /// Blocks are produced every `DEFAULT_BLOCK_TIME_SECS` seconds, unless
+    /// configured otherwise with `with_block_time`.
+    pub fn new(chain_id: ChainId, validators: &[Validator], genesis_time: Timestamp) -> Self {
+        Self {
+            chain_id,
+            genesis_time,
+            block_time: Duration::from_secs(DEFAULT_BLOCK_TIME_SECS),
+            app_hash: AppHash::default(),
+            validator_sets: BTreeMap::from([(1, validators.to_vec())]),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn with_block_time(self, block_time: Duration) -> Self {
+        Self { block_time, ..self }
+    }
+
+    pub fn chain_id(&self) -> &ChainId {
+        &self.chain_id
+    }
+
+    /// Returns the height of the latest block, or `None` if no block was
+    /// produced yet.
+    pub fn latest_height(&self) -> Option<Height> {
+        (!self.blocks.is_empty()).then(|| self.height(self.blocks.len() as u64))
+    }
+
+    /// Returns the validator set signing the block at `height`.
+    pub fn validators(&self, height: u64) -> &[Validator] {
+        self.validator_sets
+            .range(..=height)
+            .next_back()
+            .map(|(_, validators)| validators.as_slice())
+            .expect("there is a validator set from height 1")
+    }
+
+    /// Schedules `validators` to sign the blocks from `height` on, until the
+    /// next scheduled change, if any.
+    ///
+    /// Since every block commits to the validators of the next one, `height`
+    /// must be past the next block.
+    pub fn set_validators(&mut self, height: u64, validators: &[Validator]) {
+        assert!(
+            height > self.blocks.len() as u64 + 1,
+            "the validators of the blocks up to height {} are already committed to",
+            self.blocks.len() + 1
+        );
+        self.validator_sets.insert(height, validators.to_vec());
+    }
+
+    /// Schedules the validator `id` to sign the blocks from `height` on with
+    /// `voting_power`, removing it from the validator set if the power is 0.
+    ///
+    /// Like any other change of the validator set, `height` must be past the
+    /// next block.
+    pub fn set_voting_power(&mut self, height: u64, id: &str, voting_power: u64) {
+        let mut validators: Vec<_> = self
+            .validators(height)
+            .iter()
+            .filter(|validator| validator.id.as_deref() != Some(id))
+            .cloned()
+            .collect();
+        if voting_power > 0 {
+            validators.push(Validator::new(id).voting_power(voting_power));
+        }
+        self.set_validators(height, &validators);
+    }
+
+    /// Produces the next block, and returns its height.
+    pub fn produce_block(&mut self) -> Height {
+        let height = self.blocks.len() as u64 + 1;
+        let time = match self.blocks.last() {
+            Some(block) => (Timestamp::from(block.signed_header.header.time) + self.block_time)
+                .expect("Never fails"),
+            None => self.genesis_time,
+        };
+        let validators = self.validators(height);
+        let next_validators = self.validators(height + 1);
+
+        let header = TestgenHeader::new(validators)
+            .height(height)
+            .chain_id(&self.chain_id.to_string())
+            .next_validators(next_validators)
+            .time(time.into_tm_time().expect("Never fails"))
+            .app_hash(self.app_hash.clone());
+        let light_block = TestgenLightBlock::new(header.clone(), TestgenCommit::new(header, 1))
+            .validators(validators)
+            .next_validators(next_validators)
+            .generate()
+            .expect("Never fails");
+        self.blocks.push(light_block);
+
+        self.height(height)
+    }
+
+    /// Produces blocks until the chain reaches `height`.
+    pub fn produce_blocks_until(&mut self, height: u64) {
+        while (self.blocks.len() as u64) < height {
+            self.produce_block();
+        }
+    }
+
+    pub fn light_block(&self, height: &Height) -> Option<&TmLightBlock> {
+        let index = height.revision_height().checked_sub(1)?;
+        (height.revision_number() == self.chain_id.revision_number())
+            .then(|| self.blocks.get(index as usize))
+            .flatten()
+    }
+
+    pub fn consensus_state(&self, height: &Height) -> Option<TmConsensusState> {
+        self.light_block(height)
+            .map(|block| TmConsensusState::from(block.signed_header.header.clone()))
+    }
+
+    /// Returns the state of a client of the chain at `height`, with the
+    /// parameters of `ClientState::new_dummy_from_header`.
+    pub fn client_state(&self, height: &Height) -> Option<TmClientState> {
+        self.light_block(height)
+            .map(|block| TmClientState::new_dummy_from_header(block.signed_header.header.clone()))
+    }
+
+    /// Returns the header updating a client of the chain from
+    /// `trusted_height` to `height`.
+    ///
+    /// The header is built for any pair of heights, even though a client
+    /// only accepts it if enough of the validators trusted at
+    /// `trusted_height` signed the block at `height`.
+    pub fn header(&self, height: &Height, trusted_height: &Height) -> Option<TmClientHeader> {
+        let block = self.light_block(height)?;
+        let trusted_block = self.light_block(trusted_height)?;
+        Some(TmClientHeader {
+            signed_header: block.signed_header.clone(),
+            validator_set: block.validators.clone(),
+            trusted_height: *trusted_height,
+            trusted_next_validator_set: trusted_block.next_validators.clone(),
+        })
+    }
+
+    /// Forks the chain before `height`: the fork shares the blocks below
+    /// `height` with the chain, and produces blocks conflicting with those
+    /// of the chain from `height` on.
+    ///
+    /// The blocks of the fork commit to a different application state, and
+    /// are signed by the same validators unless the validators of the fork
+    /// are changed. Note that two forks of a chain at the same height produce
+    /// identical blocks as long as they are not changed otherwise.
+    pub fn fork(&self, height: u64) -> Self {
+        assert!(height > 1, "the genesis block cannot be forked");
+
+        let mut fork = self.clone();
+        fork.blocks.truncate(height as usize - 1);
+        let mut hasher = Sha256::new();
+        hasher.update(self.app_hash.as_bytes());
+        hasher.update(height.to_be_bytes());
+        fork.app_hash = AppHash::try_from(hasher.finalize().to_vec()).expect("Never fails");
+        fork
+    }
+
+    /// Returns the evidence of the misbehaviour of the validators which signed
+    /// both the block of this chain and the one of `fork` at `height`, for the
+    /// client `client_id` trusting both chains at `trusted_height`.
+    pub fn misbehaviour(
+        &self,
+        fork: &Self,
+        client_id: ClientId,
+        height: &Height,
+        trusted_height: &Height,
+    ) -> Option<TmMisbehaviour> {
+        Some(TmMisbehaviour::new(
+            client_id,
+            self.header(height, trusted_height)?,
+            fork.header(height, trusted_height)?,
+        ))
+    }
+
+    fn height(&self, height: u64) -> Height {
+        Height::new(self.chain_id.revision_number(), height).expect("Never fails")
+    }
+}
+
 impl From<SyntheticTmBlock> for AnyConsensusState {
     fn from(light_block: SyntheticTmBlock) -> Self {
         let cs = TmConsensusState::from(light_block.header().clone());
@@ -150,8 +404,28 @@ impl ErasedProtobuf<Any> for HostBlock {}
 impl TryFrom<Any> for HostBlock {
     type Error = ClientError;
 
-    fn try_from(_raw: Any) -> Result<Self, Self::Error> {
-        todo!()
+    /// The peer ID of the provider of Tendermint blocks is not part of their
+    /// encoding, and is set to the one `tendermint-testgen` uses.
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            MOCK_HEADER_TYPE_URL => Ok(Self::Mock(Box::new(MockHeader::try_from(raw)?))),
+            TENDERMINT_HEADER_TYPE_URL => {
+                let header = TmClientHeader::try_from(raw)?;
+
+                Ok(Self::SyntheticTendermint(Box::new(SyntheticTmBlock {
+                    trusted_height: header.trusted_height,
+                    light_block: TmLightBlock {
+                        signed_header: header.signed_header,
+                        validators: header.validator_set,
+                        next_validators: header.trusted_next_validator_set,
+                        provider: default_peer_id(),
+                    },
+                })))
+            }
+            _ => Err(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }),
+        }
     }
 }
 
@@ -1,7 +1,8 @@
 use displaydoc::Display;
 
-use crate::core::ics24_host::identifier::ClientId;
-use crate::core::{ics03_connection, RouterError};
+use crate::core::ics24_host::identifier::{ClientId, PortId};
+use crate::core::ics24_host::path::Path;
+use crate::core::{ics03_connection, ContextError, RouterError};
 use crate::Height;
 
 #[derive(Debug, Display)]
@@ -24,6 +25,14 @@ pub enum RelayerError {
     TransactionFailed(RouterError),
     /// connection error: `{0}`
     Connection(ics03_connection::error::ConnectionError),
+    /// context error: `{0}`
+    Context(ContextError),
+    /// no proof of `{path}` at height `{height}`
+    ProofNotFound { height: Height, path: Path },
+    /// no module is routed to port `{port_id}`
+    UnroutedPort { port_id: PortId },
+    /// the `{event_type}` event was not emitted
+    MissingEvent { event_type: &'static str },
 }
 
 #[cfg(feature = "std")]
@@ -32,6 +41,7 @@ impl std::error::Error for RelayerError {
         match &self {
             Self::TransactionFailed(e) => Some(e),
             Self::Connection(e) => Some(e),
+            Self::Context(e) => Some(e),
             _ => None,
         }
     }
@@ -0,0 +1,827 @@
+//! An in-process relayer between two mock chains: it opens a channel between
+//! them, then relays the packets sent on it along with their acknowledgements
+//! and timeouts.
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenTry as RawMsgConnectionOpenTry;
+
+use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+use crate::core::events::IbcEvent;
+use crate::core::ics02_client::client_state::ClientStateCommon;
+use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::ClientMsg;
+use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics03_connection::msgs::ConnectionMsg;
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConsensusStatePath, ClientStatePath, CommitmentPath,
+    ConnectionPath, Path, ReceiptPath, SeqRecvPath,
+};
+use crate::core::router::Router;
+use crate::core::{MsgEnvelope, ValidationContext};
+use crate::mock::client_state::MockClientState;
+use crate::mock::context::{AnyClientState, AnyConsensusState, MockContext};
+use crate::mock::host::HostBlock;
+use crate::mock::ics18_relayer::context::RelayerContext;
+use crate::mock::ics18_relayer::error::RelayerError;
+use crate::prelude::*;
+use crate::Height;
+
+/// A chain relayed by a [`Relayer`]: its context and the router of its
+/// modules.
+pub struct RelayerChain<R> {
+    pub ctx: MockContext,
+    pub router: R,
+}
+
+impl<R: Router> RelayerChain<R> {
+    /// The context is switched to a provable store (see
+    /// `MockContext::with_provable_store`), so that the relayed messages carry
+    /// proofs which a Tendermint client can verify, and a block committing to
+    /// it is produced.
+    pub fn new(ctx: MockContext, router: R) -> Self {
+        let mut ctx = ctx.with_provable_store();
+        ctx.advance_host_chain_height();
+        Self { ctx, router }
+    }
+
+    /// Delivers `msg` in a new block, and returns the events it emitted.
+    fn deliver(&mut self, msg: MsgEnvelope) -> Result<Vec<IbcEvent>, RelayerError> {
+        let emitted = self.ctx.events.len();
+        self.ctx.deliver(&mut self.router, msg)?;
+        Ok(self.ctx.events[emitted..].to_vec())
+    }
+
+    fn proof(&self, height: Height, path: Path) -> Result<CommitmentProofBytes, RelayerError> {
+        self.ctx
+            .commitment_proof(&height, &path)
+            .and_then(|proof| proof.try_into().ok())
+            .ok_or(RelayerError::ProofNotFound { height, path })
+    }
+
+    /// Creates a client of `counterparty` at its latest height.
+    fn create_client(&mut self, counterparty: &MockContext) -> Result<ClientId, RelayerError> {
+        let block = counterparty
+            .query_latest_header()
+            .expect("history cannot be empty");
+        let client_state: AnyClientState = match &block {
+            HostBlock::Mock(header) => MockClientState::new(**header).into(),
+            HostBlock::SyntheticTendermint(block) => {
+                TmClientState::new_dummy_from_header(block.header().clone()).into()
+            }
+        };
+        let msg = MsgCreateClient {
+            client_state: client_state.into(),
+            consensus_state: AnyConsensusState::from(block).into(),
+            signer: self.ctx.signer(),
+        };
+
+        self.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))?
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::CreateClient(event) => Some(event.client_id().clone()),
+                _ => None,
+            })
+            .ok_or(RelayerError::MissingEvent {
+                event_type: "create_client",
+            })
+    }
+
+    /// Updates the client `client_id`, tracking `counterparty`, to the latest
+    /// height of `counterparty` if it is not there yet.
+    fn update_client(
+        &mut self,
+        client_id: &ClientId,
+        counterparty: &MockContext,
+    ) -> Result<(), RelayerError> {
+        let client_height = self
+            .ctx
+            .query_client_full_state(client_id)
+            .ok_or_else(|| RelayerError::ClientStateNotFound {
+                client_id: client_id.clone(),
+            })?
+            .latest_height();
+
+        let mut header = counterparty
+            .query_latest_header()
+            .expect("history cannot be empty");
+        if header.height() <= client_height {
+            return Ok(());
+        }
+        header.set_trusted_height(client_height);
+
+        // Headers from the future of this chain are rejected: wait for them.
+        while self.ctx.host_timestamp().map_err(RelayerError::Context)? < header.timestamp() {
+            self.ctx.advance_host_chain_height();
+        }
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message: header.into(),
+            signer: self.ctx.signer(),
+        };
+        self.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))?;
+        Ok(())
+    }
+
+    /// Binds `port_id` to the module routed to it, if it is not bound yet.
+    fn bind_port(&mut self, port_id: &PortId) -> Result<(), RelayerError> {
+        let module_id =
+            self.router
+                .lookup_module(port_id)
+                .ok_or_else(|| RelayerError::UnroutedPort {
+                    port_id: port_id.clone(),
+                })?;
+        self.ctx
+            .ibc_store
+            .lock()
+            .port_owners
+            .entry(port_id.clone())
+            .or_insert(module_id);
+        Ok(())
+    }
+}
+
+/// The identifiers, on one of the chains, of the channel opened by
+/// [`Relayer::connect`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathEnd {
+    pub client_id: ClientId,
+    pub connection_id: ConnectionId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+}
+
+/// The channel to open with [`Relayer::connect`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelSpec {
+    pub port_id_on_a: PortId,
+    pub port_id_on_b: PortId,
+    pub version: Version,
+    pub ordering: Order,
+}
+
+impl ChannelSpec {
+    /// An unordered ICS-20 channel between the transfer ports.
+    pub fn transfer() -> Self {
+        Self {
+            port_id_on_a: PortId::transfer(),
+            port_id_on_b: PortId::transfer(),
+            version: Version::new(crate::applications::transfer::VERSION.to_string()),
+            ordering: Order::Unordered,
+        }
+    }
+}
+
+/// The packets relayed by [`Relayer::relay_pending`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelayReport {
+    /// The packets received on their destination chain.
+    pub received: Vec<Packet>,
+    /// The packets whose acknowledgement was relayed back to their source
+    /// chain.
+    pub acknowledged: Vec<Packet>,
+    /// The packets timed out on their source chain.
+    pub timed_out: Vec<Packet>,
+}
+
+/// Relays between two chains over a channel it opens.
+///
+/// The relayer scans the events emitted by both chains: every packet sent on
+/// the channel is received on the other chain, or timed out if it can no
+/// longer be received, and every acknowledgement written for a packet of the
+/// channel is relayed back to its source chain. Each message is delivered in
+/// a block of its own, after the client it is verified with is updated.
+pub struct Relayer<RA, RB> {
+    pub chain_a: RelayerChain<RA>,
+    pub chain_b: RelayerChain<RB>,
+    end_a: PathEnd,
+    end_b: PathEnd,
+    /// The number of events of each chain already relayed.
+    scanned_events_a: usize,
+    scanned_events_b: usize,
+}
+
+impl<RA: Router, RB: Router> Relayer<RA, RB> {
+    /// Creates a client of each chain on the other, then opens a connection
+    /// and the `channel` between them, starting on chain A.
+    ///
+    /// The ports of the channel are bound to the modules they are routed to,
+    /// unless they are bound already.
+    pub fn connect(
+        mut chain_a: RelayerChain<RA>,
+        mut chain_b: RelayerChain<RB>,
+        channel: ChannelSpec,
+    ) -> Result<Self, RelayerError> {
+        let client_id_on_a = chain_a.create_client(&chain_b.ctx)?;
+        let client_id_on_b = chain_b.create_client(&chain_a.ctx)?;
+
+        let (connection_id_on_a, connection_id_on_b) =
+            open_connection(&mut chain_a, &client_id_on_a, &mut chain_b, &client_id_on_b)?;
+
+        let mut end_a = PathEnd {
+            client_id: client_id_on_a,
+            connection_id: connection_id_on_a,
+            port_id: channel.port_id_on_a.clone(),
+            channel_id: ChannelId::default(),
+        };
+        let mut end_b = PathEnd {
+            client_id: client_id_on_b,
+            connection_id: connection_id_on_b,
+            port_id: channel.port_id_on_b.clone(),
+            channel_id: ChannelId::default(),
+        };
+        let (channel_id_on_a, channel_id_on_b) =
+            open_channel(&mut chain_a, &end_a, &mut chain_b, &end_b, channel)?;
+        end_a.channel_id = channel_id_on_a;
+        end_b.channel_id = channel_id_on_b;
+
+        Ok(Self {
+            scanned_events_a: chain_a.ctx.events.len(),
+            scanned_events_b: chain_b.ctx.events.len(),
+            chain_a,
+            chain_b,
+            end_a,
+            end_b,
+        })
+    }
+
+    pub fn end_a(&self) -> &PathEnd {
+        &self.end_a
+    }
+
+    pub fn end_b(&self) -> &PathEnd {
+        &self.end_b
+    }
+
+    /// Relays the packets and acknowledgements of the events emitted since
+    /// the last call, until no event is left to relay on either chain.
+    ///
+    /// A new block is first produced on each chain which emitted events
+    /// outside of the relayer, to commit to the state they reflect.
+    pub fn relay_pending(&mut self) -> Result<RelayReport, RelayerError> {
+        let Self {
+            chain_a,
+            chain_b,
+            end_a,
+            end_b,
+            scanned_events_a,
+            scanned_events_b,
+        } = self;
+
+        if chain_a.ctx.events.len() > *scanned_events_a {
+            chain_a.ctx.advance_host_chain_height();
+        }
+        if chain_b.ctx.events.len() > *scanned_events_b {
+            chain_b.ctx.advance_host_chain_height();
+        }
+
+        let mut report = RelayReport::default();
+        loop {
+            if let Some(event) = chain_a.ctx.events.get(*scanned_events_a).cloned() {
+                *scanned_events_a += 1;
+                relay_event(chain_a, end_a, chain_b, end_b, event, &mut report)?;
+            } else if let Some(event) = chain_b.ctx.events.get(*scanned_events_b).cloned() {
+                *scanned_events_b += 1;
+                relay_event(chain_b, end_b, chain_a, end_a, event, &mut report)?;
+            } else {
+                return Ok(report);
+            }
+        }
+    }
+}
+
+fn open_connection<RA: Router, RB: Router>(
+    chain_a: &mut RelayerChain<RA>,
+    client_id_on_a: &ClientId,
+    chain_b: &mut RelayerChain<RB>,
+    client_id_on_b: &ClientId,
+) -> Result<(ConnectionId, ConnectionId), RelayerError> {
+    let msg = MsgConnectionOpenInit {
+        client_id_on_a: client_id_on_a.clone(),
+        counterparty: ConnectionCounterparty::new(
+            client_id_on_b.clone(),
+            None,
+            chain_b.ctx.commitment_prefix(),
+        ),
+        version: None,
+        delay_period: Duration::ZERO,
+        signer: chain_a.ctx.signer(),
+    };
+    let connection_id_on_a = chain_a
+        .deliver(MsgEnvelope::Connection(ConnectionMsg::OpenInit(msg)))?
+        .iter()
+        .find_map(|event| match event {
+            IbcEvent::OpenInitConnection(event) => Some(event.conn_id_on_a().clone()),
+            _ => None,
+        })
+        .ok_or(RelayerError::MissingEvent {
+            event_type: "connection_open_init",
+        })?;
+
+    // Both clients are updated, so that each chain has a recent consensus
+    // state of the other one to prove.
+    chain_a.update_client(client_id_on_a, &chain_b.ctx)?;
+    chain_b.update_client(client_id_on_b, &chain_a.ctx)?;
+    let proofs_height_on_a = chain_a.ctx.latest_height();
+    let client_state_of_b_on_a = chain_a
+        .ctx
+        .client_state(client_id_on_a)
+        .map_err(RelayerError::Context)?;
+    let consensus_height_of_b_on_a = client_state_of_b_on_a.latest_height();
+    let connection_end_on_a = chain_a
+        .ctx
+        .connection_end(&connection_id_on_a)
+        .map_err(RelayerError::Context)?;
+    #[allow(deprecated)]
+    let raw_msg = RawMsgConnectionOpenTry {
+        client_id: client_id_on_b.to_string(),
+        previous_connection_id: String::new(),
+        client_state: Some(client_state_of_b_on_a.into()),
+        counterparty: Some(
+            ConnectionCounterparty::new(
+                client_id_on_a.clone(),
+                Some(connection_id_on_a.clone()),
+                chain_a.ctx.commitment_prefix(),
+            )
+            .into(),
+        ),
+        delay_period: 0,
+        counterparty_versions: connection_end_on_a
+            .versions()
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect(),
+        proof_height: Some(proofs_height_on_a.into()),
+        proof_init: chain_a
+            .proof(
+                proofs_height_on_a,
+                ConnectionPath::new(&connection_id_on_a).into(),
+            )?
+            .into(),
+        proof_client: chain_a
+            .proof(
+                proofs_height_on_a,
+                ClientStatePath::new(client_id_on_a).into(),
+            )?
+            .into(),
+        proof_consensus: chain_a
+            .proof(
+                proofs_height_on_a,
+                ClientConsensusStatePath::new(client_id_on_a, &consensus_height_of_b_on_a).into(),
+            )?
+            .into(),
+        consensus_height: Some(consensus_height_of_b_on_a.into()),
+        signer: chain_b.ctx.signer().to_string(),
+        host_consensus_state_proof: Vec::new(),
+    };
+    let msg = MsgConnectionOpenTry::try_from(raw_msg).map_err(RelayerError::Connection)?;
+    let connection_id_on_b = chain_b
+        .deliver(MsgEnvelope::Connection(ConnectionMsg::OpenTry(msg)))?
+        .iter()
+        .find_map(|event| match event {
+            IbcEvent::OpenTryConnection(event) => Some(event.conn_id_on_b().clone()),
+            _ => None,
+        })
+        .ok_or(RelayerError::MissingEvent {
+            event_type: "connection_open_try",
+        })?;
+
+    chain_b.update_client(client_id_on_b, &chain_a.ctx)?;
+    chain_a.update_client(client_id_on_a, &chain_b.ctx)?;
+    let proofs_height_on_b = chain_b.ctx.latest_height();
+    let client_state_of_a_on_b = chain_b
+        .ctx
+        .client_state(client_id_on_b)
+        .map_err(RelayerError::Context)?;
+    let consensus_height_of_a_on_b = client_state_of_a_on_b.latest_height();
+    let connection_end_on_b = chain_b
+        .ctx
+        .connection_end(&connection_id_on_b)
+        .map_err(RelayerError::Context)?;
+    let msg = MsgConnectionOpenAck {
+        conn_id_on_a: connection_id_on_a.clone(),
+        conn_id_on_b: connection_id_on_b.clone(),
+        client_state_of_a_on_b: client_state_of_a_on_b.into(),
+        proof_conn_end_on_b: chain_b.proof(
+            proofs_height_on_b,
+            ConnectionPath::new(&connection_id_on_b).into(),
+        )?,
+        proof_client_state_of_a_on_b: chain_b.proof(
+            proofs_height_on_b,
+            ClientStatePath::new(client_id_on_b).into(),
+        )?,
+        proof_consensus_state_of_a_on_b: chain_b.proof(
+            proofs_height_on_b,
+            ClientConsensusStatePath::new(client_id_on_b, &consensus_height_of_a_on_b).into(),
+        )?,
+        proofs_height_on_b,
+        consensus_height_of_a_on_b,
+        version: connection_end_on_b.versions()[0].clone(),
+        signer: chain_a.ctx.signer(),
+        proof_consensus_state_of_a: None,
+    };
+    chain_a.deliver(MsgEnvelope::Connection(ConnectionMsg::OpenAck(msg)))?;
+
+    chain_b.update_client(client_id_on_b, &chain_a.ctx)?;
+    let proof_height_on_a = chain_a.ctx.latest_height();
+    let msg = MsgConnectionOpenConfirm {
+        conn_id_on_b: connection_id_on_b.clone(),
+        proof_conn_end_on_a: chain_a.proof(
+            proof_height_on_a,
+            ConnectionPath::new(&connection_id_on_a).into(),
+        )?,
+        proof_height_on_a,
+        signer: chain_b.ctx.signer(),
+    };
+    chain_b.deliver(MsgEnvelope::Connection(ConnectionMsg::OpenConfirm(msg)))?;
+
+    Ok((connection_id_on_a, connection_id_on_b))
+}
+
+/// Opens the channel between the ports of `end_a` and `end_b`, whose channel
+/// identifiers are ignored.
+fn open_channel<RA: Router, RB: Router>(
+    chain_a: &mut RelayerChain<RA>,
+    end_a: &PathEnd,
+    chain_b: &mut RelayerChain<RB>,
+    end_b: &PathEnd,
+    channel: ChannelSpec,
+) -> Result<(ChannelId, ChannelId), RelayerError> {
+    chain_a.bind_port(&end_a.port_id)?;
+    chain_b.bind_port(&end_b.port_id)?;
+
+    let msg = MsgChannelOpenInit {
+        port_id_on_a: end_a.port_id.clone(),
+        connection_hops_on_a: vec![end_a.connection_id.clone()],
+        port_id_on_b: end_b.port_id.clone(),
+        ordering: channel.ordering,
+        signer: chain_a.ctx.signer(),
+        version_proposal: channel.version,
+    };
+    let channel_id_on_a = chain_a
+        .deliver(MsgEnvelope::Channel(ChannelMsg::OpenInit(msg)))?
+        .iter()
+        .find_map(|event| match event {
+            IbcEvent::OpenInitChannel(event) => Some(event.chan_id_on_a().clone()),
+            _ => None,
+        })
+        .ok_or(RelayerError::MissingEvent {
+            event_type: "channel_open_init",
+        })?;
+    let channel_end_path_on_a = ChannelEndPath::new(&end_a.port_id, &channel_id_on_a);
+
+    chain_b.update_client(&end_b.client_id, &chain_a.ctx)?;
+    let proof_height_on_a = chain_a.ctx.latest_height();
+    let channel_end_on_a = chain_a
+        .ctx
+        .channel_end(&channel_end_path_on_a)
+        .map_err(RelayerError::Context)?;
+    #[allow(deprecated)]
+    let msg = MsgChannelOpenTry {
+        port_id_on_b: end_b.port_id.clone(),
+        connection_hops_on_b: vec![end_b.connection_id.clone()],
+        port_id_on_a: end_a.port_id.clone(),
+        chan_id_on_a: channel_id_on_a.clone(),
+        version_supported_on_a: channel_end_on_a.version().clone(),
+        proof_chan_end_on_a: chain_a
+            .proof(proof_height_on_a, channel_end_path_on_a.clone().into())?,
+        proof_height_on_a,
+        ordering: channel.ordering,
+        signer: chain_b.ctx.signer(),
+        version_proposal: Version::empty(),
+    };
+    let channel_id_on_b = chain_b
+        .deliver(MsgEnvelope::Channel(ChannelMsg::OpenTry(msg)))?
+        .iter()
+        .find_map(|event| match event {
+            IbcEvent::OpenTryChannel(event) => Some(event.chan_id_on_b().clone()),
+            _ => None,
+        })
+        .ok_or(RelayerError::MissingEvent {
+            event_type: "channel_open_try",
+        })?;
+    let channel_end_path_on_b = ChannelEndPath::new(&end_b.port_id, &channel_id_on_b);
+
+    chain_a.update_client(&end_a.client_id, &chain_b.ctx)?;
+    let proof_height_on_b = chain_b.ctx.latest_height();
+    let channel_end_on_b = chain_b
+        .ctx
+        .channel_end(&channel_end_path_on_b)
+        .map_err(RelayerError::Context)?;
+    let msg = MsgChannelOpenAck {
+        port_id_on_a: end_a.port_id.clone(),
+        chan_id_on_a: channel_id_on_a.clone(),
+        chan_id_on_b: channel_id_on_b.clone(),
+        version_on_b: channel_end_on_b.version().clone(),
+        proof_chan_end_on_b: chain_b.proof(proof_height_on_b, channel_end_path_on_b.into())?,
+        proof_height_on_b,
+        signer: chain_a.ctx.signer(),
+    };
+    chain_a.deliver(MsgEnvelope::Channel(ChannelMsg::OpenAck(msg)))?;
+
+    chain_b.update_client(&end_b.client_id, &chain_a.ctx)?;
+    let proof_height_on_a = chain_a.ctx.latest_height();
+    let msg = MsgChannelOpenConfirm {
+        port_id_on_b: end_b.port_id.clone(),
+        chan_id_on_b: channel_id_on_b.clone(),
+        proof_chan_end_on_a: chain_a.proof(proof_height_on_a, channel_end_path_on_a.into())?,
+        proof_height_on_a,
+        signer: chain_b.ctx.signer(),
+    };
+    chain_b.deliver(MsgEnvelope::Channel(ChannelMsg::OpenConfirm(msg)))?;
+
+    Ok((channel_id_on_a, channel_id_on_b))
+}
+
+/// Relays `event`, emitted by `chain`, to `counterparty` if it concerns the
+/// channel.
+fn relay_event<R: Router, RC: Router>(
+    chain: &mut RelayerChain<R>,
+    end: &PathEnd,
+    counterparty: &mut RelayerChain<RC>,
+    counterparty_end: &PathEnd,
+    event: IbcEvent,
+    report: &mut RelayReport,
+) -> Result<(), RelayerError> {
+    match event {
+        IbcEvent::SendPacket(event)
+            if event.port_id_on_a() == &end.port_id && event.chan_id_on_a() == &end.channel_id =>
+        {
+            let packet = Packet {
+                seq_on_a: *event.seq_on_a(),
+                port_id_on_a: event.port_id_on_a().clone(),
+                chan_id_on_a: event.chan_id_on_a().clone(),
+                port_id_on_b: event.port_id_on_b().clone(),
+                chan_id_on_b: event.chan_id_on_b().clone(),
+                data: event.packet_data().to_vec(),
+                timeout_height_on_b: *event.timeout_height_on_b(),
+                timeout_timestamp_on_b: *event.timeout_timestamp_on_b(),
+            };
+            relay_packet(chain, end, counterparty, counterparty_end, packet, report)
+        }
+        IbcEvent::WriteAcknowledgement(event)
+            if event.port_id_on_b() == &end.port_id && event.chan_id_on_b() == &end.channel_id =>
+        {
+            let packet = Packet {
+                seq_on_a: *event.seq_on_a(),
+                port_id_on_a: event.port_id_on_a().clone(),
+                chan_id_on_a: event.chan_id_on_a().clone(),
+                port_id_on_b: event.port_id_on_b().clone(),
+                chan_id_on_b: event.chan_id_on_b().clone(),
+                data: event.packet_data().to_vec(),
+                timeout_height_on_b: *event.timeout_height_on_b(),
+                timeout_timestamp_on_b: *event.timeout_timestamp_on_b(),
+            };
+            let acknowledgement = event.acknowledgement().clone();
+            relay_acknowledgement(
+                chain,
+                counterparty,
+                counterparty_end,
+                packet,
+                acknowledgement,
+                report,
+            )
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Relays `packet` from `source` to `destination`, or times it out on
+/// `source` if `destination` can no longer receive it.
+fn relay_packet<RS: Router, RD: Router>(
+    source: &mut RelayerChain<RS>,
+    source_end: &PathEnd,
+    destination: &mut RelayerChain<RD>,
+    destination_end: &PathEnd,
+    packet: Packet,
+    report: &mut RelayReport,
+) -> Result<(), RelayerError> {
+    destination.update_client(&destination_end.client_id, &source.ctx)?;
+
+    let host_timestamp = destination
+        .ctx
+        .host_timestamp()
+        .map_err(RelayerError::Context)?;
+    if packet.timed_out(&host_timestamp, destination.ctx.latest_height()) {
+        return timeout_packet(source, source_end, destination, packet, report);
+    }
+
+    let proof_height_on_a = source.ctx.latest_height();
+    let commitment_path_on_a =
+        CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+    let msg = MsgRecvPacket {
+        packet: packet.clone(),
+        proof_commitment_on_a: source.proof(proof_height_on_a, commitment_path_on_a.into())?,
+        proof_height_on_a,
+        signer: destination.ctx.signer(),
+    };
+    destination.deliver(MsgEnvelope::Packet(PacketMsg::Recv(msg)))?;
+
+    report.received.push(packet);
+    Ok(())
+}
+
+/// Times out `packet` on `source`, once `destination` has a block past its
+/// timeout.
+fn timeout_packet<RS: Router, RD: Router>(
+    source: &mut RelayerChain<RS>,
+    source_end: &PathEnd,
+    destination: &mut RelayerChain<RD>,
+    packet: Packet,
+    report: &mut RelayReport,
+) -> Result<(), RelayerError> {
+    while !packet.timed_out(
+        &destination
+            .ctx
+            .query_latest_header()
+            .expect("history cannot be empty")
+            .timestamp(),
+        destination.ctx.latest_height(),
+    ) {
+        destination.ctx.advance_host_chain_height();
+    }
+
+    let proof_height_on_b = destination.ctx.latest_height();
+    let channel_end_on_b = destination
+        .ctx
+        .channel_end(&ChannelEndPath::new(
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+        ))
+        .map_err(RelayerError::Context)?;
+    let seq_recv_path_on_b = SeqRecvPath::new(&packet.port_id_on_b, &packet.chan_id_on_b);
+    let next_seq_recv_on_b = destination
+        .ctx
+        .get_next_sequence_recv(&seq_recv_path_on_b)
+        .map_err(RelayerError::Context)?;
+    let unreceived_path_on_b = match channel_end_on_b.ordering() {
+        Order::Ordered => seq_recv_path_on_b.into(),
+        _ => ReceiptPath::new(&packet.port_id_on_b, &packet.chan_id_on_b, packet.seq_on_a).into(),
+    };
+    let proof_unreceived_on_b = destination.proof(proof_height_on_b, unreceived_path_on_b)?;
+
+    source.update_client(&source_end.client_id, &destination.ctx)?;
+    let msg = MsgTimeout {
+        packet: packet.clone(),
+        next_seq_recv_on_b,
+        proof_unreceived_on_b,
+        proof_height_on_b,
+        signer: source.ctx.signer(),
+    };
+    source.deliver(MsgEnvelope::Packet(PacketMsg::Timeout(msg)))?;
+
+    report.timed_out.push(packet);
+    Ok(())
+}
+
+/// Relays the `acknowledgement` of `packet`, written on `destination`, back to
+/// `source`.
+fn relay_acknowledgement<RD: Router, RS: Router>(
+    destination: &mut RelayerChain<RD>,
+    source: &mut RelayerChain<RS>,
+    source_end: &PathEnd,
+    packet: Packet,
+    acknowledgement: Acknowledgement,
+    report: &mut RelayReport,
+) -> Result<(), RelayerError> {
+    let proof_height_on_b = destination.ctx.latest_height();
+    let ack_path_on_b = AckPath::new(&packet.port_id_on_b, &packet.chan_id_on_b, packet.seq_on_a);
+    let proof_acked_on_b = destination.proof(proof_height_on_b, ack_path_on_b.into())?;
+
+    source.update_client(&source_end.client_id, &destination.ctx)?;
+    let msg = MsgAcknowledgement {
+        packet: packet.clone(),
+        acknowledgement,
+        proof_acked_on_b,
+        proof_height_on_b,
+        signer: source.ctx.signer(),
+    };
+    source.deliver(MsgEnvelope::Packet(PacketMsg::Ack(msg)))?;
+
+    report.acknowledged.push(packet);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::applications::transfer::msgs::transfer::MsgTransfer;
+    use crate::applications::transfer::{send_transfer, MODULE_ID_STR};
+    use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics24_host::identifier::ChainId;
+    use crate::core::router::ModuleId;
+    use crate::core::timestamp::Timestamp;
+    use crate::mock::host::HostType;
+    use crate::mock::router::MockRouter;
+    use crate::test_utils::DummyTransferModule;
+
+    fn transfer_chain(chain_id: &str, host_type: HostType) -> RelayerChain<MockRouter> {
+        let module_id = ModuleId::new(MODULE_ID_STR.to_string());
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+            .scope_port_to_module(PortId::transfer(), module_id)
+            .unwrap();
+
+        let ctx = MockContext::new(
+            ChainId::new(chain_id, 1).unwrap(),
+            host_type,
+            5,
+            Height::new(1, 5).unwrap(),
+        );
+        RelayerChain::new(ctx, router)
+    }
+
+    fn transfer(relayer: &mut Relayer<MockRouter, MockRouter>, timeout_height_on_b: TimeoutHeight) {
+        let mut msg = MsgTransfer::new_dummy(timeout_height_on_b, Some(Timestamp::none()));
+        msg.port_id_on_a = relayer.end_a().port_id.clone();
+        msg.chan_id_on_a = relayer.end_a().channel_id.clone();
+        send_transfer(&mut relayer.chain_a.ctx, &mut DummyTransferModule, msg).unwrap();
+    }
+
+    fn assert_no_commitment(relayer: &Relayer<MockRouter, MockRouter>, packet: &Packet) {
+        let commitment_path =
+            CommitmentPath::new(&packet.port_id_on_a, &packet.chan_id_on_a, packet.seq_on_a);
+        assert!(relayer
+            .chain_a
+            .ctx
+            .get_packet_commitment(&commitment_path)
+            .is_err());
+    }
+
+    #[test]
+    fn relay_transfers() {
+        for host_type in [HostType::Mock, HostType::SyntheticTendermint] {
+            let mut relayer = Relayer::connect(
+                transfer_chain("chaina", host_type),
+                transfer_chain("chainb", host_type),
+                ChannelSpec::transfer(),
+            )
+            .unwrap();
+
+            transfer(&mut relayer, TimeoutHeight::no_timeout());
+            transfer(&mut relayer, TimeoutHeight::no_timeout());
+            let report = relayer.relay_pending().unwrap();
+            assert_eq!(report.received.len(), 2);
+            assert_eq!(report.acknowledged, report.received);
+            assert!(report.timed_out.is_empty());
+            for packet in &report.received {
+                let ack_path =
+                    AckPath::new(&packet.port_id_on_b, &packet.chan_id_on_b, packet.seq_on_a);
+                assert!(relayer
+                    .chain_b
+                    .ctx
+                    .get_packet_acknowledgement(&ack_path)
+                    .is_ok());
+                assert_no_commitment(&relayer, packet);
+            }
+
+            // everything pending was relayed
+            assert_eq!(relayer.relay_pending().unwrap(), RelayReport::default());
+        }
+    }
+
+    #[test]
+    fn relay_timeout() {
+        let mut relayer = Relayer::connect(
+            transfer_chain("chaina", HostType::Mock),
+            transfer_chain("chainb", HostType::Mock),
+            ChannelSpec::transfer(),
+        )
+        .unwrap();
+
+        // the packet expires as soon as chain B produces a new block
+        let timeout_height_on_b = TimeoutHeight::At(relayer.chain_b.ctx.latest_height());
+        transfer(&mut relayer, timeout_height_on_b);
+        let report = relayer.relay_pending().unwrap();
+        assert!(report.received.is_empty());
+        assert!(report.acknowledged.is_empty());
+        assert_eq!(report.timed_out.len(), 1);
+        assert_no_commitment(&relayer, &report.timed_out[0]);
+    }
+}
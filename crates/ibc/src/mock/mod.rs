@@ -15,4 +15,10 @@ pub mod ics18_relayer;
 #[cfg(any(test, feature = "mocks"))]
 pub mod misbehaviour;
 #[cfg(any(test, feature = "mocks"))]
+pub mod module;
+#[cfg(any(test, feature = "mocks"))]
 pub mod router;
+#[cfg(any(test, feature = "mocks"))]
+pub mod scenario;
+#[cfg(any(test, feature = "mocks"))]
+pub mod store;
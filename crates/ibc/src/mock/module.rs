@@ -0,0 +1,524 @@
+//! A mock application module whose behaviour is scripted, callback by
+//! callback, for testing how the core handlers react to applications.
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::core::events::{ModuleEvent, ModuleEventAttribute};
+use crate::core::ics04_channel::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
+use crate::core::ics04_channel::channel::{Counterparty, Order};
+use crate::core::ics04_channel::error::{ChannelError, PacketError};
+use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::router::{Module, ModuleExtras};
+use crate::prelude::*;
+use crate::signer::Signer;
+
+/// The kind of the events a [`ScriptedModule`] emits.
+pub const SCRIPTED_EVENT_KIND: &str = "scripted_module";
+
+/// The callbacks of the [`Module`] trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Callback {
+    ChanOpenInitValidate,
+    ChanOpenInitExecute,
+    ChanOpenTryValidate,
+    ChanOpenTryExecute,
+    ChanOpenAckValidate,
+    ChanOpenAckExecute,
+    ChanOpenConfirmValidate,
+    ChanOpenConfirmExecute,
+    ChanCloseInitValidate,
+    ChanCloseInitExecute,
+    ChanCloseConfirmValidate,
+    ChanCloseConfirmExecute,
+    RecvPacketExecute,
+    AcknowledgementPacketValidate,
+    AcknowledgementPacketExecute,
+    TimeoutPacketValidate,
+    TimeoutPacketExecute,
+}
+
+/// The arguments a callback was invoked with, besides the module itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallbackArgs {
+    /// The arguments of the `ChanOpenInit` and `ChanOpenTry` callbacks, whose
+    /// version is the counterparty one for the latter.
+    ChanOpen {
+        order: Order,
+        connection_hops: Vec<ConnectionId>,
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty: Counterparty,
+        version: Version,
+    },
+    ChanOpenAck {
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_version: Version,
+    },
+    /// The arguments of the `ChanOpenConfirm`, `ChanCloseInit` and
+    /// `ChanCloseConfirm` callbacks.
+    Channel {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    /// The arguments of the `RecvPacket` and `TimeoutPacket` callbacks.
+    Packet { packet: Packet, relayer: Signer },
+    AcknowledgementPacket {
+        packet: Packet,
+        acknowledgement: Acknowledgement,
+        relayer: Signer,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invocation {
+    pub callback: Callback,
+    pub args: CallbackArgs,
+}
+
+/// A [`Module`] which, by default, accepts every channel on the version it is
+/// proposed, acknowledges every packet successfully and emits no event.
+///
+/// Its behaviour can be changed callback by callback: versions can be
+/// rejected, callbacks made to fail, packets acknowledged with any
+/// acknowledgement, and execution callbacks made to emit events. Every
+/// invocation is recorded, with its arguments.
+///
+/// Clones share their script and their records, so a test can keep a clone
+/// of the module it adds to a router to inspect it afterwards.
+///
+/// Acknowledgements are always written synchronously: the [`Module`] trait has
+/// no way of deferring them.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedModule {
+    state: Arc<Mutex<ScriptState>>,
+}
+
+#[derive(Debug, Default)]
+struct ScriptState {
+    rejected_versions: Vec<Version>,
+    failures: BTreeMap<Callback, String>,
+    acknowledgement: Option<Acknowledgement>,
+    events_per_execution: usize,
+    invocations: Vec<Invocation>,
+    events_emitted: usize,
+}
+
+impl ScriptedModule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects the channels opened, tried or acknowledged on `version`.
+    pub fn reject_version(self, version: Version) -> Self {
+        self.state.lock().rejected_versions.push(version);
+        self
+    }
+
+    /// Makes `callback` fail with `description`. As `RecvPacketExecute`
+    /// cannot fail, it acknowledges packets with an error acknowledgement
+    /// holding `description` instead.
+    pub fn fail(self, callback: Callback, description: impl ToString) -> Self {
+        self.state
+            .lock()
+            .failures
+            .insert(callback, description.to_string());
+        self
+    }
+
+    /// Acknowledges the received packets with `acknowledgement`.
+    pub fn with_acknowledgement(self, acknowledgement: Acknowledgement) -> Self {
+        self.state.lock().acknowledgement = Some(acknowledgement);
+        self
+    }
+
+    /// Makes each successful execution callback emit `count` events of kind
+    /// [`SCRIPTED_EVENT_KIND`].
+    pub fn with_events(self, count: usize) -> Self {
+        self.state.lock().events_per_execution = count;
+        self
+    }
+
+    /// Returns all the invocations so far, in order.
+    pub fn invocations(&self) -> Vec<Invocation> {
+        self.state.lock().invocations.clone()
+    }
+
+    /// Returns the callbacks invoked so far, in order.
+    pub fn callbacks(&self) -> Vec<Callback> {
+        self.state
+            .lock()
+            .invocations
+            .iter()
+            .map(|invocation| invocation.callback)
+            .collect()
+    }
+
+    /// Returns the number of events emitted so far.
+    pub fn events_emitted(&self) -> usize {
+        self.state.lock().events_emitted
+    }
+
+    /// Records the invocation, and returns the description of the failure
+    /// scripted for `callback`, if any.
+    fn invoke(&self, callback: Callback, args: CallbackArgs) -> Result<(), String> {
+        let mut state = self.state.lock();
+        state.invocations.push(Invocation { callback, args });
+
+        match state.failures.get(&callback) {
+            Some(description) => Err(description.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn invoke_chan_open(
+        &self,
+        callback: Callback,
+        args: CallbackArgs,
+        version: &Version,
+    ) -> Result<(), ChannelError> {
+        self.invoke(callback, args)
+            .map_err(|description| ChannelError::AppModule { description })?;
+
+        if self.state.lock().rejected_versions.contains(version) {
+            return Err(ChannelError::AppModule {
+                description: format!("version `{version}` is not supported"),
+            });
+        }
+        Ok(())
+    }
+
+    fn invoke_channel(
+        &self,
+        callback: Callback,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        let args = CallbackArgs::Channel {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+        };
+        self.invoke(callback, args)
+            .map_err(|description| ChannelError::AppModule { description })
+    }
+
+    fn invoke_packet_execute(
+        &self,
+        callback: Callback,
+        args: CallbackArgs,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        match self.invoke(callback, args) {
+            Ok(()) => (self.extras(callback), Ok(())),
+            Err(description) => (
+                ModuleExtras::empty(),
+                Err(PacketError::AppModule { description }),
+            ),
+        }
+    }
+
+    /// Returns the extras of a successful execution callback.
+    fn extras(&self, callback: Callback) -> ModuleExtras {
+        let mut state = self.state.lock();
+        let count = state.events_per_execution;
+        state.events_emitted += count;
+
+        ModuleExtras {
+            events: (0..count)
+                .map(|index| ModuleEvent {
+                    kind: SCRIPTED_EVENT_KIND.to_string(),
+                    attributes: vec![
+                        ModuleEventAttribute::from(("callback", format!("{callback:?}"))),
+                        ModuleEventAttribute::from(("index", index)),
+                    ],
+                })
+                .collect(),
+            log: vec![format!("{callback:?}")],
+        }
+    }
+}
+
+impl Module for ScriptedModule {
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        let args = chan_open_args(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        );
+        self.invoke_chan_open(Callback::ChanOpenInitValidate, args, version)?;
+        Ok(version.clone())
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let args = chan_open_args(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            version,
+        );
+        self.invoke_chan_open(Callback::ChanOpenInitExecute, args, version)?;
+        Ok((self.extras(Callback::ChanOpenInitExecute), version.clone()))
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        let args = chan_open_args(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            counterparty_version,
+        );
+        self.invoke_chan_open(Callback::ChanOpenTryValidate, args, counterparty_version)?;
+        Ok(counterparty_version.clone())
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        let args = chan_open_args(
+            order,
+            connection_hops,
+            port_id,
+            channel_id,
+            counterparty,
+            counterparty_version,
+        );
+        self.invoke_chan_open(Callback::ChanOpenTryExecute, args, counterparty_version)?;
+        Ok((
+            self.extras(Callback::ChanOpenTryExecute),
+            counterparty_version.clone(),
+        ))
+    }
+
+    fn on_chan_open_ack_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        let args = chan_open_ack_args(port_id, channel_id, counterparty_version);
+        self.invoke_chan_open(Callback::ChanOpenAckValidate, args, counterparty_version)
+    }
+
+    fn on_chan_open_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        let args = chan_open_ack_args(port_id, channel_id, counterparty_version);
+        self.invoke_chan_open(Callback::ChanOpenAckExecute, args, counterparty_version)?;
+        Ok(self.extras(Callback::ChanOpenAckExecute))
+    }
+
+    fn on_chan_open_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.invoke_channel(Callback::ChanOpenConfirmValidate, port_id, channel_id)
+    }
+
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.invoke_channel(Callback::ChanOpenConfirmExecute, port_id, channel_id)?;
+        Ok(self.extras(Callback::ChanOpenConfirmExecute))
+    }
+
+    fn on_chan_close_init_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.invoke_channel(Callback::ChanCloseInitValidate, port_id, channel_id)
+    }
+
+    fn on_chan_close_init_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.invoke_channel(Callback::ChanCloseInitExecute, port_id, channel_id)?;
+        Ok(self.extras(Callback::ChanCloseInitExecute))
+    }
+
+    fn on_chan_close_confirm_validate(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        self.invoke_channel(Callback::ChanCloseConfirmValidate, port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        self.invoke_channel(Callback::ChanCloseConfirmExecute, port_id, channel_id)?;
+        Ok(self.extras(Callback::ChanCloseConfirmExecute))
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        let args = packet_args(packet, relayer);
+        let acknowledgement = match self.invoke(Callback::RecvPacketExecute, args) {
+            Ok(()) => self
+                .state
+                .lock()
+                .acknowledgement
+                .clone()
+                .unwrap_or_else(|| status_acknowledgement(AcknowledgementStatus::success, "AQ==")),
+            Err(description) => status_acknowledgement(AcknowledgementStatus::error, description),
+        };
+
+        (self.extras(Callback::RecvPacketExecute), acknowledgement)
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        let args = acknowledgement_packet_args(packet, acknowledgement, relayer);
+        self.invoke(Callback::AcknowledgementPacketValidate, args)
+            .map_err(|description| PacketError::AppModule { description })
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        let args = acknowledgement_packet_args(packet, acknowledgement, relayer);
+        self.invoke_packet_execute(Callback::AcknowledgementPacketExecute, args)
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        self.invoke(
+            Callback::TimeoutPacketValidate,
+            packet_args(packet, relayer),
+        )
+        .map_err(|description| PacketError::AppModule { description })
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.invoke_packet_execute(Callback::TimeoutPacketExecute, packet_args(packet, relayer))
+    }
+}
+
+fn chan_open_args(
+    order: Order,
+    connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty: &Counterparty,
+    version: &Version,
+) -> CallbackArgs {
+    CallbackArgs::ChanOpen {
+        order,
+        connection_hops: connection_hops.to_vec(),
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        counterparty: counterparty.clone(),
+        version: version.clone(),
+    }
+}
+
+fn chan_open_ack_args(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_version: &Version,
+) -> CallbackArgs {
+    CallbackArgs::ChanOpenAck {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        counterparty_version: counterparty_version.clone(),
+    }
+}
+
+fn packet_args(packet: &Packet, relayer: &Signer) -> CallbackArgs {
+    CallbackArgs::Packet {
+        packet: packet.clone(),
+        relayer: relayer.clone(),
+    }
+}
+
+fn acknowledgement_packet_args(
+    packet: &Packet,
+    acknowledgement: &Acknowledgement,
+    relayer: &Signer,
+) -> CallbackArgs {
+    CallbackArgs::AcknowledgementPacket {
+        packet: packet.clone(),
+        acknowledgement: acknowledgement.clone(),
+        relayer: relayer.clone(),
+    }
+}
+
+fn status_acknowledgement(
+    status: fn(StatusValue) -> AcknowledgementStatus,
+    value: impl ToString,
+) -> Acknowledgement {
+    let value = StatusValue::new(value)
+        .unwrap_or_else(|_| StatusValue::new("empty description").expect("the value is not empty"));
+    status(value).into()
+}
@@ -1,16 +1,11 @@
-use alloc::collections::BTreeMap;
-use alloc::sync::Arc;
-
 use crate::core::ics24_host::identifier::PortId;
-use crate::core::router::{Module, ModuleId, Router};
+use crate::core::router::{Module, ModuleId, ModuleRouter, ModuleRouterError, Router};
 use crate::prelude::*;
 
-#[derive(Default)]
+/// A [`Router`] for tests, built on top of [`ModuleRouter`]
+#[derive(Debug, Default)]
 pub struct MockRouter {
-    router: BTreeMap<ModuleId, Arc<dyn Module>>,
-
-    /// Maps ports to the the module that owns it
-    pub port_to_module: BTreeMap<PortId, ModuleId>,
+    router: ModuleRouter,
 }
 
 impl MockRouter {
@@ -18,39 +13,42 @@ impl MockRouter {
         &mut self,
         module_id: ModuleId,
         module: impl Module + 'static,
-    ) -> Result<(), String> {
-        match self.router.insert(module_id, Arc::new(module)) {
-            None => Ok(()),
-            Some(_) => Err("Duplicate module_id".to_owned()),
-        }
+    ) -> Result<(), ModuleRouterError> {
+        self.router.add_module(module_id, module)
+    }
+
+    pub fn scope_port_to_module(
+        &mut self,
+        port_id: PortId,
+        module_id: ModuleId,
+    ) -> Result<(), ModuleRouterError> {
+        self.router.add_port_route(port_id, module_id)
+    }
+
+    pub fn scope_port_prefix_to_module(
+        &mut self,
+        prefix: String,
+        module_id: ModuleId,
+    ) -> Result<(), ModuleRouterError> {
+        self.router.add_port_prefix_route(prefix, module_id)
     }
 
-    pub fn scope_port_to_module(&mut self, port_id: PortId, module_id: ModuleId) {
-        self.port_to_module.insert(port_id, module_id);
+    /// Returns the identifiers of all the registered modules.
+    pub fn module_ids(&self) -> impl Iterator<Item = &ModuleId> {
+        self.router.module_ids()
     }
 }
 
 impl Router for MockRouter {
     fn get_route(&self, module_id: &ModuleId) -> Option<&dyn Module> {
-        self.router.get(module_id).map(Arc::as_ref)
+        self.router.get_route(module_id)
     }
-    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
-        // NOTE: The following:
 
-        // self.router.get_mut(module_id).and_then(Arc::get_mut)
-
-        // doesn't work due to a compiler bug. So we expand it out manually.
-
-        match self.router.get_mut(module_id) {
-            Some(arc_mod) => match Arc::get_mut(arc_mod) {
-                Some(m) => Some(m),
-                None => None,
-            },
-            None => None,
-        }
+    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
+        self.router.get_route_mut(module_id)
     }
 
     fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId> {
-        self.port_to_module.get(port_id).cloned()
+        self.router.lookup_module(port_id)
     }
 }
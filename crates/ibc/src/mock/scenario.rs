@@ -0,0 +1,1194 @@
+//! Declarative scenarios for handler tests.
+//!
+//! A [`Scenario`] describes the state the chains start from (their clients,
+//! connections and channels) and the steps to run on them: messages to
+//! deliver, packets to send and blocks to produce, each with its expected
+//! outcome. Scenarios are built with the builders of this module or, with the
+//! `serde` feature, loaded from JSON files, so that protocol edge cases can be
+//! captured as data.
+//!
+//! Messages are described with [`ScenarioMsg`], from which the runner builds
+//! the full messages: proofs are dummy ones, which the mock clients accept,
+//! at the latest height of the client they are verified with.
+
+use alloc::collections::btree_map::BTreeMap;
+use core::time::Duration;
+
+use displaydoc::Display;
+use ibc_proto::ibc::core::connection::v1::MsgConnectionOpenTry as RawMsgConnectionOpenTry;
+
+use crate::core::events::{IbcEvent, MESSAGE_EVENT};
+use crate::core::ics02_client::client_state::ClientStateCommon;
+use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use crate::core::ics02_client::msgs::ClientMsg;
+use crate::core::ics02_client::ClientExecutionContext;
+use crate::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics03_connection::msgs::ConnectionMsg;
+use crate::core::ics03_connection::version::get_compatible_versions;
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
+use crate::core::ics04_channel::channel::{
+    ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+};
+use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::Version;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{ChannelEndPath, ClientConsensusStatePath, ClientStatePath};
+use crate::core::router::{ModuleId, Router};
+use crate::core::timestamp::Timestamp;
+use crate::core::{dispatch, send_packet, ExecutionContext, MsgEnvelope, ValidationContext};
+use crate::mock::client_state::MockClientState;
+use crate::mock::consensus_state::MockConsensusState;
+use crate::mock::context::MockContext;
+use crate::mock::header::MockHeader;
+use crate::mock::host::HostType;
+use crate::mock::ics18_relayer::context::RelayerContext;
+use crate::mock::router::MockRouter;
+use crate::prelude::*;
+use crate::test_utils::DummyTransferModule;
+use crate::Height;
+
+/// A set of chains and the steps to run on them.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct Scenario {
+    pub chains: Vec<ChainSetup>,
+    pub steps: Vec<Step>,
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a scenario from its JSON representation.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, ScenarioError> {
+        serde_json::from_str(json).map_err(|e| ScenarioError::InvalidJson {
+            reason: e.to_string(),
+        })
+    }
+
+    pub fn with_chain(mut self, chain: ChainSetup) -> Self {
+        self.chains.push(chain);
+        self
+    }
+
+    pub fn with_step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Runs the scenario with, on each chain, the transfer port routed to a
+    /// `DummyTransferModule`, and returns the chains in their final state.
+    pub fn run(&self) -> Result<BTreeMap<ChainId, MockContext>, ScenarioError> {
+        self.run_with_routers(|_| transfer_router())
+    }
+
+    /// Runs the scenario with the routers returned by `router_of` for each
+    /// chain, and returns the chains in their final state.
+    ///
+    /// The ports of the channels are bound to the modules routed to them
+    /// before the first message on them is delivered.
+    pub fn run_with_routers(
+        &self,
+        mut router_of: impl FnMut(&ChainId) -> MockRouter,
+    ) -> Result<BTreeMap<ChainId, MockContext>, ScenarioError> {
+        let mut chains = BTreeMap::new();
+        for setup in &self.chains {
+            let router = router_of(&setup.chain_id);
+            let ctx = setup.build(&router)?;
+            chains.insert(setup.chain_id.clone(), (ctx, router));
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let (ctx, router) =
+                chains
+                    .get_mut(&step.chain)
+                    .ok_or_else(|| ScenarioError::UnknownChain {
+                        chain_id: step.chain.clone(),
+                    })?;
+            let emitted = ctx.events.len();
+            let result = step.action.run(ctx, router);
+            step.expect.check(index, result, &ctx.events[emitted..])?;
+        }
+
+        Ok(chains
+            .into_iter()
+            .map(|(chain_id, (ctx, _))| (chain_id, ctx))
+            .collect())
+    }
+}
+
+/// Returns a router with the transfer port routed to a `DummyTransferModule`,
+/// the one [`Scenario::run`] gives each chain.
+pub fn transfer_router() -> MockRouter {
+    let module_id = ModuleId::new(crate::applications::transfer::MODULE_ID_STR.into());
+    let mut router = MockRouter::default();
+    router
+        .add_route(module_id.clone(), DummyTransferModule::new())
+        .expect("the router is empty");
+    router
+        .scope_port_to_module(PortId::transfer(), module_id)
+        .expect("the router is empty");
+    router
+}
+
+/// The initial state of a mock chain.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ChainSetup {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_string"))]
+    pub chain_id: ChainId,
+    /// The height of the chain, in the revision of its identifier.
+    pub height: u64,
+    /// The timestamp of the latest block of the chain in nanoseconds, 0 for
+    /// the current time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timestamp: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub clients: Vec<ClientSetup>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub connections: Vec<ConnectionSetup>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub channels: Vec<ChannelSetup>,
+}
+
+impl ChainSetup {
+    pub fn new(chain_id: ChainId, height: u64) -> Self {
+        Self {
+            chain_id,
+            height,
+            timestamp: 0,
+            clients: Vec::new(),
+            connections: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    /// Sets the timestamp of the latest block of the chain, so that its blocks
+    /// are the same across runs.
+    pub fn with_timestamp(self, timestamp: Timestamp) -> Self {
+        Self {
+            timestamp: timestamp.nanoseconds(),
+            ..self
+        }
+    }
+
+    pub fn with_client(mut self, client: ClientSetup) -> Self {
+        self.clients.push(client);
+        self
+    }
+
+    pub fn with_connection(mut self, connection: ConnectionSetup) -> Self {
+        self.connections.push(connection);
+        self
+    }
+
+    pub fn with_channel(mut self, channel: ChannelSetup) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// Adds the mock client `9999-mock-0` at `client_height`, and the open
+    /// connection `connection-0` over it to the same client on the
+    /// counterparty.
+    pub fn with_open_connection(self, client_height: Height) -> Self {
+        let client_id = ClientId::new(crate::mock::client_state::client_type(), 0)
+            .expect("the mock client type is valid");
+        self.with_client(ClientSetup {
+            client_id: client_id.clone(),
+            height: client_height,
+        })
+        .with_connection(ConnectionSetup {
+            connection_id: ConnectionId::new(0),
+            state: ConnectionState::Open,
+            client_id: client_id.clone(),
+            counterparty_client_id: client_id,
+            counterparty_connection_id: Some(ConnectionId::new(0)),
+        })
+    }
+
+    /// Adds the unordered `ics20-1` channel `transfer/channel-0`, in `state`,
+    /// over `connection-0` to the same channel on the counterparty.
+    pub fn with_transfer_channel(self, state: ChannelState) -> Self {
+        self.with_channel(ChannelSetup {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            state,
+            ordering: Order::Unordered,
+            version: Version::new("ics20-1".to_string()),
+            connection_id: ConnectionId::new(0),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: Some(ChannelId::new(0)),
+        })
+    }
+
+    /// Adds the open transfer channel of `with_transfer_channel`, over the
+    /// connection and the mock client of `with_open_connection`.
+    pub fn with_open_transfer_channel(self, client_height: Height) -> Self {
+        self.with_open_connection(client_height)
+            .with_transfer_channel(ChannelState::Open)
+    }
+
+    /// Builds the chain, binding the ports of its channels to the modules
+    /// `router` routes them to.
+    pub fn build(&self, router: &MockRouter) -> Result<MockContext, ScenarioError> {
+        let invalid_setup = |reason: String| ScenarioError::InvalidSetup {
+            chain_id: self.chain_id.clone(),
+            reason,
+        };
+
+        let height = Height::new(self.chain_id.revision_number(), self.height)
+            .map_err(|e| invalid_setup(e.to_string()))?;
+        let mut ctx = MockContext::new(self.chain_id.clone(), HostType::Mock, 5, height);
+        let timestamp = (self.timestamp != 0)
+            .then(|| Timestamp::from_nanoseconds(self.timestamp))
+            .transpose()
+            .map_err(|e| invalid_setup(e.to_string()))?;
+        if let Some(timestamp) = timestamp {
+            ctx = ctx.with_latest_timestamp(timestamp);
+        }
+
+        for client in &self.clients {
+            ctx = ctx.with_client(&client.client_id, client.height);
+            if let Some(timestamp) = timestamp {
+                // The states of the client are at the time of the chain,
+                // rather than at the current time.
+                let header = MockHeader::new(client.height).with_timestamp(timestamp);
+                ctx.store_client_state(
+                    ClientStatePath::new(&client.client_id),
+                    MockClientState::new(header).into(),
+                )
+                .and_then(|_| {
+                    ctx.store_consensus_state(
+                        ClientConsensusStatePath::new(&client.client_id, &client.height),
+                        MockConsensusState::new(header).into(),
+                    )
+                })
+                .map_err(|e| invalid_setup(e.to_string()))?;
+            }
+
+            // Proofs at the height of the client are only accepted once it
+            // has been processed.
+            let host_timestamp = ctx
+                .host_timestamp()
+                .map_err(|e| invalid_setup(e.to_string()))?;
+            ctx.store_update_time(client.client_id.clone(), client.height, host_timestamp)
+                .and_then(|_| {
+                    ctx.store_update_height(client.client_id.clone(), client.height, height)
+                })
+                .map_err(|e| invalid_setup(e.to_string()))?;
+        }
+
+        for connection in &self.connections {
+            let connection_end = ConnectionEnd::new(
+                connection.state,
+                connection.client_id.clone(),
+                ConnectionCounterparty::new(
+                    connection.counterparty_client_id.clone(),
+                    connection.counterparty_connection_id.clone(),
+                    ctx.commitment_prefix(),
+                ),
+                get_compatible_versions(),
+                Duration::ZERO,
+            )
+            .map_err(|e| invalid_setup(e.to_string()))?;
+            ctx = ctx.with_connection(connection.connection_id.clone(), connection_end);
+        }
+
+        for channel in &self.channels {
+            let channel_end = ChannelEnd::new(
+                channel.state,
+                channel.ordering,
+                ChannelCounterparty::new(
+                    channel.counterparty_port_id.clone(),
+                    channel.counterparty_channel_id.clone(),
+                ),
+                vec![channel.connection_id.clone()],
+                channel.version.clone(),
+            )
+            .map_err(|e| invalid_setup(e.to_string()))?;
+            let (port_id, channel_id) = (channel.port_id.clone(), channel.channel_id.clone());
+            ctx = ctx
+                .with_channel(port_id.clone(), channel_id.clone(), channel_end)
+                .with_send_sequence(port_id.clone(), channel_id.clone(), Sequence::from(1))
+                .with_recv_sequence(port_id.clone(), channel_id.clone(), Sequence::from(1))
+                .with_ack_sequence(port_id.clone(), channel_id, Sequence::from(1));
+            bind_port(&mut ctx, router, &port_id);
+        }
+
+        Ok(ctx)
+    }
+}
+
+/// A mock client, with its consensus state at `height`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ClientSetup {
+    pub client_id: ClientId,
+    pub height: Height,
+}
+
+/// A connection on the version of `get_compatible_versions`, without delay.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ConnectionSetup {
+    pub connection_id: ConnectionId,
+    pub state: ConnectionState,
+    pub client_id: ClientId,
+    pub counterparty_client_id: ClientId,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub counterparty_connection_id: Option<ConnectionId>,
+}
+
+/// A channel over a single connection, whose next sequences are 1.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ChannelSetup {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub state: ChannelState,
+    pub ordering: Order,
+    pub version: Version,
+    pub connection_id: ConnectionId,
+    pub counterparty_port_id: PortId,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub counterparty_channel_id: Option<ChannelId>,
+}
+
+/// An action to run on a chain, and its expected outcome.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Step {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serializers::serde_string"))]
+    pub chain: ChainId,
+    pub action: Action,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub expect: Expect,
+}
+
+impl Step {
+    /// Delivers `msg` on `chain`, expecting it to succeed.
+    pub fn deliver(chain: ChainId, msg: impl Into<ScenarioMsg>) -> Self {
+        Self {
+            chain,
+            action: Action::Deliver(msg.into()),
+            expect: Expect::Success,
+        }
+    }
+
+    /// Sends `packet` from `chain`, expecting it to succeed.
+    pub fn send_packet(chain: ChainId, packet: PacketSpec) -> Self {
+        Self {
+            chain,
+            action: Action::SendPacket(packet),
+            expect: Expect::Success,
+        }
+    }
+
+    /// Produces `blocks` blocks on `chain`.
+    pub fn advance_blocks(chain: ChainId, blocks: u64) -> Self {
+        Self {
+            chain,
+            action: Action::AdvanceBlocks(blocks),
+            expect: Expect::Success,
+        }
+    }
+
+    /// Expects the step to succeed, emitting events of the given types.
+    pub fn expect_events<T: Into<String>>(self, event_types: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            expect: Expect::Events(event_types.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Expects the step to fail with an error whose message contains
+    /// `error`.
+    pub fn expect_error(self, error: impl Into<String>) -> Self {
+        Self {
+            expect: Expect::Error(error.into()),
+            ..self
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Debug)]
+pub enum Action {
+    /// Delivers a message in a block of its own.
+    Deliver(ScenarioMsg),
+    /// Sends a packet, as an application would, without producing a block.
+    SendPacket(PacketSpec),
+    /// Produces the given number of blocks.
+    AdvanceBlocks(u64),
+}
+
+impl Action {
+    /// Runs the action on `ctx`, dispatching messages with `router`.
+    pub fn run(&self, ctx: &mut MockContext, router: &mut MockRouter) -> Result<(), String> {
+        match self {
+            Self::Deliver(msg) => {
+                let msg = msg.build(ctx, router)?;
+                dispatch(ctx, router, msg).map_err(|e| e.to_string())?;
+                ctx.advance_host_chain_height();
+            }
+            Self::SendPacket(packet) => {
+                send_packet(ctx, packet.build()?).map_err(|e| e.to_string())?;
+            }
+            Self::AdvanceBlocks(blocks) => {
+                for _ in 0..*blocks {
+                    ctx.advance_host_chain_height();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The expected outcome of a step.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expect {
+    /// The step succeeds.
+    Success,
+    /// The step succeeds, and emits events of these types, in this order.
+    /// `message` events are left out.
+    Events(Vec<String>),
+    /// The step fails with an error whose message contains this string.
+    Error(String),
+}
+
+impl Default for Expect {
+    fn default() -> Self {
+        Self::Success
+    }
+}
+
+impl Expect {
+    fn check(
+        &self,
+        step: usize,
+        result: Result<(), String>,
+        events: &[IbcEvent],
+    ) -> Result<(), ScenarioError> {
+        match (self, result) {
+            (Self::Success, Ok(())) => Ok(()),
+            (Self::Events(expected), Ok(())) => {
+                let actual: Vec<String> = events
+                    .iter()
+                    .map(IbcEvent::event_type)
+                    .filter(|event_type| *event_type != MESSAGE_EVENT)
+                    .map(ToString::to_string)
+                    .collect();
+                if &actual == expected {
+                    Ok(())
+                } else {
+                    Err(ScenarioError::UnexpectedEvents {
+                        step,
+                        expected: expected.clone(),
+                        actual,
+                    })
+                }
+            }
+            (Self::Success | Self::Events(_), Err(error)) => {
+                Err(ScenarioError::UnexpectedFailure { step, error })
+            }
+            (Self::Error(expected), Ok(())) => Err(ScenarioError::UnexpectedSuccess {
+                step,
+                expected: expected.clone(),
+            }),
+            (Self::Error(expected), Err(error)) => {
+                if error.contains(expected.as_str()) {
+                    Ok(())
+                } else {
+                    Err(ScenarioError::UnexpectedError {
+                        step,
+                        expected: expected.clone(),
+                        error,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// A message to deliver on a chain, whose identifiers are those of that
+/// chain, with the counterparty ones prefixed as such.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Debug)]
+pub enum ScenarioMsg {
+    /// Creates a mock client at `height`.
+    CreateClient {
+        height: Height,
+    },
+    /// Updates a mock client to `height`.
+    UpdateClient {
+        client_id: ClientId,
+        height: Height,
+    },
+    ConnOpenInit {
+        client_id: ClientId,
+        counterparty_client_id: ClientId,
+    },
+    ConnOpenTry {
+        client_id: ClientId,
+        counterparty_client_id: ClientId,
+        counterparty_connection_id: ConnectionId,
+    },
+    ConnOpenAck {
+        connection_id: ConnectionId,
+        counterparty_connection_id: ConnectionId,
+    },
+    ConnOpenConfirm {
+        connection_id: ConnectionId,
+    },
+    ChanOpenInit {
+        port_id: PortId,
+        connection_id: ConnectionId,
+        counterparty_port_id: PortId,
+        ordering: Order,
+        version: Version,
+    },
+    ChanOpenTry {
+        port_id: PortId,
+        connection_id: ConnectionId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        ordering: Order,
+        version: Version,
+    },
+    ChanOpenAck {
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_channel_id: ChannelId,
+        counterparty_version: Version,
+    },
+    ChanOpenConfirm {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    ChanCloseInit {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    ChanCloseConfirm {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    /// Receives a packet sent by the counterparty.
+    RecvPacket(PacketSpec),
+    /// Acknowledges a packet sent by this chain with `acknowledgement`.
+    Acknowledgement {
+        packet: PacketSpec,
+        acknowledgement: String,
+    },
+    /// Times out a packet sent by this chain, which the counterparty did not
+    /// receive.
+    Timeout {
+        packet: PacketSpec,
+        next_sequence_recv: u64,
+    },
+    /// Any other message, delivered as is. Not available in data files.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Envelope(MsgEnvelope),
+}
+
+impl From<MsgEnvelope> for ScenarioMsg {
+    fn from(msg: MsgEnvelope) -> Self {
+        Self::Envelope(msg)
+    }
+}
+
+impl ScenarioMsg {
+    /// Builds the message to deliver on `ctx`. The ports of channel messages
+    /// are bound first, if they are routed.
+    fn build(&self, ctx: &mut MockContext, router: &MockRouter) -> Result<MsgEnvelope, String> {
+        let signer = ctx.signer();
+        let proof = || CommitmentProofBytes::try_from(vec![1]).expect("the proof is not empty");
+
+        Ok(match self.clone() {
+            Self::CreateClient { height } => {
+                let header = host_header(ctx, height)?;
+                MsgEnvelope::Client(ClientMsg::CreateClient(MsgCreateClient::new(
+                    MockClientState::new(header).into(),
+                    MockConsensusState::new(header).into(),
+                    signer,
+                )))
+            }
+            Self::UpdateClient { client_id, height } => {
+                MsgEnvelope::Client(ClientMsg::UpdateClient(MsgUpdateClient {
+                    client_id,
+                    client_message: host_header(ctx, height)?.into(),
+                    signer,
+                }))
+            }
+            Self::ConnOpenInit {
+                client_id,
+                counterparty_client_id,
+            } => MsgEnvelope::Connection(ConnectionMsg::OpenInit(MsgConnectionOpenInit {
+                client_id_on_a: client_id,
+                counterparty: ConnectionCounterparty::new(
+                    counterparty_client_id,
+                    None,
+                    ctx.commitment_prefix(),
+                ),
+                version: None,
+                delay_period: Duration::ZERO,
+                signer,
+            })),
+            Self::ConnOpenTry {
+                client_id,
+                counterparty_client_id,
+                counterparty_connection_id,
+            } => {
+                let proof_height = client_height(ctx, &client_id)?;
+                let host_height = ctx.latest_height();
+                #[allow(deprecated)]
+                let raw_msg = RawMsgConnectionOpenTry {
+                    client_id: client_id.to_string(),
+                    previous_connection_id: String::new(),
+                    client_state: Some(MockClientState::new(host_header(ctx, host_height)?).into()),
+                    counterparty: Some(
+                        ConnectionCounterparty::new(
+                            counterparty_client_id,
+                            Some(counterparty_connection_id),
+                            ctx.commitment_prefix(),
+                        )
+                        .into(),
+                    ),
+                    delay_period: 0,
+                    counterparty_versions: get_compatible_versions()
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
+                    proof_height: Some(proof_height.into()),
+                    proof_init: proof().into(),
+                    proof_client: proof().into(),
+                    proof_consensus: proof().into(),
+                    consensus_height: Some(host_height.into()),
+                    signer: signer.to_string(),
+                    host_consensus_state_proof: Vec::new(),
+                };
+                MsgEnvelope::Connection(ConnectionMsg::OpenTry(
+                    MsgConnectionOpenTry::try_from(raw_msg).map_err(|e| e.to_string())?,
+                ))
+            }
+            Self::ConnOpenAck {
+                connection_id,
+                counterparty_connection_id,
+            } => {
+                let proofs_height_on_b = connection_client_height(ctx, &connection_id)?;
+                let host_height = ctx.latest_height();
+                MsgEnvelope::Connection(ConnectionMsg::OpenAck(MsgConnectionOpenAck {
+                    conn_id_on_a: connection_id,
+                    conn_id_on_b: counterparty_connection_id,
+                    client_state_of_a_on_b: MockClientState::new(host_header(ctx, host_height)?)
+                        .into(),
+                    proof_conn_end_on_b: proof(),
+                    proof_client_state_of_a_on_b: proof(),
+                    proof_consensus_state_of_a_on_b: proof(),
+                    proofs_height_on_b,
+                    consensus_height_of_a_on_b: host_height,
+                    version: get_compatible_versions()[0].clone(),
+                    signer,
+                    proof_consensus_state_of_a: None,
+                }))
+            }
+            Self::ConnOpenConfirm { connection_id } => {
+                MsgEnvelope::Connection(ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
+                    proof_height_on_a: connection_client_height(ctx, &connection_id)?,
+                    conn_id_on_b: connection_id,
+                    proof_conn_end_on_a: proof(),
+                    signer,
+                }))
+            }
+            Self::ChanOpenInit {
+                port_id,
+                connection_id,
+                counterparty_port_id,
+                ordering,
+                version,
+            } => {
+                bind_port(ctx, router, &port_id);
+                MsgEnvelope::Channel(ChannelMsg::OpenInit(MsgChannelOpenInit {
+                    port_id_on_a: port_id,
+                    connection_hops_on_a: vec![connection_id],
+                    port_id_on_b: counterparty_port_id,
+                    ordering,
+                    signer,
+                    version_proposal: version,
+                }))
+            }
+            Self::ChanOpenTry {
+                port_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+                ordering,
+                version,
+            } => {
+                bind_port(ctx, router, &port_id);
+                #[allow(deprecated)]
+                let msg = MsgChannelOpenTry {
+                    proof_height_on_a: connection_client_height(ctx, &connection_id)?,
+                    port_id_on_b: port_id,
+                    connection_hops_on_b: vec![connection_id],
+                    port_id_on_a: counterparty_port_id,
+                    chan_id_on_a: counterparty_channel_id,
+                    version_supported_on_a: version,
+                    proof_chan_end_on_a: proof(),
+                    ordering,
+                    signer,
+                    version_proposal: Version::empty(),
+                };
+                MsgEnvelope::Channel(ChannelMsg::OpenTry(msg))
+            }
+            Self::ChanOpenAck {
+                port_id,
+                channel_id,
+                counterparty_channel_id,
+                counterparty_version,
+            } => MsgEnvelope::Channel(ChannelMsg::OpenAck(MsgChannelOpenAck {
+                proof_height_on_b: channel_client_height(ctx, &port_id, &channel_id)?,
+                port_id_on_a: port_id,
+                chan_id_on_a: channel_id,
+                chan_id_on_b: counterparty_channel_id,
+                version_on_b: counterparty_version,
+                proof_chan_end_on_b: proof(),
+                signer,
+            })),
+            Self::ChanOpenConfirm {
+                port_id,
+                channel_id,
+            } => MsgEnvelope::Channel(ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
+                proof_height_on_a: channel_client_height(ctx, &port_id, &channel_id)?,
+                port_id_on_b: port_id,
+                chan_id_on_b: channel_id,
+                proof_chan_end_on_a: proof(),
+                signer,
+            })),
+            Self::ChanCloseInit {
+                port_id,
+                channel_id,
+            } => MsgEnvelope::Channel(ChannelMsg::CloseInit(MsgChannelCloseInit {
+                port_id_on_a: port_id,
+                chan_id_on_a: channel_id,
+                signer,
+            })),
+            Self::ChanCloseConfirm {
+                port_id,
+                channel_id,
+            } => MsgEnvelope::Channel(ChannelMsg::CloseConfirm(MsgChannelCloseConfirm {
+                proof_height_on_a: channel_client_height(ctx, &port_id, &channel_id)?,
+                port_id_on_b: port_id,
+                chan_id_on_b: channel_id,
+                proof_chan_end_on_a: proof(),
+                signer,
+            })),
+            Self::RecvPacket(packet) => {
+                let packet = packet.build()?;
+                MsgEnvelope::Packet(PacketMsg::Recv(MsgRecvPacket {
+                    proof_height_on_a: channel_client_height(
+                        ctx,
+                        &packet.port_id_on_b,
+                        &packet.chan_id_on_b,
+                    )?,
+                    packet,
+                    proof_commitment_on_a: proof(),
+                    signer,
+                }))
+            }
+            Self::Acknowledgement {
+                packet,
+                acknowledgement,
+            } => {
+                let packet = packet.build()?;
+                MsgEnvelope::Packet(PacketMsg::Ack(MsgAcknowledgement {
+                    proof_height_on_b: channel_client_height(
+                        ctx,
+                        &packet.port_id_on_a,
+                        &packet.chan_id_on_a,
+                    )?,
+                    packet,
+                    acknowledgement: Acknowledgement::try_from(acknowledgement.into_bytes())
+                        .map_err(|e| e.to_string())?,
+                    proof_acked_on_b: proof(),
+                    signer,
+                }))
+            }
+            Self::Timeout {
+                packet,
+                next_sequence_recv,
+            } => {
+                let packet = packet.build()?;
+                MsgEnvelope::Packet(PacketMsg::Timeout(MsgTimeout {
+                    proof_height_on_b: channel_client_height(
+                        ctx,
+                        &packet.port_id_on_a,
+                        &packet.chan_id_on_a,
+                    )?,
+                    packet,
+                    next_seq_recv_on_b: Sequence::from(next_sequence_recv),
+                    proof_unreceived_on_b: proof(),
+                    signer,
+                }))
+            }
+            Self::Envelope(msg) => msg,
+        })
+    }
+}
+
+/// A packet whose data is a UTF-8 string.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct PacketSpec {
+    pub sequence: u64,
+    pub source_port: PortId,
+    pub source_channel: ChannelId,
+    pub destination_port: PortId,
+    pub destination_channel: ChannelId,
+    pub data: String,
+    #[cfg_attr(feature = "serde", serde(default = "TimeoutHeight::no_timeout"))]
+    pub timeout_height: TimeoutHeight,
+    /// The timeout timestamp in nanoseconds, 0 for none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timeout_timestamp: u64,
+}
+
+impl PacketSpec {
+    fn build(&self) -> Result<Packet, String> {
+        Ok(Packet {
+            seq_on_a: Sequence::from(self.sequence),
+            port_id_on_a: self.source_port.clone(),
+            chan_id_on_a: self.source_channel.clone(),
+            port_id_on_b: self.destination_port.clone(),
+            chan_id_on_b: self.destination_channel.clone(),
+            data: self.data.clone().into_bytes(),
+            timeout_height_on_b: self.timeout_height,
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(self.timeout_timestamp)
+                .map_err(|e| e.to_string())?,
+        })
+    }
+}
+
+#[derive(Debug, Display)]
+pub enum ScenarioError {
+    /// invalid scenario: `{reason}`
+    InvalidJson { reason: String },
+    /// invalid setup of chain `{chain_id}`: `{reason}`
+    InvalidSetup { chain_id: ChainId, reason: String },
+    /// chain `{chain_id}` is not part of the scenario
+    UnknownChain { chain_id: ChainId },
+    /// step {step} failed: `{error}`
+    UnexpectedFailure { step: usize, error: String },
+    /// step {step} succeeded, but was expected to fail with `{expected}`
+    UnexpectedSuccess { step: usize, expected: String },
+    /// step {step} failed with `{error}`, but was expected to fail with `{expected}`
+    UnexpectedError {
+        step: usize,
+        expected: String,
+        error: String,
+    },
+    /// step {step} emitted events `{actual:?}` instead of `{expected:?}`
+    UnexpectedEvents {
+        step: usize,
+        expected: Vec<String>,
+        actual: Vec<String>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ScenarioError {}
+
+/// Returns a mock header at `height` with the timestamp of the host, which
+/// the host does not consider to be in the future.
+fn host_header(ctx: &MockContext, height: Height) -> Result<MockHeader, String> {
+    let timestamp = ctx.host_timestamp().map_err(|e| e.to_string())?;
+    Ok(MockHeader::new(height).with_timestamp(timestamp))
+}
+
+fn client_height(ctx: &MockContext, client_id: &ClientId) -> Result<Height, String> {
+    ctx.client_state(client_id)
+        .map(|client_state| client_state.latest_height())
+        .map_err(|e| e.to_string())
+}
+
+fn connection_client_height(
+    ctx: &MockContext,
+    connection_id: &ConnectionId,
+) -> Result<Height, String> {
+    let connection_end = ctx
+        .connection_end(connection_id)
+        .map_err(|e| e.to_string())?;
+    client_height(ctx, connection_end.client_id())
+}
+
+fn channel_client_height(
+    ctx: &MockContext,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Height, String> {
+    let channel_end = ctx
+        .channel_end(&ChannelEndPath::new(port_id, channel_id))
+        .map_err(|e| e.to_string())?;
+    let connection_id = channel_end
+        .connection_hops()
+        .first()
+        .ok_or_else(|| format!("channel `{channel_id}` has no connection"))?;
+    connection_client_height(ctx, connection_id)
+}
+
+/// Binds `port_id` to the module routed to it, if any, unless it is bound
+/// already.
+fn bind_port(ctx: &mut MockContext, router: &MockRouter, port_id: &PortId) {
+    if let Some(module_id) = router.lookup_module(port_id) {
+        ctx.ibc_store
+            .lock()
+            .port_owners
+            .entry(port_id.clone())
+            .or_insert(module_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::context::DEFAULT_BLOCK_TIME_SECS;
+
+    fn chain(name: &str) -> ChainId {
+        ChainId::new(name, 0).unwrap()
+    }
+
+    fn client_id() -> ClientId {
+        ClientId::new(crate::mock::client_state::client_type(), 0).unwrap()
+    }
+
+    fn transfer_packet(sequence: u64) -> PacketSpec {
+        PacketSpec {
+            sequence,
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(0),
+            data: "data".to_string(),
+            timeout_height: TimeoutHeight::At(Height::new(0, 30).unwrap()),
+            timeout_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn handshakes_between_two_chains() {
+        let (a, b) = (chain("chain-a"), chain("chain-b"));
+        let connection_id = ConnectionId::new(0);
+        let channel_id = ChannelId::new(0);
+        let version = Version::new("ics20-1".to_string());
+        let client_setup = ClientSetup {
+            client_id: client_id(),
+            height: Height::new(0, 5).unwrap(),
+        };
+
+        let chains = Scenario::new()
+            .with_chain(ChainSetup::new(a.clone(), 10).with_client(client_setup.clone()))
+            .with_chain(ChainSetup::new(b.clone(), 10).with_client(client_setup))
+            .with_step(
+                Step::deliver(
+                    a.clone(),
+                    ScenarioMsg::ConnOpenInit {
+                        client_id: client_id(),
+                        counterparty_client_id: client_id(),
+                    },
+                )
+                .expect_events(["connection_open_init"]),
+            )
+            .with_step(Step::deliver(
+                b.clone(),
+                ScenarioMsg::ConnOpenTry {
+                    client_id: client_id(),
+                    counterparty_client_id: client_id(),
+                    counterparty_connection_id: connection_id.clone(),
+                },
+            ))
+            .with_step(Step::deliver(
+                a.clone(),
+                ScenarioMsg::ConnOpenAck {
+                    connection_id: connection_id.clone(),
+                    counterparty_connection_id: connection_id.clone(),
+                },
+            ))
+            .with_step(Step::deliver(
+                b.clone(),
+                ScenarioMsg::ConnOpenConfirm {
+                    connection_id: connection_id.clone(),
+                },
+            ))
+            .with_step(Step::deliver(
+                a.clone(),
+                ScenarioMsg::ChanOpenInit {
+                    port_id: PortId::transfer(),
+                    connection_id: connection_id.clone(),
+                    counterparty_port_id: PortId::transfer(),
+                    ordering: Order::Unordered,
+                    version: version.clone(),
+                },
+            ))
+            .with_step(Step::deliver(
+                b.clone(),
+                ScenarioMsg::ChanOpenTry {
+                    port_id: PortId::transfer(),
+                    connection_id,
+                    counterparty_port_id: PortId::transfer(),
+                    counterparty_channel_id: channel_id.clone(),
+                    ordering: Order::Unordered,
+                    version: version.clone(),
+                },
+            ))
+            .with_step(Step::deliver(
+                a.clone(),
+                ScenarioMsg::ChanOpenAck {
+                    port_id: PortId::transfer(),
+                    channel_id: channel_id.clone(),
+                    counterparty_channel_id: channel_id.clone(),
+                    counterparty_version: version,
+                },
+            ))
+            .with_step(
+                Step::deliver(
+                    b.clone(),
+                    ScenarioMsg::ChanOpenConfirm {
+                        port_id: PortId::transfer(),
+                        channel_id: channel_id.clone(),
+                    },
+                )
+                .expect_events(["channel_open_confirm"]),
+            )
+            .run()
+            .unwrap();
+
+        for chain_id in [a, b] {
+            let channel_end = chains[&chain_id]
+                .channel_end(&ChannelEndPath::new(&PortId::transfer(), &channel_id))
+                .unwrap();
+            assert_eq!(channel_end.state, ChannelState::Open);
+        }
+    }
+
+    #[test]
+    fn reports_unmet_expectations() {
+        let a = chain("chain-a");
+        let scenario = |step: Step| {
+            Scenario::new()
+                .with_chain(ChainSetup::new(a.clone(), 10))
+                .with_step(Step::advance_blocks(a.clone(), 2))
+                .with_step(step)
+                .run()
+        };
+        let update = ScenarioMsg::UpdateClient {
+            client_id: client_id(),
+            height: Height::new(0, 1).unwrap(),
+        };
+
+        assert!(matches!(
+            scenario(Step::deliver(a.clone(), update.clone())),
+            Err(ScenarioError::UnexpectedFailure { step: 1, .. })
+        ));
+        assert!(matches!(
+            scenario(Step::deliver(a.clone(), update.clone()).expect_error("connection")),
+            Err(ScenarioError::UnexpectedError { step: 1, .. })
+        ));
+        scenario(Step::deliver(a.clone(), update).expect_error("not found")).unwrap();
+
+        let create = ScenarioMsg::CreateClient {
+            height: Height::new(0, 1).unwrap(),
+        };
+        assert!(matches!(
+            scenario(Step::deliver(a.clone(), create.clone()).expect_error("")),
+            Err(ScenarioError::UnexpectedSuccess { step: 1, .. })
+        ));
+        assert!(matches!(
+            scenario(Step::deliver(a.clone(), create.clone()).expect_events(["update_client"])),
+            Err(ScenarioError::UnexpectedEvents { step: 1, .. })
+        ));
+        assert!(matches!(
+            scenario(Step::deliver(chain("chain-b"), create)),
+            Err(ScenarioError::UnknownChain { .. })
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn runs_data_files() {
+        for scenario in [
+            include_str!("../../tests/support/scenarios/packet_lifecycle.json"),
+            include_str!("../../tests/support/scenarios/packet_timeout.json"),
+        ] {
+            Scenario::from_json(scenario).unwrap().run().unwrap();
+        }
+    }
+
+    #[test]
+    fn chains_with_a_timestamp_have_the_same_blocks_across_builds() {
+        let timestamp = Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap();
+        let setup = ChainSetup::new(chain("chain-a"), 10)
+            .with_timestamp(timestamp)
+            .with_open_transfer_channel(Height::new(0, 5).unwrap());
+        let router = transfer_router();
+        let (first, second) = (setup.build(&router).unwrap(), setup.build(&router).unwrap());
+
+        let latest_height = first.latest_height();
+        assert_eq!(
+            first.host_consensus_state(&latest_height).unwrap(),
+            second.host_consensus_state(&latest_height).unwrap()
+        );
+        let client_consensus_state_path =
+            ClientConsensusStatePath::new(&client_id(), &Height::new(0, 5).unwrap());
+        assert_eq!(
+            first.consensus_state(&client_consensus_state_path).unwrap(),
+            second
+                .consensus_state(&client_consensus_state_path)
+                .unwrap()
+        );
+        assert_eq!(
+            first.host_timestamp().unwrap(),
+            (timestamp + Duration::from_secs(DEFAULT_BLOCK_TIME_SECS)).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_packets_after_their_timeout() {
+        let a = chain("chain-a");
+        Scenario::new()
+            .with_chain(
+                ChainSetup::new(a.clone(), 50)
+                    .with_open_transfer_channel(Height::new(0, 40).unwrap()),
+            )
+            .with_step(
+                Step::deliver(a, ScenarioMsg::RecvPacket(transfer_packet(1)))
+                    .expect_error("packet timeout height"),
+            )
+            .run()
+            .unwrap();
+    }
+}
@@ -0,0 +1,435 @@
+//! In-memory authenticated key-value stores, used by the mock context to
+//! commit to its IBC state and to prove it against the roots of its blocks.
+
+use alloc::collections::btree_map::BTreeMap;
+
+use ibc_proto::ics23::commitment_proof::Proof;
+use ibc_proto::ics23::{
+    CommitmentProof, ExistenceProof, HashOp, InnerOp, LeafOp, LengthOp, NonExistenceProof,
+};
+use prost::encoding::encode_varint;
+use sha2::{Digest, Sha256};
+
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::core::ics23_commitment::merkle::MerkleProof;
+use crate::prelude::*;
+
+/// The version stamped on the nodes of IAVL trees, which keep no history here.
+const IAVL_VERSION: i64 = 1;
+
+/// The layout of the nodes of a [`MerkleStore`], each matching one of the
+/// ICS-23 proof specs of Cosmos SDK chains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeLayout {
+    /// Nodes of an IAVL tree, proven with `ics23::iavl_spec`.
+    Iavl,
+    /// Nodes of a Tendermint simple Merkle tree, proven with
+    /// `ics23::tendermint_spec`.
+    Tendermint,
+}
+
+impl TreeLayout {
+    fn leaf_op(&self) -> LeafOp {
+        let prefix = match self {
+            Self::Iavl => {
+                // height 0, size 1
+                let mut prefix = Vec::new();
+                encode_zigzag(0, &mut prefix);
+                encode_zigzag(1, &mut prefix);
+                encode_zigzag(IAVL_VERSION, &mut prefix);
+                prefix
+            }
+            Self::Tendermint => vec![0],
+        };
+
+        LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::NoHash.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::VarProto.into(),
+            prefix,
+        }
+    }
+
+    /// Returns the bytes hashed before the left child of an inner node, and
+    /// those hashed between its two children.
+    fn inner_node_parts(&self, height: i64, size: i64) -> (Vec<u8>, Vec<u8>) {
+        match self {
+            Self::Iavl => {
+                let mut prefix = Vec::new();
+                encode_zigzag(height, &mut prefix);
+                encode_zigzag(size, &mut prefix);
+                encode_zigzag(IAVL_VERSION, &mut prefix);
+                prefix.push(32);
+                (prefix, vec![32])
+            }
+            Self::Tendermint => (vec![1], Vec::new()),
+        }
+    }
+
+    /// Returns the number of the `len` sorted entries of a subtree, at least
+    /// 2, in its left subtree.
+    fn split_point(&self, len: usize) -> usize {
+        match self {
+            Self::Iavl => (len + 1) / 2,
+            // the largest power of two less than `len`, as in the simple
+            // Merkle trees of Tendermint
+            Self::Tendermint => 1 << (usize::BITS - (len - 1).leading_zeros() - 1),
+        }
+    }
+}
+
+fn encode_zigzag(value: i64, buf: &mut Vec<u8>) {
+    encode_varint(((value << 1) ^ (value >> 63)) as u64, buf)
+}
+
+/// A subtree of a [`MerkleStore`], as built while computing a root or a proof.
+struct Subtree {
+    hash: Vec<u8>,
+    height: i64,
+}
+
+/// An in-memory key-value store committing to its entries with a binary
+/// Merkle tree, whose proofs are ICS-23 commitment proofs.
+///
+/// Trees of the Tendermint layout are the simple Merkle trees of Tendermint,
+/// while those of the IAVL layout are balanced but, unlike actual IAVL trees,
+/// do not depend on the order in which the entries were set.
+///
+/// The tree is rebuilt from the sorted entries whenever a root or a proof is
+/// requested, which is fine for the sizes of the stores of tests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleStore {
+    layout: TreeLayout,
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl MerkleStore {
+    pub fn new(layout: TreeLayout) -> Self {
+        Self {
+            layout,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn layout(&self) -> TreeLayout {
+        self.layout
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(Vec::as_slice)
+    }
+
+    /// Sets the value at `key`, returning the previous one if any.
+    pub fn set(&mut self, key: Vec<u8>, value: Vec<u8>) -> Option<Vec<u8>> {
+        self.entries.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the root hash of the store, empty if the store is.
+    pub fn root_hash(&self) -> Vec<u8> {
+        let entries = self.sorted_entries();
+        if entries.is_empty() {
+            return Vec::new();
+        }
+        self.subtree(&entries, None, &mut Vec::new()).hash
+    }
+
+    /// Returns a proof of the existence of `key` in the store if it is set,
+    /// or of its non-existence otherwise.
+    ///
+    /// There is no proof for any key of an empty store.
+    pub fn proof(&self, key: &[u8]) -> Option<CommitmentProof> {
+        let entries = self.sorted_entries();
+        if entries.is_empty() {
+            return None;
+        }
+
+        let proof = match entries.binary_search_by(|(k, _)| (*k).cmp(key)) {
+            Ok(index) => Proof::Exist(self.existence_proof(&entries, index)),
+            Err(index) => Proof::Nonexist(NonExistenceProof {
+                key: key.to_vec(),
+                left: index
+                    .checked_sub(1)
+                    .map(|left| self.existence_proof(&entries, left)),
+                right: (index < entries.len()).then(|| self.existence_proof(&entries, index)),
+            }),
+        };
+
+        Some(CommitmentProof { proof: Some(proof) })
+    }
+
+    fn sorted_entries(&self) -> Vec<(&[u8], &[u8])> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+            .collect()
+    }
+
+    fn existence_proof(&self, entries: &[(&[u8], &[u8])], index: usize) -> ExistenceProof {
+        let mut path = Vec::new();
+        self.subtree(entries, Some(index), &mut path);
+
+        let (key, value) = entries[index];
+        ExistenceProof {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            leaf: Some(self.layout.leaf_op()),
+            path,
+        }
+    }
+
+    /// Hashes the subtree over `entries`, pushing onto `path` the inner
+    /// operations from the leaf at `index`, if any, up to the subtree root.
+    fn subtree(
+        &self,
+        entries: &[(&[u8], &[u8])],
+        index: Option<usize>,
+        path: &mut Vec<InnerOp>,
+    ) -> Subtree {
+        if let [(key, value)] = entries {
+            return Subtree {
+                hash: leaf_hash(&self.layout.leaf_op(), key, value),
+                height: 0,
+            };
+        }
+
+        let mid = self.layout.split_point(entries.len());
+        let (left_entries, right_entries) = entries.split_at(mid);
+        let left_index = index.filter(|index| *index < mid);
+        let right_index = index.and_then(|index| index.checked_sub(mid));
+
+        let left = self.subtree(left_entries, left_index, path);
+        let right = self.subtree(right_entries, right_index, path);
+
+        let height = left.height.max(right.height) + 1;
+        let (mut prefix, separator) = self.layout.inner_node_parts(height, entries.len() as i64);
+
+        let mut hasher = Sha256::new();
+        hasher.update(&prefix);
+        hasher.update(&left.hash);
+        hasher.update(&separator);
+        hasher.update(&right.hash);
+        let hash = hasher.finalize().to_vec();
+
+        if left_index.is_some() {
+            let mut suffix = separator;
+            suffix.extend(&right.hash);
+            path.push(InnerOp {
+                hash: HashOp::Sha256.into(),
+                prefix,
+                suffix,
+            });
+        } else if right_index.is_some() {
+            prefix.extend(&left.hash);
+            prefix.extend(separator);
+            path.push(InnerOp {
+                hash: HashOp::Sha256.into(),
+                prefix,
+                suffix: Vec::new(),
+            });
+        }
+
+        Subtree { hash, height }
+    }
+}
+
+fn leaf_hash(leaf_op: &LeafOp, key: &[u8], value: &[u8]) -> Vec<u8> {
+    let hashed_value = Sha256::digest(value);
+
+    let mut preimage = leaf_op.prefix.clone();
+    encode_varint(key.len() as u64, &mut preimage);
+    preimage.extend(key);
+    encode_varint(hashed_value.len() as u64, &mut preimage);
+    preimage.extend(hashed_value);
+
+    Sha256::digest(preimage).to_vec()
+}
+
+/// A set of IAVL stores mounted under their names, committed to by a
+/// Tendermint Merkle tree over the roots of the stores, like the multistore of
+/// Cosmos SDK chains.
+///
+/// Its proofs are two-layered, and verify with `ProofSpecs::cosmos()` against
+/// its root, under a Merkle path starting with the name of the store.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiStore {
+    stores: BTreeMap<Vec<u8>, MerkleStore>,
+}
+
+impl MultiStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts `store` under `name`, replacing the store previously mounted
+    /// there, if any.
+    pub fn mount(&mut self, name: impl Into<Vec<u8>>, store: MerkleStore) {
+        self.stores.insert(name.into(), store);
+    }
+
+    pub fn store(&self, name: &[u8]) -> Option<&MerkleStore> {
+        self.stores.get(name)
+    }
+
+    pub fn store_mut(&mut self, name: &[u8]) -> Option<&mut MerkleStore> {
+        self.stores.get_mut(name)
+    }
+
+    pub fn root(&self) -> CommitmentRoot {
+        CommitmentRoot::from_bytes(&self.commitment_tree().root_hash())
+    }
+
+    /// Returns the proof of the existence, or the non-existence, of `key` in
+    /// the store mounted under `name`, or `None` if there is no such store
+    /// or it is empty.
+    pub fn proof(&self, name: &[u8], key: &[u8]) -> Option<MerkleProof> {
+        let store_proof = self.store(name)?.proof(key)?;
+        let root_proof = self.commitment_tree().proof(name)?;
+
+        Some(MerkleProof {
+            proofs: vec![store_proof, root_proof],
+        })
+    }
+
+    fn commitment_tree(&self) -> MerkleStore {
+        let mut tree = MerkleStore::new(TreeLayout::Tendermint);
+        for (name, store) in &self.stores {
+            tree.set(name.clone(), store.root_hash());
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::commitment::v1::MerklePath;
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics23_commitment::specs::ProofSpecs;
+
+    fn merkle_path(store: &str, key: &str) -> MerklePath {
+        MerklePath {
+            key_path: vec![store.to_string(), key.to_string()],
+        }
+    }
+
+    #[test]
+    fn prove_store_entries() {
+        let mut ibc_store = MerkleStore::new(TreeLayout::Iavl);
+        for i in 0..7 {
+            ibc_store.set(format!("key{i}").into_bytes(), vec![i; 3]);
+        }
+        let mut other_store = MerkleStore::new(TreeLayout::Iavl);
+        other_store.set(b"key".to_vec(), b"value".to_vec());
+
+        let mut store = MultiStore::new();
+        store.mount("ibc", ibc_store);
+        store.mount("other", other_store);
+        let specs = ProofSpecs::default();
+        let root = store.root();
+
+        for i in 0..7 {
+            let key = format!("key{i}");
+            let proof = store.proof(b"ibc", key.as_bytes()).unwrap();
+            proof
+                .verify_membership(
+                    &specs,
+                    root.clone().into(),
+                    merkle_path("ibc", &key),
+                    vec![i; 3],
+                    0,
+                )
+                .unwrap();
+            assert!(proof
+                .verify_membership(
+                    &specs,
+                    root.clone().into(),
+                    merkle_path("ibc", &key),
+                    vec![i; 2],
+                    0,
+                )
+                .is_err());
+        }
+
+        // keys before, between and after the set ones
+        for key in ["a", "key3a", "z"] {
+            let proof = store.proof(b"ibc", key.as_bytes()).unwrap();
+            proof
+                .verify_non_membership(&specs, root.clone().into(), merkle_path("ibc", key))
+                .unwrap();
+            assert!(proof
+                .verify_non_membership(&specs, root.clone().into(), merkle_path("other", key))
+                .is_err());
+        }
+
+        // a change to any store changes the root
+        store
+            .store_mut(b"other")
+            .unwrap()
+            .set(b"key".to_vec(), b"new value".to_vec());
+        assert_ne!(store.root(), root);
+
+        assert!(store.proof(b"none", b"key").is_none());
+        store.mount("empty", MerkleStore::new(TreeLayout::Iavl));
+        assert!(store.proof(b"empty", b"key").is_none());
+    }
+
+    /// Checks the root of multistores against the simple Merkle root of
+    /// Tendermint over their stores, which Cosmos SDK chains use as app hash.
+    #[test]
+    fn multistore_root_is_the_simple_merkle_root_of_its_stores() {
+        for count in 1..=9 {
+            let mut store = MultiStore::new();
+            for i in 0..count {
+                let mut inner = MerkleStore::new(TreeLayout::Iavl);
+                inner.set(b"key".to_vec(), vec![i]);
+                store.mount(format!("store{i}"), inner);
+            }
+
+            let leaves: Vec<_> = store
+                .stores
+                .iter()
+                .map(|(name, inner)| {
+                    let hashed_root = Sha256::digest(inner.root_hash());
+                    let mut leaf = Vec::new();
+                    encode_varint(name.len() as u64, &mut leaf);
+                    leaf.extend(name);
+                    encode_varint(hashed_root.len() as u64, &mut leaf);
+                    leaf.extend(hashed_root);
+                    leaf
+                })
+                .collect();
+            let expected = tendermint::merkle::simple_hash_from_byte_vectors::<Sha256>(&leaves);
+            assert_eq!(store.root().as_bytes(), expected, "{count} stores");
+
+            let root = store.root();
+            for i in 0..count {
+                let name = format!("store{i}");
+                store
+                    .proof(name.as_bytes(), b"key")
+                    .unwrap()
+                    .verify_membership(
+                        &ProofSpecs::default(),
+                        root.clone().into(),
+                        merkle_path(&name, "key"),
+                        vec![i],
+                        0,
+                    )
+                    .unwrap();
+            }
+        }
+    }
+}
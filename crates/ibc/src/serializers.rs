@@ -1,6 +1,9 @@
+use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use subtle_encoding::{Encoding, Hex};
 
+use crate::prelude::*;
+
 pub fn ser_hex_upper<S, T>(data: T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -12,6 +15,16 @@ where
     hex.serialize(serializer)
 }
 
+pub fn de_hex_upper<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    Hex::upper_case()
+        .decode(hex)
+        .map_err(|e| serde::de::Error::custom(alloc::format!("failed to deserialize hex: {}", e)))
+}
+
 pub mod serde_string {
     use core::fmt::Display;
     use core::str::FromStr;
@@ -38,6 +51,35 @@ pub mod serde_string {
     }
 }
 
+/// (De)serializes a map as the sequence of its entries, for formats such as
+/// JSON whose map keys can only be strings.
+#[cfg(any(test, feature = "mocks"))]
+pub mod serde_entries {
+    use alloc::collections::btree_map::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::prelude::*;
+
+    pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<(K, V)>::deserialize(deserializer).map(|entries| entries.into_iter().collect())
+    }
+}
+
 /// Test that a struct `T` can be:
 ///
 /// - parsed out of the provided JSON data
@@ -0,0 +1,592 @@
+//! Generic query service of IBC applications.
+//!
+//! Each application registered in the [`Router`] may answer its own gRPC queries, e.g.
+//! `/ibc.applications.transfer.v1.Query/DenomTrace`. Instead of serving a bespoke tonic
+//! service for each of them, applications implement [`ApplicationQuery`] to expose their
+//! query handlers keyed by gRPC method path, and register them in a single
+//! [`ApplicationQueryService`].
+//!
+//! The service can then be queried ABCI-style, with a path and a protobuf-encoded request,
+//! through [`ApplicationQueryService::query`], or over gRPC through the
+//! [`ApplicationQueryServer`], which dispatches every registered `/ibc.applications.*` path.
+//!
+//! As the paths are only known at runtime, and belong to several gRPC services, the
+//! [`ApplicationQueryServer`] is mounted on a `tonic::transport::Server` through the
+//! [`ApplicationQueryLayer`]. The layer routes the requests on the registered paths to the
+//! server, and passes the others through to the services added to the transport server:
+//! ```rust,ignore
+//! let application_queries = ApplicationQueryLayer::new(ApplicationQueryServer::new(service));
+//!
+//! tonic::transport::Server::builder()
+//!     .layer(application_queries)
+//!     .add_service(client_service)
+//!     .add_service(channel_service)
+//!     .serve(addr)
+//!     .await?;
+//! ```
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::future::Future;
+use std::boxed::Box;
+
+use displaydoc::Display;
+use prost::bytes::{Buf, BufMut};
+use prost::Message;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::{
+    empty_body, http, Arc, Body, BoxFuture, Bytes, Context, Poll, Service, StdError,
+};
+use tonic::{Code, Request, Response, Status};
+use tower_layer::Layer;
+
+use crate::core::router::{ModuleId, Router};
+use crate::prelude::*;
+
+/// Prefix of the gRPC method paths of the IBC application queries.
+pub const APPLICATION_QUERY_PATH_PREFIX: &str = "/ibc.applications.";
+
+/// Errors raised while registering application queries in an [`ApplicationQueryService`]
+#[derive(Debug, Display)]
+pub enum ApplicationQueryError {
+    /// module `{module_id}` is not registered in the router
+    UnknownModule { module_id: ModuleId },
+    /// query path `{path}` is not an IBC application query path
+    InvalidQueryPath { path: String },
+    /// query path `{path}` is already registered by module `{module_id}`
+    DuplicateQueryPath { path: String, module_id: ModuleId },
+    /// query path `{path}` has several handlers
+    DuplicateQueryHandler { path: String },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ApplicationQueryError {}
+
+type QueryHandler = Box<dyn Fn(Request<Bytes>) -> BoxFuture<Vec<u8>, Status> + Send + Sync>;
+
+/// The query handlers of an application, keyed by gRPC method path.
+#[derive(Default)]
+pub struct ApplicationQueryHandlers {
+    handlers: Vec<(String, QueryHandler)>,
+}
+
+impl ApplicationQueryHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the handler of the query at `path`, e.g.
+    /// `/ibc.applications.transfer.v1.Query/DenomTrace`.
+    ///
+    /// The handler receives the decoded request, with the metadata of the gRPC request.
+    pub fn add<Req, Res, F, Fut>(mut self, path: &str, handler: F) -> Self
+    where
+        Req: Message + Default + 'static,
+        Res: Message + 'static,
+        F: Fn(Request<Req>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Response<Res>, Status>> + Send + 'static,
+    {
+        let handler = move |request: Request<Bytes>| -> BoxFuture<Vec<u8>, Status> {
+            let (metadata, extensions, data) = request.into_parts();
+            match Req::decode(data) {
+                Ok(message) => {
+                    let response = handler(Request::from_parts(metadata, extensions, message));
+                    Box::pin(async move { Ok(response.await?.into_inner().encode_to_vec()) })
+                }
+                Err(e) => {
+                    let status = Status::invalid_argument(e.to_string());
+                    Box::pin(async move { Err(status) })
+                }
+            }
+        };
+        self.handlers.push((path.to_string(), Box::new(handler)));
+        self
+    }
+
+    /// Returns the paths of the handled queries.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.handlers.iter().map(|(path, _)| path.as_str())
+    }
+}
+
+/// An IBC application that answers queries through the [`ApplicationQueryService`].
+pub trait ApplicationQuery {
+    /// Returns the handlers of the queries of this application.
+    fn query_handlers(self) -> ApplicationQueryHandlers;
+}
+
+/// Registry of the query handlers of the IBC applications of the host.
+#[derive(Default)]
+pub struct ApplicationQueryService {
+    handlers: BTreeMap<String, (ModuleId, QueryHandler)>,
+}
+
+impl ApplicationQueryService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the queries of `application` for the module `module_id`, which must be
+    /// registered in `router`.
+    ///
+    /// Nothing is registered if any of the query paths is invalid, already registered, or
+    /// handled more than once by `application`.
+    pub fn register(
+        &mut self,
+        router: &impl Router,
+        module_id: ModuleId,
+        application: impl ApplicationQuery,
+    ) -> Result<(), ApplicationQueryError> {
+        if router.get_route(&module_id).is_none() {
+            return Err(ApplicationQueryError::UnknownModule { module_id });
+        }
+
+        let handlers = application.query_handlers();
+        let mut paths = BTreeSet::new();
+        for path in handlers.paths() {
+            if !path.starts_with(APPLICATION_QUERY_PATH_PREFIX) {
+                return Err(ApplicationQueryError::InvalidQueryPath {
+                    path: path.to_string(),
+                });
+            }
+            if !paths.insert(path) {
+                return Err(ApplicationQueryError::DuplicateQueryHandler {
+                    path: path.to_string(),
+                });
+            }
+            if let Some((owner, _)) = self.handlers.get(path) {
+                return Err(ApplicationQueryError::DuplicateQueryPath {
+                    path: path.to_string(),
+                    module_id: owner.clone(),
+                });
+            }
+        }
+
+        for (path, handler) in handlers.handlers {
+            self.handlers.insert(path, (module_id.clone(), handler));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the registered query paths, along with the module answering them.
+    pub fn query_paths(&self) -> impl Iterator<Item = (&str, &ModuleId)> {
+        self.handlers
+            .iter()
+            .map(|(path, (module_id, _))| (path.as_str(), module_id))
+    }
+
+    /// Answers the query at `path` with the protobuf-encoded `data`, and returns the
+    /// protobuf-encoded response.
+    pub async fn query(&self, path: &str, data: &[u8]) -> Result<Vec<u8>, Status> {
+        self.handle(path, Request::new(Bytes::copy_from_slice(data)))
+            .await
+    }
+
+    fn handle(&self, path: &str, request: Request<Bytes>) -> BoxFuture<Vec<u8>, Status> {
+        match self.handlers.get(path) {
+            Some((_, handler)) => handler(request),
+            None => {
+                let status = Status::unimplemented(format!("Unknown query path {path}"));
+                Box::pin(async move { Err(status) })
+            }
+        }
+    }
+}
+
+/// Passes the protobuf-encoded messages through, so that they are decoded and encoded by
+/// the query handlers.
+#[derive(Clone, Copy, Debug, Default)]
+struct BytesCodec;
+
+impl Codec for BytesCodec {
+    type Encode = Vec<u8>;
+    type Decode = Bytes;
+    type Encoder = BytesCodec;
+    type Decoder = BytesCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        BytesCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        BytesCodec
+    }
+}
+
+impl Encoder for BytesCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for BytesCodec {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining())))
+    }
+}
+
+/// gRPC server dispatching the queries registered in an [`ApplicationQueryService`].
+pub struct ApplicationQueryServer {
+    inner: Arc<ApplicationQueryService>,
+}
+
+impl ApplicationQueryServer {
+    pub fn new(inner: ApplicationQueryService) -> Self {
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl Clone for ApplicationQueryServer {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+struct QuerySvc {
+    inner: Arc<ApplicationQueryService>,
+    path: String,
+}
+
+impl tonic::server::UnaryService<Bytes> for QuerySvc {
+    type Response = Vec<u8>;
+    type Future = BoxFuture<Response<Self::Response>, Status>;
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        let response = self.inner.handle(&self.path, request);
+        Box::pin(async move { response.await.map(Response::new) })
+    }
+}
+
+impl<B> Service<http::Request<B>> for ApplicationQueryServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = core::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let inner = Arc::clone(&self.inner);
+        let path = req.uri().path().to_string();
+        if inner.handlers.contains_key(&path) {
+            Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(BytesCodec);
+                Ok(grpc.unary(QuerySvc { inner, path }, req).await)
+            })
+        } else {
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .header("grpc-status", (Code::Unimplemented as i32).to_string())
+                    .header("content-type", "application/grpc")
+                    .body(empty_body())
+                    .expect("valid response"))
+            })
+        }
+    }
+}
+
+/// Mounts an [`ApplicationQueryServer`] in front of the services of a
+/// `tonic::transport::Server`.
+#[derive(Clone)]
+pub struct ApplicationQueryLayer {
+    server: ApplicationQueryServer,
+}
+
+impl ApplicationQueryLayer {
+    pub fn new(server: ApplicationQueryServer) -> Self {
+        Self { server }
+    }
+}
+
+impl<S> Layer<S> for ApplicationQueryLayer {
+    type Service = ApplicationQueryRouter<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApplicationQueryRouter {
+            server: self.server.clone(),
+            inner,
+        }
+    }
+}
+
+/// Routes the requests on the query paths registered in an [`ApplicationQueryServer`] to
+/// it, and the others to the `inner` service. Built by the [`ApplicationQueryLayer`].
+#[derive(Clone)]
+pub struct ApplicationQueryRouter<S> {
+    server: ApplicationQueryServer,
+    inner: S,
+}
+
+impl<S, B> Service<http::Request<B>> for ApplicationQueryRouter<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<tonic::body::BoxBody>>,
+    S::Future: Send + 'static,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if self.server.inner.handlers.contains_key(req.uri().path()) {
+            let response = self.server.call(req);
+            Box::pin(async move {
+                match response.await {
+                    Ok(response) => Ok(response),
+                    Err(infallible) => match infallible {},
+                }
+            })
+        } else {
+            Box::pin(self.inner.call(req))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::applications::transfer::v1::{
+        QueryDenomHashRequest, QueryDenomHashResponse,
+    };
+    use ibc_proto::ibc::core::connection::v1::query_server::QueryServer as ConnectionQueryServer;
+    use ibc_proto::ibc::core::connection::v1::{
+        QueryConnectionParamsRequest, QueryConnectionParamsResponse,
+    };
+    use tonic::codec::ProstCodec;
+    use tonic::transport::{Endpoint, Server, Uri};
+
+    use super::*;
+    use crate::mock::context::MockContext;
+    use crate::mock::router::MockRouter;
+    use crate::services::core::connection::ConnectionQueryService;
+    use crate::test_utils::DummyTransferModule;
+
+    const DENOM_HASH_PATH: &str = "/ibc.applications.transfer.v1.Query/DenomHash";
+
+    struct EchoApplication;
+
+    impl ApplicationQuery for EchoApplication {
+        fn query_handlers(self) -> ApplicationQueryHandlers {
+            ApplicationQueryHandlers::new().add(
+                DENOM_HASH_PATH,
+                |request: Request<QueryDenomHashRequest>| async move {
+                    Ok(Response::new(QueryDenomHashResponse {
+                        hash: request.into_inner().trace,
+                    }))
+                },
+            )
+        }
+    }
+
+    struct InvalidApplication;
+
+    impl ApplicationQuery for InvalidApplication {
+        fn query_handlers(self) -> ApplicationQueryHandlers {
+            ApplicationQueryHandlers::new().add(
+                "/ibc.core.client.v1.Query/ClientState",
+                |_: Request<QueryDenomHashRequest>| async move {
+                    Ok(Response::new(QueryDenomHashResponse::default()))
+                },
+            )
+        }
+    }
+
+    struct DuplicateApplication;
+
+    impl ApplicationQuery for DuplicateApplication {
+        fn query_handlers(self) -> ApplicationQueryHandlers {
+            let handler = |request: Request<QueryDenomHashRequest>| async move {
+                Ok(Response::new(QueryDenomHashResponse {
+                    hash: request.into_inner().trace,
+                }))
+            };
+            ApplicationQueryHandlers::new()
+                .add(DENOM_HASH_PATH, handler)
+                .add(DENOM_HASH_PATH, handler)
+        }
+    }
+
+    fn transfer_router(module_id: &ModuleId) -> MockRouter {
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+        router
+    }
+
+    #[test]
+    fn reject_duplicate_query_handlers() {
+        let module_id = ModuleId::new("transfer".to_string());
+        let router = transfer_router(&module_id);
+
+        let mut service = ApplicationQueryService::new();
+        assert!(matches!(
+            service.register(&router, module_id, DuplicateApplication),
+            Err(ApplicationQueryError::DuplicateQueryHandler { .. })
+        ));
+        assert_eq!(service.query_paths().count(), 0);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn serve_application_queries_on_a_transport_server() {
+        let module_id = ModuleId::new("transfer".to_string());
+        let router = transfer_router(&module_id);
+        let mut service = ApplicationQueryService::new();
+        service
+            .register(&router, module_id, EchoApplication)
+            .unwrap();
+
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let application_queries = ApplicationQueryLayer::new(ApplicationQueryServer::new(service));
+        tokio::spawn(async move {
+            Server::builder()
+                .layer(application_queries)
+                .add_service(ConnectionQueryServer::new(ConnectionQueryService::new(
+                    MockContext::default(),
+                )))
+                .serve_with_incoming(tokio_stream::once(Ok::<_, std::io::Error>(server_io)))
+                .await
+        });
+
+        let mut client_io = Some(client_io);
+        let channel = Endpoint::try_from("http://[::]:50051")
+            .unwrap()
+            .connect_with_connector(tower::service_fn(move |_: Uri| {
+                let io = client_io.take();
+                async move {
+                    io.ok_or_else(|| {
+                        std::io::Error::new(std::io::ErrorKind::Other, "Client already connected")
+                    })
+                }
+            }))
+            .await
+            .unwrap();
+
+        let request = QueryDenomHashRequest {
+            trace: "transfer/channel-0/uatom".to_string(),
+        };
+        let mut client = tonic::client::Grpc::new(channel);
+        client.ready().await.unwrap();
+        let response: Response<QueryDenomHashResponse> = client
+            .unary(
+                Request::new(request.clone()),
+                http::uri::PathAndQuery::from_static(DENOM_HASH_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().hash, request.trace);
+
+        // The other services of the transport server are still reachable.
+        client.ready().await.unwrap();
+        let response: Response<QueryConnectionParamsResponse> = client
+            .unary(
+                Request::new(QueryConnectionParamsRequest {}),
+                http::uri::PathAndQuery::from_static(
+                    "/ibc.core.connection.v1.Query/ConnectionParams",
+                ),
+                ProstCodec::default(),
+            )
+            .await
+            .unwrap();
+        assert!(response.into_inner().params.is_some());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn dispatch_application_queries() {
+        let module_id = ModuleId::new("transfer".to_string());
+        let router = transfer_router(&module_id);
+
+        let mut service = ApplicationQueryService::new();
+        assert!(matches!(
+            service.register(
+                &router,
+                ModuleId::new("unknown".to_string()),
+                EchoApplication
+            ),
+            Err(ApplicationQueryError::UnknownModule { .. })
+        ));
+        assert!(matches!(
+            service.register(&router, module_id.clone(), InvalidApplication),
+            Err(ApplicationQueryError::InvalidQueryPath { .. })
+        ));
+        service
+            .register(&router, module_id.clone(), EchoApplication)
+            .unwrap();
+        assert!(matches!(
+            service.register(&router, module_id.clone(), EchoApplication),
+            Err(ApplicationQueryError::DuplicateQueryPath { .. })
+        ));
+        assert_eq!(
+            service.query_paths().collect::<Vec<_>>(),
+            vec![(DENOM_HASH_PATH, &module_id)]
+        );
+
+        let request = QueryDenomHashRequest {
+            trace: "transfer/channel-0/uatom".to_string(),
+        };
+
+        let response = service
+            .query(DENOM_HASH_PATH, &request.encode_to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            QueryDenomHashResponse::decode(response.as_slice())
+                .unwrap()
+                .hash,
+            request.trace
+        );
+        assert_eq!(
+            service
+                .query(DENOM_HASH_PATH, &[0xff])
+                .await
+                .unwrap_err()
+                .code(),
+            Code::InvalidArgument
+        );
+
+        let mut client = tonic::client::Grpc::new(ApplicationQueryServer::new(service));
+
+        let response: Response<QueryDenomHashResponse> = client
+            .unary(
+                Request::new(request.clone()),
+                http::uri::PathAndQuery::from_static(DENOM_HASH_PATH),
+                ProstCodec::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().hash, request.trace);
+
+        let unknown: Result<Response<QueryDenomHashResponse>, _> = client
+            .unary(
+                Request::new(request),
+                http::uri::PathAndQuery::from_static(
+                    "/ibc.applications.transfer.v1.Query/DenomTrace",
+                ),
+                ProstCodec::default(),
+            )
+            .await;
+        assert_eq!(unknown.unwrap_err().code(), Code::Unimplemented);
+    }
+}
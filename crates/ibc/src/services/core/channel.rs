@@ -32,10 +32,10 @@ use crate::core::ics24_host::path::{
 use crate::core::ValidationContext;
 use crate::prelude::*;
 use crate::services::core::context::QueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::core::pagination::PageRequest;
 use crate::Height;
 
-// TODO(rano): currently the services don't support pagination, so we return all the results.
-
 /// The generic `I` must be a type where writes from one thread are readable from another.
 /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
 pub struct ChannelQueryService<I>
@@ -71,6 +71,8 @@ where
         &self,
         request: Request<QueryChannelRequest>,
     ) -> Result<Response<QueryChannelResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -79,12 +81,11 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let channel_end = self.ibc_context.channel_end(&channel_end_path)?;
+        let channel_end = ibc_context.channel_end(&channel_end_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::ChannelEnd(channel_end_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -102,15 +103,18 @@ where
 
     async fn channels(
         &self,
-        _request: Request<QueryChannelsRequest>,
+        request: Request<QueryChannelsRequest>,
     ) -> Result<Response<QueryChannelsResponse>, Status> {
-        let channel_ends = self.ibc_context.channel_ends()?;
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let page = PageRequest::try_from(request.get_ref().pagination.clone())?;
+
+        let (channel_ends, page_response) = ibc_context.channel_ends_paginated(&page)?;
 
         Ok(Response::new(QueryChannelsResponse {
             channels: channel_ends.into_iter().map(Into::into).collect(),
-            height: Some(self.ibc_context.host_height()?.into()),
-            // no support for pagination yet
-            pagination: None,
+            height: Some(ibc_context.host_height()?.into()),
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -118,29 +122,24 @@ where
         &self,
         request: Request<QueryConnectionChannelsRequest>,
     ) -> Result<Response<QueryConnectionChannelsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let connection_id = ConnectionId::from_str(request_ref.connection.as_str())?;
 
-        let all_channel_ends = self.ibc_context.channel_ends()?;
+        let page = PageRequest::try_from(request_ref.pagination.clone())?;
 
-        let connection_channel_ends = all_channel_ends
-            .into_iter()
-            .filter(|channel_end| {
-                channel_end
-                    .channel_end
-                    .connection_hops()
-                    .iter()
-                    .any(|connection_hop| connection_hop == &connection_id)
-            })
-            .map(Into::into)
-            .collect();
+        let (connection_channel_ends, page_response) =
+            ibc_context.connection_channel_ends_paginated(&connection_id, &page)?;
 
         Ok(Response::new(QueryConnectionChannelsResponse {
-            channels: connection_channel_ends,
-            height: Some(self.ibc_context.host_height()?.into()),
-            // no support for pagination yet
-            pagination: None,
+            channels: connection_channel_ends
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            height: Some(ibc_context.host_height()?.into()),
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -148,6 +147,8 @@ where
         &self,
         request: Request<QueryChannelClientStateRequest>,
     ) -> Result<Response<QueryChannelClientStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -156,22 +157,21 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let channel_end = self.ibc_context.channel_end(&channel_end_path)?;
+        let channel_end = ibc_context.channel_end(&channel_end_path)?;
 
         let connection_end = channel_end
             .connection_hops()
             .first()
-            .map(|connection_id| self.ibc_context.connection_end(connection_id))
+            .map(|connection_id| ibc_context.connection_end(connection_id))
             .ok_or_else(|| {
                 Status::not_found(format!("Channel {} has no connection hops", channel_id))
             })??;
 
-        let client_state = self.ibc_context.client_state(connection_end.client_id())?;
+        let client_state = ibc_context.client_state(connection_end.client_id())?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(
                 current_height,
                 &Path::ClientState(ClientStatePath::new(connection_end.client_id())),
@@ -197,6 +197,8 @@ where
         &self,
         request: Request<QueryChannelConsensusStateRequest>,
     ) -> Result<Response<QueryChannelConsensusStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -208,24 +210,23 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let channel_end = self.ibc_context.channel_end(&channel_end_path)?;
+        let channel_end = ibc_context.channel_end(&channel_end_path)?;
 
         let connection_end = channel_end
             .connection_hops()
             .first()
-            .map(|connection_id| self.ibc_context.connection_end(connection_id))
+            .map(|connection_id| ibc_context.connection_end(connection_id))
             .ok_or_else(|| {
                 Status::not_found(format!("Channel {} has no connection hops", channel_id))
             })??;
 
         let consensus_path = ClientConsensusStatePath::new(connection_end.client_id(), &height);
 
-        let consensus_state = self.ibc_context.consensus_state(&consensus_path)?;
+        let consensus_state = ibc_context.consensus_state(&consensus_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::ClientConsensusState(consensus_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -246,6 +247,8 @@ where
         &self,
         request: Request<QueryPacketCommitmentRequest>,
     ) -> Result<Response<QueryPacketCommitmentResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -256,12 +259,11 @@ where
 
         let commitment_path = CommitmentPath::new(&port_id, &channel_id, sequence);
 
-        let packet_commitment_data = self.ibc_context.get_packet_commitment(&commitment_path)?;
+        let packet_commitment_data = ibc_context.get_packet_commitment(&commitment_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::Commitment(commitment_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -281,6 +283,8 @@ where
         &self,
         request: Request<QueryPacketCommitmentsRequest>,
     ) -> Result<Response<QueryPacketCommitmentsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -289,12 +293,15 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let commitments = self
-            .ibc_context
-            .packet_commitments(&channel_end_path)?
+        let page = PageRequest::try_from(request_ref.pagination.clone())?;
+
+        let (commitment_paths, page_response) =
+            ibc_context.packet_commitments_paginated(&channel_end_path, &page)?;
+
+        let commitments = commitment_paths
             .into_iter()
             .map(|path| {
-                self.ibc_context
+                ibc_context
                     .get_packet_commitment(&path)
                     .map(|commitment| PacketState {
                         port_id: path.port_id.as_str().into(),
@@ -307,9 +314,8 @@ where
 
         Ok(Response::new(QueryPacketCommitmentsResponse {
             commitments,
-            height: Some(self.ibc_context.host_height()?.into()),
-            // no support for pagination yet
-            pagination: None,
+            height: Some(ibc_context.host_height()?.into()),
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -317,6 +323,8 @@ where
         &self,
         request: Request<QueryPacketReceiptRequest>,
     ) -> Result<Response<QueryPacketReceiptResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -329,12 +337,11 @@ where
 
         // Receipt only has one enum
         // Unreceived packets are not stored
-        let packet_receipt_data = self.ibc_context.get_packet_receipt(&receipt_path);
+        let packet_receipt_data = ibc_context.get_packet_receipt(&receipt_path);
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::Receipt(receipt_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -354,6 +361,8 @@ where
         &self,
         request: Request<QueryPacketAcknowledgementRequest>,
     ) -> Result<Response<QueryPacketAcknowledgementResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -364,14 +373,12 @@ where
 
         let acknowledgement_path = AckPath::new(&port_id, &channel_id, sequence);
 
-        let packet_acknowledgement_data = self
-            .ibc_context
-            .get_packet_acknowledgement(&acknowledgement_path)?;
+        let packet_acknowledgement_data =
+            ibc_context.get_packet_acknowledgement(&acknowledgement_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::Ack(acknowledgement_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -392,6 +399,8 @@ where
         &self,
         request: Request<QueryPacketAcknowledgementsRequest>,
     ) -> Result<Response<QueryPacketAcknowledgementsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -406,12 +415,15 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let acknowledgements = self
-            .ibc_context
-            .packet_acknowledgements(&channel_end_path, commitment_sequences)?
+        let page = PageRequest::try_from(request_ref.pagination.clone())?;
+
+        let (acknowledgement_paths, page_response) = ibc_context
+            .packet_acknowledgements_paginated(&channel_end_path, commitment_sequences, &page)?;
+
+        let acknowledgements = acknowledgement_paths
             .into_iter()
             .map(|path| {
-                self.ibc_context
+                ibc_context
                     .get_packet_acknowledgement(&path)
                     .map(|acknowledgement| PacketState {
                         port_id: path.port_id.as_str().into(),
@@ -424,9 +436,8 @@ where
 
         Ok(Response::new(QueryPacketAcknowledgementsResponse {
             acknowledgements,
-            height: Some(self.ibc_context.host_height()?.into()),
-            // no support for pagination yet
-            pagination: None,
+            height: Some(ibc_context.host_height()?.into()),
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -434,6 +445,8 @@ where
         &self,
         request: Request<QueryUnreceivedPacketsRequest>,
     ) -> Result<Response<QueryUnreceivedPacketsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -448,13 +461,11 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let unreceived_packets = self
-            .ibc_context
-            .unreceived_packets(&channel_end_path, sequences)?;
+        let unreceived_packets = ibc_context.unreceived_packets(&channel_end_path, sequences)?;
 
         Ok(Response::new(QueryUnreceivedPacketsResponse {
             sequences: unreceived_packets.into_iter().map(Into::into).collect(),
-            height: Some(self.ibc_context.host_height()?.into()),
+            height: Some(ibc_context.host_height()?.into()),
         }))
     }
 
@@ -463,6 +474,8 @@ where
         &self,
         request: Request<QueryUnreceivedAcksRequest>,
     ) -> Result<Response<QueryUnreceivedAcksResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -477,13 +490,11 @@ where
 
         let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
 
-        let unreceived_acks = self
-            .ibc_context
-            .unreceived_acks(&channel_end_path, sequences)?;
+        let unreceived_acks = ibc_context.unreceived_acks(&channel_end_path, sequences)?;
 
         Ok(Response::new(QueryUnreceivedAcksResponse {
             sequences: unreceived_acks.into_iter().map(Into::into).collect(),
-            height: Some(self.ibc_context.host_height()?.into()),
+            height: Some(ibc_context.host_height()?.into()),
         }))
     }
 
@@ -491,6 +502,8 @@ where
         &self,
         request: Request<QueryNextSequenceReceiveRequest>,
     ) -> Result<Response<QueryNextSequenceReceiveResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -499,14 +512,11 @@ where
 
         let next_seq_recv_path = SeqRecvPath::new(&port_id, &channel_id);
 
-        let next_sequence_recv = self
-            .ibc_context
-            .get_next_sequence_recv(&next_seq_recv_path)?;
+        let next_sequence_recv = ibc_context.get_next_sequence_recv(&next_seq_recv_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::SeqRecv(next_seq_recv_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -526,6 +536,8 @@ where
         &self,
         request: Request<QueryNextSequenceSendRequest>,
     ) -> Result<Response<QueryNextSequenceSendResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
@@ -534,14 +546,11 @@ where
 
         let next_seq_send_path = SeqSendPath::new(&port_id, &channel_id);
 
-        let next_sequence_send = self
-            .ibc_context
-            .get_next_sequence_send(&next_seq_send_path)?;
+        let next_sequence_send = ibc_context.get_next_sequence_send(&next_seq_send_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::SeqSend(next_seq_send_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
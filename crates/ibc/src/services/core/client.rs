@@ -29,10 +29,10 @@ use crate::core::{ContextError, ValidationContext};
 use crate::hosts::tendermint::upgrade_proposal::UpgradeValidationContext;
 use crate::prelude::*;
 use crate::services::core::context::QueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::core::pagination::PageRequest;
 use crate::Height;
 
-// TODO(rano): currently the services don't support pagination, so we return all the results.
-
 /// Generics `I` and `U` must be a type where writes from one thread are readable from another.
 /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
 pub struct ClientQueryService<I, U>
@@ -81,15 +81,16 @@ where
         &self,
         request: Request<QueryClientStateRequest>,
     ) -> Result<Response<QueryClientStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
-        let client_state = self.ibc_context.client_state(&client_id)?;
+        let client_state = ibc_context.client_state(&client_id)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(
                 current_height,
                 &Path::ClientState(ClientStatePath::new(&client_id)),
@@ -110,9 +111,13 @@ where
 
     async fn client_states(
         &self,
-        _request: Request<QueryClientStatesRequest>,
+        request: Request<QueryClientStatesRequest>,
     ) -> Result<Response<QueryClientStatesResponse>, Status> {
-        let client_states = self.ibc_context.client_states()?;
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let page = PageRequest::try_from(request.get_ref().pagination.clone())?;
+
+        let (client_states, page_response) = ibc_context.client_states_paginated(&page)?;
 
         Ok(Response::new(QueryClientStatesResponse {
             client_states: client_states
@@ -122,8 +127,7 @@ where
                     client_state: Some(state.into()),
                 })
                 .collect(),
-            // no support for pagination yet
-            pagination: None,
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -131,12 +135,14 @@ where
         &self,
         request: Request<QueryConsensusStateRequest>,
     ) -> Result<Response<QueryConsensusStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
 
         let (height, consensus_state) = if request_ref.latest_height {
-            self.ibc_context
+            ibc_context
                 .consensus_states(&client_id)?
                 .into_iter()
                 .max_by_key(|(h, _)| *h)
@@ -149,17 +155,15 @@ where
         } else {
             let height = Height::new(request_ref.revision_number, request_ref.revision_height)
                 .map_err(|e| Status::invalid_argument(e.to_string()))?;
-            let consensus_state = self
-                .ibc_context
-                .consensus_state(&ClientConsensusStatePath::new(&client_id, &height))?;
+            let consensus_state =
+                ibc_context.consensus_state(&ClientConsensusStatePath::new(&client_id, &height))?;
 
             (height, consensus_state)
         };
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(
                 current_height,
                 &Path::ClientConsensusState(ClientConsensusStatePath::new(&client_id, &height)),
@@ -182,11 +186,16 @@ where
         &self,
         request: Request<QueryConsensusStatesRequest>,
     ) -> Result<Response<QueryConsensusStatesResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
 
-        let consensus_states = self.ibc_context.consensus_states(&client_id)?;
+        let page = PageRequest::try_from(request_ref.pagination.clone())?;
+
+        let (consensus_states, page_response) =
+            ibc_context.consensus_states_paginated(&client_id, &page)?;
 
         Ok(Response::new(QueryConsensusStatesResponse {
             consensus_states: consensus_states
@@ -196,8 +205,7 @@ where
                     consensus_state: Some(state.into()),
                 })
                 .collect(),
-            // no support for pagination yet
-            pagination: None,
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -205,19 +213,23 @@ where
         &self,
         request: Request<QueryConsensusStateHeightsRequest>,
     ) -> Result<Response<QueryConsensusStateHeightsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
 
-        let consensus_state_heights = self.ibc_context.consensus_state_heights(&client_id)?;
+        let page = PageRequest::try_from(request_ref.pagination.clone())?;
+
+        let (consensus_state_heights, page_response) =
+            ibc_context.consensus_state_heights_paginated(&client_id, &page)?;
 
         Ok(Response::new(QueryConsensusStateHeightsResponse {
             consensus_state_heights: consensus_state_heights
                 .into_iter()
                 .map(|height| height.into())
                 .collect(),
-            // no support for pagination yet
-            pagination: None,
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -225,12 +237,14 @@ where
         &self,
         request: Request<QueryClientStatusRequest>,
     ) -> Result<Response<QueryClientStatusResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
 
-        let client_state = self.ibc_context.client_state(&client_id)?;
-        let client_validation_ctx = self.ibc_context.get_client_validation_context();
+        let client_state = ibc_context.client_state(&client_id)?;
+        let client_validation_ctx = ibc_context.get_client_validation_context();
         let client_status = client_state
             .status(client_validation_ctx, &client_id)
             .map_err(ContextError::from)?;
@@ -242,11 +256,17 @@ where
 
     async fn client_params(
         &self,
-        _request: Request<QueryClientParamsRequest>,
+        request: Request<QueryClientParamsRequest>,
     ) -> Result<Response<QueryClientParamsResponse>, Status> {
-        Err(Status::unimplemented(
-            "Querying ClientParams is not supported yet",
-        ))
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let params = ibc_context
+            .client_params()
+            .map_err(|_| Status::not_found("Client params not found"))?;
+
+        Ok(Response::new(QueryClientParamsResponse {
+            params: Some(params.into()),
+        }))
     }
 
     async fn upgraded_client_state(
@@ -0,0 +1,237 @@
+//! Client introspection query service.
+//!
+//! Operators monitoring their light clients can query, for each client, its
+//! status, the range of its stored consensus states and the time at which it
+//! expires if it is not updated, through the
+//! `ibc_rs.core.client.v1.ClientInfoQuery` gRPC service defined in
+//! `proto/ibc_rs/core/client/v1/client_info.proto`, whose server and client are
+//! generated in [`crate::services::proto::core::client::v1`]. The underlying
+//! [`ClientInfo`] is computed by
+//! [`QueryContext::client_info`](crate::services::core::context::QueryContext::client_info).
+//!
+//! [`ClientInfoQueryService`](ClientInfoQueryService) takes a generic `I` to store `ibc_context` that implements [`QueryContext`](QueryContext).
+//! `I` must be a type where writes from one thread are readable from another.
+//! This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+
+use alloc::str::FromStr;
+
+use tonic::{Request, Response, Status};
+
+use crate::core::ics02_client::client_state::Status as ClientStatus;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::core::timestamp::Timestamp;
+use crate::prelude::*;
+use crate::services::core::context::QueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::proto::core::client::v1::client_info_query_server::ClientInfoQuery;
+use crate::services::proto::core::client::v1::{
+    ClientInfo as RawClientInfo, QueryClientInfoRequest, QueryClientInfoResponse,
+    QueryClientInfosRequest, QueryClientInfosResponse,
+};
+use crate::Height;
+
+/// A summary of the state of a client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub client_id: ClientId,
+    pub client_type: ClientType,
+    pub status: ClientStatus,
+    /// Latest height of the client state.
+    pub latest_height: Height,
+    /// Height of the earliest consensus state still stored for the client.
+    pub earliest_height: Option<Height>,
+    /// Time at which the client expires if it is not updated, if ever.
+    pub expiry_timestamp: Option<Timestamp>,
+}
+
+impl ClientInfo {
+    /// Returns whether the client expires, or has expired, before `timestamp`.
+    pub fn expires_before(&self, timestamp: Timestamp) -> bool {
+        self.expiry_timestamp.map_or(false, |expiry| {
+            expiry.nanoseconds() < timestamp.nanoseconds()
+        })
+    }
+}
+
+impl From<ClientInfo> for RawClientInfo {
+    fn from(value: ClientInfo) -> Self {
+        Self {
+            client_id: value.client_id.to_string(),
+            client_type: value.client_type.to_string(),
+            status: value.status.to_string(),
+            latest_height: Some(value.latest_height.into()),
+            earliest_height: value.earliest_height.map(Into::into),
+            expiry_timestamp: value.expiry_timestamp.map_or(0, Timestamp::nanoseconds),
+        }
+    }
+}
+
+/// The generic `I` must be a type where writes from one thread are readable from another.
+/// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+pub struct ClientInfoQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    ibc_context: I,
+}
+
+impl<I> ClientInfoQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    /// The parameter `ibc_context` must be a type where writes from one thread are readable from another.
+    /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+    pub fn new(ibc_context: I) -> Self {
+        Self { ibc_context }
+    }
+}
+
+#[tonic::async_trait]
+impl<I> ClientInfoQuery for ClientInfoQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    async fn client_info(
+        &self,
+        request: Request<QueryClientInfoRequest>,
+    ) -> Result<Response<QueryClientInfoResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let client_id = ClientId::from_str(request.get_ref().client_id.as_str())?;
+
+        let client_info = ibc_context.client_info(&client_id)?;
+
+        Ok(Response::new(QueryClientInfoResponse {
+            client_info: Some(client_info.into()),
+            height: Some(ibc_context.host_height()?.into()),
+        }))
+    }
+
+    async fn client_infos(
+        &self,
+        request: Request<QueryClientInfosRequest>,
+    ) -> Result<Response<QueryClientInfosResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let client_infos = match request.get_ref().expiring_before {
+            0 => ibc_context.client_infos()?,
+            expiring_before => {
+                let timestamp = Timestamp::from_nanoseconds(expiring_before)
+                    .map_err(|e| Status::invalid_argument(e.to_string()))?;
+                ibc_context.clients_expiring_before(timestamp)?
+            }
+        };
+
+        Ok(Response::new(QueryClientInfosResponse {
+            client_infos: client_infos.into_iter().map(Into::into).collect(),
+            height: Some(ibc_context.host_height()?.into()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::core::ValidationContext;
+    use crate::mock::client_state::client_type as mock_client_type;
+    use crate::mock::context::{AnyClientState, MockContext};
+    use crate::services::proto::core::client::v1::client_info_query_client::ClientInfoQueryClient;
+    use crate::services::proto::core::client::v1::client_info_query_server::ClientInfoQueryServer;
+
+    #[test_log::test(tokio::test)]
+    async fn query_client_infos() {
+        let mock_client_id = ClientId::new(mock_client_type(), 0).unwrap();
+        let tm_client_id = ClientId::new(tm_client_type(), 1).unwrap();
+
+        let ctx = MockContext::default()
+            .with_client_parametrized(
+                &mock_client_id,
+                Height::new(0, 5).unwrap(),
+                Some(mock_client_type()),
+                None,
+            )
+            .with_client_parametrized(
+                &tm_client_id,
+                Height::new(0, 4).unwrap(),
+                Some(tm_client_type()),
+                None,
+            );
+
+        let tm_client_info = ctx.client_info(&tm_client_id).unwrap();
+        let trusting_period = match ctx.client_state(&tm_client_id).unwrap() {
+            AnyClientState::Tendermint(client_state) => client_state.trusting_period,
+            AnyClientState::Mock(_) => unreachable!(),
+        };
+        let expiry_timestamp = tm_client_info.expiry_timestamp.unwrap();
+        assert!(expiry_timestamp.nanoseconds() > Timestamp::now().nanoseconds());
+        assert!(
+            (Timestamp::now() + trusting_period).unwrap().nanoseconds()
+                >= expiry_timestamp.nanoseconds()
+        );
+        assert_eq!(tm_client_info.status, ClientStatus::Active);
+
+        let heights = Height::new(0, 5).unwrap()..=Height::new(0, 6).unwrap();
+        assert_eq!(
+            ctx.consensus_states_in_range(&mock_client_id, heights.clone())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(ctx
+            .consensus_states_in_range(&tm_client_id, heights)
+            .unwrap()
+            .is_empty());
+
+        let mut client = ClientInfoQueryClient::new(ClientInfoQueryServer::new(
+            ClientInfoQueryService::new(ctx),
+        ));
+
+        let mock_client_info = client
+            .client_info(QueryClientInfoRequest {
+                client_id: mock_client_id.to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .client_info
+            .unwrap();
+        assert_eq!(mock_client_info.status, "Active");
+        assert_eq!(mock_client_info.latest_height.unwrap().revision_height, 5);
+        assert_eq!(mock_client_info.earliest_height.unwrap().revision_height, 5);
+        assert_eq!(mock_client_info.expiry_timestamp, 0);
+
+        let client_infos = client
+            .client_infos(QueryClientInfosRequest { expiring_before: 0 })
+            .await
+            .unwrap()
+            .into_inner()
+            .client_infos;
+        assert_eq!(client_infos.len(), 2);
+
+        let expiring_before = |timestamp: Timestamp| QueryClientInfosRequest {
+            expiring_before: timestamp.nanoseconds(),
+        };
+
+        let client_infos = client
+            .client_infos(expiring_before(Timestamp::now()))
+            .await
+            .unwrap()
+            .into_inner()
+            .client_infos;
+        assert!(client_infos.is_empty());
+
+        let later = (Timestamp::now() + (trusting_period + Duration::from_secs(1))).unwrap();
+        let client_infos = client
+            .client_infos(expiring_before(later))
+            .await
+            .unwrap()
+            .into_inner()
+            .client_infos;
+        assert_eq!(client_infos.len(), 1);
+        assert_eq!(client_infos[0].client_id, tm_client_id.to_string());
+    }
+}
@@ -9,7 +9,7 @@ use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::client::v1::IdentifiedClientState;
 use ibc_proto::ibc::core::connection::v1::query_server::Query as ConnectionQuery;
 use ibc_proto::ibc::core::connection::v1::{
-    Params as ConnectionParams, QueryClientConnectionsRequest, QueryClientConnectionsResponse,
+    QueryClientConnectionsRequest, QueryClientConnectionsResponse,
     QueryConnectionClientStateRequest, QueryConnectionClientStateResponse,
     QueryConnectionConsensusStateRequest, QueryConnectionConsensusStateResponse,
     QueryConnectionParamsRequest, QueryConnectionParamsResponse, QueryConnectionRequest,
@@ -24,10 +24,10 @@ use crate::core::ics24_host::path::{
 use crate::core::ValidationContext;
 use crate::prelude::*;
 use crate::services::core::context::QueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::core::pagination::PageRequest;
 use crate::Height;
 
-// TODO(rano): currently the services don't support pagination, so we return all the results.
-
 /// The generic `I` must be a type where writes from one thread are readable from another.
 /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
 pub struct ConnectionQueryService<I>
@@ -63,16 +63,17 @@ where
         &self,
         request: Request<QueryConnectionRequest>,
     ) -> Result<Response<QueryConnectionResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let connection_id = ConnectionId::from_str(request_ref.connection_id.as_str())?;
 
-        let connection_end = self.ibc_context.connection_end(&connection_id)?;
+        let connection_end = ibc_context.connection_end(&connection_id)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(
                 current_height,
                 &Path::Connection(ConnectionPath::new(&connection_id)),
@@ -93,15 +94,18 @@ where
 
     async fn connections(
         &self,
-        _request: Request<QueryConnectionsRequest>,
+        request: Request<QueryConnectionsRequest>,
     ) -> Result<Response<QueryConnectionsResponse>, Status> {
-        let connections = self.ibc_context.connection_ends()?;
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let page = PageRequest::try_from(request.get_ref().pagination.clone())?;
+
+        let (connections, page_response) = ibc_context.connection_ends_paginated(&page)?;
 
         Ok(Response::new(QueryConnectionsResponse {
             connections: connections.into_iter().map(Into::into).collect(),
-            height: Some(self.ibc_context.host_height()?.into()),
-            // no support for pagination yet
-            pagination: None,
+            height: Some(ibc_context.host_height()?.into()),
+            pagination: Some(page_response.into()),
         }))
     }
 
@@ -109,16 +113,17 @@ where
         &self,
         request: Request<QueryClientConnectionsRequest>,
     ) -> Result<Response<QueryClientConnectionsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let client_id = ClientId::from_str(request_ref.client_id.as_str())?;
 
-        let connections = self.ibc_context.client_connection_ends(&client_id)?;
+        let connections = ibc_context.client_connection_ends(&client_id)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof: Vec<u8> = self
-            .ibc_context
+        let proof: Vec<u8> = ibc_context
             .get_proof(
                 current_height,
                 &Path::ClientConnection(ClientConnectionPath::new(&client_id)),
@@ -141,18 +146,19 @@ where
         &self,
         request: Request<QueryConnectionClientStateRequest>,
     ) -> Result<Response<QueryConnectionClientStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let connection_id = ConnectionId::from_str(request_ref.connection_id.as_str())?;
 
-        let connection_end = self.ibc_context.connection_end(&connection_id)?;
+        let connection_end = ibc_context.connection_end(&connection_id)?;
 
-        let client_state = self.ibc_context.client_state(connection_end.client_id())?;
+        let client_state = ibc_context.client_state(connection_end.client_id())?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(
                 current_height,
                 &Path::ClientState(ClientStatePath::new(connection_end.client_id())),
@@ -178,23 +184,24 @@ where
         &self,
         request: Request<QueryConnectionConsensusStateRequest>,
     ) -> Result<Response<QueryConnectionConsensusStateResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
         let request_ref = request.get_ref();
 
         let connection_id = ConnectionId::from_str(request_ref.connection_id.as_str())?;
 
-        let connection_end = self.ibc_context.connection_end(&connection_id)?;
+        let connection_end = ibc_context.connection_end(&connection_id)?;
 
         let height = Height::new(request_ref.revision_number, request_ref.revision_height)
             .map_err(|e| Status::invalid_argument(e.to_string()))?;
 
         let consensus_path = ClientConsensusStatePath::new(connection_end.client_id(), &height);
 
-        let consensus_state = self.ibc_context.consensus_state(&consensus_path)?;
+        let consensus_state = ibc_context.consensus_state(&consensus_path)?;
 
-        let current_height = self.ibc_context.host_height()?;
+        let current_height = ibc_context.host_height()?;
 
-        let proof = self
-            .ibc_context
+        let proof = ibc_context
             .get_proof(current_height, &Path::ClientConsensusState(consensus_path))
             .ok_or_else(|| {
                 Status::not_found(format!(
@@ -213,15 +220,16 @@ where
 
     async fn connection_params(
         &self,
-        _request: Request<QueryConnectionParamsRequest>,
+        request: Request<QueryConnectionParamsRequest>,
     ) -> Result<Response<QueryConnectionParamsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let params = ibc_context
+            .connection_params()
+            .map_err(|_| Status::not_found("Connection params not found"))?;
+
         Ok(Response::new(QueryConnectionParamsResponse {
-            params: Some(ConnectionParams {
-                max_expected_time_per_block: self
-                    .ibc_context
-                    .max_expected_time_per_block()
-                    .as_secs(),
-            }),
+            params: Some(params.into()),
         }))
     }
 }
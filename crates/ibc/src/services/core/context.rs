@@ -1,12 +1,25 @@
 //! Required traits for blanket implementations of [`gRPC query services`](crate::services::core).
 
+use core::ops::RangeBounds;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use prost::Message;
+
+use crate::core::ics02_client::client_state::{ClientStateCommon, ClientStateValidation};
 use crate::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::IdentifiedChannelEnd;
-use crate::core::ics04_channel::packet::Sequence;
-use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics04_channel::packet::{Packet, Sequence};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics23_commitment::merkle::MerkleProof;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::core::ics24_host::path::{AckPath, ChannelEndPath, CommitmentPath, Path};
+use crate::core::timestamp::Timestamp;
 use crate::core::{ContextError, ValidationContext};
 use crate::prelude::*;
+use crate::services::core::client_info::ClientInfo;
+use crate::services::core::height::QueryHeightError;
+use crate::services::core::pagination::{PageRequest, Paginated};
 use crate::Height;
 
 /// Context to be implemented by the host to provide proofs in gRPC query responses
@@ -16,12 +29,50 @@ pub trait ProvableContext {
     /// Returns the proof for the given path at the given height.
     /// As this is in the context of IBC, the path is expected to be an [`IbcPath`](Path).
     fn get_proof(&self, height: Height, path: &Path) -> Option<Vec<u8>>;
+
+    /// Returns a single proof for all the given paths at the given height, whose
+    /// lowest layer is an ICS-23 `CompressedBatchProof`.
+    ///
+    /// The default implementation combines the proofs of each path with
+    /// [`MerkleProof::batch`]; hosts able to build a multi-proof directly
+    /// from their store should override it.
+    fn get_batch_proof(&self, height: Height, paths: &[Path]) -> Option<Vec<u8>> {
+        let proofs = paths
+            .iter()
+            .map(|path| {
+                let proof = self.get_proof(height, path)?;
+                RawMerkleProof::decode(proof.as_slice())
+                    .ok()
+                    .map(MerkleProof::from)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let batch_proof = MerkleProof::batch(proofs).ok()?;
+
+        CommitmentProofBytes::try_from(batch_proof)
+            .ok()
+            .map(Into::into)
+    }
 }
 
 /// Context to be implemented by the host that provides gRPC query services.
 ///
 /// Trait used for the [`gRPC query services`](crate::services).
 pub trait QueryContext: ProvableContext + ValidationContext {
+    /// Returns a read-only view of the host state at the given past height,
+    /// used to answer queries made at that height.
+    ///
+    /// The `host_height` of the returned context must be `height`, so that the
+    /// proofs it provides are at the requested height. Hosts should return
+    /// [`QueryHeightError::Pruned`] once the state at `height` is no longer
+    /// available. The default implementation does not support past heights.
+    fn query_context_at(&self, height: &Height) -> Result<Self, QueryHeightError>
+    where
+        Self: Sized,
+    {
+        Err(QueryHeightError::Unsupported { height: *height })
+    }
+
     // Client queries
 
     /// Returns the list of all clients.
@@ -38,6 +89,69 @@ pub trait QueryContext: ProvableContext + ValidationContext {
     /// Returns the list of all heights at which consensus states for the given client are.
     fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError>;
 
+    /// Returns the consensus states of the given client at heights within
+    /// `range`, sorted by height.
+    fn consensus_states_in_range(
+        &self,
+        client_id: &ClientId,
+        range: impl RangeBounds<Height>,
+    ) -> Result<Vec<(Height, <Self as ValidationContext>::AnyConsensusState)>, ContextError> {
+        let mut consensus_states: Vec<_> = self
+            .consensus_states(client_id)?
+            .into_iter()
+            .filter(|(height, _)| range.contains(height))
+            .collect();
+
+        consensus_states.sort_by_key(|(height, _)| *height);
+
+        Ok(consensus_states)
+    }
+
+    /// Returns the status, expiry and stored heights of the given client.
+    fn client_info(&self, client_id: &ClientId) -> Result<ClientInfo, ContextError> {
+        let client_state = self.client_state(client_id)?;
+        let client_validation_ctx = self.get_client_validation_context();
+
+        let status = client_state.status(client_validation_ctx, client_id)?;
+        let expiry_timestamp = client_state.expiry_timestamp(client_validation_ctx, client_id)?;
+        let earliest_height = self.consensus_state_heights(client_id)?.into_iter().min();
+
+        Ok(ClientInfo {
+            client_id: client_id.clone(),
+            client_type: client_state.client_type(),
+            status,
+            latest_height: client_state.latest_height(),
+            earliest_height,
+            expiry_timestamp,
+        })
+    }
+
+    /// Returns the [`ClientInfo`] of all clients.
+    fn client_infos(&self) -> Result<Vec<ClientInfo>, ContextError> {
+        self.client_states()?
+            .iter()
+            .map(|(client_id, _)| self.client_info(client_id))
+            .collect()
+    }
+
+    /// Returns the clients expiring before `timestamp`, including the already
+    /// expired ones, sorted by expiry. Clients that never expire are omitted.
+    fn clients_expiring_before(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Vec<ClientInfo>, ContextError> {
+        let mut client_infos: Vec<_> = self
+            .client_infos()?
+            .into_iter()
+            .filter(|client_info| client_info.expires_before(timestamp))
+            .collect();
+
+        client_infos
+            .sort_by_key(|client_info| client_info.expiry_timestamp.map(Timestamp::nanoseconds));
+
+        Ok(client_infos)
+    }
+
     // Connection queries
 
     /// Returns the list of all connection ends.
@@ -84,4 +198,189 @@ pub trait QueryContext: ProvableContext + ValidationContext {
         channel_end_path: &ChannelEndPath,
         sequences: impl ExactSizeIterator<Item = Sequence>,
     ) -> Result<Vec<Sequence>, ContextError>;
+
+    // Paginated queries
+    //
+    // The default implementations load the full list from the methods above and
+    // paginate it in memory. Hosts with large stores should override them to
+    // only iterate over the requested page of their store.
+
+    /// Returns a page of clients, keyed by client identifier.
+    fn client_states_paginated(
+        &self,
+        page: &PageRequest,
+    ) -> Result<Paginated<(ClientId, <Self as ValidationContext>::AnyClientState)>, ContextError>
+    {
+        let client_states = self.client_states()?;
+
+        Ok(page.paginate(
+            client_states
+                .into_iter()
+                .map(|entry| (entry.0.as_str().as_bytes().to_vec(), entry)),
+        ))
+    }
+
+    /// Returns a page of consensus states for the given client, keyed by height.
+    fn consensus_states_paginated(
+        &self,
+        client_id: &ClientId,
+        page: &PageRequest,
+    ) -> Result<Paginated<(Height, <Self as ValidationContext>::AnyConsensusState)>, ContextError>
+    {
+        let consensus_states = self.consensus_states(client_id)?;
+
+        Ok(page.paginate(
+            consensus_states
+                .into_iter()
+                .map(|entry| (height_key(&entry.0), entry)),
+        ))
+    }
+
+    /// Returns a page of consensus state heights for the given client.
+    fn consensus_state_heights_paginated(
+        &self,
+        client_id: &ClientId,
+        page: &PageRequest,
+    ) -> Result<Paginated<Height>, ContextError> {
+        let heights = self.consensus_state_heights(client_id)?;
+
+        Ok(page.paginate(
+            heights
+                .into_iter()
+                .map(|height| (height_key(&height), height)),
+        ))
+    }
+
+    /// Returns a page of connection ends, keyed by connection identifier.
+    fn connection_ends_paginated(
+        &self,
+        page: &PageRequest,
+    ) -> Result<Paginated<IdentifiedConnectionEnd>, ContextError> {
+        let connection_ends = self.connection_ends()?;
+
+        Ok(
+            page.paginate(connection_ends.into_iter().map(|connection_end| {
+                (
+                    connection_end.connection_id.as_str().as_bytes().to_vec(),
+                    connection_end,
+                )
+            })),
+        )
+    }
+
+    /// Returns a page of channel ends, keyed by port and channel identifiers.
+    fn channel_ends_paginated(
+        &self,
+        page: &PageRequest,
+    ) -> Result<Paginated<IdentifiedChannelEnd>, ContextError> {
+        let channel_ends = self.channel_ends()?;
+
+        Ok(page.paginate(channel_ends.into_iter().map(|channel_end| {
+            (
+                channel_key(&channel_end.port_id, &channel_end.channel_id),
+                channel_end,
+            )
+        })))
+    }
+
+    /// Returns a page of the channel ends whose connection hops include the given connection.
+    fn connection_channel_ends_paginated(
+        &self,
+        connection_id: &ConnectionId,
+        page: &PageRequest,
+    ) -> Result<Paginated<IdentifiedChannelEnd>, ContextError> {
+        let channel_ends = self.channel_ends()?;
+
+        Ok(page.paginate(
+            channel_ends
+                .into_iter()
+                .filter(|channel_end| {
+                    channel_end
+                        .channel_end
+                        .connection_hops()
+                        .contains(connection_id)
+                })
+                .map(|channel_end| {
+                    (
+                        channel_key(&channel_end.port_id, &channel_end.channel_id),
+                        channel_end,
+                    )
+                }),
+        ))
+    }
+
+    /// Returns a page of packet commitments for the given channel end, keyed by sequence.
+    fn packet_commitments_paginated(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        page: &PageRequest,
+    ) -> Result<Paginated<CommitmentPath>, ContextError> {
+        let commitments = self.packet_commitments(channel_end_path)?;
+
+        Ok(page.paginate(
+            commitments
+                .into_iter()
+                .map(|path| (sequence_key(path.sequence), path)),
+        ))
+    }
+
+    /// Returns a page of the packet acknowledgements for the given channel end, keyed by sequence.
+    /// Pages through all the packet acknowledgements if `sequences` is empty.
+    fn packet_acknowledgements_paginated(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+        page: &PageRequest,
+    ) -> Result<Paginated<AckPath>, ContextError> {
+        let acknowledgements = self.packet_acknowledgements(channel_end_path, sequences)?;
+
+        Ok(page.paginate(
+            acknowledgements
+                .into_iter()
+                .map(|path| (sequence_key(path.sequence), path)),
+        ))
+    }
+}
+
+/// Pagination key of a height, ordered by revision number then revision height.
+fn height_key(height: &Height) -> Vec<u8> {
+    [
+        height.revision_number().to_be_bytes(),
+        height.revision_height().to_be_bytes(),
+    ]
+    .concat()
+}
+
+/// Pagination key of a packet sequence.
+fn sequence_key(sequence: Sequence) -> Vec<u8> {
+    u64::from(sequence).to_be_bytes().to_vec()
+}
+
+/// Pagination key of a channel end.
+fn channel_key(port_id: &PortId, channel_id: &ChannelId) -> Vec<u8> {
+    format!("{port_id}/{channel_id}").into_bytes()
+}
+
+/// Context to be implemented by hosts that record packet data, through
+/// [`ExecutionContext::record_sent_packet`](crate::core::ExecutionContext::record_sent_packet) and
+/// [`ExecutionContext::record_written_acknowledgement`](crate::core::ExecutionContext::record_written_acknowledgement).
+///
+/// Trait used for the [`packet data query service`](crate::services::core::packet_data).
+pub trait PacketDataQueryContext: QueryContext {
+    /// Returns the recorded packets sent on the given channel end with the given sequences.
+    /// Returns all the recorded packets of the channel end if `sequences` is empty.
+    fn sent_packets(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<Packet>, ContextError>;
+
+    /// Returns the recorded packets received on the given channel end with the given
+    /// sequences, along with the acknowledgements written for them.
+    /// Returns all the recorded acknowledgements of the channel end if `sequences` is empty.
+    fn written_acknowledgements(
+        &self,
+        channel_end_path: &ChannelEndPath,
+        sequences: impl ExactSizeIterator<Item = Sequence>,
+    ) -> Result<Vec<(Packet, Acknowledgement)>, ContextError>;
 }
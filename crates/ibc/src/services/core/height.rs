@@ -0,0 +1,160 @@
+//! Resolves the height at which the [`gRPC query services`](crate::services::core) answer a request.
+//!
+//! As in the Cosmos SDK, a client selects a past height by setting the
+//! [`BLOCK_HEIGHT_METADATA_KEY`] request metadata to a block height. A missing
+//! header, or a height of `0`, selects the latest height of the host.
+
+use core::ops::Deref;
+use core::str::FromStr;
+
+use displaydoc::Display;
+use tonic::{Request, Status};
+
+use crate::prelude::*;
+use crate::services::core::context::QueryContext;
+use crate::Height;
+
+/// The gRPC metadata key carrying the height a query is made at.
+pub const BLOCK_HEIGHT_METADATA_KEY: &str = "x-cosmos-block-height";
+
+#[derive(Debug, Display)]
+pub enum QueryHeightError {
+    /// invalid query height `{value}`
+    InvalidHeight { value: String },
+    /// query height `{height}` is greater than the latest height `{latest}`
+    FutureHeight { height: Height, latest: Height },
+    /// state at height `{height}` has been pruned
+    Pruned { height: Height },
+    /// queries at past height `{height}` are not supported by the host
+    Unsupported { height: Height },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QueryHeightError {}
+
+impl From<QueryHeightError> for Status {
+    fn from(err: QueryHeightError) -> Self {
+        match err {
+            QueryHeightError::InvalidHeight { .. } | QueryHeightError::FutureHeight { .. } => {
+                Status::invalid_argument(err.to_string())
+            }
+            QueryHeightError::Pruned { .. } => Status::not_found(err.to_string()),
+            QueryHeightError::Unsupported { .. } => Status::unimplemented(err.to_string()),
+        }
+    }
+}
+
+/// Returns the block height set in the request metadata, if any.
+///
+/// The height is interpreted in the current revision of the host.
+pub fn requested_height<T>(
+    request: &Request<T>,
+    host_height: Height,
+) -> Result<Option<Height>, QueryHeightError> {
+    let value = match request.metadata().get(BLOCK_HEIGHT_METADATA_KEY) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    let invalid = || QueryHeightError::InvalidHeight {
+        value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+    };
+
+    let revision_height = value
+        .to_str()
+        .ok()
+        .and_then(|value| u64::from_str(value).ok())
+        .ok_or_else(invalid)?;
+
+    if revision_height == 0 {
+        return Ok(None);
+    }
+
+    let height =
+        Height::new(host_height.revision_number(), revision_height).map_err(|_| invalid())?;
+
+    if height > host_height {
+        return Err(QueryHeightError::FutureHeight {
+            height,
+            latest: host_height,
+        });
+    }
+
+    Ok(Some(height))
+}
+
+/// The context a query is answered from: either the host context itself, or a
+/// view of its state at a past height.
+pub(crate) enum QueryView<'a, I> {
+    Latest(&'a I),
+    Historical(I),
+}
+
+impl<'a, I> QueryView<'a, I>
+where
+    I: QueryContext,
+{
+    /// Selects the context answering `request`, based on its block height metadata.
+    pub(crate) fn new<T>(ibc_context: &'a I, request: &Request<T>) -> Result<Self, Status> {
+        let host_height = ibc_context.host_height()?;
+
+        match requested_height(request, host_height)? {
+            Some(height) if height != host_height => {
+                Ok(Self::Historical(ibc_context.query_context_at(&height)?))
+            }
+            _ => Ok(Self::Latest(ibc_context)),
+        }
+    }
+}
+
+impl<'a, I> Deref for QueryView<'a, I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        match self {
+            Self::Latest(ibc_context) => ibc_context,
+            Self::Historical(ibc_context) => ibc_context,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    fn request_at(value: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert(BLOCK_HEIGHT_METADATA_KEY, value.parse().unwrap());
+        request
+    }
+
+    #[test]
+    fn parse_requested_height() {
+        let host_height = Height::new(1, 10).unwrap();
+
+        assert_eq!(
+            requested_height(&Request::new(()), host_height).unwrap(),
+            None
+        );
+        assert_eq!(
+            requested_height(&request_at("0"), host_height).unwrap(),
+            None
+        );
+        assert_eq!(
+            requested_height(&request_at("7"), host_height).unwrap(),
+            Some(Height::new(1, 7).unwrap())
+        );
+        assert!(matches!(
+            requested_height(&request_at("11"), host_height),
+            Err(QueryHeightError::FutureHeight { .. })
+        ));
+        assert!(matches!(
+            requested_height(&request_at("-1"), host_height),
+            Err(QueryHeightError::InvalidHeight { .. })
+        ));
+    }
+}
@@ -1,4 +1,10 @@
 pub mod channel;
 pub mod client;
+pub mod client_info;
 pub mod connection;
 pub mod context;
+pub mod height;
+pub mod packet_data;
+pub mod pagination;
+pub mod proof;
+pub mod verify;
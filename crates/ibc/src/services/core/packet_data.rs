@@ -0,0 +1,195 @@
+//! Packet data query service.
+//!
+//! The IBC store only holds packet and acknowledgement commitments, so relayers
+//! usually rebuild packets from the events of the transactions that sent them.
+//! Hosts that record packet data through
+//! [`ExecutionContext::record_sent_packet`](crate::core::ExecutionContext::record_sent_packet) and
+//! [`ExecutionContext::record_written_acknowledgement`](crate::core::ExecutionContext::record_written_acknowledgement)
+//! can instead serve it with [`PacketDataQueryService`], through the
+//! `ibc_rs.core.channel.v1.PacketDataQuery` gRPC service defined in
+//! `proto/ibc_rs/core/channel/v1/packet_data.proto`. Its server and client are
+//! generated in [`crate::services::proto::core::channel::v1`].
+//!
+//! [`PacketDataQueryService`](PacketDataQueryService) takes a generic `I` to store `ibc_context` that implements [`PacketDataQueryContext`](PacketDataQueryContext).
+//! `I` must be a type where writes from one thread are readable from another.
+//! This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+
+use alloc::str::FromStr;
+
+use tonic::{Request, Response, Status};
+
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::path::ChannelEndPath;
+use crate::prelude::*;
+use crate::services::core::context::PacketDataQueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::proto::core::channel::v1::packet_data_query_server::PacketDataQuery;
+use crate::services::proto::core::channel::v1::{
+    PacketAcknowledgement, QuerySentPacketsRequest, QuerySentPacketsResponse,
+    QueryWrittenAcknowledgementsRequest, QueryWrittenAcknowledgementsResponse,
+};
+
+/// The generic `I` must be a type where writes from one thread are readable from another.
+/// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+pub struct PacketDataQueryService<I>
+where
+    I: PacketDataQueryContext + Send + Sync + 'static,
+{
+    ibc_context: I,
+}
+
+impl<I> PacketDataQueryService<I>
+where
+    I: PacketDataQueryContext + Send + Sync + 'static,
+{
+    /// The parameter `ibc_context` must be a type where writes from one thread are readable from another.
+    /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+    pub fn new(ibc_context: I) -> Self {
+        Self { ibc_context }
+    }
+}
+
+#[tonic::async_trait]
+impl<I> PacketDataQuery for PacketDataQueryService<I>
+where
+    I: PacketDataQueryContext + Send + Sync + 'static,
+{
+    async fn sent_packets(
+        &self,
+        request: Request<QuerySentPacketsRequest>,
+    ) -> Result<Response<QuerySentPacketsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let request_ref = request.get_ref();
+
+        let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
+
+        let port_id = PortId::from_str(request_ref.port_id.as_str())?;
+
+        let sequences = request_ref.sequences.iter().copied().map(Sequence::from);
+
+        let packets =
+            ibc_context.sent_packets(&ChannelEndPath::new(&port_id, &channel_id), sequences)?;
+
+        Ok(Response::new(QuerySentPacketsResponse {
+            packets: packets.into_iter().map(Into::into).collect(),
+            height: Some(ibc_context.host_height()?.into()),
+        }))
+    }
+
+    async fn written_acknowledgements(
+        &self,
+        request: Request<QueryWrittenAcknowledgementsRequest>,
+    ) -> Result<Response<QueryWrittenAcknowledgementsResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let request_ref = request.get_ref();
+
+        let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
+
+        let port_id = PortId::from_str(request_ref.port_id.as_str())?;
+
+        let sequences = request_ref.sequences.iter().copied().map(Sequence::from);
+
+        let acknowledgements = ibc_context
+            .written_acknowledgements(&ChannelEndPath::new(&port_id, &channel_id), sequences)?;
+
+        Ok(Response::new(QueryWrittenAcknowledgementsResponse {
+            acknowledgements: acknowledgements
+                .into_iter()
+                .map(|(packet, acknowledgement)| PacketAcknowledgement {
+                    packet: Some(packet.into()),
+                    acknowledgement: acknowledgement.into(),
+                })
+                .collect(),
+            height: Some(ibc_context.host_height()?.into()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::*;
+    use crate::core::ics04_channel::acknowledgement::Acknowledgement;
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ExecutionContext;
+    use crate::mock::context::MockContext;
+    use crate::services::proto::core::channel::v1::packet_data_query_client::PacketDataQueryClient;
+    use crate::services::proto::core::channel::v1::packet_data_query_server::PacketDataQueryServer;
+
+    fn packet(sequence: u64) -> Packet {
+        let mut raw_packet = get_dummy_raw_packet(10, 0);
+        raw_packet.sequence = sequence;
+        Packet::try_from(raw_packet).unwrap()
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_recorded_packet_data() {
+        let mut ctx = MockContext::default();
+        for sequence in 1..=3 {
+            ctx.record_sent_packet(&packet(sequence)).unwrap();
+        }
+        let acknowledgement = Acknowledgement::try_from(vec![1]).unwrap();
+        ctx.record_written_acknowledgement(&packet(2), &acknowledgement)
+            .unwrap();
+
+        let mut client = PacketDataQueryClient::new(PacketDataQueryServer::new(
+            PacketDataQueryService::new(ctx),
+        ));
+
+        let request = QuerySentPacketsRequest {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            sequences: vec![1, 3, 4],
+        };
+        let packets = client
+            .sent_packets(request.clone())
+            .await
+            .unwrap()
+            .into_inner()
+            .packets;
+        assert_eq!(
+            packets.iter().map(|p| p.sequence).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        let packets = client
+            .sent_packets(QuerySentPacketsRequest {
+                sequences: vec![],
+                ..request
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .packets;
+        assert_eq!(packets.len(), 3);
+
+        let acknowledgements = client
+            .written_acknowledgements(QueryWrittenAcknowledgementsRequest {
+                port_id: PortId::default().to_string(),
+                channel_id: ChannelId::default().to_string(),
+                sequences: vec![],
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .acknowledgements;
+        assert_eq!(acknowledgements.len(), 1);
+        assert_eq!(acknowledgements[0].packet.as_ref().unwrap().sequence, 2);
+        assert_eq!(acknowledgements[0].acknowledgement, vec![1]);
+
+        let status = client
+            .sent_packets(QuerySentPacketsRequest {
+                port_id: "invalid port".to_string(),
+                channel_id: ChannelId::default().to_string(),
+                sequences: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
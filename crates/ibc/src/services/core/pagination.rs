@@ -0,0 +1,234 @@
+//! Defines the pagination types used by the list endpoints of the [`gRPC query services`](crate::services::core).
+//!
+//! The semantics follow the Cosmos SDK `query.Paginate` helper: a page either
+//! starts at an opaque `key` (inclusive) or at an `offset`, but not both.
+
+use displaydoc::Display;
+use ibc_proto::cosmos::base::query::v1beta1::{
+    PageRequest as RawPageRequest, PageResponse as RawPageResponse,
+};
+
+use crate::prelude::*;
+
+/// Number of items returned when a request does not set a limit.
+pub const DEFAULT_PAGE_LIMIT: u64 = 100;
+
+#[derive(Debug, Display)]
+pub enum PaginationError {
+    /// invalid page request: either offset or key is expected, got both
+    KeyAndOffset,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PaginationError {}
+
+/// A page of items along with its [`PageResponse`].
+pub type Paginated<T> = (Vec<T>, PageResponse);
+
+/// A request for a single page of a list query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Key of the first item of the page, as returned in a previous `next_key`.
+    pub key: Vec<u8>,
+    /// Number of items to skip; only used when `key` is empty.
+    pub offset: u64,
+    /// Maximum number of items in the page; `0` means [`DEFAULT_PAGE_LIMIT`].
+    pub limit: u64,
+    /// Whether the response should carry the total number of items.
+    pub count_total: bool,
+    /// Whether items are returned in descending key order.
+    pub reverse: bool,
+}
+
+impl PageRequest {
+    /// Returns a request for every item in a single page.
+    pub fn all() -> Self {
+        Self {
+            limit: u64::MAX,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the effective maximum number of items in the page.
+    pub fn limit(&self) -> u64 {
+        if self.limit == 0 {
+            DEFAULT_PAGE_LIMIT
+        } else {
+            self.limit
+        }
+    }
+
+    /// Returns the page of `items` selected by this request.
+    ///
+    /// Each item is paired with the key it is ordered by. Keys must be unique
+    /// and the encoding must preserve the intended ordering (e.g. big-endian
+    /// integers). `items` need not be sorted.
+    pub fn paginate<T>(&self, items: impl IntoIterator<Item = (Vec<u8>, T)>) -> Paginated<T> {
+        let mut items: Vec<_> = items.into_iter().collect();
+
+        items.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if self.reverse {
+            items.reverse();
+        }
+
+        let total = items.len() as u64;
+
+        let start = if self.key.is_empty() {
+            usize::try_from(self.offset).unwrap_or(usize::MAX)
+        } else {
+            items
+                .iter()
+                .position(|(key, _)| {
+                    if self.reverse {
+                        key <= &self.key
+                    } else {
+                        key >= &self.key
+                    }
+                })
+                .unwrap_or(items.len())
+        };
+
+        let limit = usize::try_from(self.limit()).unwrap_or(usize::MAX);
+
+        let mut rest = items.into_iter().skip(start);
+        let page = rest.by_ref().take(limit).map(|(_, item)| item).collect();
+        let next_key = rest.next().map(|(key, _)| key).unwrap_or_default();
+
+        (
+            page,
+            PageResponse {
+                next_key,
+                total: if self.count_total { Some(total) } else { None },
+            },
+        )
+    }
+}
+
+impl TryFrom<RawPageRequest> for PageRequest {
+    type Error = PaginationError;
+
+    fn try_from(raw: RawPageRequest) -> Result<Self, Self::Error> {
+        if !raw.key.is_empty() && raw.offset != 0 {
+            return Err(PaginationError::KeyAndOffset);
+        }
+
+        Ok(Self {
+            key: raw.key,
+            offset: raw.offset,
+            limit: raw.limit,
+            count_total: raw.count_total,
+            reverse: raw.reverse,
+        })
+    }
+}
+
+impl TryFrom<Option<RawPageRequest>> for PageRequest {
+    type Error = PaginationError;
+
+    fn try_from(raw: Option<RawPageRequest>) -> Result<Self, Self::Error> {
+        raw.map_or_else(|| Ok(Self::default()), TryInto::try_into)
+    }
+}
+
+/// The pagination metadata of a list query response.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageResponse {
+    /// Key of the first item of the next page; empty if there are no more items.
+    pub next_key: Vec<u8>,
+    /// Total number of items, if requested with `count_total`.
+    pub total: Option<u64>,
+}
+
+impl From<PageResponse> for RawPageResponse {
+    fn from(value: PageResponse) -> Self {
+        Self {
+            next_key: value.next_key,
+            total: value.total.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    fn items() -> Vec<(Vec<u8>, u64)> {
+        // deliberately unsorted
+        [3u64, 1, 5, 2, 4]
+            .into_iter()
+            .map(|i| (i.to_be_bytes().to_vec(), i))
+            .collect()
+    }
+
+    #[test]
+    fn paginate_by_key() {
+        let mut request = PageRequest {
+            limit: 2,
+            count_total: true,
+            ..Default::default()
+        };
+
+        let mut pages = vec![];
+        loop {
+            let (page, response) = request.paginate(items());
+            assert_eq!(response.total, Some(5));
+            pages.push(page);
+            if response.next_key.is_empty() {
+                break;
+            }
+            request.key = response.next_key;
+        }
+
+        assert_eq!(pages, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn paginate_reverse_by_key() {
+        let (page, response) = PageRequest {
+            key: 4u64.to_be_bytes().to_vec(),
+            limit: 2,
+            reverse: true,
+            ..Default::default()
+        }
+        .paginate(items());
+
+        assert_eq!(page, vec![4, 3]);
+        assert_eq!(response.next_key, 2u64.to_be_bytes().to_vec());
+        assert_eq!(response.total, None);
+    }
+
+    #[test]
+    fn paginate_by_offset() {
+        let request = PageRequest {
+            offset: 3,
+            ..Default::default()
+        };
+        let (page, response) = request.paginate(items());
+
+        assert_eq!(page, vec![4, 5]);
+        assert!(response.next_key.is_empty());
+
+        let (page, _) = PageRequest {
+            offset: 10,
+            ..Default::default()
+        }
+        .paginate(items());
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn reject_key_and_offset() {
+        let raw = RawPageRequest {
+            key: vec![1],
+            offset: 1,
+            limit: 0,
+            count_total: false,
+            reverse: false,
+        };
+
+        assert!(PageRequest::try_from(Some(raw)).is_err());
+        assert_eq!(PageRequest::try_from(None).unwrap(), PageRequest::default());
+    }
+}
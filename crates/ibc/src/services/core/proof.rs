@@ -0,0 +1,214 @@
+//! Batch proof query service.
+//!
+//! Relayers proving many packet commitments, receipts or acknowledgements at
+//! once can fetch a single proof for all their paths, computed with
+//! [`ProvableContext::get_batch_proof`](crate::services::core::context::ProvableContext::get_batch_proof),
+//! through the `ibc_rs.core.commitment.v1.ProofQuery` gRPC service defined in
+//! `proto/ibc_rs/core/commitment/v1/proof.proto`. Its server and client are
+//! generated in [`crate::services::proto::core::commitment::v1`].
+//! The proof is verified with
+//! [`MerkleProof::verify_batch`](crate::core::ics23_commitment::merkle::MerkleProof::verify_batch).
+//!
+//! [`ProofQueryService`](ProofQueryService) takes a generic `I` to store `ibc_context` that implements [`QueryContext`](QueryContext).
+//! `I` must be a type where writes from one thread are readable from another.
+//! This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+
+use alloc::str::FromStr;
+
+use tonic::{Request, Response, Status};
+
+use crate::core::ics24_host::path::Path;
+use crate::prelude::*;
+use crate::services::core::context::QueryContext;
+use crate::services::core::height::QueryView;
+use crate::services::proto::core::commitment::v1::proof_query_server::ProofQuery;
+use crate::services::proto::core::commitment::v1::{
+    QueryBatchProofRequest, QueryBatchProofResponse,
+};
+
+/// The generic `I` must be a type where writes from one thread are readable from another.
+/// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+pub struct ProofQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    ibc_context: I,
+}
+
+impl<I> ProofQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    /// The parameter `ibc_context` must be a type where writes from one thread are readable from another.
+    /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+    pub fn new(ibc_context: I) -> Self {
+        Self { ibc_context }
+    }
+}
+
+#[tonic::async_trait]
+impl<I> ProofQuery for ProofQueryService<I>
+where
+    I: QueryContext + Send + Sync + 'static,
+{
+    async fn batch_proof(
+        &self,
+        request: Request<QueryBatchProofRequest>,
+    ) -> Result<Response<QueryBatchProofResponse>, Status> {
+        let ibc_context = QueryView::new(&self.ibc_context, &request)?;
+
+        let paths = request
+            .get_ref()
+            .paths
+            .iter()
+            .map(|path| Path::from_str(path))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        if paths.is_empty() {
+            return Err(Status::invalid_argument("No path to prove"));
+        }
+
+        let current_height = ibc_context.host_height()?;
+
+        let proof = ibc_context
+            .get_batch_proof(current_height, &paths)
+            .ok_or_else(|| Status::not_found("Batch proof not found for the requested paths"))?;
+
+        Ok(Response::new(QueryBatchProofResponse {
+            proof,
+            proof_height: Some(current_height.into()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof};
+    use prost::Message;
+    use tonic::Code;
+
+    use super::*;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics23_commitment::merkle::MerkleProof;
+    use crate::core::ics23_commitment::specs::ProofSpecs;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::ics24_host::path::{CommitmentPath, ReceiptPath};
+    use crate::core::ValidationContext;
+    use crate::mock::context::MockContext;
+    use crate::services::proto::core::commitment::v1::proof_query_client::ProofQueryClient;
+    use crate::services::proto::core::commitment::v1::proof_query_server::ProofQueryServer;
+
+    fn commitment_path(sequence: u64) -> Path {
+        Path::Commitment(CommitmentPath::new(
+            &PortId::transfer(),
+            &ChannelId::default(),
+            Sequence::from(sequence),
+        ))
+    }
+
+    fn receipt_path(sequence: u64) -> Path {
+        Path::Receipt(ReceiptPath {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::default(),
+            sequence: Sequence::from(sequence),
+        })
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_batch_proof() {
+        let mut ctx = MockContext::default()
+            .with_provable_store()
+            .with_packet_commitment(
+                PortId::transfer(),
+                ChannelId::default(),
+                Sequence::from(1),
+                vec![1].into(),
+            )
+            .with_packet_commitment(
+                PortId::transfer(),
+                ChannelId::default(),
+                Sequence::from(2),
+                vec![2].into(),
+            );
+        ctx.advance_host_chain_height();
+        let height = ctx.host_height().unwrap();
+        let prefix = ctx.commitment_prefix();
+        let root = ctx.commitment_root(&height).unwrap();
+
+        let mut client = ProofQueryClient::new(ProofQueryServer::new(ProofQueryService::new(ctx)));
+
+        // Proves the membership of the commitments 1 and 2, and the
+        // non-membership of the commitment 3 and of the receipt 1.
+        let items = vec![
+            (commitment_path(1), Some(vec![1])),
+            (commitment_path(2), Some(vec![2])),
+            (commitment_path(3), None),
+            (receipt_path(1), None),
+        ];
+        let response = client
+            .batch_proof(QueryBatchProofRequest {
+                paths: items.iter().map(|(path, _)| path.to_string()).collect(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.proof_height, Some(height.into()));
+
+        let proof = MerkleProof::from(RawMerkleProof::decode(response.proof.as_slice()).unwrap());
+        let merkle_path = |path: &Path| MerklePath {
+            key_path: vec![
+                String::from_utf8(prefix.as_bytes().to_vec()).unwrap(),
+                path.to_string(),
+            ],
+        };
+        let verified_items = |items: &[(Path, Option<Vec<u8>>)]| {
+            items
+                .iter()
+                .map(|(path, value)| (merkle_path(path), value.clone()))
+                .collect()
+        };
+        proof
+            .verify_batch(
+                &ProofSpecs::default(),
+                root.clone().into(),
+                verified_items(&items),
+            )
+            .unwrap();
+
+        // The proof does not prove a different value, nor the absence of a
+        // stored commitment.
+        let mut wrong_value = items.clone();
+        wrong_value[0].1 = Some(vec![3]);
+        assert!(proof
+            .verify_batch(
+                &ProofSpecs::default(),
+                root.clone().into(),
+                verified_items(&wrong_value)
+            )
+            .is_err());
+        let mut wrong_absence = items;
+        wrong_absence[1].1 = None;
+        assert!(proof
+            .verify_batch(
+                &ProofSpecs::default(),
+                root.into(),
+                verified_items(&wrong_absence)
+            )
+            .is_err());
+
+        let status = client
+            .batch_proof(QueryBatchProofRequest {
+                paths: vec!["not/an/ibc/path".to_string()],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let status = client
+            .batch_proof(QueryBatchProofRequest { paths: vec![] })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,363 @@
+//! Verification of the proofs carried by the responses of the
+//! [`gRPC query services`](crate::services::core).
+//!
+//! A client that does not trust the node it queries can check a response
+//! against a commitment root it trusts, e.g. the root of a consensus state
+//! verified by a light client, with [`verify_query_response`].
+
+use displaydoc::Display;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    QueryChannelClientStateResponse, QueryChannelConsensusStateResponse, QueryChannelResponse,
+    QueryNextSequenceReceiveResponse, QueryNextSequenceSendResponse,
+    QueryPacketAcknowledgementResponse, QueryPacketCommitmentResponse, QueryPacketReceiptResponse,
+};
+use ibc_proto::ibc::core::client::v1::{
+    Height as RawHeight, QueryClientStateResponse, QueryConsensusStateResponse,
+};
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ibc::core::connection::v1::{
+    QueryConnectionClientStateResponse, QueryConnectionConsensusStateResponse,
+    QueryConnectionResponse,
+};
+use prost::Message;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics23_commitment::error::CommitmentError;
+use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
+use crate::core::ics23_commitment::specs::ProofSpecs;
+use crate::core::ics24_host::path::Path;
+use crate::prelude::*;
+use crate::Height;
+
+#[derive(Debug, Display)]
+pub enum QueryVerificationError {
+    /// query response carries no value
+    MissingValue,
+    /// query response carries no proof height
+    MissingProofHeight,
+    /// invalid proof height: `{0}`
+    InvalidProofHeight(ClientError),
+    /// proof height `{proof_height}` does not match the height `{trusted_height}` of the trusted root
+    ProofHeightMismatch {
+        proof_height: Height,
+        trusted_height: Height,
+    },
+    /// invalid proof: `{0}`
+    InvalidProof(CommitmentError),
+    /// proof verification failed: `{0}`
+    VerificationFailure(CommitmentError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QueryVerificationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidProofHeight(e) => Some(e),
+            Self::InvalidProof(e) | Self::VerificationFailure(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A query response carrying the proof of the value it returns.
+pub trait ProvenQueryResponse {
+    /// Returns the value stored at the queried path, encoded as committed by
+    /// the host, or `None` if the response proves that no value is stored.
+    fn proven_value(&self) -> Result<Option<Vec<u8>>, QueryVerificationError>;
+
+    /// Returns the encoded `MerkleProof` of the value.
+    fn proof(&self) -> &[u8];
+
+    /// Returns the height of the state the proof was retrieved from.
+    fn proof_height(&self) -> Option<RawHeight>;
+}
+
+/// Verifies that the value of `response` is the one stored at `path`, or that
+/// no value is stored there, in the state committed to by `root` at `height`.
+///
+/// `path` must be the path of the query, e.g.
+/// [`Path::ChannelEnd`](crate::core::ics24_host::path::Path::ChannelEnd) for a
+/// `QueryChannelResponse`, and `prefix` the commitment prefix of the queried host.
+pub fn verify_query_response<R>(
+    response: &R,
+    path: Path,
+    height: Height,
+    root: &CommitmentRoot,
+    prefix: &CommitmentPrefix,
+    proof_specs: &ProofSpecs,
+) -> Result<(), QueryVerificationError>
+where
+    R: ProvenQueryResponse,
+{
+    let proof_height: Height = response
+        .proof_height()
+        .ok_or(QueryVerificationError::MissingProofHeight)?
+        .try_into()
+        .map_err(QueryVerificationError::InvalidProofHeight)?;
+    if proof_height != height {
+        return Err(QueryVerificationError::ProofHeightMismatch {
+            proof_height,
+            trusted_height: height,
+        });
+    }
+
+    let proof = CommitmentProofBytes::try_from(response.proof().to_vec())
+        .map_err(QueryVerificationError::InvalidProof)?;
+    let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof)
+        .map_err(QueryVerificationError::InvalidProof)?
+        .into();
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
+
+    match response.proven_value()? {
+        Some(value) => {
+            merkle_proof.verify_membership(proof_specs, root.clone().into(), merkle_path, value, 0)
+        }
+        None => merkle_proof.verify_non_membership(proof_specs, root.clone().into(), merkle_path),
+    }
+    .map_err(QueryVerificationError::VerificationFailure)
+}
+
+fn encode_any(value: &Option<Any>) -> Result<Option<Vec<u8>>, QueryVerificationError> {
+    value
+        .as_ref()
+        .map(|value| Some(value.encode_to_vec()))
+        .ok_or(QueryVerificationError::MissingValue)
+}
+
+macro_rules! impl_proven_query_response {
+    ($response:ty, |$r:ident| $value:expr) => {
+        impl ProvenQueryResponse for $response {
+            fn proven_value(&self) -> Result<Option<Vec<u8>>, QueryVerificationError> {
+                let $r = self;
+                $value
+            }
+
+            fn proof(&self) -> &[u8] {
+                &self.proof
+            }
+
+            fn proof_height(&self) -> Option<RawHeight> {
+                self.proof_height.clone()
+            }
+        }
+    };
+}
+
+impl_proven_query_response!(QueryClientStateResponse, |r| encode_any(&r.client_state));
+
+impl_proven_query_response!(QueryConsensusStateResponse, |r| encode_any(
+    &r.consensus_state
+));
+
+impl_proven_query_response!(QueryConnectionResponse, |r| r
+    .connection
+    .as_ref()
+    .map(|connection| Some(connection.encode_to_vec()))
+    .ok_or(QueryVerificationError::MissingValue));
+
+impl_proven_query_response!(QueryConnectionClientStateResponse, |r| encode_any(
+    &r.identified_client_state
+        .as_ref()
+        .and_then(|identified_client_state| identified_client_state.client_state.clone())
+));
+
+impl_proven_query_response!(QueryConnectionConsensusStateResponse, |r| encode_any(
+    &r.consensus_state
+));
+
+impl_proven_query_response!(QueryChannelResponse, |r| r
+    .channel
+    .as_ref()
+    .map(|channel| Some(channel.encode_to_vec()))
+    .ok_or(QueryVerificationError::MissingValue));
+
+impl_proven_query_response!(QueryChannelClientStateResponse, |r| encode_any(
+    &r.identified_client_state
+        .as_ref()
+        .and_then(|identified_client_state| identified_client_state.client_state.clone())
+));
+
+impl_proven_query_response!(QueryChannelConsensusStateResponse, |r| encode_any(
+    &r.consensus_state
+));
+
+impl_proven_query_response!(QueryPacketCommitmentResponse, |r| Ok(Some(
+    r.commitment.clone()
+)));
+
+// A receipt is stored as a single byte once the packet is received.
+impl_proven_query_response!(QueryPacketReceiptResponse, |r| Ok(if r.received {
+    Some(vec![1])
+} else {
+    None
+}));
+
+impl_proven_query_response!(QueryPacketAcknowledgementResponse, |r| Ok(Some(
+    r.acknowledgement.clone()
+)));
+
+impl_proven_query_response!(QueryNextSequenceReceiveResponse, |r| Ok(Some(
+    r.next_sequence_receive.encode_to_vec()
+)));
+
+impl_proven_query_response!(QueryNextSequenceSendResponse, |r| Ok(Some(
+    r.next_sequence_send.encode_to_vec()
+)));
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, NonExistenceProof};
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics04_channel::packet::Sequence;
+    use crate::core::ics23_commitment::merkle::test_utils::{existence_proof, leaf_hash};
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::ics24_host::path::{CommitmentPath, ReceiptPath};
+
+    const COMMITMENT: [u8; 32] = [7; 32];
+
+    fn commitment_path() -> Path {
+        CommitmentPath::new(
+            &PortId::transfer(),
+            &ChannelId::default(),
+            Sequence::from(1),
+        )
+        .into()
+    }
+
+    fn receipt_path() -> Path {
+        ReceiptPath::new(
+            &PortId::transfer(),
+            &ChannelId::default(),
+            Sequence::from(1),
+        )
+        .into()
+    }
+
+    /// Returns the root of a two-layer store holding a single packet
+    /// commitment, along with the proofs of the commitment and of the absence
+    /// of the receipt of the packet.
+    fn store() -> (CommitmentRoot, Vec<u8>, Vec<u8>) {
+        let key = commitment_path().to_string();
+        let commitment_proof = existence_proof(&key, &COMMITMENT, vec![]);
+
+        let store_root = leaf_hash(key.as_bytes(), &COMMITMENT);
+        let store_proof = CommitmentProof {
+            proof: Some(Proof::Exist(existence_proof("ibc", &store_root, vec![]))),
+        };
+        let root = CommitmentRoot::from(leaf_hash(b"ibc", &store_root));
+
+        let encode = |proof: Proof| {
+            RawMerkleProof {
+                proofs: vec![CommitmentProof { proof: Some(proof) }, store_proof.clone()],
+            }
+            .encode_to_vec()
+        };
+
+        let membership_proof = encode(Proof::Exist(commitment_proof.clone()));
+        // receipt paths sort after commitment paths
+        let non_membership_proof = encode(Proof::Nonexist(NonExistenceProof {
+            key: receipt_path().to_string().into_bytes(),
+            left: Some(commitment_proof),
+            right: None,
+        }));
+
+        (root, membership_proof, non_membership_proof)
+    }
+
+    fn specs() -> ProofSpecs {
+        vec![ics23::tendermint_spec(), ics23::tendermint_spec()].into()
+    }
+
+    #[test]
+    fn verify_proven_responses() {
+        let (root, membership_proof, non_membership_proof) = store();
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let height = Height::new(0, 10).unwrap();
+
+        let mut commitment_response = QueryPacketCommitmentResponse {
+            commitment: COMMITMENT.to_vec(),
+            proof: membership_proof,
+            proof_height: Some(height.into()),
+        };
+        verify_query_response(
+            &commitment_response,
+            commitment_path(),
+            height,
+            &root,
+            &prefix,
+            &specs(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            verify_query_response(
+                &commitment_response,
+                commitment_path(),
+                height.increment(),
+                &root,
+                &prefix,
+                &specs(),
+            ),
+            Err(QueryVerificationError::ProofHeightMismatch { .. })
+        ));
+
+        commitment_response.commitment = vec![8; 32];
+        assert!(matches!(
+            verify_query_response(
+                &commitment_response,
+                commitment_path(),
+                height,
+                &root,
+                &prefix,
+                &specs(),
+            ),
+            Err(QueryVerificationError::VerificationFailure(_))
+        ));
+
+        let mut receipt_response = QueryPacketReceiptResponse {
+            received: false,
+            proof: non_membership_proof,
+            proof_height: Some(height.into()),
+        };
+        verify_query_response(
+            &receipt_response,
+            receipt_path(),
+            height,
+            &root,
+            &prefix,
+            &specs(),
+        )
+        .unwrap();
+
+        receipt_response.received = true;
+        assert!(verify_query_response(
+            &receipt_response,
+            receipt_path(),
+            height,
+            &root,
+            &prefix,
+            &specs(),
+        )
+        .is_err());
+
+        receipt_response.proof = vec![];
+        assert!(matches!(
+            verify_query_response(
+                &receipt_response,
+                receipt_path(),
+                height,
+                &root,
+                &prefix,
+                &specs(),
+            ),
+            Err(QueryVerificationError::InvalidProof(_))
+        ));
+    }
+}
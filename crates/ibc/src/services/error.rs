@@ -2,8 +2,11 @@ use alloc::string::ToString;
 
 use tonic::Status;
 
+#[cfg(feature = "serde")]
+use crate::applications::transfer::error::TokenTransferError;
 use crate::core::ics24_host::identifier::IdentifierError;
 use crate::core::ContextError;
+use crate::services::core::pagination::PaginationError;
 
 impl From<IdentifierError> for Status {
     fn from(err: IdentifierError) -> Self {
@@ -16,3 +19,19 @@ impl From<ContextError> for Status {
         Status::not_found(err.to_string())
     }
 }
+
+impl From<PaginationError> for Status {
+    fn from(err: PaginationError) -> Self {
+        Status::invalid_argument(err.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TokenTransferError> for Status {
+    fn from(err: TokenTransferError) -> Self {
+        match err {
+            TokenTransferError::DenomTraceNotFound { .. } => Status::not_found(err.to_string()),
+            _ => Status::internal(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,544 @@
+//! Streaming subscription to the IBC events emitted by the host.
+//!
+//! Hosts feed an [`IbcEventBroadcaster`] by returning it as the listener of
+//! their [`ExecutionContext`](crate::core::ExecutionContext), from
+//! [`ExecutionContext::ibc_event_listener`](crate::core::ExecutionContext::ibc_event_listener).
+//! The broadcaster holds back the events of a transaction until the host
+//! commits it with [`IbcEventBroadcaster::commit`], or drops them when the host
+//! reverts it with [`IbcEventBroadcaster::discard`], so that subscribers never
+//! see the events of a failed transaction.
+//! The [`EventSubscriptionService`] streams the published events matching the
+//! [`IbcEventFilter`] of each subscriber, through the
+//! `ibc_rs.core.v1.EventSubscription` gRPC service defined in
+//! `proto/ibc_rs/core/v1/events.proto`, whose server and client are generated
+//! in [`crate::services::proto::core::v1`].
+//!
+//! Events are buffered in a bounded broadcast channel: a subscriber that falls
+//! more than the channel capacity behind misses the oldest events, and its
+//! stream ends with a `DATA_LOSS` status.
+
+use core::ops::RangeInclusive;
+use core::pin::Pin;
+use core::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use tendermint::abci;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::core::events::{IbcEvent, IbcEventListener};
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::prelude::*;
+use crate::services::proto::core::v1::event_subscription_server::EventSubscription;
+use crate::services::proto::core::v1::{
+    EventEncoding, SubscribeIbcEventsRequest, SubscribeIbcEventsResponse,
+};
+
+/// Number of events buffered for each subscriber by default.
+pub const DEFAULT_EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Distributes the events emitted by the host to the subscribers of the
+/// [`EventSubscriptionService`].
+///
+/// The events the broadcaster is notified of are pending until the host
+/// commits them. Clones share the same channel and pending events, so the
+/// broadcaster can be handed both to the execution context and to the service.
+#[derive(Clone, Debug)]
+pub struct IbcEventBroadcaster {
+    sender: broadcast::Sender<IbcEvent>,
+    pending: Arc<Mutex<Vec<IbcEvent>>>,
+}
+
+impl IbcEventBroadcaster {
+    /// Creates a broadcaster buffering up to `capacity` events for each subscriber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Sends `event` to all current subscribers right away, regardless of the
+    /// pending events.
+    pub fn publish(&self, event: &IbcEvent) {
+        if self.sender.receiver_count() > 0 {
+            // only fails if all subscribers dropped in the meantime
+            let _ = self.sender.send(event.clone());
+        }
+    }
+
+    /// Sends the pending events to all current subscribers, in the order they
+    /// were emitted. Hosts call it once the transaction that emitted them is
+    /// committed.
+    pub fn commit(&self) {
+        for event in self.take_pending() {
+            self.publish(&event);
+        }
+    }
+
+    /// Drops the pending events. Hosts call it when the transaction that
+    /// emitted them is reverted.
+    pub fn discard(&self) {
+        self.take_pending();
+    }
+
+    /// Returns a receiver of the events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<IbcEvent> {
+        self.sender.subscribe()
+    }
+
+    fn take_pending(&self) -> Vec<IbcEvent> {
+        core::mem::take(&mut *self.pending.lock().expect("never poisoned"))
+    }
+}
+
+impl IbcEventListener for IbcEventBroadcaster {
+    fn on_ibc_event(&self, event: &IbcEvent) {
+        self.pending
+            .lock()
+            .expect("never poisoned")
+            .push(event.clone());
+    }
+}
+
+impl Default for IbcEventBroadcaster {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUFFER_SIZE)
+    }
+}
+
+/// Selects the events sent to a subscriber. An event matches if it matches
+/// every criterion that is set.
+///
+/// Identifiers are matched against the attributes of the event, on either end
+/// of the channel for packet events.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IbcEventFilter {
+    /// Event types, e.g. `send_packet`; any type if empty.
+    pub kinds: Vec<String>,
+    pub client_id: Option<ClientId>,
+    pub connection_id: Option<ConnectionId>,
+    pub port_id: Option<PortId>,
+    pub channel_id: Option<ChannelId>,
+    /// Packet sequences; events without a sequence do not match once set.
+    pub sequences: Option<RangeInclusive<Sequence>>,
+}
+
+impl IbcEventFilter {
+    pub fn matches(&self, event: &IbcEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.iter().any(|kind| kind == event.event_type()) {
+            return false;
+        }
+
+        if self.client_id.is_none()
+            && self.connection_id.is_none()
+            && self.port_id.is_none()
+            && self.channel_id.is_none()
+            && self.sequences.is_none()
+        {
+            return true;
+        }
+
+        let abci_event = match abci::Event::try_from(event.clone()) {
+            Ok(abci_event) => abci_event,
+            Err(_) => return false,
+        };
+        let has_attribute = |keys: &[&str], value: &str| {
+            abci_event
+                .attributes
+                .iter()
+                .any(|attribute| keys.contains(&attribute.key.as_str()) && attribute.value == value)
+        };
+
+        self.client_id.as_ref().map_or(true, |client_id| {
+            has_attribute(&["client_id"], client_id.as_str())
+        }) && self.connection_id.as_ref().map_or(true, |connection_id| {
+            has_attribute(
+                &["connection_id", "packet_connection"],
+                connection_id.as_str(),
+            )
+        }) && self.port_id.as_ref().map_or(true, |port_id| {
+            has_attribute(
+                &["port_id", "packet_src_port", "packet_dst_port"],
+                port_id.as_str(),
+            )
+        }) && self.channel_id.as_ref().map_or(true, |channel_id| {
+            has_attribute(
+                &["channel_id", "packet_src_channel", "packet_dst_channel"],
+                channel_id.as_str(),
+            )
+        }) && self.sequences.as_ref().map_or(true, |sequences| {
+            abci_event.attributes.iter().any(|attribute| {
+                attribute.key == "packet_sequence"
+                    && u64::from_str(&attribute.value)
+                        .map_or(false, |sequence| sequences.contains(&sequence.into()))
+            })
+        })
+    }
+}
+
+impl TryFrom<&SubscribeIbcEventsRequest> for IbcEventFilter {
+    type Error = Status;
+
+    fn try_from(request: &SubscribeIbcEventsRequest) -> Result<Self, Self::Error> {
+        fn parse<T: FromStr>(value: &str) -> Result<Option<T>, Status>
+        where
+            T::Err: ToString,
+        {
+            if value.is_empty() {
+                Ok(None)
+            } else {
+                T::from_str(value)
+                    .map(Some)
+                    .map_err(|e| Status::invalid_argument(e.to_string()))
+            }
+        }
+
+        let sequences = match (request.min_sequence, request.max_sequence) {
+            (0, 0) => None,
+            (min, 0) => Some(min.into()..=u64::MAX.into()),
+            (min, max) => Some(min.into()..=max.into()),
+        };
+
+        Ok(Self {
+            kinds: request.kinds.clone(),
+            client_id: parse(&request.client_id)?,
+            connection_id: parse(&request.connection_id)?,
+            port_id: parse(&request.port_id)?,
+            channel_id: parse(&request.channel_id)?,
+            sequences,
+        })
+    }
+}
+
+fn encode_event(
+    event: IbcEvent,
+    encoding: EventEncoding,
+) -> Result<SubscribeIbcEventsResponse, Status> {
+    match encoding {
+        EventEncoding::Protobuf => {
+            let abci_event =
+                abci::Event::try_from(event).map_err(|e| Status::internal(e.to_string()))?;
+            Ok(SubscribeIbcEventsResponse {
+                event: Some(abci_event.into()),
+                event_json: String::new(),
+            })
+        }
+        #[cfg(feature = "serde")]
+        EventEncoding::Json => Ok(SubscribeIbcEventsResponse {
+            event: None,
+            event_json: serde_json::to_string(&event)
+                .map_err(|e| Status::internal(e.to_string()))?,
+        }),
+        #[cfg(not(feature = "serde"))]
+        EventEncoding::Json => Err(Status::unimplemented(
+            "JSON encoding requires the `serde` feature",
+        )),
+    }
+}
+
+/// The stream of events sent to a subscriber.
+pub type IbcEventStream =
+    Pin<Box<dyn Stream<Item = Result<SubscribeIbcEventsResponse, Status>> + Send + 'static>>;
+
+/// Streams the events published to an [`IbcEventBroadcaster`].
+pub struct EventSubscriptionService {
+    broadcaster: IbcEventBroadcaster,
+}
+
+impl EventSubscriptionService {
+    pub fn new(broadcaster: IbcEventBroadcaster) -> Self {
+        Self { broadcaster }
+    }
+}
+
+#[tonic::async_trait]
+impl EventSubscription for EventSubscriptionService {
+    type SubscribeStream = IbcEventStream;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeIbcEventsRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let request_ref = request.get_ref();
+
+        let filter = IbcEventFilter::try_from(request_ref)?;
+
+        let encoding = EventEncoding::from_i32(request_ref.encoding)
+            .ok_or_else(|| Status::invalid_argument("Unknown event encoding"))?;
+        if cfg!(not(feature = "serde")) && encoding == EventEncoding::Json {
+            return Err(Status::unimplemented(
+                "JSON encoding requires the `serde` feature",
+            ));
+        }
+
+        let stream =
+            BroadcastStream::new(self.broadcaster.subscribe()).filter_map(
+                move |event| match event {
+                    Ok(event) if filter.matches(&event) => Some(encode_event(event, encoding)),
+                    Ok(_) => None,
+                    Err(BroadcastStreamRecvError::Lagged(missed)) => Some(Err(Status::data_loss(
+                        format!("Subscriber lagged behind and missed {missed} events"),
+                    ))),
+                },
+            );
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tonic::transport::{Endpoint, Server, Uri};
+    use tonic::Code;
+
+    use super::*;
+    use crate::core::events::IbcEvent;
+    use crate::core::ics02_client::events::CreateClient;
+    use crate::core::ics04_channel::channel::Order;
+    use crate::core::ics04_channel::events::SendPacket;
+    use crate::core::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::core::ics04_channel::packet::Packet;
+    use crate::core::ics04_channel::Version as ChannelVersion;
+    use crate::core::ics24_host::identifier::ChainId;
+    use crate::core::router::ModuleId;
+    use crate::core::ExecutionContext;
+    use crate::mock::client_state::client_type as mock_client_type;
+    use crate::mock::context::MockContext;
+    use crate::mock::module::{Callback, ScriptedModule};
+    use crate::mock::router::MockRouter;
+    use crate::mock::scenario::{Action, ChainSetup, ScenarioMsg};
+    use crate::services::proto::core::v1::event_subscription_client::EventSubscriptionClient;
+    use crate::services::proto::core::v1::event_subscription_server::EventSubscriptionServer;
+    use crate::Height;
+
+    fn send_packet_event(sequence: u64) -> IbcEvent {
+        let mut raw_packet = get_dummy_raw_packet(10, 0);
+        raw_packet.sequence = sequence;
+        let packet = Packet::try_from(raw_packet).unwrap();
+
+        IbcEvent::SendPacket(SendPacket::new(
+            packet,
+            Order::Unordered,
+            ConnectionId::default(),
+        ))
+    }
+
+    #[test]
+    fn filter_events() {
+        let create_client = IbcEvent::CreateClient(CreateClient::new(
+            ClientId::default(),
+            mock_client_type(),
+            Height::new(0, 1).unwrap(),
+        ));
+
+        assert!(IbcEventFilter::default().matches(&create_client));
+        assert!(IbcEventFilter::default().matches(&send_packet_event(1)));
+
+        let by_client = IbcEventFilter {
+            client_id: Some(ClientId::default()),
+            ..Default::default()
+        };
+        assert!(by_client.matches(&create_client));
+        assert!(!by_client.matches(&send_packet_event(1)));
+
+        let by_channel = IbcEventFilter {
+            kinds: vec!["send_packet".to_string()],
+            port_id: Some(PortId::default()),
+            channel_id: Some(ChannelId::default()),
+            sequences: Some(2.into()..=3.into()),
+            ..Default::default()
+        };
+        assert!(!by_channel.matches(&create_client));
+        assert!(!by_channel.matches(&send_packet_event(1)));
+        assert!(by_channel.matches(&send_packet_event(2)));
+        assert!(by_channel.matches(&send_packet_event(3)));
+        assert!(!by_channel.matches(&send_packet_event(4)));
+
+        let by_other_channel = IbcEventFilter {
+            channel_id: Some(ChannelId::new(1)),
+            ..Default::default()
+        };
+        assert!(!by_other_channel.matches(&send_packet_event(1)));
+    }
+
+    /// Delivers `msgs` in a single transaction, as a host would: the events
+    /// are committed if all messages succeed, and discarded otherwise.
+    fn deliver_tx(
+        ctx: &mut MockContext,
+        router: &mut MockRouter,
+        broadcaster: &IbcEventBroadcaster,
+        msgs: Vec<ScenarioMsg>,
+    ) -> Result<(), String> {
+        let result = msgs
+            .into_iter()
+            .try_for_each(|msg| Action::Deliver(msg).run(ctx, router));
+        match result {
+            Ok(()) => broadcaster.commit(),
+            Err(_) => broadcaster.discard(),
+        }
+        result
+    }
+
+    #[test]
+    fn only_committed_events_are_published() {
+        let module = ScriptedModule::new().fail(Callback::ChanOpenTryExecute, "try failed");
+        let module_id = ModuleId::new("scripted".to_string());
+        let mut router = MockRouter::default();
+        router.add_route(module_id.clone(), module).unwrap();
+        router
+            .scope_port_to_module(PortId::transfer(), module_id)
+            .unwrap();
+
+        let broadcaster = IbcEventBroadcaster::new(16);
+        let mut ctx = ChainSetup::new(ChainId::new("mockgaia", 0).unwrap(), 10)
+            .with_open_connection(Height::new(0, 5).unwrap())
+            .build(&router)
+            .unwrap()
+            .with_event_listener(broadcaster.clone());
+        let mut subscriber = broadcaster.subscribe();
+
+        let chan_open_init = ScenarioMsg::ChanOpenInit {
+            port_id: PortId::transfer(),
+            connection_id: ConnectionId::new(0),
+            counterparty_port_id: PortId::transfer(),
+            ordering: Order::Unordered,
+            version: ChannelVersion::new("ics20-1".to_string()),
+        };
+        let chan_open_try = ScenarioMsg::ChanOpenTry {
+            port_id: PortId::transfer(),
+            connection_id: ConnectionId::new(0),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: ChannelId::new(3),
+            ordering: Order::Unordered,
+            version: ChannelVersion::new("ics20-1".to_string()),
+        };
+
+        let err = deliver_tx(
+            &mut ctx,
+            &mut router,
+            &broadcaster,
+            vec![chan_open_init.clone(), chan_open_try],
+        )
+        .unwrap_err();
+        assert!(err.contains("try failed"), "{err}");
+        assert!(!ctx.events.is_empty());
+        assert_eq!(
+            subscriber.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        );
+
+        let emitted = ctx.events.len();
+        deliver_tx(&mut ctx, &mut router, &broadcaster, vec![chan_open_init]).unwrap();
+        for event in &ctx.events[emitted..] {
+            assert_eq!(&subscriber.try_recv().unwrap(), event);
+        }
+        assert_eq!(
+            subscriber.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribe_over_grpc() {
+        let broadcaster = IbcEventBroadcaster::new(16);
+        let mut ctx = MockContext::default().with_event_listener(broadcaster.clone());
+
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let server =
+            EventSubscriptionServer::new(EventSubscriptionService::new(broadcaster.clone()));
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(server)
+                .serve_with_incoming(tokio_stream::once(Ok::<_, std::io::Error>(server_io)))
+                .await
+        });
+
+        let mut client_io = Some(client_io);
+        let channel = Endpoint::try_from("http://[::]:50051")
+            .unwrap()
+            .connect_with_connector(tower::service_fn(move |_: Uri| {
+                let io = client_io.take();
+                async move {
+                    io.ok_or_else(|| {
+                        std::io::Error::new(std::io::ErrorKind::Other, "Client already connected")
+                    })
+                }
+            }))
+            .await
+            .unwrap();
+        let mut client = EventSubscriptionClient::new(channel);
+
+        let mut packets = client
+            .subscribe(SubscribeIbcEventsRequest {
+                kinds: vec!["send_packet".to_string()],
+                channel_id: ChannelId::default().to_string(),
+                min_sequence: 2,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        #[cfg(feature = "serde")]
+        let mut all_events = client
+            .subscribe(SubscribeIbcEventsRequest {
+                encoding: EventEncoding::Json as i32,
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let create_client = IbcEvent::CreateClient(CreateClient::new(
+            ClientId::default(),
+            mock_client_type(),
+            Height::new(0, 1).unwrap(),
+        ));
+        for event in [
+            create_client,
+            send_packet_event(1),
+            send_packet_event(2),
+            send_packet_event(3),
+        ] {
+            ctx.publish_ibc_event(event).unwrap();
+        }
+        broadcaster.commit();
+
+        for sequence in ["2", "3"] {
+            let event = packets.message().await.unwrap().unwrap().event.unwrap();
+            assert_eq!(event.r#type, "send_packet");
+            assert!(
+                event
+                    .attributes
+                    .iter()
+                    .any(|attribute| attribute.key == "packet_sequence"
+                        && attribute.value == sequence)
+            );
+        }
+
+        #[cfg(feature = "serde")]
+        for event in &ctx.events {
+            let received = all_events.message().await.unwrap().unwrap();
+            assert_eq!(received.event, None);
+            assert_eq!(received.event_json, serde_json::to_string(event).unwrap());
+        }
+
+        let invalid = client
+            .subscribe(SubscribeIbcEventsRequest {
+                client_id: "invalid client".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(invalid.unwrap_err().code(), Code::InvalidArgument);
+    }
+}
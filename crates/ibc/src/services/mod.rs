@@ -1,4 +1,4 @@
-//! Implementation of the gRPC services of core IBC components.
+//! Implementation of the gRPC services of core IBC components and of the token transfer application.
 //!
 //! The provided structs includes blanket implementation of their corresponding gRPC service traits,
 //! if the host implements the following _context_ traits.
@@ -7,6 +7,40 @@
 //! - [`QueryContext`](crate::services::core::context::QueryContext)
 //! - [`UpgradeValidationContext`](crate::hosts::tendermint::upgrade_proposal::UpgradeValidationContext)
 //!   - Only for [`ClientQuery::upgraded_client_state`](ibc_proto::ibc::core::client::v1::query_server::Query::upgraded_client_state) and [`ClientQuery::upgraded_client_state`](ibc_proto::ibc::core::client::v1::query_server::Query::upgraded_consensus_state)
+//! - [`PacketDataQueryContext`](crate::services::core::context::PacketDataQueryContext)
+//!   - Only for the [`PacketDataQueryService`](crate::services::core::packet_data::PacketDataQueryService)
+//! - [`TransferQueryContext`](crate::services::transfer::TransferQueryContext)
+//!   - Only for the ICS-20 [`TransferQueryService`](crate::services::transfer::TransferQueryService)
+//!
+//! The [`ProofQueryService`](crate::services::core::proof::ProofQueryService) returns a single
+//! proof for many paths, built with
+//! [`ProvableContext::get_batch_proof`](crate::services::core::context::ProvableContext::get_batch_proof).
+//!
+//! The [`ClientInfoQueryService`](crate::services::core::client_info::ClientInfoQueryService)
+//! reports the status and expiry of clients, as computed by
+//! [`QueryContext::client_info`](crate::services::core::context::QueryContext::client_info).
+//!
+//! Clients that do not trust the queried node can check the proofs of the responses against
+//! a trusted commitment root with
+//! [`verify_query_response`](crate::services::core::verify::verify_query_response).
+//!
+//! The queries of the IBC applications registered in the [`Router`](crate::core::router::Router),
+//! such as the ICS-20 queries of the [`TransferQueryService`](crate::services::transfer::TransferQueryService),
+//! can all be served by a single
+//! [`ApplicationQueryService`](crate::services::application::ApplicationQueryService).
+//!
+//! With the `rest` feature, the [`RestGateway`](crate::services::rest::RestGateway) serves the
+//! client, connection and channel query services as JSON, under the REST routes of the ibc-go
+//! grpc-gateway.
+//!
+//! Hosts that publish the events they emit to an
+//! [`IbcEventBroadcaster`](crate::services::events::IbcEventBroadcaster) can stream them to
+//! subscribers with the [`EventSubscriptionService`](crate::services::events::EventSubscriptionService).
+//!
+//! Queries are answered at the latest height of the host, unless the request sets the
+//! [`x-cosmos-block-height`](crate::services::core::height::BLOCK_HEIGHT_METADATA_KEY)
+//! metadata, in which case they are answered from
+//! [`QueryContext::query_context_at`](crate::services::core::context::QueryContext::query_context_at).
 //!
 //! Example
 //! ```rust,ignore
@@ -44,5 +78,12 @@
 //!       .serve(addr);
 //! ```
 
+pub mod application;
 pub mod core;
 pub mod error;
+pub mod events;
+pub mod proto;
+#[cfg(feature = "rest")]
+pub mod rest;
+#[cfg(feature = "serde")]
+pub mod transfer;
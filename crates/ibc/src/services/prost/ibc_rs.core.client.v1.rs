@@ -0,0 +1,438 @@
+/// ClientInfo is a summary of the state of a client.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientInfo {
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub client_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub status: ::prost::alloc::string::String,
+    /// latest height of the client state
+    #[prost(message, optional, tag = "4")]
+    pub latest_height: ::core::option::Option<::ibc_proto::ibc::core::client::v1::Height>,
+    /// height of the earliest consensus state still stored for the client
+    #[prost(message, optional, tag = "5")]
+    pub earliest_height: ::core::option::Option<::ibc_proto::ibc::core::client::v1::Height>,
+    /// time at which the client expires if it is not updated, in nanoseconds since
+    /// the Unix epoch; 0 if the client never expires
+    #[prost(uint64, tag = "6")]
+    pub expiry_timestamp: u64,
+}
+/// QueryClientInfoRequest is the request type for the
+/// ClientInfoQuery/ClientInfo RPC method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryClientInfoRequest {
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+}
+/// QueryClientInfoResponse is the response type for the
+/// ClientInfoQuery/ClientInfo RPC method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryClientInfoResponse {
+    #[prost(message, optional, tag = "1")]
+    pub client_info: ::core::option::Option<ClientInfo>,
+    /// height at which the query was answered
+    #[prost(message, optional, tag = "2")]
+    pub height: ::core::option::Option<::ibc_proto::ibc::core::client::v1::Height>,
+}
+/// QueryClientInfosRequest is the request type for the
+/// ClientInfoQuery/ClientInfos RPC method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryClientInfosRequest {
+    /// if set, only the clients expiring before this time, in nanoseconds since
+    /// the Unix epoch, are returned, sorted by expiry
+    #[prost(uint64, tag = "1")]
+    pub expiring_before: u64,
+}
+/// QueryClientInfosResponse is the response type for the
+/// ClientInfoQuery/ClientInfos RPC method.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryClientInfosResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub client_infos: ::prost::alloc::vec::Vec<ClientInfo>,
+    /// height at which the query was answered
+    #[prost(message, optional, tag = "2")]
+    pub height: ::core::option::Option<::ibc_proto::ibc::core::client::v1::Height>,
+}
+/// Generated client implementations.
+pub mod client_info_query_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use crate::prelude::*;
+    use tonic::codegen::http::Uri;
+    /// ClientInfoQuery serves the status and expiry of the clients of the host.
+    #[derive(Debug, Clone)]
+    pub struct ClientInfoQueryClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ClientInfoQueryClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ClientInfoQueryClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ClientInfoQueryClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            ClientInfoQueryClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// ClientInfo returns the ClientInfo of a client.
+        pub async fn client_info(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryClientInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryClientInfoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc_rs.core.client.v1.ClientInfoQuery/ClientInfo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("ibc_rs.core.client.v1.ClientInfoQuery", "ClientInfo"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// ClientInfos returns the ClientInfo of all clients, or of the clients
+        /// expiring before a given time.
+        pub async fn client_infos(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryClientInfosRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryClientInfosResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/ibc_rs.core.client.v1.ClientInfoQuery/ClientInfos",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("ibc_rs.core.client.v1.ClientInfoQuery", "ClientInfos"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod client_info_query_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use crate::prelude::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ClientInfoQueryServer.
+    #[async_trait]
+    pub trait ClientInfoQuery: Send + Sync + 'static {
+        /// ClientInfo returns the ClientInfo of a client.
+        async fn client_info(
+            &self,
+            request: tonic::Request<super::QueryClientInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryClientInfoResponse>,
+            tonic::Status,
+        >;
+        /// ClientInfos returns the ClientInfo of all clients, or of the clients
+        /// expiring before a given time.
+        async fn client_infos(
+            &self,
+            request: tonic::Request<super::QueryClientInfosRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryClientInfosResponse>,
+            tonic::Status,
+        >;
+    }
+    /// ClientInfoQuery serves the status and expiry of the clients of the host.
+    #[derive(Debug)]
+    pub struct ClientInfoQueryServer<T: ClientInfoQuery> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ClientInfoQuery> ClientInfoQueryServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ClientInfoQueryServer<T>
+    where
+        T: ClientInfoQuery,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/ibc_rs.core.client.v1.ClientInfoQuery/ClientInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClientInfoSvc<T: ClientInfoQuery>(pub Arc<T>);
+                    impl<
+                        T: ClientInfoQuery,
+                    > tonic::server::UnaryService<super::QueryClientInfoRequest>
+                    for ClientInfoSvc<T> {
+                        type Response = super::QueryClientInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryClientInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).client_info(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ClientInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/ibc_rs.core.client.v1.ClientInfoQuery/ClientInfos" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClientInfosSvc<T: ClientInfoQuery>(pub Arc<T>);
+                    impl<
+                        T: ClientInfoQuery,
+                    > tonic::server::UnaryService<super::QueryClientInfosRequest>
+                    for ClientInfosSvc<T> {
+                        type Response = super::QueryClientInfosResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryClientInfosRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).client_infos(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ClientInfosSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: ClientInfoQuery> Clone for ClientInfoQueryServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: ClientInfoQuery> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: ClientInfoQuery> tonic::server::NamedService for ClientInfoQueryServer<T> {
+        const NAME: &'static str = "ibc_rs.core.client.v1.ClientInfoQuery";
+    }
+}
@@ -0,0 +1,31 @@
+//! Messages, servers and clients of the gRPC services defined by this crate.
+//!
+//! The services are defined in the `proto` directory of the crate. Their code,
+//! under `prost`, is generated with `tonic-build` by the `tools/proto-compiler`
+//! crate of the repository, and must not be edited by hand.
+#![allow(
+    unused_qualifications,
+    clippy::derive_partial_eq_without_eq,
+    clippy::unwrap_used
+)]
+
+pub mod core {
+    pub mod client {
+        pub mod v1 {
+            include!("prost/ibc_rs.core.client.v1.rs");
+        }
+    }
+    pub mod channel {
+        pub mod v1 {
+            include!("prost/ibc_rs.core.channel.v1.rs");
+        }
+    }
+    pub mod commitment {
+        pub mod v1 {
+            include!("prost/ibc_rs.core.commitment.v1.rs");
+        }
+    }
+    pub mod v1 {
+        include!("prost/ibc_rs.core.v1.rs");
+    }
+}
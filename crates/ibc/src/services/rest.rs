@@ -0,0 +1,847 @@
+//! REST/JSON gateway of the gRPC query services of IBC core.
+//!
+//! [`RestGateway`] answers `GET` requests on the routes of the ibc-go grpc-gateway, e.g.
+//! `/ibc/core/channel/v1/channels/{channel_id}/ports/{port_id}`, by calling the
+//! corresponding method of the client, connection and channel query services, such as
+//! the [`ClientQueryService`](crate::services::core::client::ClientQueryService),
+//! [`ConnectionQueryService`](crate::services::core::connection::ConnectionQueryService) and
+//! [`ChannelQueryService`](crate::services::core::channel::ChannelQueryService).
+//!
+//! Responses are encoded in JSON with the `serde` representations of the `ibc-proto` messages
+//! returned by the query services, which keep the field names of their protobuf definitions.
+//! Unlike the protojson of the grpc-gateway, 64-bit integers and enumerations are numbers,
+//! `bytes` are arrays of numbers, and the `Any`s of the client and consensus states hold their
+//! `type_url` and encoded `value`. Errors are returned as
+//! `{"code": .., "message": .., "details": []}`, with the HTTP status matching the gRPC
+//! status code, as done by the grpc-gateway.
+//!
+//! Pagination is read from the `pagination.key` (base64), `pagination.offset`,
+//! `pagination.limit`, `pagination.count_total` and `pagination.reverse` query parameters,
+//! and the query height from the
+//! [`x-cosmos-block-height`](crate::services::core::height::BLOCK_HEIGHT_METADATA_KEY) header.
+//!
+//! The gateway is a `tower` service, which can be served by `hyper`:
+//! ```rust,ignore
+//! let gateway = RestGateway::new(client_service, connection_service, channel_service);
+//!
+//! hyper::Server::bind(&addr)
+//!     .serve(tower::make::Shared::new(gateway))
+//!     .await?;
+//! ```
+
+use alloc::collections::BTreeMap;
+use core::convert::Infallible;
+use std::boxed::Box;
+
+use hyper::Body as HyperBody;
+use ibc_proto::cosmos::base::query::v1beta1::PageRequest as RawPageRequest;
+use ibc_proto::ibc::core::channel::v1::query_server::Query as ChannelQuery;
+use ibc_proto::ibc::core::channel::v1::{
+    QueryChannelClientStateRequest, QueryChannelConsensusStateRequest, QueryChannelRequest,
+    QueryChannelsRequest, QueryConnectionChannelsRequest, QueryNextSequenceReceiveRequest,
+    QueryNextSequenceSendRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryUnreceivedAcksRequest,
+    QueryUnreceivedPacketsRequest,
+};
+use ibc_proto::ibc::core::client::v1::query_server::Query as ClientQuery;
+use ibc_proto::ibc::core::client::v1::{
+    QueryClientParamsRequest, QueryClientStateRequest, QueryClientStatesRequest,
+    QueryClientStatusRequest, QueryConsensusStateHeightsRequest, QueryConsensusStateRequest,
+    QueryConsensusStatesRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
+};
+use ibc_proto::ibc::core::connection::v1::query_server::Query as ConnectionQuery;
+use ibc_proto::ibc::core::connection::v1::{
+    QueryClientConnectionsRequest, QueryConnectionClientStateRequest,
+    QueryConnectionConsensusStateRequest, QueryConnectionParamsRequest, QueryConnectionRequest,
+    QueryConnectionsRequest,
+};
+use serde::Serialize;
+use subtle_encoding::base64;
+use tonic::codegen::{http, Arc, BoxFuture, Context, Poll, Service};
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::{Code, Request, Response, Status};
+
+use crate::prelude::*;
+use crate::services::core::height::BLOCK_HEIGHT_METADATA_KEY;
+
+/// Serves the client, connection and channel query services under the REST routes of the
+/// ibc-go grpc-gateway.
+pub struct RestGateway<C, N, H> {
+    client: Arc<C>,
+    connection: Arc<N>,
+    channel: Arc<H>,
+}
+
+impl<C, N, H> RestGateway<C, N, H>
+where
+    C: ClientQuery,
+    N: ConnectionQuery,
+    H: ChannelQuery,
+{
+    pub fn new(client: C, connection: N, channel: H) -> Self {
+        Self {
+            client: Arc::new(client),
+            connection: Arc::new(connection),
+            channel: Arc::new(channel),
+        }
+    }
+
+    async fn handle(&self, request: http::request::Parts) -> http::Response<HyperBody> {
+        if request.method != http::Method::GET {
+            return json_response(
+                http::StatusCode::METHOD_NOT_ALLOWED,
+                error_body(&Status::unimplemented("Method Not Allowed")),
+            );
+        }
+
+        let mut metadata = MetadataMap::new();
+        if let Some(height) = request.headers.get(BLOCK_HEIGHT_METADATA_KEY) {
+            if let Ok(height) = MetadataValue::try_from(height.as_bytes()) {
+                metadata.insert(BLOCK_HEIGHT_METADATA_KEY, height);
+            }
+        }
+
+        let response = match QueryParams::parse(request.uri.query().unwrap_or_default()) {
+            Ok(params) => self.route(request.uri.path(), &params, metadata).await,
+            Err(status) => Err(status),
+        };
+
+        match response {
+            Ok(body) => json_response(http::StatusCode::OK, body),
+            Err(status) => json_response(http_status(status.code()), error_body(&status)),
+        }
+    }
+
+    async fn route(
+        &self,
+        path: &str,
+        params: &QueryParams,
+        metadata: MetadataMap,
+    ) -> Result<Vec<u8>, Status> {
+        let segments = path
+            .trim_matches('/')
+            .split('/')
+            .map(percent_decode)
+            .collect::<Result<Vec<_>, _>>()?;
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+
+        macro_rules! request {
+            ($message:expr $(,)?) => {{
+                let mut request = Request::new($message);
+                *request.metadata_mut() = metadata.clone();
+                request
+            }};
+        }
+
+        match segments.as_slice() {
+            // ICS-02 client queries
+            ["ibc", "core", "client", "v1", "client_states"] => json(
+                self.client
+                    .client_states(request!(QueryClientStatesRequest {
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "client_states", client_id] => json(
+                self.client
+                    .client_state(request!(QueryClientStateRequest {
+                        client_id: client_id.to_string(),
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "consensus_states", client_id] => json(
+                self.client
+                    .consensus_states(request!(QueryConsensusStatesRequest {
+                        client_id: client_id.to_string(),
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "consensus_states", client_id, "heights"] => json(
+                self.client
+                    .consensus_state_heights(request!(QueryConsensusStateHeightsRequest {
+                        client_id: client_id.to_string(),
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "consensus_states", client_id, "revision", revision_number, "height", revision_height] => {
+                json(
+                    self.client
+                        .consensus_state(request!(QueryConsensusStateRequest {
+                            client_id: client_id.to_string(),
+                            revision_number: parse_u64(revision_number)?,
+                            revision_height: parse_u64(revision_height)?,
+                            latest_height: params.bool("latest_height")?.unwrap_or_default(),
+                        }))
+                        .await,
+                )
+            }
+            ["ibc", "core", "client", "v1", "client_status", client_id] => json(
+                self.client
+                    .client_status(request!(QueryClientStatusRequest {
+                        client_id: client_id.to_string(),
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "params"] => json(
+                self.client
+                    .client_params(request!(QueryClientParamsRequest {}))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "upgraded_client_states"] => json(
+                self.client
+                    .upgraded_client_state(request!(QueryUpgradedClientStateRequest {}))
+                    .await,
+            ),
+            ["ibc", "core", "client", "v1", "upgraded_consensus_states"] => json(
+                self.client
+                    .upgraded_consensus_state(request!(QueryUpgradedConsensusStateRequest {}))
+                    .await,
+            ),
+
+            // ICS-03 connection queries
+            ["ibc", "core", "connection", "v1", "connections"] => json(
+                self.connection
+                    .connections(request!(QueryConnectionsRequest {
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "connection", "v1", "connections", connection_id] => json(
+                self.connection
+                    .connection(request!(QueryConnectionRequest {
+                        connection_id: connection_id.to_string(),
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "connection", "v1", "client_connections", client_id] => json(
+                self.connection
+                    .client_connections(request!(QueryClientConnectionsRequest {
+                        client_id: client_id.to_string(),
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "connection", "v1", "connections", connection_id, "client_state"] => {
+                json(
+                    self.connection
+                        .connection_client_state(request!(QueryConnectionClientStateRequest {
+                            connection_id: connection_id.to_string(),
+                        }))
+                        .await,
+                )
+            }
+            ["ibc", "core", "connection", "v1", "connections", connection_id, "consensus_state", "revision", revision_number, "height", revision_height] => {
+                json(
+                    self.connection
+                        .connection_consensus_state(request!(
+                            QueryConnectionConsensusStateRequest {
+                                connection_id: connection_id.to_string(),
+                                revision_number: parse_u64(revision_number)?,
+                                revision_height: parse_u64(revision_height)?,
+                            },
+                        ))
+                        .await,
+                )
+            }
+            ["ibc", "core", "connection", "v1", "params"] => json(
+                self.connection
+                    .connection_params(request!(QueryConnectionParamsRequest {}))
+                    .await,
+            ),
+
+            // ICS-04 channel queries
+            ["ibc", "core", "channel", "v1", "channels"] => json(
+                self.channel
+                    .channels(request!(QueryChannelsRequest {
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "channel", "v1", "connections", connection, "channels"] => json(
+                self.channel
+                    .connection_channels(request!(QueryConnectionChannelsRequest {
+                        connection: connection.to_string(),
+                        pagination: params.pagination()?,
+                    }))
+                    .await,
+            ),
+            ["ibc", "core", "channel", "v1", "channels", channel_id, "ports", port_id, rest @ ..] =>
+            {
+                let port_id = port_id.to_string();
+                let channel_id = channel_id.to_string();
+
+                match rest {
+                    [] => json(
+                        self.channel
+                            .channel(request!(QueryChannelRequest {
+                                port_id,
+                                channel_id,
+                            }))
+                            .await,
+                    ),
+                    ["client_state"] => json(
+                        self.channel
+                            .channel_client_state(request!(QueryChannelClientStateRequest {
+                                port_id,
+                                channel_id,
+                            }))
+                            .await,
+                    ),
+                    ["consensus_state", "revision", revision_number, "height", revision_height] => {
+                        json(
+                            self.channel
+                                .channel_consensus_state(request!(
+                                    QueryChannelConsensusStateRequest {
+                                        port_id,
+                                        channel_id,
+                                        revision_number: parse_u64(revision_number)?,
+                                        revision_height: parse_u64(revision_height)?,
+                                    },
+                                ))
+                                .await,
+                        )
+                    }
+                    ["packet_commitments"] => json(
+                        self.channel
+                            .packet_commitments(request!(QueryPacketCommitmentsRequest {
+                                port_id,
+                                channel_id,
+                                pagination: params.pagination()?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_commitments", sequence] => json(
+                        self.channel
+                            .packet_commitment(request!(QueryPacketCommitmentRequest {
+                                port_id,
+                                channel_id,
+                                sequence: parse_u64(sequence)?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_commitments", sequences, "unreceived_packets"] => json(
+                        self.channel
+                            .unreceived_packets(request!(QueryUnreceivedPacketsRequest {
+                                port_id,
+                                channel_id,
+                                packet_commitment_sequences: parse_u64_list(sequences)?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_commitments", sequences, "unreceived_acks"] => json(
+                        self.channel
+                            .unreceived_acks(request!(QueryUnreceivedAcksRequest {
+                                port_id,
+                                channel_id,
+                                packet_ack_sequences: parse_u64_list(sequences)?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_receipts", sequence] => json(
+                        self.channel
+                            .packet_receipt(request!(QueryPacketReceiptRequest {
+                                port_id,
+                                channel_id,
+                                sequence: parse_u64(sequence)?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_acks", sequence] => json(
+                        self.channel
+                            .packet_acknowledgement(request!(QueryPacketAcknowledgementRequest {
+                                port_id,
+                                channel_id,
+                                sequence: parse_u64(sequence)?,
+                            }))
+                            .await,
+                    ),
+                    ["packet_acknowledgements"] => json(
+                        self.channel
+                            .packet_acknowledgements(request!(QueryPacketAcknowledgementsRequest {
+                                port_id,
+                                channel_id,
+                                pagination: params.pagination()?,
+                                packet_commitment_sequences: params
+                                    .get("packet_commitment_sequences")
+                                    .map(parse_u64_list)
+                                    .transpose()?
+                                    .unwrap_or_default(),
+                            }))
+                            .await,
+                    ),
+                    ["next_sequence"] => json(
+                        self.channel
+                            .next_sequence_receive(request!(QueryNextSequenceReceiveRequest {
+                                port_id,
+                                channel_id,
+                            }))
+                            .await,
+                    ),
+                    ["next_sequence_send"] => json(
+                        self.channel
+                            .next_sequence_send(request!(QueryNextSequenceSendRequest {
+                                port_id,
+                                channel_id,
+                            }))
+                            .await,
+                    ),
+                    _ => Err(Status::not_found("Not Found")),
+                }
+            }
+
+            _ => Err(Status::not_found("Not Found")),
+        }
+    }
+}
+
+impl<C, N, H> Clone for RestGateway<C, N, H> {
+    fn clone(&self) -> Self {
+        Self {
+            client: Arc::clone(&self.client),
+            connection: Arc::clone(&self.connection),
+            channel: Arc::clone(&self.channel),
+        }
+    }
+}
+
+impl<C, N, H, B> Service<http::Request<B>> for RestGateway<C, N, H>
+where
+    C: ClientQuery,
+    N: ConnectionQuery,
+    H: ChannelQuery,
+{
+    type Response = http::Response<HyperBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let gateway = self.clone();
+        let (parts, _) = req.into_parts();
+        Box::pin(async move { Ok(gateway.handle(parts).await) })
+    }
+}
+
+/// The decoded query parameters of a request.
+struct QueryParams(BTreeMap<String, String>);
+
+impl QueryParams {
+    fn parse(query: &str) -> Result<Self, Status> {
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                Ok((form_decode(key)?, form_decode(value)?))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    fn u64(&self, key: &str) -> Result<Option<u64>, Status> {
+        self.get(key).map(parse_u64).transpose()
+    }
+
+    fn bool(&self, key: &str) -> Result<Option<bool>, Status> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| Status::invalid_argument(format!("Invalid boolean `{value}`")))
+            })
+            .transpose()
+    }
+
+    fn pagination(&self) -> Result<Option<RawPageRequest>, Status> {
+        if !self.0.keys().any(|key| key.starts_with("pagination.")) {
+            return Ok(None);
+        }
+
+        let key = self
+            .get("pagination.key")
+            .map(|key| {
+                base64::decode(key)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid pagination key: {e}")))
+            })
+            .transpose()?;
+
+        Ok(Some(RawPageRequest {
+            key: key.unwrap_or_default(),
+            offset: self.u64("pagination.offset")?.unwrap_or_default(),
+            limit: self.u64("pagination.limit")?.unwrap_or_default(),
+            count_total: self.bool("pagination.count_total")?.unwrap_or_default(),
+            reverse: self.bool("pagination.reverse")?.unwrap_or_default(),
+        }))
+    }
+}
+
+/// Decodes a percent-encoded path segment, in which `+` is a literal plus sign.
+fn percent_decode(value: &str) -> Result<String, Status> {
+    let invalid = || Status::invalid_argument(format!("Invalid percent-encoding in `{value}`"));
+
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'%' => {
+                let hex = [
+                    chars.next().ok_or_else(invalid)?,
+                    chars.next().ok_or_else(invalid)?,
+                ];
+                let hex = core::str::from_utf8(&hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Decodes a form-encoded query parameter, in which `+` stands for a space.
+fn form_decode(value: &str) -> Result<String, Status> {
+    percent_decode(&value.replace('+', " "))
+}
+
+fn parse_u64(value: &str) -> Result<u64, Status> {
+    value
+        .parse()
+        .map_err(|_| Status::invalid_argument(format!("Invalid number `{value}`")))
+}
+
+fn parse_u64_list(values: &str) -> Result<Vec<u64>, Status> {
+    values
+        .split(',')
+        .filter(|value| !value.is_empty())
+        .map(parse_u64)
+        .collect()
+}
+
+fn json<T: Serialize>(response: Result<Response<T>, Status>) -> Result<Vec<u8>, Status> {
+    serde_json::to_vec(response?.get_ref()).map_err(|e| Status::internal(e.to_string()))
+}
+
+fn error_body(status: &Status) -> Vec<u8> {
+    serde_json::json!({
+        "code": status.code() as i32,
+        "message": status.message(),
+        "details": [],
+    })
+    .to_string()
+    .into_bytes()
+}
+
+fn json_response(status: http::StatusCode, body: Vec<u8>) -> http::Response<HyperBody> {
+    http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(HyperBody::from(body))
+        .expect("valid response")
+}
+
+/// Maps gRPC status codes to HTTP status codes, as done by the grpc-gateway.
+fn http_status(code: Code) -> http::StatusCode {
+    match code {
+        Code::Ok => http::StatusCode::OK,
+        Code::Cancelled => http::StatusCode::REQUEST_TIMEOUT,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            http::StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => http::StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => http::StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => http::StatusCode::CONFLICT,
+        Code::PermissionDenied => http::StatusCode::FORBIDDEN,
+        Code::Unauthenticated => http::StatusCode::UNAUTHORIZED,
+        Code::ResourceExhausted => http::StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => http::StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => http::StatusCode::SERVICE_UNAVAILABLE,
+        Code::Unknown | Code::Internal | Code::DataLoss => http::StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::connection::v1::State as ConnectionState;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::core::ics02_client::error::UpgradeClientError;
+    use crate::core::ics04_channel::commitment::PacketCommitment;
+    use crate::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use crate::core::ics24_host::path::UpgradeClientPath;
+    use crate::core::timestamp::Timestamp;
+    use crate::hosts::tendermint::upgrade_proposal::{Plan, UpgradeValidationContext};
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::context::{AnyClientState, AnyConsensusState, MockContext};
+    use crate::mock::scenario::{transfer_router, ChainSetup};
+    use crate::services::core::channel::ChannelQueryService;
+    use crate::services::core::client::ClientQueryService;
+    use crate::services::core::connection::ConnectionQueryService;
+    use crate::Height;
+
+    struct NoUpgrade;
+
+    impl UpgradeValidationContext for NoUpgrade {
+        type ClientValidationContext = MockContext;
+        type E = MockContext;
+        type AnyConsensusState = AnyConsensusState;
+        type AnyClientState = AnyClientState;
+
+        fn upgrade_plan(&self) -> Result<Plan, UpgradeClientError> {
+            Err(UpgradeClientError::Other {
+                reason: "no upgrade plan".to_string(),
+            })
+        }
+
+        fn upgraded_client_state(
+            &self,
+            _upgrade_path: &UpgradeClientPath,
+        ) -> Result<Self::AnyClientState, UpgradeClientError> {
+            self.upgrade_plan().map(|_| unreachable!())
+        }
+
+        fn upgraded_consensus_state(
+            &self,
+            _upgrade_path: &UpgradeClientPath,
+        ) -> Result<Self::AnyConsensusState, UpgradeClientError> {
+            self.upgrade_plan().map(|_| unreachable!())
+        }
+    }
+
+    type Gateway = RestGateway<
+        ClientQueryService<MockContext, NoUpgrade>,
+        ConnectionQueryService<MockContext>,
+        ChannelQueryService<MockContext>,
+    >;
+
+    async fn get(
+        gateway: &Gateway,
+        uri: &str,
+        height: Option<u64>,
+    ) -> (http::StatusCode, serde_json::Value) {
+        let mut request = http::Request::get(uri);
+        if let Some(height) = height {
+            request = request.header(BLOCK_HEIGHT_METADATA_KEY, height);
+        }
+
+        let response = gateway
+            .clone()
+            .oneshot(request.body(HyperBody::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn decode_path_segments_and_query_parameters() {
+        assert_eq!(percent_decode("a+b%2Fc").unwrap(), "a+b/c");
+        assert_eq!(form_decode("a+b%2Bc").unwrap(), "a b+c");
+        assert!(percent_decode("a%2").is_err());
+
+        let params = QueryParams::parse("pagination.key=a%2Bb+c&reverse").unwrap();
+        assert_eq!(params.get("pagination.key"), Some("a+b c"));
+        assert_eq!(params.get("reverse"), Some(""));
+    }
+
+    /// Serves a chain, at height 8, with an open transfer channel over the
+    /// mock client `9999-mock-0`, on which the packets 1 to 3 are committed
+    /// at heights 6 to 8.
+    fn gateway() -> Gateway {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+
+        let mut ctx = ChainSetup::new(ChainId::new("mockgaia", 0).unwrap(), 5)
+            .with_timestamp(Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap())
+            .with_open_transfer_channel(Height::new(0, 5).unwrap())
+            .build(&transfer_router())
+            .unwrap();
+        for sequence in 1..=3 {
+            ctx.advance_host_chain_height();
+            ctx = ctx.with_packet_commitment(
+                port_id.clone(),
+                channel_id.clone(),
+                sequence.into(),
+                PacketCommitment::from(vec![sequence as u8]),
+            );
+        }
+
+        RestGateway::new(
+            ClientQueryService::new(ctx.clone(), NoUpgrade),
+            ConnectionQueryService::new(ctx.clone()),
+            ChannelQueryService::new(ctx),
+        )
+    }
+
+    /// Returns the body of a response with a proof, without the proof.
+    fn without_proof(mut body: serde_json::Value) -> serde_json::Value {
+        let proof = body.as_object_mut().unwrap().remove("proof").unwrap();
+        assert!(proof.is_array());
+        body
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn serve_grpc_gateway_json() {
+        let gateway = gateway();
+        let height = serde_json::json!({ "revision_number": 0, "revision_height": 8 });
+
+        let (status, mut body) = get(
+            &gateway,
+            "/ibc/core/client/v1/client_states/9999-mock-0",
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        let client_state: Any = serde_json::from_value(body["client_state"].take()).unwrap();
+        assert_eq!(
+            MockClientState::try_from(client_state)
+                .unwrap()
+                .latest_height(),
+            Height::new(0, 5).unwrap()
+        );
+        assert_eq!(
+            without_proof(body),
+            serde_json::json!({
+                "client_state": null,
+                "proof_height": height,
+            })
+        );
+
+        let (status, body) = get(
+            &gateway,
+            "/ibc/core/connection/v1/connections/connection-0",
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(
+            without_proof(body),
+            serde_json::json!({
+                "connection": {
+                    "client_id": "9999-mock-0",
+                    "versions": [{
+                        "identifier": "1",
+                        "features": ["ORDER_ORDERED", "ORDER_UNORDERED"],
+                    }],
+                    "state": ConnectionState::Open as i32,
+                    "counterparty": {
+                        "client_id": "9999-mock-0",
+                        "connection_id": "connection-0",
+                        "prefix": { "key_prefix": "bW9jaw==" },
+                    },
+                    "delay_period": 0,
+                },
+                "proof_height": height,
+            })
+        );
+
+        let (status, body) = get(
+            &gateway,
+            "/ibc/core/channel/v1/channels/channel-0/ports/transfer/packet_commitments?pagination.limit=1",
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "commitments": [{
+                    "port_id": "transfer",
+                    "channel_id": "channel-0",
+                    "sequence": 1,
+                    "data": [1],
+                }],
+                "pagination": { "next_key": [0, 0, 0, 0, 0, 0, 0, 2], "total": 0 },
+                "height": height,
+            })
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn serve_grpc_gateway_routes() {
+        let client_id = "9999-mock-0";
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+        let gateway = gateway();
+
+        let (status, body) = get(
+            &gateway,
+            &format!("/ibc/core/client/v1/client_states/{client_id}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["client_state"]["type_url"], "/ibc.mock.ClientState");
+
+        let (status, body) = get(
+            &gateway,
+            &format!("/ibc/core/client/v1/client_status/{client_id}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["status"], "Active");
+
+        let commitments = format!(
+            "/ibc/core/channel/v1/channels/{channel_id}/ports/{port_id}/packet_commitments"
+        );
+
+        let (status, body) = get(
+            &gateway,
+            &format!("{commitments}?pagination.limit=2&pagination.count_total=true"),
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["commitments"].as_array().unwrap().len(), 2);
+        assert_eq!(body["pagination"]["total"], 3);
+
+        // the third commitment is committed at height 8
+        let (status, body) = get(&gateway, &format!("{commitments}/3"), Some(8)).await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["proof_height"]["revision_height"], 8);
+        let (status, body) = get(&gateway, &format!("{commitments}/3"), Some(7)).await;
+        assert_eq!(status, http::StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], Code::NotFound as i32);
+
+        let (status, body) = get(
+            &gateway,
+            &format!("{commitments}/1,2,4/unreceived_acks"),
+            None,
+        )
+        .await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["sequences"], serde_json::json!([1, 2]));
+
+        let (status, _) = get(&gateway, &format!("{commitments}/one"), None).await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+        let (status, body) = get(&gateway, "/ibc/core/channel/v1/unknown", None).await;
+        assert_eq!(status, http::StatusCode::NOT_FOUND);
+        assert_eq!(body["message"], "Not Found");
+
+        let response = gateway
+            .clone()
+            .oneshot(
+                http::Request::post("/ibc/core/channel/v1/channels")
+                    .body(HyperBody::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
@@ -0,0 +1,42 @@
+//! Required traits for the blanket implementation of the [`gRPC transfer query service`](crate::services::transfer).
+
+use crate::applications::transfer::context::TokenTransferValidationContext;
+use crate::applications::transfer::error::TokenTransferError;
+use crate::applications::transfer::{Amount, PrefixedDenom};
+use crate::prelude::*;
+use crate::services::core::pagination::{PageRequest, Paginated};
+
+/// Context to be implemented by the host that provides the gRPC transfer query service.
+///
+/// Trait used for the [`gRPC transfer query service`](crate::services::transfer).
+pub trait TransferQueryContext: TokenTransferValidationContext {
+    /// Returns the denomination trace whose hash is `hash`, as computed by
+    /// [`denom_hash_string`](TokenTransferValidationContext::denom_hash_string).
+    fn denom_trace(&self, hash: &str) -> Result<PrefixedDenom, TokenTransferError>;
+
+    /// Returns the list of all denomination traces.
+    fn denom_traces(&self) -> Result<Vec<PrefixedDenom>, TokenTransferError>;
+
+    /// Returns a page of denomination traces, keyed by their full path.
+    ///
+    /// The default implementation paginates [`denom_traces`](Self::denom_traces) in memory.
+    fn denom_traces_paginated(
+        &self,
+        page: &PageRequest,
+    ) -> Result<Paginated<PrefixedDenom>, TokenTransferError> {
+        let denom_traces = self.denom_traces()?;
+
+        Ok(page.paginate(
+            denom_traces
+                .into_iter()
+                .map(|denom| (denom.to_string().into_bytes(), denom)),
+        ))
+    }
+
+    /// Returns the total amount of `denom` held in the escrow accounts of the host.
+    fn total_escrow_for_denom(&self, denom: &str) -> Result<Amount, TokenTransferError>;
+
+    /// Returns the bech32 human-readable prefix of the account addresses of the host,
+    /// used to encode escrow addresses.
+    fn account_address_prefix(&self) -> String;
+}
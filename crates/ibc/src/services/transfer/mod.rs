@@ -0,0 +1,11 @@
+//! Implementation of the gRPC query service of the [token transfer application](crate::applications::transfer) (ICS-20).
+//!
+//! [`TransferQueryService`] includes a blanket implementation of the
+//! `ibc.applications.transfer.v1.Query` service, if the host implements
+//! [`TransferQueryContext`].
+
+pub mod context;
+pub mod query;
+
+pub use context::TransferQueryContext;
+pub use query::TransferQueryService;
@@ -0,0 +1,450 @@
+//! [`TransferQueryService`](TransferQueryService) takes a generic `I` to store `transfer_context` that implements [`TransferQueryContext`](TransferQueryContext).
+//! `I` must be a type where writes from one thread are readable from another.
+//! This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+
+use alloc::str::FromStr;
+use std::boxed::Box;
+
+use ibc_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use ibc_proto::ibc::applications::transfer::v1::query_server::Query as TransferQuery;
+use ibc_proto::ibc::applications::transfer::v1::{
+    QueryDenomHashRequest, QueryDenomHashResponse, QueryDenomTraceRequest, QueryDenomTraceResponse,
+    QueryDenomTracesRequest, QueryDenomTracesResponse, QueryEscrowAddressRequest,
+    QueryEscrowAddressResponse, QueryParamsRequest, QueryParamsResponse,
+    QueryTotalEscrowForDenomRequest, QueryTotalEscrowForDenomResponse,
+};
+use subtle_encoding::bech32;
+use tonic::codegen::Arc;
+use tonic::{Request, Response, Status};
+
+use crate::applications::transfer::context::cosmos_adr028_escrow_address;
+use crate::applications::transfer::{PrefixedDenom, RawCoin};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+use crate::services::application::{ApplicationQuery, ApplicationQueryHandlers};
+use crate::services::core::pagination::PageRequest;
+use crate::services::transfer::context::TransferQueryContext;
+
+/// Prefix of the denominations of vouchers minted by the token transfer application.
+const IBC_DENOM_PREFIX: &str = "ibc/";
+
+/// The generic `I` must be a type where writes from one thread are readable from another.
+/// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+pub struct TransferQueryService<I>
+where
+    I: TransferQueryContext + Send + Sync + 'static,
+{
+    transfer_context: I,
+}
+
+impl<I> TransferQueryService<I>
+where
+    I: TransferQueryContext + Send + Sync + 'static,
+{
+    /// The parameter `transfer_context` must be a type where writes from one thread are readable from another.
+    /// This means using `Arc<Mutex<_>>` or `Arc<RwLock<_>>` in most cases.
+    pub fn new(transfer_context: I) -> Self {
+        Self { transfer_context }
+    }
+}
+
+#[tonic::async_trait]
+impl<I> TransferQuery for TransferQueryService<I>
+where
+    I: TransferQueryContext + Send + Sync + 'static,
+{
+    /// Accepts the hash with or without the `ibc/` prefix.
+    async fn denom_trace(
+        &self,
+        request: Request<QueryDenomTraceRequest>,
+    ) -> Result<Response<QueryDenomTraceResponse>, Status> {
+        let request_ref = request.get_ref();
+
+        let hash = request_ref
+            .hash
+            .strip_prefix(IBC_DENOM_PREFIX)
+            .unwrap_or(&request_ref.hash);
+
+        let denom_trace = self.transfer_context.denom_trace(hash)?;
+
+        Ok(Response::new(QueryDenomTraceResponse {
+            denom_trace: Some(denom_trace.into()),
+        }))
+    }
+
+    async fn denom_traces(
+        &self,
+        request: Request<QueryDenomTracesRequest>,
+    ) -> Result<Response<QueryDenomTracesResponse>, Status> {
+        let page = PageRequest::try_from(request.get_ref().pagination.clone())?;
+
+        let (denom_traces, page_response) = self.transfer_context.denom_traces_paginated(&page)?;
+
+        Ok(Response::new(QueryDenomTracesResponse {
+            denom_traces: denom_traces.into_iter().map(Into::into).collect(),
+            pagination: Some(page_response.into()),
+        }))
+    }
+
+    async fn params(
+        &self,
+        _request: Request<QueryParamsRequest>,
+    ) -> Result<Response<QueryParamsResponse>, Status> {
+        let params = self.transfer_context.get_params()?;
+
+        Ok(Response::new(QueryParamsResponse {
+            params: Some(params.into()),
+        }))
+    }
+
+    async fn denom_hash(
+        &self,
+        request: Request<QueryDenomHashRequest>,
+    ) -> Result<Response<QueryDenomHashResponse>, Status> {
+        let denom = PrefixedDenom::from_str(request.get_ref().trace.as_str())
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let hash = self
+            .transfer_context
+            .denom_hash_string(&denom)
+            .ok_or_else(|| Status::unimplemented("Host does not support hashed denominations"))?;
+
+        Ok(Response::new(QueryDenomHashResponse { hash }))
+    }
+
+    async fn escrow_address(
+        &self,
+        request: Request<QueryEscrowAddressRequest>,
+    ) -> Result<Response<QueryEscrowAddressResponse>, Status> {
+        let request_ref = request.get_ref();
+
+        let port_id = PortId::from_str(request_ref.port_id.as_str())?;
+
+        let channel_id = ChannelId::from_str(request_ref.channel_id.as_str())?;
+
+        let escrow_address = bech32::encode(
+            self.transfer_context.account_address_prefix(),
+            cosmos_adr028_escrow_address(&port_id, &channel_id),
+        );
+
+        Ok(Response::new(QueryEscrowAddressResponse { escrow_address }))
+    }
+
+    async fn total_escrow_for_denom(
+        &self,
+        request: Request<QueryTotalEscrowForDenomRequest>,
+    ) -> Result<Response<QueryTotalEscrowForDenomResponse>, Status> {
+        let denom = request.into_inner().denom;
+
+        let amount = self.transfer_context.total_escrow_for_denom(&denom)?;
+
+        Ok(Response::new(QueryTotalEscrowForDenomResponse {
+            amount: Some(ProtoCoin::from(RawCoin { denom, amount })),
+        }))
+    }
+}
+
+/// Registers the `ibc.applications.transfer.v1.Query` methods, so that the transfer
+/// queries can be served by the
+/// [`ApplicationQueryService`](crate::services::application::ApplicationQueryService).
+impl<I> ApplicationQuery for TransferQueryService<I>
+where
+    I: TransferQueryContext + Send + Sync + 'static,
+{
+    fn query_handlers(self) -> ApplicationQueryHandlers {
+        let service = Arc::new(self);
+
+        macro_rules! handler {
+            ($method:ident) => {{
+                let service = Arc::clone(&service);
+                move |request| {
+                    let service = Arc::clone(&service);
+                    async move { service.$method(request).await }
+                }
+            }};
+        }
+
+        ApplicationQueryHandlers::new()
+            .add(
+                "/ibc.applications.transfer.v1.Query/DenomTrace",
+                handler!(denom_trace),
+            )
+            .add(
+                "/ibc.applications.transfer.v1.Query/DenomTraces",
+                handler!(denom_traces),
+            )
+            .add(
+                "/ibc.applications.transfer.v1.Query/Params",
+                handler!(params),
+            )
+            .add(
+                "/ibc.applications.transfer.v1.Query/DenomHash",
+                handler!(denom_hash),
+            )
+            .add(
+                "/ibc.applications.transfer.v1.Query/EscrowAddress",
+                handler!(escrow_address),
+            )
+            .add(
+                "/ibc.applications.transfer.v1.Query/TotalEscrowForDenom",
+                handler!(total_escrow_for_denom),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::query::v1beta1::PageRequest as RawPageRequest;
+    use prost::Message;
+    use sha2::{Digest, Sha256};
+    use subtle_encoding::hex;
+    use tonic::codec::ProstCodec;
+    use tonic::codegen::http::uri::PathAndQuery;
+    use tonic::Code;
+
+    use super::*;
+    use crate::applications::transfer::context::TokenTransferValidationContext;
+    use crate::applications::transfer::error::TokenTransferError;
+    use crate::applications::transfer::params::Params;
+    use crate::applications::transfer::{Amount, PrefixedCoin};
+    use crate::core::router::ModuleId;
+    use crate::mock::router::MockRouter;
+    use crate::services::application::{ApplicationQueryServer, ApplicationQueryService};
+    use crate::signer::Signer;
+    use crate::test_utils::DummyTransferModule;
+
+    struct TransferQueryMock {
+        denom_traces: Vec<PrefixedDenom>,
+    }
+
+    impl TokenTransferValidationContext for TransferQueryMock {
+        type AccountId = Signer;
+
+        fn get_port(&self) -> Result<PortId, TokenTransferError> {
+            Ok(PortId::transfer())
+        }
+
+        fn get_escrow_account(
+            &self,
+            port_id: &PortId,
+            channel_id: &ChannelId,
+        ) -> Result<Self::AccountId, TokenTransferError> {
+            let addr = cosmos_adr028_escrow_address(port_id, channel_id);
+            Ok(bech32::encode("cosmos", addr).into())
+        }
+
+        fn get_params(&self) -> Result<Params, TokenTransferError> {
+            Ok(Params {
+                send_enabled: false,
+                receive_enabled: true,
+            })
+        }
+
+        fn is_transfer_params_authority(&self, _signer: &Signer) -> bool {
+            false
+        }
+
+        fn send_coins_validate(
+            &self,
+            _from_account: &Self::AccountId,
+            _to_account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn mint_coins_validate(
+            &self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn burn_coins_validate(
+            &self,
+            _account: &Self::AccountId,
+            _coin: &PrefixedCoin,
+        ) -> Result<(), TokenTransferError> {
+            Ok(())
+        }
+
+        fn denom_hash_string(&self, denom: &PrefixedDenom) -> Option<String> {
+            let hash = Sha256::digest(denom.to_string().as_bytes());
+            String::from_utf8(hex::encode_upper(hash)).ok()
+        }
+    }
+
+    impl TransferQueryContext for TransferQueryMock {
+        fn denom_trace(&self, hash: &str) -> Result<PrefixedDenom, TokenTransferError> {
+            self.denom_traces
+                .iter()
+                .find(|denom| self.denom_hash_string(denom).as_deref() == Some(hash))
+                .cloned()
+                .ok_or_else(|| TokenTransferError::DenomTraceNotFound {
+                    hash: hash.to_string(),
+                })
+        }
+
+        fn denom_traces(&self) -> Result<Vec<PrefixedDenom>, TokenTransferError> {
+            Ok(self.denom_traces.clone())
+        }
+
+        fn total_escrow_for_denom(&self, denom: &str) -> Result<Amount, TokenTransferError> {
+            Ok(if denom == "uatom" { 100u64 } else { 0 }.into())
+        }
+
+        fn account_address_prefix(&self) -> String {
+            "cosmos".to_string()
+        }
+    }
+
+    fn service() -> TransferQueryService<TransferQueryMock> {
+        TransferQueryService::new(TransferQueryMock {
+            denom_traces: vec![
+                "transfer/channel-0/uatom".parse().unwrap(),
+                "transfer/channel-1/uosmo".parse().unwrap(),
+            ],
+        })
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_denom_traces() {
+        let service = service();
+
+        let hash = service
+            .denom_hash(Request::new(QueryDenomHashRequest {
+                trace: "transfer/channel-0/uatom".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .hash;
+
+        let denom_trace = service
+            .denom_trace(Request::new(QueryDenomTraceRequest {
+                hash: format!("{IBC_DENOM_PREFIX}{hash}"),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .denom_trace
+            .unwrap();
+        assert_eq!(denom_trace.path, "transfer/channel-0");
+        assert_eq!(denom_trace.base_denom, "uatom");
+
+        let status = service
+            .denom_trace(Request::new(QueryDenomTraceRequest {
+                hash: "ABCD".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        let response = service
+            .denom_traces(Request::new(QueryDenomTracesRequest {
+                pagination: Some(RawPageRequest {
+                    key: vec![],
+                    offset: 0,
+                    limit: 1,
+                    count_total: true,
+                    reverse: false,
+                }),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.denom_traces.len(), 1);
+        let pagination = response.pagination.unwrap();
+        assert_eq!(pagination.total, 2);
+        assert_eq!(pagination.next_key, b"transfer/channel-1/uosmo".to_vec());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_params_and_escrow() {
+        let service = service();
+
+        let params = service
+            .params(Request::new(QueryParamsRequest {}))
+            .await
+            .unwrap()
+            .into_inner()
+            .params
+            .unwrap();
+        assert!(!params.send_enabled);
+        assert!(params.receive_enabled);
+
+        let escrow_address = service
+            .escrow_address(Request::new(QueryEscrowAddressRequest {
+                port_id: "transfer".to_string(),
+                channel_id: "channel-141".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .escrow_address;
+        assert_eq!(
+            escrow_address,
+            "cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+        );
+
+        let amount = service
+            .total_escrow_for_denom(Request::new(QueryTotalEscrowForDenomRequest {
+                denom: "uatom".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .amount
+            .unwrap();
+        assert_eq!(amount.denom, "uatom");
+        assert_eq!(amount.amount, "100");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn query_through_application_query_service() {
+        let module_id = ModuleId::new("transfer".to_string());
+        let mut router = MockRouter::default();
+        router
+            .add_route(module_id.clone(), DummyTransferModule::new())
+            .unwrap();
+
+        let mut application_queries = ApplicationQueryService::new();
+        application_queries
+            .register(&router, module_id.clone(), service())
+            .unwrap();
+        assert_eq!(application_queries.query_paths().count(), 6);
+        assert!(application_queries
+            .query_paths()
+            .all(|(_, owner)| owner == &module_id));
+
+        let response = application_queries
+            .query(
+                "/ibc.applications.transfer.v1.Query/DenomTraces",
+                &QueryDenomTracesRequest { pagination: None }.encode_to_vec(),
+            )
+            .await
+            .unwrap();
+        let denom_traces = QueryDenomTracesResponse::decode(response.as_slice())
+            .unwrap()
+            .denom_traces;
+        assert_eq!(denom_traces.len(), 2);
+
+        let mut client = tonic::client::Grpc::new(ApplicationQueryServer::new(application_queries));
+        let response: Response<QueryEscrowAddressResponse> = client
+            .unary(
+                Request::new(QueryEscrowAddressRequest {
+                    port_id: "transfer".to_string(),
+                    channel_id: "channel-141".to_string(),
+                }),
+                PathAndQuery::from_static("/ibc.applications.transfer.v1.Query/EscrowAddress"),
+                ProstCodec::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.into_inner().escrow_address,
+            "cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+        );
+    }
+}
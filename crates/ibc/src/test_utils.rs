@@ -4,6 +4,7 @@ use crate::applications::transfer::context::{
     cosmos_adr028_escrow_address, TokenTransferExecutionContext, TokenTransferValidationContext,
 };
 use crate::applications::transfer::error::TokenTransferError;
+use crate::applications::transfer::params::Params;
 use crate::applications::transfer::PrefixedCoin;
 use crate::core::ics04_channel::acknowledgement::Acknowledgement;
 use crate::core::ics04_channel::channel::{Counterparty, Order};
@@ -160,12 +161,12 @@ impl TokenTransferValidationContext for DummyTransferModule {
         Ok(bech32::encode("cosmos", addr).into())
     }
 
-    fn can_send_coins(&self) -> Result<(), TokenTransferError> {
-        Ok(())
+    fn get_params(&self) -> Result<Params, TokenTransferError> {
+        Ok(Params::default())
     }
 
-    fn can_receive_coins(&self) -> Result<(), TokenTransferError> {
-        Ok(())
+    fn is_transfer_params_authority(&self, _signer: &Signer) -> bool {
+        false
     }
 
     fn send_coins_validate(
@@ -195,6 +196,10 @@ impl TokenTransferValidationContext for DummyTransferModule {
 }
 
 impl TokenTransferExecutionContext for DummyTransferModule {
+    fn store_params(&mut self, _params: Params) -> Result<(), TokenTransferError> {
+        Ok(())
+    }
+
     fn send_coins_execute(
         &mut self,
         _from_account: &Self::AccountId,
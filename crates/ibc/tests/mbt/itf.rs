@@ -0,0 +1,179 @@
+//! A parser for the Informal Trace Format (ITF), in which Apalache writes the
+//! traces it finds.
+//!
+//! The values of a trace are turned into plain JSON before its states are
+//! deserialized: big integers into numbers, sets and tuples into arrays, and
+//! functions into objects keyed by their arguments, which must be strings or
+//! integers. Variants are left as `{ "tag": ..., "value": ... }` objects, to
+//! be deserialized as adjacently tagged enums.
+
+use displaydoc::Display;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// A trace, whose states are of type `S`.
+#[derive(Clone, Debug)]
+pub struct Trace<S> {
+    /// The variables of the model, in each of the states.
+    pub vars: Vec<String>,
+    pub states: Vec<S>,
+}
+
+#[derive(Debug, Display)]
+pub enum ItfError {
+    /// invalid trace: `{0}`
+    InvalidJson(serde_json::Error),
+    /// unsupported value: `{0}`
+    UnsupportedValue(String),
+    /// state {index} does not fit the model: `{reason}`
+    InvalidState { index: usize, reason: String },
+}
+
+#[derive(serde::Deserialize)]
+struct RawTrace {
+    vars: Vec<String>,
+    states: Vec<Map<String, Value>>,
+}
+
+/// Parses an ITF trace, deserializing each of its states into an `S`.
+pub fn parse<S: DeserializeOwned>(json: &str) -> Result<Trace<S>, ItfError> {
+    let raw: RawTrace = serde_json::from_str(json).map_err(ItfError::InvalidJson)?;
+
+    let states = raw
+        .states
+        .into_iter()
+        .enumerate()
+        .map(|(index, state)| {
+            let state = decode(Value::Object(state))?;
+            serde_json::from_value(state).map_err(|e| ItfError::InvalidState {
+                index,
+                reason: e.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Trace {
+        vars: raw.vars,
+        states,
+    })
+}
+
+/// Turns an ITF value into plain JSON, dropping the `#meta` fields.
+fn decode(value: Value) -> Result<Value, ItfError> {
+    match value {
+        Value::Array(items) => decode_all(items).map(Value::Array),
+        Value::Object(mut object) => {
+            object.remove("#meta");
+
+            let tag = match object.keys().next() {
+                Some(key) if object.len() == 1 && key.starts_with('#') => key.clone(),
+                _ => {
+                    return object
+                        .into_iter()
+                        .map(|(key, value)| Ok((key, decode(value)?)))
+                        .collect::<Result<Map<_, _>, _>>()
+                        .map(Value::Object)
+                }
+            };
+            let value = object.remove(&tag).expect("the object has this key");
+
+            match (tag.as_str(), value) {
+                ("#bigint", Value::String(digits)) => {
+                    digits.parse::<i64>().map(Value::from).map_err(|_| {
+                        ItfError::UnsupportedValue(format!("`{digits}` does not fit in an `i64`"))
+                    })
+                }
+                ("#set" | "#tup", Value::Array(items)) => decode_all(items).map(Value::Array),
+                ("#map", Value::Array(entries)) => entries
+                    .into_iter()
+                    .map(decode_entry)
+                    .collect::<Result<Map<_, _>, _>>()
+                    .map(Value::Object),
+                (tag, value) => Err(ItfError::UnsupportedValue(format!(
+                    "{{\"{tag}\": {value}}}"
+                ))),
+            }
+        }
+        value => Ok(value),
+    }
+}
+
+fn decode_all(items: Vec<Value>) -> Result<Vec<Value>, ItfError> {
+    items.into_iter().map(decode).collect()
+}
+
+/// Decodes an entry of a function, an `[argument, value]` pair.
+fn decode_entry(entry: Value) -> Result<(String, Value), ItfError> {
+    let invalid = |entry: &Value| ItfError::UnsupportedValue(format!("function entry {entry}"));
+
+    let (key, value) = match &entry {
+        Value::Array(pair) if pair.len() == 2 => (decode(pair[0].clone())?, pair[1].clone()),
+        _ => return Err(invalid(&entry)),
+    };
+    let key = match key {
+        Value::String(key) => key,
+        Value::Number(key) => key.to_string(),
+        _ => return Err(invalid(&entry)),
+    };
+
+    Ok((key, decode(value)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct State {
+        height: u64,
+        heights: BTreeMap<u64, BTreeSet<i64>>,
+        pair: (String, bool),
+    }
+
+    #[test]
+    fn decodes_values_into_plain_json() {
+        let json = r##"{
+            "#meta": { "format": "ITF" },
+            "vars": ["height", "heights", "pair"],
+            "states": [{
+                "#meta": { "index": 0 },
+                "height": { "#bigint": "12" },
+                "heights": { "#map": [[{ "#bigint": "0" }, { "#set": [{ "#bigint": "-1" }, 3] }]] },
+                "pair": { "#tup": ["a", true] }
+            }]
+        }"##;
+
+        let trace = parse::<State>(json).unwrap();
+        assert_eq!(trace.vars, ["height", "heights", "pair"]);
+        assert_eq!(
+            trace.states,
+            [State {
+                height: 12,
+                heights: BTreeMap::from([(0, BTreeSet::from([-1, 3]))]),
+                pair: ("a".to_string(), true),
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_values() {
+        let trace = |value: &str| {
+            format!(r#"{{ "vars": ["height"], "states": [{{ "height": {value} }}] }}"#)
+        };
+
+        assert!(matches!(
+            parse::<Value>(&trace(r##"{ "#bigint": "100000000000000000000" }"##)),
+            Err(ItfError::UnsupportedValue(_))
+        ));
+        assert!(matches!(
+            parse::<Value>(&trace(r##"{ "#unserializable": "Nat" }"##)),
+            Err(ItfError::UnsupportedValue(_))
+        ));
+        assert!(matches!(
+            parse::<State>(&trace("1")),
+            Err(ItfError::InvalidState { index: 0, .. })
+        ));
+    }
+}
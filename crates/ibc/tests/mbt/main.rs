@@ -0,0 +1,31 @@
+//! Model-based tests: replays the traces of the TLA+ model of
+//! `tests/support/model_based` against the handlers, and checks that the
+//! chains end up in the state of the model after every step.
+
+mod itf;
+mod model;
+mod runner;
+
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn traces_match_the_model() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/support/model_based/traces");
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .expect("the traces directory exists")
+        .map(|entry| entry.expect("the traces directory is readable").path())
+        .filter(|path| path.to_string_lossy().ends_with(".itf.json"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no traces in `{}`", dir.display());
+
+    for path in paths {
+        let json = fs::read_to_string(&path).expect("the trace is readable");
+        let trace =
+            itf::parse::<model::State>(&json).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        if let Err(e) = runner::replay(&trace) {
+            panic!("{}: {e}", path.display());
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! The abstract state of `IBC.tla`, as found in its traces.
+//!
+//! Clients, connections and channels are identified by their counter, from
+//! which the identifiers the handlers give them are derived, and the
+//! counterparty identifiers a connection or channel end does not know yet are
+//! [`NULL_ID`]. Channels are unordered, and bound to the transfer port on
+//! both ends.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ibc::core::ics03_connection::connection::State as ConnectionState;
+use ibc::core::ics04_channel::channel::State as ChannelState;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+pub const NULL_ID: i64 = -1;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {
+    pub chains: BTreeMap<String, Chain>,
+    pub action: Action,
+    pub action_outcome: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Chain {
+    pub height: u64,
+    pub clients: BTreeMap<u64, Client>,
+    pub connections: BTreeMap<u64, Connection>,
+    pub channels: BTreeMap<u64, Channel>,
+}
+
+/// A client, with the heights of its consensus states.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Client {
+    pub heights: BTreeSet<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection {
+    pub state: ConnectionState,
+    pub client_id: u64,
+    pub counterparty_client_id: u64,
+    pub counterparty_connection_id: i64,
+}
+
+/// A channel, with the sequences of its packet commitments, receipts and
+/// acknowledgements.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Channel {
+    pub state: ChannelState,
+    pub connection_id: u64,
+    pub counterparty_channel_id: i64,
+    pub next_send_seq: u64,
+    pub next_recv_seq: u64,
+    pub next_ack_seq: u64,
+    pub commitments: BTreeSet<u64>,
+    pub receipts: BTreeSet<u64>,
+    pub acks: BTreeSet<u64>,
+}
+
+/// A packet, whose source and destination chains are left out: they are the
+/// ones the actions on it run on.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Packet {
+    pub sequence: u64,
+    pub source_channel_id: u64,
+    pub destination_channel_id: u64,
+    pub timeout_height: u64,
+}
+
+/// The step that led to a state. Ids are those of the chain the action is
+/// run on, with the counterparty ones prefixed as such.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "tag", content = "value")]
+pub enum Action {
+    /// The action of the initial state.
+    None(IgnoredAny),
+    #[serde(rename_all = "camelCase")]
+    CreateClient { chain_id: String, height: u64 },
+    #[serde(rename_all = "camelCase")]
+    UpdateClient {
+        chain_id: String,
+        client_id: u64,
+        height: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ConnOpenInit {
+        chain_id: String,
+        client_id: u64,
+        counterparty_client_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ConnOpenTry {
+        chain_id: String,
+        client_id: u64,
+        counterparty_client_id: u64,
+        counterparty_connection_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ConnOpenAck {
+        chain_id: String,
+        connection_id: u64,
+        counterparty_connection_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ConnOpenConfirm {
+        chain_id: String,
+        connection_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChanOpenInit {
+        chain_id: String,
+        connection_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChanOpenTry {
+        chain_id: String,
+        connection_id: u64,
+        counterparty_channel_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChanOpenAck {
+        chain_id: String,
+        channel_id: u64,
+        counterparty_channel_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChanOpenConfirm { chain_id: String, channel_id: u64 },
+    #[serde(rename_all = "camelCase")]
+    SendPacket { chain_id: String, packet: Packet },
+    #[serde(rename_all = "camelCase")]
+    RecvPacket { chain_id: String, packet: Packet },
+    #[serde(rename_all = "camelCase")]
+    AcknowledgePacket { chain_id: String, packet: Packet },
+    #[serde(rename_all = "camelCase")]
+    TimeoutPacket { chain_id: String, packet: Packet },
+    #[serde(rename_all = "camelCase")]
+    AdvanceChain { chain_id: String },
+}
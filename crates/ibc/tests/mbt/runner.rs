@@ -0,0 +1,487 @@
+//! Replays the traces of the model on mock chains.
+//!
+//! The chains are set up from the initial state of a trace, with the transfer
+//! port routed to a `DummyTransferModule`. The action of each following state
+//! is mapped to a scenario action, which builds the message it delivers, and
+//! run on its chain. The outcome of the handler must be the one of the model,
+//! and the store of every chain must then abstract to its state in the model.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+
+use displaydoc::Display;
+use ibc::applications::transfer::VERSION;
+use ibc::core::ics02_client::client_state::ClientStateCommon;
+use ibc::core::ics04_channel::channel::Order;
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics04_channel::timeout::TimeoutHeight;
+use ibc::core::ics04_channel::Version;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use ibc::mock::client_state::client_type;
+use ibc::mock::context::MockContext;
+use ibc::mock::router::MockRouter;
+use ibc::mock::scenario::{
+    transfer_router, Action as ScenarioAction, ChainSetup, PacketSpec, ScenarioMsg,
+};
+use ibc::Height;
+
+use crate::itf::Trace;
+use crate::model::{Action, Chain, Channel, Client, Connection, Packet, State, NULL_ID};
+
+type PortChannelIdMap<V> = BTreeMap<PortId, BTreeMap<ChannelId, V>>;
+
+const PACKET_DATA: &str = "packet";
+const ACKNOWLEDGEMENT: &str = r#"{"result":"AQ=="}"#;
+
+#[derive(Debug, Display)]
+pub enum ReplayError {
+    /// the trace has no states
+    EmptyTrace,
+    /// chain `{chain_id}` cannot be set up: `{reason}`
+    InvalidSetup { chain_id: String, reason: String },
+    /// step {step} has no action
+    MissingAction { step: usize },
+    /// step {step} runs on chain `{chain_id}`, which is not part of the model
+    UnknownChain { step: usize, chain_id: String },
+    /// step {step} has the unknown outcome `{outcome}`
+    UnknownOutcome { step: usize, outcome: String },
+    /// step {step} succeeded, but was expected to fail with `{expected}`
+    UnexpectedSuccess { step: usize, expected: String },
+    /// step {step} failed with `{error}`, but was expected to end with `{expected}`
+    UnexpectedError {
+        step: usize,
+        expected: String,
+        error: String,
+    },
+    /// after step {step}, the store of chain `{chain_id}` cannot be abstracted: `{reason}`
+    InvalidStore {
+        step: usize,
+        chain_id: String,
+        reason: String,
+    },
+    /// after step {step}, chain `{chain_id}` has {part} `{actual}` instead of `{expected}`
+    StateMismatch {
+        step: usize,
+        chain_id: String,
+        part: &'static str,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Replays `trace`, checking the outcome of each step and the state of the
+/// chains after it.
+pub fn replay(trace: &Trace<State>) -> Result<(), ReplayError> {
+    let (initial, steps) = trace.states.split_first().ok_or(ReplayError::EmptyTrace)?;
+
+    let mut chains = BTreeMap::new();
+    for (name, chain) in &initial.chains {
+        let router = transfer_router();
+        let ctx = ChainSetup::new(chain_id(name), chain.height)
+            .build(&router)
+            .map_err(|e| ReplayError::InvalidSetup {
+                chain_id: name.clone(),
+                reason: e.to_string(),
+            })?;
+        chains.insert(name.clone(), (ctx, router));
+    }
+    check_state(0, initial, &chains)?;
+
+    for (index, state) in steps.iter().enumerate() {
+        let step = index + 1;
+        let (name, action) =
+            scenario_action(&state.action).ok_or(ReplayError::MissingAction { step })?;
+        let (ctx, router) = chains
+            .get_mut(name)
+            .ok_or_else(|| ReplayError::UnknownChain {
+                step,
+                chain_id: name.clone(),
+            })?;
+
+        check_outcome(step, &state.action_outcome, action.run(ctx, router))?;
+        check_state(step, state, &chains)?;
+    }
+
+    Ok(())
+}
+
+fn chain_id(name: &str) -> ChainId {
+    ChainId::new(name, 0).expect("the chain names of the model are valid")
+}
+
+fn height(height: u64) -> Height {
+    Height::new(0, height).expect("the heights of the model are not zero")
+}
+
+fn client_id(id: u64) -> ClientId {
+    ClientId::new(client_type(), id).expect("the client type is valid")
+}
+
+/// Returns the chain the action runs on, and the corresponding scenario
+/// action.
+fn scenario_action(action: &Action) -> Option<(&String, ScenarioAction)> {
+    let deliver = |msg: ScenarioMsg| ScenarioAction::Deliver(msg);
+    let version = || Version::new(VERSION.to_string());
+
+    Some(match action {
+        Action::None(_) => return None,
+        Action::CreateClient {
+            chain_id,
+            height: client_height,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::CreateClient {
+                height: height(*client_height),
+            }),
+        ),
+        Action::UpdateClient {
+            chain_id,
+            client_id: id,
+            height: header_height,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::UpdateClient {
+                client_id: client_id(*id),
+                height: height(*header_height),
+            }),
+        ),
+        Action::ConnOpenInit {
+            chain_id,
+            client_id: id,
+            counterparty_client_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ConnOpenInit {
+                client_id: client_id(*id),
+                counterparty_client_id: client_id(*counterparty_client_id),
+            }),
+        ),
+        Action::ConnOpenTry {
+            chain_id,
+            client_id: id,
+            counterparty_client_id,
+            counterparty_connection_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ConnOpenTry {
+                client_id: client_id(*id),
+                counterparty_client_id: client_id(*counterparty_client_id),
+                counterparty_connection_id: ConnectionId::new(*counterparty_connection_id),
+            }),
+        ),
+        Action::ConnOpenAck {
+            chain_id,
+            connection_id,
+            counterparty_connection_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ConnOpenAck {
+                connection_id: ConnectionId::new(*connection_id),
+                counterparty_connection_id: ConnectionId::new(*counterparty_connection_id),
+            }),
+        ),
+        Action::ConnOpenConfirm {
+            chain_id,
+            connection_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ConnOpenConfirm {
+                connection_id: ConnectionId::new(*connection_id),
+            }),
+        ),
+        Action::ChanOpenInit {
+            chain_id,
+            connection_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ChanOpenInit {
+                port_id: PortId::transfer(),
+                connection_id: ConnectionId::new(*connection_id),
+                counterparty_port_id: PortId::transfer(),
+                ordering: Order::Unordered,
+                version: version(),
+            }),
+        ),
+        Action::ChanOpenTry {
+            chain_id,
+            connection_id,
+            counterparty_channel_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ChanOpenTry {
+                port_id: PortId::transfer(),
+                connection_id: ConnectionId::new(*connection_id),
+                counterparty_port_id: PortId::transfer(),
+                counterparty_channel_id: ChannelId::new(*counterparty_channel_id),
+                ordering: Order::Unordered,
+                version: version(),
+            }),
+        ),
+        Action::ChanOpenAck {
+            chain_id,
+            channel_id,
+            counterparty_channel_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ChanOpenAck {
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(*channel_id),
+                counterparty_channel_id: ChannelId::new(*counterparty_channel_id),
+                counterparty_version: version(),
+            }),
+        ),
+        Action::ChanOpenConfirm {
+            chain_id,
+            channel_id,
+        } => (
+            chain_id,
+            deliver(ScenarioMsg::ChanOpenConfirm {
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(*channel_id),
+            }),
+        ),
+        Action::SendPacket { chain_id, packet } => {
+            (chain_id, ScenarioAction::SendPacket(packet_spec(packet)))
+        }
+        Action::RecvPacket { chain_id, packet } => (
+            chain_id,
+            deliver(ScenarioMsg::RecvPacket(packet_spec(packet))),
+        ),
+        Action::AcknowledgePacket { chain_id, packet } => (
+            chain_id,
+            deliver(ScenarioMsg::Acknowledgement {
+                packet: packet_spec(packet),
+                acknowledgement: ACKNOWLEDGEMENT.to_string(),
+            }),
+        ),
+        Action::TimeoutPacket { chain_id, packet } => (
+            chain_id,
+            deliver(ScenarioMsg::Timeout {
+                packet: packet_spec(packet),
+                next_sequence_recv: packet.sequence,
+            }),
+        ),
+        Action::AdvanceChain { chain_id } => (chain_id, ScenarioAction::AdvanceBlocks(1)),
+    })
+}
+
+fn packet_spec(packet: &Packet) -> PacketSpec {
+    PacketSpec {
+        sequence: packet.sequence,
+        source_port: PortId::transfer(),
+        source_channel: ChannelId::new(packet.source_channel_id),
+        destination_port: PortId::transfer(),
+        destination_channel: ChannelId::new(packet.destination_channel_id),
+        data: PACKET_DATA.to_string(),
+        timeout_height: TimeoutHeight::At(height(packet.timeout_height)),
+        timeout_timestamp: 0,
+    }
+}
+
+/// Returns the text of the error each failed outcome of the model stands for.
+fn expected_error(outcome: &str) -> Option<&'static str> {
+    Some(match outcome {
+        "ClientNotFound" => "client state not found",
+        "LowHeaderHeight" => "is lower than (or equal to) client latest height",
+        "ConnectionNotFound" => "no connection was found",
+        "InvalidConnectionState" => "invalid connection state",
+        "ChannelNotFound" => "does not exist",
+        "InvalidChannelState" => "invalid channel state",
+        "InvalidPacketCounterparty" => "invalid channel counterparty",
+        "InvalidPacketSequence" => "Invalid packet sequence",
+        "LowPacketHeight" => "Receiving chain block height",
+        "AcknowledgementExists" => "Packet acknowledgement exists",
+        "PacketTimeoutNotReached" => "Packet timeout height",
+        _ => return None,
+    })
+}
+
+fn check_outcome(
+    step: usize,
+    outcome: &str,
+    result: Result<(), String>,
+) -> Result<(), ReplayError> {
+    let expected = if outcome == "OK" {
+        None
+    } else {
+        Some(
+            expected_error(outcome).ok_or_else(|| ReplayError::UnknownOutcome {
+                step,
+                outcome: outcome.to_string(),
+            })?,
+        )
+    };
+
+    match (expected, result) {
+        (None, Ok(())) => Ok(()),
+        (Some(expected), Err(error)) if error.contains(expected) => Ok(()),
+        (Some(_), Ok(())) => Err(ReplayError::UnexpectedSuccess {
+            step,
+            expected: outcome.to_string(),
+        }),
+        (_, Err(error)) => Err(ReplayError::UnexpectedError {
+            step,
+            expected: outcome.to_string(),
+            error,
+        }),
+    }
+}
+
+fn check_state(
+    step: usize,
+    state: &State,
+    chains: &BTreeMap<String, (MockContext, MockRouter)>,
+) -> Result<(), ReplayError> {
+    for (name, expected) in &state.chains {
+        let (ctx, _) = chains.get(name).ok_or_else(|| ReplayError::UnknownChain {
+            step,
+            chain_id: name.clone(),
+        })?;
+        let actual = abstract_chain(ctx).map_err(|reason| ReplayError::InvalidStore {
+            step,
+            chain_id: name.clone(),
+            reason,
+        })?;
+
+        let compare = |part: &'static str, expected: &dyn Debug, actual: &dyn Debug| {
+            Err(ReplayError::StateMismatch {
+                step,
+                chain_id: name.clone(),
+                part,
+                expected: format!("{expected:?}"),
+                actual: format!("{actual:?}"),
+            })
+        };
+        if actual.height != expected.height {
+            return compare("height", &expected.height, &actual.height);
+        }
+        if actual.clients != expected.clients {
+            return compare("clients", &expected.clients, &actual.clients);
+        }
+        if actual.connections != expected.connections {
+            return compare("connections", &expected.connections, &actual.connections);
+        }
+        if actual.channels != expected.channels {
+            return compare("channels", &expected.channels, &actual.channels);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the counter an identifier was derived from.
+fn counter(id: &str) -> Result<u64, String> {
+    id.rsplit('-')
+        .next()
+        .and_then(|counter| counter.parse().ok())
+        .ok_or_else(|| format!("`{id}` was not derived from a counter"))
+}
+
+/// Returns the state of the model the store of `ctx` stands for.
+fn abstract_chain(ctx: &MockContext) -> Result<Chain, String> {
+    let store = ctx.ibc_store.lock();
+
+    let mut clients = BTreeMap::new();
+    for (client_id, record) in &store.clients {
+        let heights: BTreeSet<u64> = record
+            .consensus_states
+            .keys()
+            .map(Height::revision_height)
+            .collect();
+        let latest_height = record
+            .client_state
+            .as_ref()
+            .ok_or_else(|| format!("client `{client_id}` has no client state"))?
+            .latest_height();
+        if heights.iter().next_back() != Some(&latest_height.revision_height()) {
+            return Err(format!(
+                "client `{client_id}` is at `{latest_height}`, not at its highest consensus state"
+            ));
+        }
+        clients.insert(counter(client_id.as_str())?, Client { heights });
+    }
+
+    let mut connections = BTreeMap::new();
+    for (connection_id, connection_end) in &store.connections {
+        let counterparty = connection_end.counterparty();
+        connections.insert(
+            counter(connection_id.as_str())?,
+            Connection {
+                state: *connection_end.state(),
+                client_id: counter(connection_end.client_id().as_str())?,
+                counterparty_client_id: counter(counterparty.client_id().as_str())?,
+                counterparty_connection_id: optional_counter(
+                    counterparty.connection_id().map(ConnectionId::as_str),
+                )?,
+            },
+        );
+    }
+
+    let port_id = PortId::transfer();
+    let mut channels = BTreeMap::new();
+    for (channel_id, channel_end) in store.channels.get(&port_id).into_iter().flatten() {
+        let connection_id = channel_end
+            .connection_hops()
+            .first()
+            .ok_or_else(|| format!("channel `{channel_id}` has no connection"))?;
+        channels.insert(
+            counter(channel_id.as_str())?,
+            Channel {
+                state: *channel_end.state(),
+                connection_id: counter(connection_id.as_str())?,
+                counterparty_channel_id: optional_counter(
+                    channel_end
+                        .counterparty()
+                        .channel_id()
+                        .map(ChannelId::as_str),
+                )?,
+                next_send_seq: next_sequence(&store.next_sequence_send, &port_id, channel_id)?,
+                next_recv_seq: next_sequence(&store.next_sequence_recv, &port_id, channel_id)?,
+                next_ack_seq: next_sequence(&store.next_sequence_ack, &port_id, channel_id)?,
+                commitments: packet_sequences(&store.packet_commitment, &port_id, channel_id),
+                receipts: packet_sequences(&store.packet_receipt, &port_id, channel_id),
+                acks: packet_sequences(&store.packet_acknowledgement, &port_id, channel_id),
+            },
+        );
+    }
+
+    Ok(Chain {
+        height: ctx.latest_height().revision_height(),
+        clients,
+        connections,
+        channels,
+    })
+}
+
+fn optional_counter(id: Option<&str>) -> Result<i64, String> {
+    match id {
+        Some(id) => Ok(counter(id)? as i64),
+        None => Ok(NULL_ID),
+    }
+}
+
+fn next_sequence(
+    sequences: &PortChannelIdMap<Sequence>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<u64, String> {
+    sequences
+        .get(port_id)
+        .and_then(|channels| channels.get(channel_id))
+        .map(|sequence| u64::from(*sequence))
+        .ok_or_else(|| format!("channel `{channel_id}` has no next sequence"))
+}
+
+/// Returns the sequences of the packets stored for a channel.
+fn packet_sequences<T>(
+    packets: &PortChannelIdMap<BTreeMap<Sequence, T>>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> BTreeSet<u64> {
+    packets
+        .get(port_id)
+        .and_then(|channels| channels.get(channel_id))
+        .into_iter()
+        .flat_map(|packets| packets.keys().map(|sequence| u64::from(*sequence)))
+        .collect()
+}
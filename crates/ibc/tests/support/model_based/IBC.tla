@@ -0,0 +1,501 @@
+-------------------------------- MODULE IBC --------------------------------
+(*
+ * An abstract model of the IBC handlers of a set of chains: their ICS-02
+ * clients, ICS-03 connections and ICS-04 unordered channels on the transfer
+ * port, along with the packets sent over them.
+ *
+ * Proofs are not modelled: the outcome of a message only depends on the
+ * state of the chain it is delivered to, as it does with the mock light
+ * clients the traces are replayed with. Each step delivers a message in a
+ * block of its own, sends a packet or produces an empty block, and records
+ * the outcome of the handler in `actionOutcome`: "OK", or the name of the
+ * error it fails with. Chains are left unchanged by failed steps.
+ *)
+
+EXTENDS Integers, FiniteSets, TLC, Apalache, Variants
+
+CONSTANTS
+    \* @type: Set(Str);
+    ChainIds,
+    \* The highest height of the chains, and of the headers of their clients.
+    \* @type: Int;
+    MaxHeight,
+    \* The highest number of clients, connections and channels of a chain.
+    \* @type: Int;
+    MaxIds,
+    \* The highest sequence of the packets sent on a channel.
+    \* @type: Int;
+    MaxSequence
+
+(*
+  @typeAlias: client = { heights: Set(Int) };
+  @typeAlias: connection = {
+    state: Str,
+    clientId: Int,
+    counterpartyClientId: Int,
+    counterpartyConnectionId: Int
+  };
+  @typeAlias: channel = {
+    state: Str,
+    connectionId: Int,
+    counterpartyChannelId: Int,
+    nextSendSeq: Int,
+    nextRecvSeq: Int,
+    nextAckSeq: Int,
+    commitments: Set(Int),
+    receipts: Set(Int),
+    acks: Set(Int)
+  };
+  @typeAlias: chain = {
+    height: Int,
+    clients: Int -> $client,
+    connections: Int -> $connection,
+    channels: Int -> $channel
+  };
+  @typeAlias: packet = {
+    sequence: Int,
+    sourceChainId: Str,
+    sourceChannelId: Int,
+    destinationChainId: Str,
+    destinationChannelId: Int,
+    timeoutHeight: Int
+  };
+  @typeAlias: action =
+      None(UNIT)
+    | CreateClient({ chainId: Str, height: Int })
+    | UpdateClient({ chainId: Str, clientId: Int, height: Int })
+    | ConnOpenInit({ chainId: Str, clientId: Int, counterpartyClientId: Int })
+    | ConnOpenTry({ chainId: Str, clientId: Int, counterpartyClientId: Int, counterpartyConnectionId: Int })
+    | ConnOpenAck({ chainId: Str, connectionId: Int, counterpartyConnectionId: Int })
+    | ConnOpenConfirm({ chainId: Str, connectionId: Int })
+    | ChanOpenInit({ chainId: Str, connectionId: Int })
+    | ChanOpenTry({ chainId: Str, connectionId: Int, counterpartyChannelId: Int })
+    | ChanOpenAck({ chainId: Str, channelId: Int, counterpartyChannelId: Int })
+    | ChanOpenConfirm({ chainId: Str, channelId: Int })
+    | SendPacket({ chainId: Str, packet: $packet })
+    | RecvPacket({ chainId: Str, packet: $packet })
+    | AcknowledgePacket({ chainId: Str, packet: $packet })
+    | TimeoutPacket({ chainId: Str, packet: $packet })
+    | AdvanceChain({ chainId: Str });
+*)
+
+VARIABLES
+    \* @type: Str -> $chain;
+    chains,
+    \* The packets sent so far, which relayers may deliver.
+    \* @type: Set($packet);
+    sentPackets,
+    \* @type: $action;
+    action,
+    \* @type: Str;
+    actionOutcome
+
+vars == <<chains, sentPackets, action, actionOutcome>>
+
+\* The counterparty identifier of a connection or channel end that has none.
+NullId == -1
+
+Heights == 1..MaxHeight
+Ids == 0..(MaxIds - 1)
+Sequences == 1..MaxSequence
+
+\* @type: Set(Int) => Int;
+Max(S) == ApaFoldSet(LAMBDA x, y: IF x > y THEN x ELSE y, 0, S)
+
+\* The identifier the next client, connection or channel is given.
+\* @type: (Int -> a) => Int;
+NextId(f) == Cardinality(DOMAIN f)
+
+\* @type: ($chain, Int) => Int;
+LatestHeight(chain, clientId) == Max(chain.clients[clientId].heights)
+
+\* The latest height of the client the proofs of a channel are verified with.
+\* @type: ($chain, Int) => Int;
+ChannelClientHeight(chain, channelId) ==
+    LET connectionId == chain.channels[channelId].connectionId IN
+    LatestHeight(chain, chain.connections[connectionId].clientId)
+
+\* @type: ($chain, Int) => Bool;
+ChannelConnectionOpen(chain, channelId) ==
+    LET connectionId == chain.channels[channelId].connectionId IN
+    chain.connections[connectionId].state = "Open"
+
+\* Produces a block.
+\* @type: $chain => $chain;
+Commit(chain) == [chain EXCEPT !.height = @ + 1]
+
+\* Records the outcome of the action on `chainId`, after which the chain is
+\* `chain` if the action succeeded.
+\* @type: (Str, $action, Str, $chain) => Bool;
+Apply(chainId, a, outcome, chain) ==
+    /\ action' = a
+    /\ actionOutcome' = outcome
+    /\ chains' = IF outcome = "OK" THEN [chains EXCEPT ![chainId] = chain] ELSE chains
+
+(*************************** ICS-02: clients *********************************)
+
+CreateClient(chainId, height) ==
+    LET chain == chains[chainId] IN
+    LET client == [heights |-> {height}] IN
+    /\ NextId(chain.clients) < MaxIds
+    /\ Apply(
+        chainId,
+        Variant("CreateClient", [chainId |-> chainId, height |-> height]),
+        "OK",
+        Commit([chain EXCEPT !.clients = @ @@ (NextId(chain.clients) :> client)])
+       )
+    /\ UNCHANGED sentPackets
+
+UpdateClient(chainId, clientId, height) ==
+    LET chain == chains[chainId] IN
+    LET outcome ==
+        IF clientId \notin DOMAIN chain.clients THEN "ClientNotFound"
+        ELSE IF height <= LatestHeight(chain, clientId) THEN "LowHeaderHeight"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("UpdateClient", [chainId |-> chainId, clientId |-> clientId, height |-> height]),
+        outcome,
+        Commit([chain EXCEPT !.clients[clientId].heights = @ \union {height}])
+       )
+    /\ UNCHANGED sentPackets
+
+(************************* ICS-03: connections *******************************)
+
+ConnOpenInit(chainId, clientId, counterpartyClientId) ==
+    LET chain == chains[chainId] IN
+    LET connection == [
+        state |-> "Init",
+        clientId |-> clientId,
+        counterpartyClientId |-> counterpartyClientId,
+        counterpartyConnectionId |-> NullId
+    ] IN
+    LET outcome == IF clientId \notin DOMAIN chain.clients THEN "ClientNotFound" ELSE "OK" IN
+    /\ NextId(chain.connections) < MaxIds
+    /\ Apply(
+        chainId,
+        Variant("ConnOpenInit", [
+            chainId |-> chainId,
+            clientId |-> clientId,
+            counterpartyClientId |-> counterpartyClientId
+        ]),
+        outcome,
+        Commit([chain EXCEPT !.connections = @ @@ (NextId(chain.connections) :> connection)])
+       )
+    /\ UNCHANGED sentPackets
+
+ConnOpenTry(chainId, clientId, counterpartyClientId, counterpartyConnectionId) ==
+    LET chain == chains[chainId] IN
+    LET connection == [
+        state |-> "TryOpen",
+        clientId |-> clientId,
+        counterpartyClientId |-> counterpartyClientId,
+        counterpartyConnectionId |-> counterpartyConnectionId
+    ] IN
+    LET outcome == IF clientId \notin DOMAIN chain.clients THEN "ClientNotFound" ELSE "OK" IN
+    /\ NextId(chain.connections) < MaxIds
+    /\ Apply(
+        chainId,
+        Variant("ConnOpenTry", [
+            chainId |-> chainId,
+            clientId |-> clientId,
+            counterpartyClientId |-> counterpartyClientId,
+            counterpartyConnectionId |-> counterpartyConnectionId
+        ]),
+        outcome,
+        Commit([chain EXCEPT !.connections = @ @@ (NextId(chain.connections) :> connection)])
+       )
+    /\ UNCHANGED sentPackets
+
+ConnOpenAck(chainId, connectionId, counterpartyConnectionId) ==
+    LET chain == chains[chainId] IN
+    LET outcome ==
+        IF connectionId \notin DOMAIN chain.connections THEN "ConnectionNotFound"
+        ELSE IF chain.connections[connectionId].state /= "Init" THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("ConnOpenAck", [
+            chainId |-> chainId,
+            connectionId |-> connectionId,
+            counterpartyConnectionId |-> counterpartyConnectionId
+        ]),
+        outcome,
+        Commit([chain EXCEPT
+            !.connections[connectionId].state = "Open",
+            !.connections[connectionId].counterpartyConnectionId = counterpartyConnectionId
+        ])
+       )
+    /\ UNCHANGED sentPackets
+
+ConnOpenConfirm(chainId, connectionId) ==
+    LET chain == chains[chainId] IN
+    LET outcome ==
+        IF connectionId \notin DOMAIN chain.connections THEN "ConnectionNotFound"
+        ELSE IF chain.connections[connectionId].state /= "TryOpen" THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("ConnOpenConfirm", [chainId |-> chainId, connectionId |-> connectionId]),
+        outcome,
+        Commit([chain EXCEPT !.connections[connectionId].state = "Open"])
+       )
+    /\ UNCHANGED sentPackets
+
+(*************************** ICS-04: channels ********************************)
+
+\* @type: (Str, Int, Int) => $channel;
+NewChannel(state, connectionId, counterpartyChannelId) == [
+    state |-> state,
+    connectionId |-> connectionId,
+    counterpartyChannelId |-> counterpartyChannelId,
+    nextSendSeq |-> 1,
+    nextRecvSeq |-> 1,
+    nextAckSeq |-> 1,
+    commitments |-> {},
+    receipts |-> {},
+    acks |-> {}
+]
+
+ChanOpenInit(chainId, connectionId) ==
+    LET chain == chains[chainId] IN
+    LET channel == NewChannel("Init", connectionId, NullId) IN
+    LET outcome ==
+        IF connectionId \notin DOMAIN chain.connections THEN "ConnectionNotFound" ELSE "OK"
+    IN
+    /\ NextId(chain.channels) < MaxIds
+    /\ Apply(
+        chainId,
+        Variant("ChanOpenInit", [chainId |-> chainId, connectionId |-> connectionId]),
+        outcome,
+        Commit([chain EXCEPT !.channels = @ @@ (NextId(chain.channels) :> channel)])
+       )
+    /\ UNCHANGED sentPackets
+
+ChanOpenTry(chainId, connectionId, counterpartyChannelId) ==
+    LET chain == chains[chainId] IN
+    LET channel == NewChannel("TryOpen", connectionId, counterpartyChannelId) IN
+    LET outcome ==
+        IF connectionId \notin DOMAIN chain.connections THEN "ConnectionNotFound"
+        ELSE IF chain.connections[connectionId].state /= "Open" THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ NextId(chain.channels) < MaxIds
+    /\ Apply(
+        chainId,
+        Variant("ChanOpenTry", [
+            chainId |-> chainId,
+            connectionId |-> connectionId,
+            counterpartyChannelId |-> counterpartyChannelId
+        ]),
+        outcome,
+        Commit([chain EXCEPT !.channels = @ @@ (NextId(chain.channels) :> channel)])
+       )
+    /\ UNCHANGED sentPackets
+
+ChanOpenAck(chainId, channelId, counterpartyChannelId) ==
+    LET chain == chains[chainId] IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].state /= "Init" THEN "InvalidChannelState"
+        ELSE IF ~ChannelConnectionOpen(chain, channelId) THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("ChanOpenAck", [
+            chainId |-> chainId,
+            channelId |-> channelId,
+            counterpartyChannelId |-> counterpartyChannelId
+        ]),
+        outcome,
+        Commit([chain EXCEPT
+            !.channels[channelId].state = "Open",
+            !.channels[channelId].counterpartyChannelId = counterpartyChannelId
+        ])
+       )
+    /\ UNCHANGED sentPackets
+
+ChanOpenConfirm(chainId, channelId) ==
+    LET chain == chains[chainId] IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].state /= "TryOpen" THEN "InvalidChannelState"
+        ELSE IF ~ChannelConnectionOpen(chain, channelId) THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("ChanOpenConfirm", [chainId |-> chainId, channelId |-> channelId]),
+        outcome,
+        Commit([chain EXCEPT !.channels[channelId].state = "Open"])
+       )
+    /\ UNCHANGED sentPackets
+
+(**************************** ICS-04: packets ********************************)
+
+\* Sending a packet is not a message of its own, and produces no block.
+\* @type: $packet => Bool;
+SendPacket(packet) ==
+    LET chainId == packet.sourceChainId IN
+    LET chain == chains[chainId] IN
+    LET channelId == packet.sourceChannelId IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].counterpartyChannelId /= packet.destinationChannelId
+            THEN "InvalidPacketCounterparty"
+        ELSE IF ChannelClientHeight(chain, channelId) > packet.timeoutHeight THEN "LowPacketHeight"
+        ELSE IF packet.sequence /= chain.channels[channelId].nextSendSeq
+            THEN "InvalidPacketSequence"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("SendPacket", [chainId |-> chainId, packet |-> packet]),
+        outcome,
+        [chain EXCEPT
+            !.channels[channelId].commitments = @ \union {packet.sequence},
+            !.channels[channelId].nextSendSeq = @ + 1
+        ]
+       )
+    /\ sentPackets' = IF outcome = "OK" THEN sentPackets \union {packet} ELSE sentPackets
+
+\* @type: $packet => Bool;
+RecvPacket(packet) ==
+    LET chainId == packet.destinationChainId IN
+    LET chain == chains[chainId] IN
+    LET channelId == packet.destinationChannelId IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].state /= "Open" THEN "InvalidChannelState"
+        ELSE IF chain.channels[channelId].counterpartyChannelId /= packet.sourceChannelId
+            THEN "InvalidPacketCounterparty"
+        ELSE IF ~ChannelConnectionOpen(chain, channelId) THEN "InvalidConnectionState"
+        ELSE IF chain.height > packet.timeoutHeight THEN "LowPacketHeight"
+        ELSE IF packet.sequence \in chain.channels[channelId].acks THEN "AcknowledgementExists"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("RecvPacket", [chainId |-> chainId, packet |-> packet]),
+        outcome,
+        Commit([chain EXCEPT
+            !.channels[channelId].receipts = @ \union {packet.sequence},
+            !.channels[channelId].acks = @ \union {packet.sequence}
+        ])
+       )
+    /\ UNCHANGED sentPackets
+
+\* Packets whose commitment is gone were acknowledged or timed out already,
+\* and are accepted as no-ops.
+\* @type: $packet => Bool;
+AcknowledgePacket(packet) ==
+    LET chainId == packet.sourceChainId IN
+    LET chain == chains[chainId] IN
+    LET channelId == packet.sourceChannelId IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].state /= "Open" THEN "InvalidChannelState"
+        ELSE IF chain.channels[channelId].counterpartyChannelId /= packet.destinationChannelId
+            THEN "InvalidPacketCounterparty"
+        ELSE IF ~ChannelConnectionOpen(chain, channelId) THEN "InvalidConnectionState"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("AcknowledgePacket", [chainId |-> chainId, packet |-> packet]),
+        outcome,
+        Commit([chain EXCEPT !.channels[channelId].commitments = @ \ {packet.sequence}])
+       )
+    /\ UNCHANGED sentPackets
+
+\* @type: $packet => Bool;
+TimeoutPacket(packet) ==
+    LET chainId == packet.sourceChainId IN
+    LET chain == chains[chainId] IN
+    LET channelId == packet.sourceChannelId IN
+    LET outcome ==
+        IF channelId \notin DOMAIN chain.channels THEN "ChannelNotFound"
+        ELSE IF chain.channels[channelId].state /= "Open" THEN "InvalidChannelState"
+        ELSE IF chain.channels[channelId].counterpartyChannelId /= packet.destinationChannelId
+            THEN "InvalidPacketCounterparty"
+        ELSE IF packet.sequence \notin chain.channels[channelId].commitments THEN "OK"
+        ELSE IF ChannelClientHeight(chain, channelId) <= packet.timeoutHeight
+            THEN "PacketTimeoutNotReached"
+        ELSE "OK"
+    IN
+    /\ Apply(
+        chainId,
+        Variant("TimeoutPacket", [chainId |-> chainId, packet |-> packet]),
+        outcome,
+        Commit([chain EXCEPT !.channels[channelId].commitments = @ \ {packet.sequence}])
+       )
+    /\ UNCHANGED sentPackets
+
+AdvanceChain(chainId) ==
+    /\ Apply(
+        chainId,
+        Variant("AdvanceChain", [chainId |-> chainId]),
+        "OK",
+        Commit(chains[chainId])
+       )
+    /\ UNCHANGED sentPackets
+
+(******************************** Spec ***************************************)
+
+Init ==
+    /\ chains = [chainId \in ChainIds |-> [
+            height |-> 1,
+            clients |-> SetAsFun({}),
+            connections |-> SetAsFun({}),
+            channels |-> SetAsFun({})
+       ]]
+    /\ sentPackets = {}
+    /\ action = Variant("None", UNIT)
+    /\ actionOutcome = "None"
+
+Next ==
+    \E chainId \in ChainIds:
+        /\ chains[chainId].height < MaxHeight
+        /\ \/ \E height \in Heights: CreateClient(chainId, height)
+           \/ \E clientId \in Ids, height \in Heights: UpdateClient(chainId, clientId, height)
+           \/ \E clientId, counterpartyClientId \in Ids:
+                ConnOpenInit(chainId, clientId, counterpartyClientId)
+           \/ \E clientId, counterpartyClientId, counterpartyConnectionId \in Ids:
+                ConnOpenTry(chainId, clientId, counterpartyClientId, counterpartyConnectionId)
+           \/ \E connectionId, counterpartyConnectionId \in Ids:
+                ConnOpenAck(chainId, connectionId, counterpartyConnectionId)
+           \/ \E connectionId \in Ids: ConnOpenConfirm(chainId, connectionId)
+           \/ \E connectionId \in Ids: ChanOpenInit(chainId, connectionId)
+           \/ \E connectionId, counterpartyChannelId \in Ids:
+                ChanOpenTry(chainId, connectionId, counterpartyChannelId)
+           \/ \E channelId, counterpartyChannelId \in Ids:
+                ChanOpenAck(chainId, channelId, counterpartyChannelId)
+           \/ \E channelId \in Ids: ChanOpenConfirm(chainId, channelId)
+           \/ \E destinationChainId \in ChainIds \ {chainId},
+                 sequence \in Sequences,
+                 sourceChannelId, destinationChannelId \in Ids,
+                 timeoutHeight \in Heights:
+                SendPacket([
+                    sequence |-> sequence,
+                    sourceChainId |-> chainId,
+                    sourceChannelId |-> sourceChannelId,
+                    destinationChainId |-> destinationChainId,
+                    destinationChannelId |-> destinationChannelId,
+                    timeoutHeight |-> timeoutHeight
+                ])
+           \/ \E packet \in sentPackets:
+                /\ chainId = packet.destinationChainId
+                /\ RecvPacket(packet)
+           \/ \E packet \in sentPackets:
+                /\ chainId = packet.sourceChainId
+                /\ \/ AcknowledgePacket(packet)
+                   \/ TimeoutPacket(packet)
+           \/ AdvanceChain(chainId)
+
+Spec == Init /\ [][Next]_vars
+
+=============================================================================
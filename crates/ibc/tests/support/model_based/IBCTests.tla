@@ -0,0 +1,60 @@
+------------------------------ MODULE IBCTests ------------------------------
+(*
+ * The scenarios of the traces in `traces/`. Each trace ends in the state
+ * described by one of the tests below, and traces like it are found as
+ * counterexamples to the invariant negating that test:
+ *
+ *   apalache-mc check --cinit=ConstInit --inv=<Test>Inv --length=<steps> IBCTests.tla
+ *)
+
+EXTENDS IBC
+
+ConstInit ==
+    /\ ChainIds = {"chainA", "chainB"}
+    /\ MaxHeight = 30
+    /\ MaxIds = 2
+    /\ MaxSequence = 3
+
+\* @type: (Str, Str) => Bool;
+Outcome(tag, outcome) == VariantTag(action) = tag /\ actionOutcome = outcome
+
+\* A client is updated, then updated again with a header that is not higher.
+ClientUpdateTest ==
+    /\ Outcome("UpdateClient", "LowHeaderHeight")
+    /\ \E chainId \in ChainIds, clientId \in Ids:
+        /\ clientId \in DOMAIN chains[chainId].clients
+        /\ Cardinality(chains[chainId].clients[clientId].heights) > 1
+
+\* Both chains end up with an open connection.
+ConnectionOpenTest ==
+    \A chainId \in ChainIds:
+        \E connectionId \in DOMAIN chains[chainId].connections:
+            chains[chainId].connections[connectionId].state = "Open"
+
+\* Both chains end up with an open channel.
+ChannelOpenTest ==
+    \A chainId \in ChainIds:
+        \E channelId \in DOMAIN chains[chainId].channels:
+            chains[chainId].channels[channelId].state = "Open"
+
+\* A packet is acknowledged after it was received.
+PacketAcknowledgedTest ==
+    /\ Outcome("AcknowledgePacket", "OK")
+    /\ \E packet \in sentPackets:
+        /\ packet.sequence \notin chains[packet.sourceChainId].channels[packet.sourceChannelId].commitments
+        /\ packet.sequence \in chains[packet.destinationChainId].channels[packet.destinationChannelId].acks
+
+\* A packet times out after the chain it was sent to failed to receive it.
+PacketTimedOutTest ==
+    /\ Outcome("TimeoutPacket", "OK")
+    /\ \E packet \in sentPackets:
+        /\ packet.sequence \notin chains[packet.sourceChainId].channels[packet.sourceChannelId].commitments
+        /\ packet.sequence \notin chains[packet.destinationChainId].channels[packet.destinationChannelId].receipts
+
+ClientUpdateInv == ~ClientUpdateTest
+ConnectionOpenInv == ~ConnectionOpenTest
+ChannelOpenInv == ~ChannelOpenTest
+PacketAcknowledgedInv == ~PacketAcknowledgedTest
+PacketTimedOutInv == ~PacketTimedOutTest
+
+=============================================================================
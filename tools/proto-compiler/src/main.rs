@@ -0,0 +1,103 @@
+//! Generates the code of the gRPC services defined by the `ibc` crate, from the
+//! protobuf definitions under `crates/ibc/proto`, into
+//! `crates/ibc/src/services/prost`.
+//!
+//! The definitions import those of IBC-Go and of its dependencies, which can be
+//! exported with [buf](https://buf.build):
+//!
+//! ```sh
+//! buf export buf.build/cosmos/ibc --output /tmp/ibc-go-proto
+//! cargo run --manifest-path tools/proto-compiler/Cargo.toml -- /tmp/ibc-go-proto
+//! ```
+//!
+//! The types of IBC-Go, Tendermint and Google are not generated again but
+//! referred to in `ibc-proto` and `tendermint-proto`.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Prefix of the packages defined by the `ibc` crate.
+const PACKAGE_PREFIX: &str = "ibc_rs.";
+
+fn main() {
+    let include_dirs: Vec<PathBuf> = env::args_os().skip(1).map(PathBuf::from).collect();
+    if include_dirs.is_empty() {
+        eprintln!("usage: ibc-proto-compiler <IBC-Go proto dir>...");
+        process::exit(1);
+    }
+
+    if let Err(e) = compile(&include_dirs) {
+        eprintln!("failed to compile the protobuf definitions: {e}");
+        process::exit(1);
+    }
+}
+
+fn compile(include_dirs: &[PathBuf]) -> io::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let proto_dir = root.join("crates/ibc/proto");
+    let out_dir = root.join("crates/ibc/src/services/prost");
+    let tmp_dir = env::temp_dir().join("ibc-proto-compiler");
+
+    let mut protos = Vec::new();
+    find_protos(&proto_dir, &mut protos)?;
+
+    let mut includes = vec![proto_dir];
+    includes.extend_from_slice(include_dirs);
+
+    if tmp_dir.exists() {
+        fs::remove_dir_all(&tmp_dir)?;
+    }
+    fs::create_dir_all(&tmp_dir)?;
+
+    tonic_build::configure()
+        .build_client(true)
+        .build_server(true)
+        .out_dir(&tmp_dir)
+        .extern_path(".ibc", "::ibc_proto::ibc")
+        .extern_path(".tendermint", "::tendermint_proto")
+        .extern_path(".google.protobuf", "::ibc_proto::google::protobuf")
+        .compile(&protos, &includes)?;
+
+    // prost-build also generates the imported packages, which are all extern
+    for entry in fs::read_dir(&out_dir)? {
+        fs::remove_file(entry?.path())?;
+    }
+    for entry in fs::read_dir(&tmp_dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().expect("read_dir yields file names");
+        if !file_name.to_string_lossy().starts_with(PACKAGE_PREFIX) {
+            continue;
+        }
+
+        let code = fs::read_to_string(&path)?;
+        fs::write(out_dir.join(file_name), patch_no_std(&code))?;
+        println!("generated {}", file_name.to_string_lossy());
+    }
+
+    fs::remove_dir_all(&tmp_dir)
+}
+
+/// The `ibc` crate is `no_std`: the generated servers and clients need its
+/// prelude for `Box` and `format!`.
+fn patch_no_std(code: &str) -> String {
+    code.replace(
+        "    use tonic::codegen::*;\n",
+        "    use tonic::codegen::*;\n    use crate::prelude::*;\n",
+    )
+}
+
+fn find_protos(dir: &Path, protos: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_protos(&path, protos)?;
+        } else if path.extension().map_or(false, |ext| ext == "proto") {
+            protos.push(path);
+        }
+    }
+    protos.sort();
+    Ok(())
+}